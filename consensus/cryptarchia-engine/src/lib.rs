@@ -128,6 +128,37 @@ where
         self.branches.get(id)
     }
 
+    // Remove all forks whose lowest common ancestor with `local_chain` is below
+    // the `cutoff` length, returning the ids of the removed blocks
+    fn prune_forks(&mut self, local_chain: &Branch<Id>, cutoff: u64) -> Vec<Id> {
+        let stale_forks = self
+            .tips
+            .iter()
+            .filter(|tip| **tip != local_chain.id)
+            .filter_map(|tip| {
+                let lca = self.lca(&self.branches[tip], local_chain);
+                (lca.length < cutoff).then_some((*tip, lca.id))
+            })
+            .collect::<Vec<_>>();
+
+        let mut pruned = Vec::new();
+        for (tip, lca) in stale_forks {
+            self.tips.remove(&tip);
+            let mut current = tip;
+            // Walk back until we either reach the local chain or a block already
+            // removed while pruning a fork sharing part of this one
+            while current != lca {
+                let Some(branch) = self.branches.remove(&current) else {
+                    break;
+                };
+                pruned.push(current);
+                current = branch.parent;
+            }
+        }
+
+        pruned
+    }
+
     // Walk back the chain until the target slot
     fn walk_back_before(&self, branch: &Branch<Id>, slot: Slot) -> Branch<Id> {
         let mut current = branch;
@@ -184,10 +215,20 @@ where
         self.local_chain.id
     }
 
-    // prune all states deeper than 'depth' with regard to the current
-    // local chain except for states belonging to the local chain
-    pub fn prune_forks(&mut self, _depth: u64) {
-        todo!()
+    /// Prune all forks that diverged from the local chain more than `depth`
+    /// blocks behind the current tip.
+    ///
+    /// Blocks belonging to the local chain are never pruned.
+    ///
+    /// # Returns
+    ///
+    /// The ids of the pruned blocks, so that any state associated to them can
+    /// be dropped as well.
+    pub fn prune_forks(&mut self, depth: u64) -> Vec<Id> {
+        let Some(cutoff) = self.local_chain.length.checked_sub(depth) else {
+            return Vec::new();
+        };
+        self.branches.prune_forks(&self.local_chain, cutoff)
     }
 
     pub const fn genesis(&self) -> Id {
@@ -382,6 +423,69 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_prune_forks() {
+        let mut engine = Cryptarchia::new([0; 32], config());
+        engine.config.security_param = NonZero::new(10).unwrap();
+
+        // build the local chain: genesis <- 1 <- 2 <- ... <- 20
+        let mut local_chain = vec![engine.genesis()];
+        for slot in 1..=20u64 {
+            let new_block = hash(&slot);
+            engine = engine
+                .receive_block(new_block, *local_chain.last().unwrap(), slot.into())
+                .unwrap();
+            local_chain.push(new_block);
+        }
+
+        // a fork off block 2, with a nested fork on top of it
+        let mut deep_fork = vec![];
+        let mut parent = local_chain[2];
+        for slot in 3..6u64 {
+            let new_block = hash(&format!("deep-{slot}"));
+            engine = engine
+                .receive_block(new_block, parent, slot.into())
+                .unwrap();
+            deep_fork.push(new_block);
+            parent = new_block;
+        }
+        let nested_fork = hash(&"deep-nested");
+        engine = engine
+            .receive_block(nested_fork, deep_fork[0], 10.into())
+            .unwrap();
+
+        // a fork off block 15, within the pruning depth
+        let shallow_fork = hash(&"shallow");
+        engine = engine
+            .receive_block(shallow_fork, local_chain[15], 16.into())
+            .unwrap();
+
+        assert_eq!(engine.tip(), *local_chain.last().unwrap());
+        assert_eq!(engine.branches().branches().len(), 4);
+
+        // nothing to prune if the depth is larger than the local chain
+        assert!(engine.prune_forks(50).is_empty());
+
+        let mut pruned = engine.prune_forks(10);
+        pruned.sort_unstable();
+        let mut expected = deep_fork.clone();
+        expected.push(nested_fork);
+        expected.sort_unstable();
+        assert_eq!(pruned, expected);
+
+        for id in &expected {
+            assert!(engine.branches().get(id).is_none());
+        }
+        for id in &local_chain {
+            assert!(engine.branches().get(id).is_some());
+        }
+        assert!(engine.branches().get(&shallow_fork).is_some());
+        assert_eq!(engine.branches().branches().len(), 2);
+
+        // pruning is idempotent
+        assert!(engine.prune_forks(10).is_empty());
+    }
+
     #[test]
     fn test_get_security_block() {
         let mut engine = Cryptarchia::new([0; 32], config());
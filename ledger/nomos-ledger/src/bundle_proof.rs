@@ -0,0 +1,12 @@
+pub trait BundleProof {
+    // Verifies the proofs of the partial transactions, of the covenants of their
+    // inputs and of the balance of the bundle, and that they all refer to the
+    // bundle
+    fn verify(&self) -> bool;
+    // The bundle the proofs are for
+    fn bundle(&self) -> &cl::Bundle;
+    // The merkle roots used to prove membership of the inputs
+    // Like leader proofs, bundles may use an old merkle root, so they have to
+    // tell the ledger which ones they are using.
+    fn cm_roots(&self) -> &[[u8; 32]];
+}
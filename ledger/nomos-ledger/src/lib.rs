@@ -1,12 +1,19 @@
+pub mod bundle_proof;
 mod config;
 mod crypto;
 pub mod leader_proof;
 mod notetree;
+pub mod stake_proof;
 
 use std::{collections::HashMap, hash::Hash};
 
 use blake2::Digest;
-use cl::{balance::Value, note::NoteCommitment, nullifier::Nullifier};
+use bundle_proof::BundleProof;
+use cl::{
+    balance::{Unit, Value},
+    note::NoteCommitment,
+    nullifier::Nullifier,
+};
 pub use config::Config;
 use cryptarchia_engine::{Epoch, Slot};
 use crypto::Blake2b;
@@ -14,6 +21,7 @@ use leader_proof::OrphanProof;
 use nomos_proof_statements::leadership::LeaderPublic;
 pub use notetree::NoteTree;
 use rpds::{HashTrieSet, HashTrieSetSync};
+use stake_proof::{StakeProof, StakeProofError};
 use thiserror::Error;
 
 #[derive(Clone, Debug, Error)]
@@ -30,6 +38,12 @@ pub enum LedgerError<Id> {
     InvalidProof,
     #[error("Invalid leader proof root")]
     InvalidRoot,
+    #[error("Invalid bundle proof")]
+    InvalidBundleProof,
+    #[error("Unknown note commitments root used in bundle")]
+    InvalidBundleRoot,
+    #[error("Invalid stake: {0}")]
+    InvalidStake(#[from] StakeProofError),
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -113,6 +127,73 @@ where
         // (update corresponding to the leader proof, leader proof)
         orphan_proofs: impl IntoIterator<Item = (Id, OrphanProof)>,
     ) -> Result<Self, LedgerError<Id>>
+    where
+        LeaderProof: leader_proof::LeaderProof,
+    {
+        let new_state = self.try_update_state(parent_id, slot, proof, orphan_proofs)?;
+        Ok(self.with_state(id, new_state))
+    }
+
+    /// Like [`Self::try_update`], but also applies the transaction bundles
+    /// included in the block, in order.
+    #[must_use = "this returns the result of the operation, without modifying the original"]
+    pub fn try_update_with_bundles<'b, LeaderProof, Bundle>(
+        &self,
+        id: Id,
+        parent_id: Id,
+        slot: Slot,
+        proof: &LeaderProof,
+        orphan_proofs: impl IntoIterator<Item = (Id, OrphanProof)>,
+        bundles: impl IntoIterator<Item = &'b Bundle>,
+    ) -> Result<Self, LedgerError<Id>>
+    where
+        LeaderProof: leader_proof::LeaderProof,
+        Bundle: BundleProof + 'b,
+    {
+        self.try_update_with_transactions(
+            id,
+            parent_id,
+            slot,
+            proof,
+            orphan_proofs,
+            bundles,
+            LedgerState::try_apply_bundle,
+        )
+    }
+
+    /// Like [`Self::try_update`], but also applies the transactions included
+    /// in the block, in order, with `apply`.
+    ///
+    /// Transactions that do not touch the ledger, e.g. service declarations,
+    /// can be applied as a no-op.
+    #[must_use = "this returns the result of the operation, without modifying the original"]
+    pub fn try_update_with_transactions<LeaderProof, Tx>(
+        &self,
+        id: Id,
+        parent_id: Id,
+        slot: Slot,
+        proof: &LeaderProof,
+        orphan_proofs: impl IntoIterator<Item = (Id, OrphanProof)>,
+        transactions: impl IntoIterator<Item = Tx>,
+        apply: impl FnMut(LedgerState, Tx) -> Result<LedgerState, LedgerError<Id>>,
+    ) -> Result<Self, LedgerError<Id>>
+    where
+        LeaderProof: leader_proof::LeaderProof,
+    {
+        let new_state = transactions.into_iter().try_fold(
+            self.try_update_state(parent_id, slot, proof, orphan_proofs)?,
+            apply,
+        )?;
+        Ok(self.with_state(id, new_state))
+    }
+
+    fn try_update_state<LeaderProof>(
+        &self,
+        parent_id: Id,
+        slot: Slot,
+        proof: &LeaderProof,
+        orphan_proofs: impl IntoIterator<Item = (Id, OrphanProof)>,
+    ) -> Result<LedgerState, LedgerError<Id>>
     where
         LeaderProof: leader_proof::LeaderProof,
     {
@@ -136,25 +217,32 @@ where
             }
         }
 
-        let new_state =
-            parent_state
-                .clone()
-                .try_update(slot, proof, &orphan_proofs, &self.config)?;
+        parent_state
+            .clone()
+            .try_update(slot, proof, &orphan_proofs, &self.config)
+    }
 
+    fn with_state(&self, id: Id, state: LedgerState) -> Self {
         let mut states = self.states.clone();
-
-        states.insert(id, new_state);
-
-        Ok(Self {
+        states.insert(id, state);
+        Self {
             states,
             config: self.config,
-        })
+        }
     }
 
     pub fn state(&self, id: &Id) -> Option<&LedgerState> {
         self.states.get(id)
     }
 
+    /// Removes the states associated to the given blocks, e.g. the ones
+    /// returned by [`cryptarchia_engine::Cryptarchia::prune_forks`].
+    pub fn prune_state_at(&mut self, ids: impl IntoIterator<Item = Id>) {
+        for id in ids {
+            self.states.remove(&id);
+        }
+    }
+
     #[must_use]
     pub const fn config(&self) -> &Config {
         &self.config
@@ -350,11 +438,90 @@ impl LedgerState {
         Ok(self)
     }
 
+    /// Applies a transaction bundle, spending its inputs and adding its outputs
+    /// to the spendable notes.
+    ///
+    /// New notes are not eligible for leadership until they are included in an
+    /// epoch stake distribution snapshot.
+    pub fn try_apply_bundle<Bundle, Id>(self, bundle: &Bundle) -> Result<Self, LedgerError<Id>>
+    where
+        Bundle: BundleProof,
+    {
+        if !bundle.verify() {
+            return Err(LedgerError::InvalidBundleProof);
+        }
+        self.try_apply_verified_bundle(bundle)
+    }
+
+    /// Like [`Self::try_apply_bundle`], for a bundle whose proofs were
+    /// already verified.
+    pub fn try_apply_verified_bundle<Bundle, Id>(
+        self,
+        bundle: &Bundle,
+    ) -> Result<Self, LedgerError<Id>>
+    where
+        Bundle: BundleProof,
+    {
+        if !bundle
+            .cm_roots()
+            .iter()
+            .all(|root| self.spend_commitments.is_valid_root(root))
+        {
+            return Err(LedgerError::InvalidBundleRoot);
+        }
+
+        let mut nullifiers = self.nullifiers;
+        let mut spend_commitments = self.spend_commitments;
+        for ptx in bundle.bundle().partial_txs() {
+            for input in &ptx.inputs {
+                // also catches notes spent twice in the same bundle
+                if nullifiers.contains(&input.nullifier) {
+                    return Err(LedgerError::DoubleSpend(input.nullifier));
+                }
+                nullifiers.insert_mut(input.nullifier);
+            }
+            for output in &ptx.outputs {
+                spend_commitments = spend_commitments.insert(output.note_comm);
+            }
+        }
+
+        Ok(Self {
+            spend_commitments,
+            nullifiers,
+            ..self
+        })
+    }
+
     #[must_use]
     pub fn is_nullified(&self, nullifier: &Nullifier) -> bool {
         self.nullifiers.contains(nullifier)
     }
 
+    /// Checks that `proof` is bound to `message` and proves ownership of a
+    /// spendable note of at least `min_value` of `unit`.
+    pub fn verify_stake(
+        &self,
+        proof: &impl StakeProof,
+        message: [u8; 32],
+        unit: Unit,
+        min_value: Value,
+    ) -> Result<(), StakeProofError> {
+        self.verify_stake_note(proof)?;
+        stake_proof::verify_stake_proof(proof, message, unit, min_value)
+    }
+
+    /// Checks that the note staked by `proof` is spendable in this state,
+    /// without verifying the proof itself.
+    pub fn verify_stake_note(&self, proof: &impl StakeProof) -> Result<(), StakeProofError> {
+        if !self.spend_commitments.is_valid_root(&proof.merkle_root()) {
+            return Err(StakeProofError::UnknownNote);
+        }
+        if self.is_nullified(&proof.nullifier()) {
+            return Err(StakeProofError::SpentNote);
+        }
+        Ok(())
+    }
+
     fn update_nonce(self, nullifier: Nullifier, slot: Slot) -> Self {
         Self {
             nonce: <[u8; 32]>::from(
@@ -413,6 +580,11 @@ impl LedgerState {
     pub const fn lead_commitments(&self) -> &NoteTree {
         &self.lead_commitments
     }
+
+    #[must_use]
+    pub const fn spend_commitments(&self) -> &NoteTree {
+        &self.spend_commitments
+    }
 }
 
 #[expect(
@@ -436,7 +608,10 @@ pub mod tests {
     use std::num::NonZero;
 
     use blake2::Digest;
-    use cl::{note::NoteWitness as Note, NullifierSecret};
+    use cl::{
+        note::NoteWitness as Note, BundleWitness, InputWitness, NullifierSecret, OutputWitness,
+        PartialTxWitness,
+    };
     use cryptarchia_engine::{EpochConfig, Slot};
     use rand::thread_rng;
     use rpds::HashTrieSet;
@@ -476,6 +651,44 @@ pub mod tests {
         }
     }
 
+    struct DummyBundle {
+        bundle: cl::Bundle,
+        cm_roots: Vec<[u8; 32]>,
+        valid: bool,
+    }
+
+    impl BundleProof for DummyBundle {
+        fn verify(&self) -> bool {
+            self.valid
+        }
+
+        fn bundle(&self) -> &cl::Bundle {
+            &self.bundle
+        }
+
+        fn cm_roots(&self) -> &[[u8; 32]] {
+            &self.cm_roots
+        }
+    }
+
+    // a bundle spending `inputs` to create `outputs` in a single partial tx
+    fn bundle(inputs: &[Note], outputs: &[Note], cm_root: [u8; 32]) -> DummyBundle {
+        let output = |note: &Note| OutputWitness::new(*note, NF_SK.commit());
+        let ptx = PartialTxWitness {
+            inputs: inputs
+                .iter()
+                .map(|note| InputWitness::from_output(output(note), NF_SK, vec![]))
+                .collect(),
+            outputs: outputs.iter().map(output).collect(),
+            balance_blinding: [0; 16],
+        };
+        DummyBundle {
+            bundle: BundleWitness::new(vec![ptx]).commit(),
+            cm_roots: vec![cm_root],
+            valid: true,
+        }
+    }
+
     fn commit(note: Note) -> NoteCommitment {
         note.commit(NF_SK.commit())
     }
@@ -578,6 +791,26 @@ pub mod tests {
         Ok(id)
     }
 
+    fn update_bundles(
+        ledger: &mut Ledger<HeaderId>,
+        parent: HeaderId,
+        slot: impl Into<Slot>,
+        note: Note,
+        bundles: &[DummyBundle],
+    ) -> Result<HeaderId, LedgerError<HeaderId>> {
+        let slot = slot.into();
+        let ledger_state = ledger
+            .state(&parent)
+            .unwrap()
+            .clone()
+            .update_epoch_state::<HeaderId>(slot, ledger.config())
+            .unwrap();
+        let id = make_id(parent, slot, note);
+        let (proof, _) = generate_proofs(&ledger_state, note, vec![]);
+        *ledger = ledger.try_update_with_bundles(id, parent, slot, &proof, vec![], bundles)?;
+        Ok(id)
+    }
+
     #[must_use]
     pub const fn config() -> Config {
         Config {
@@ -887,6 +1120,31 @@ pub mod tests {
         ));
     }
 
+    #[test]
+    fn test_prune_state_at() {
+        let note_1 = note();
+        let note_2 = note();
+        let (mut ledger, genesis) = ledger(&[commit(note_1), commit(note_2)]);
+
+        let h_1 = update_ledger(&mut ledger, genesis, 1, note_1).unwrap();
+        let h_2 = update_ledger(&mut ledger, genesis, 1, note_2).unwrap();
+
+        ledger.prune_state_at([h_2]);
+        assert!(ledger.state(&h_1).is_some());
+        assert!(ledger.state(&h_2).is_none());
+
+        // blocks can't be built on top of a pruned state anymore
+        let proof = DummyProof {
+            cm_root: [0; 32],
+            nullifier: Nullifier::new(NF_SK, commit(evolve(note_2))),
+            commitment: commit(evolve(evolve(note_2))),
+        };
+        assert!(matches!(
+            ledger.try_update([1; 32], h_2, 2.into(), &proof, vec![]),
+            Err(LedgerError::ParentNotFound(_))
+        ));
+    }
+
     #[test]
     fn test_update_epoch_state_with_outdated_slot_error() {
         let note = note();
@@ -913,4 +1171,126 @@ pub mod tests {
             _ => panic!("error does not match the LedgerError::InvalidSlot pattern"),
         }
     }
+
+    #[test]
+    fn test_bundle_spends_inputs_and_creates_outputs() {
+        let (leader_note, note, output) = (note(), note(), note());
+        let (mut ledger, genesis) = ledger(&[commit(leader_note), commit(note)]);
+        let cm_root = ledger.state(&genesis).unwrap().spend_commitments.root();
+
+        let h = update_bundles(
+            &mut ledger,
+            genesis,
+            1,
+            leader_note,
+            &[bundle(&[note], &[output], cm_root)],
+        )
+        .unwrap();
+        let state = ledger.state(&h).unwrap();
+        assert!(state.is_nullified(&Nullifier::new(NF_SK, commit(note))));
+        assert!(state.spend_commitments.index_of(&commit(output)).is_some());
+        // new notes are not eligible for leadership right away
+        assert!(state.lead_commitments.index_of(&commit(output)).is_none());
+
+        // the new note can be spent using the updated root
+        let cm_root = state.spend_commitments.root();
+        update_bundles(
+            &mut ledger,
+            h,
+            2,
+            evolve(leader_note),
+            &[bundle(&[output], &[], cm_root)],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_bundle_double_spend() {
+        let (leader_note, note) = (note(), note());
+        let (mut ledger, genesis) = ledger(&[commit(leader_note), commit(note)]);
+        let cm_root = ledger.state(&genesis).unwrap().spend_commitments.root();
+
+        // within the same bundle
+        assert!(matches!(
+            update_bundles(
+                &mut ledger,
+                genesis,
+                1,
+                leader_note,
+                &[bundle(&[note, note], &[], cm_root)],
+            ),
+            Err(LedgerError::DoubleSpend(_))
+        ));
+
+        // in two bundles of the same block
+        assert!(matches!(
+            update_bundles(
+                &mut ledger,
+                genesis,
+                1,
+                leader_note,
+                &[bundle(&[note], &[], cm_root), bundle(&[note], &[], cm_root)],
+            ),
+            Err(LedgerError::DoubleSpend(_))
+        ));
+
+        // in a later block
+        let h = update_bundles(
+            &mut ledger,
+            genesis,
+            1,
+            leader_note,
+            &[bundle(&[note], &[], cm_root)],
+        )
+        .unwrap();
+        assert!(matches!(
+            update_bundles(
+                &mut ledger,
+                h,
+                2,
+                evolve(leader_note),
+                &[bundle(&[note], &[], cm_root)],
+            ),
+            Err(LedgerError::DoubleSpend(_))
+        ));
+
+        // a note used for leadership cannot be spent anymore
+        assert!(matches!(
+            update_bundles(
+                &mut ledger,
+                h,
+                2,
+                evolve(leader_note),
+                &[bundle(&[leader_note], &[], cm_root)],
+            ),
+            Err(LedgerError::DoubleSpend(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_bundle() {
+        let (leader_note, note) = (note(), note());
+        let (mut ledger, genesis) = ledger(&[commit(leader_note), commit(note)]);
+        let cm_root = ledger.state(&genesis).unwrap().spend_commitments.root();
+
+        let invalid_proof = DummyBundle {
+            valid: false,
+            ..bundle(&[note], &[], cm_root)
+        };
+        assert!(matches!(
+            update_bundles(&mut ledger, genesis, 1, leader_note, &[invalid_proof]),
+            Err(LedgerError::InvalidBundleProof)
+        ));
+
+        assert!(matches!(
+            update_bundles(
+                &mut ledger,
+                genesis,
+                1,
+                leader_note,
+                &[bundle(&[note], &[], [0; 32])]
+            ),
+            Err(LedgerError::InvalidBundleRoot)
+        ));
+    }
 }
@@ -1,35 +1,114 @@
+use std::sync::LazyLock;
+
 use cl::{merkle::PathNode, note::NoteCommitment};
-// up to 2^14 commitments
-const MAX_NOTE_COMMS: usize = 1 << 14;
+// trees hold at least 2^14 commitments, and grow as needed: witnesses get
+// longer, which the proof circuits accept as they take paths of any length
+const MIN_DEPTH: u8 = 14;
+const MAX_DEPTH: u8 = 63;
+
+// roots of empty subtrees of each height, leaves are padded with zeros
+static EMPTY_ROOTS: LazyLock<[[u8; 32]; MAX_DEPTH as usize + 1]> = LazyLock::new(|| {
+    let mut roots = [[0; 32]; MAX_DEPTH as usize + 1];
+    for height in 1..roots.len() {
+        roots[height] = cl::merkle::node(roots[height - 1], roots[height - 1]);
+    }
+    roots
+});
 
+/// Append-only Merkle tree of note commitments.
+///
+/// Nodes are cached and shared between versions of the tree, so inserting a
+/// commitment or building a witness only touches the path from its leaf to
+/// the root.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    serde(from = "SerializedNoteTree", into = "SerializedNoteTree")
+)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct NoteTree {
     commitments: rpds::VectorSync<NoteCommitment>,
     // current root + previous roots
     roots: rpds::HashTrieSetSync<[u8; 32]>,
+    // position of each commitment in the tree, first one wins for duplicates
+    indexes: rpds::HashTrieMapSync<NoteCommitment, usize>,
+    // non empty nodes, by height (0 for leaves) and position in that level
+    nodes: rpds::HashTrieMapSync<(u8, usize), [u8; 32]>,
+    depth: u8,
+    root: [u8; 32],
 }
 
-fn note_commitment_leaves(
-    commitments: &rpds::VectorSync<NoteCommitment>,
-) -> [[u8; 32]; MAX_NOTE_COMMS] {
-    let note_comm_bytes: Vec<Vec<u8>> = commitments.iter().map(|c| c.as_bytes().to_vec()).collect();
-    cl::merkle::padded_leaves::<MAX_NOTE_COMMS>(&note_comm_bytes)
+impl Default for NoteTree {
+    fn default() -> Self {
+        Self {
+            commitments: rpds::VectorSync::default(),
+            roots: rpds::HashTrieSetSync::default(),
+            indexes: rpds::HashTrieMapSync::default(),
+            nodes: rpds::HashTrieMapSync::default(),
+            depth: MIN_DEPTH,
+            root: EMPTY_ROOTS[MIN_DEPTH as usize],
+        }
+    }
 }
 
 impl NoteTree {
     #[must_use]
     pub fn insert(&self, note: NoteCommitment) -> Self {
-        let commitments = self.commitments.push_back(note);
-        let root = cl::merkle::root(note_commitment_leaves(&commitments));
-        let roots = self.roots.insert(root);
-        Self { commitments, roots }
+        let mut tree = self.push(note);
+        tree.roots = tree.roots.insert(tree.root);
+        tree
+    }
+
+    // Appends a commitment without recording the new root as a valid one
+    fn push(&self, note: NoteCommitment) -> Self {
+        let index = self.commitments.len();
+        let mut depth = self.depth;
+        if index >> depth != 0 {
+            assert!(depth < MAX_DEPTH, "note tree is full");
+            depth += 1;
+        }
+
+        let mut nodes = self
+            .nodes
+            .insert((0, index), cl::merkle::leaf(note.as_bytes()));
+        let mut position = index;
+        for height in 1..=depth {
+            position /= 2;
+            let left = Self::node_in(&nodes, height - 1, position * 2);
+            let right = Self::node_in(&nodes, height - 1, position * 2 + 1);
+            nodes.insert_mut((height, position), cl::merkle::node(left, right));
+        }
+
+        let indexes = if self.indexes.contains_key(&note) {
+            self.indexes.clone()
+        } else {
+            self.indexes.insert(note, index)
+        };
+
+        Self {
+            commitments: self.commitments.push_back(note),
+            roots: self.roots.clone(),
+            indexes,
+            root: Self::node_in(&nodes, depth, 0),
+            nodes,
+            depth,
+        }
+    }
+
+    fn node_in(
+        nodes: &rpds::HashTrieMapSync<(u8, usize), [u8; 32]>,
+        height: u8,
+        position: usize,
+    ) -> [u8; 32] {
+        nodes
+            .get(&(height, position))
+            .copied()
+            .unwrap_or(EMPTY_ROOTS[height as usize])
     }
 
-    // TODO: cache if called frequently
     #[must_use]
-    pub fn root(&self) -> [u8; 32] {
-        cl::merkle::root(note_commitment_leaves(&self.commitments))
+    pub const fn root(&self) -> [u8; 32] {
+        self.root
     }
 
     #[must_use]
@@ -42,8 +121,26 @@ impl NoteTree {
         if index >= self.commitments.len() {
             return None;
         }
-        let leaves = note_commitment_leaves(&self.commitments);
-        Some(cl::merkle::path(leaves, index))
+        let mut position = index;
+        let path = (0..self.depth)
+            .map(|height| {
+                let sibling = Self::node_in(&self.nodes, height, position ^ 1);
+                let path_node = if position % 2 == 0 {
+                    PathNode::Right(sibling)
+                } else {
+                    PathNode::Left(sibling)
+                };
+                position /= 2;
+                path_node
+            })
+            .collect();
+        Some(path)
+    }
+
+    /// Position of `note` in the tree, if present.
+    #[must_use]
+    pub fn index_of(&self, note: &NoteCommitment) -> Option<usize> {
+        self.indexes.get(note).copied()
     }
 
     #[must_use]
@@ -54,11 +151,117 @@ impl NoteTree {
 
 impl FromIterator<NoteCommitment> for NoteTree {
     fn from_iter<I: IntoIterator<Item = NoteCommitment>>(iter: I) -> Self {
-        let commitments = rpds::VectorSync::from_iter(iter);
-        let root = cl::merkle::root(note_commitment_leaves(&commitments));
+        let tree = iter
+            .into_iter()
+            .fold(Self::default(), |tree, note| tree.push(note));
         Self {
-            commitments,
-            roots: rpds::HashTrieSetSync::default().insert(root),
+            roots: rpds::HashTrieSetSync::default().insert(tree.root),
+            ..tree
         }
     }
 }
+
+// Only commitments and valid roots are serialized, the rest of the tree is
+// rebuilt from them
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SerializedNoteTree {
+    commitments: rpds::VectorSync<NoteCommitment>,
+    roots: rpds::HashTrieSetSync<[u8; 32]>,
+}
+
+#[cfg(feature = "serde")]
+impl From<SerializedNoteTree> for NoteTree {
+    fn from(SerializedNoteTree { commitments, roots }: SerializedNoteTree) -> Self {
+        let tree = commitments
+            .iter()
+            .fold(Self::default(), |tree, note| tree.push(*note));
+        Self { roots, ..tree }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<NoteTree> for SerializedNoteTree {
+    fn from(
+        NoteTree {
+            commitments, roots, ..
+        }: NoteTree,
+    ) -> Self {
+        Self { commitments, roots }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TREE_SIZE: usize = 1 << MIN_DEPTH;
+    // the first leaves are checked against a small padded tree, whose root is
+    // then combined with empty subtrees up to the root of the note tree
+    const SUBTREE_SIZE: usize = 16;
+
+    fn commitment(i: usize) -> NoteCommitment {
+        let mut bytes = [0; 32];
+        bytes[..8].copy_from_slice(&i.to_le_bytes());
+        NoteCommitment(bytes)
+    }
+
+    fn padded_leaves(commitments: &[NoteCommitment]) -> [[u8; 32]; SUBTREE_SIZE] {
+        let commitments: Vec<Vec<u8>> = commitments.iter().map(|c| c.as_bytes().to_vec()).collect();
+        cl::merkle::padded_leaves::<SUBTREE_SIZE>(&commitments)
+    }
+
+    fn padded_root(commitments: &[NoteCommitment]) -> [u8; 32] {
+        (SUBTREE_SIZE.ilog2()..u32::from(MIN_DEPTH)).fold(
+            cl::merkle::root(padded_leaves(commitments)),
+            |root, height| cl::merkle::node(root, EMPTY_ROOTS[height as usize]),
+        )
+    }
+
+    fn padded_path(commitments: &[NoteCommitment], index: usize) -> Vec<PathNode> {
+        let mut path = cl::merkle::path(padded_leaves(commitments), index);
+        path.extend(
+            (SUBTREE_SIZE.ilog2()..u32::from(MIN_DEPTH))
+                .map(|height| PathNode::Right(EMPTY_ROOTS[height as usize])),
+        );
+        path
+    }
+
+    #[test]
+    fn test_matches_padded_tree() {
+        let commitments: Vec<_> = (0..10).map(commitment).collect();
+        let mut tree = NoteTree::default();
+        assert_eq!(tree.root(), padded_root(&[]));
+
+        for (i, cm) in commitments.iter().enumerate() {
+            tree = tree.insert(*cm);
+            assert_eq!(tree.root(), padded_root(&commitments[..=i]));
+            assert!(tree.is_valid_root(&tree.root()));
+        }
+
+        for i in 0..commitments.len() {
+            assert_eq!(tree.witness(i), Some(padded_path(&commitments, i)));
+        }
+        assert_eq!(tree.witness(commitments.len()), None);
+        assert_eq!(tree.index_of(&commitments[3]), Some(3));
+
+        let collected: NoteTree = commitments.into_iter().collect();
+        assert_eq!(collected.root(), tree.root());
+        assert_eq!(collected.witness(3), tree.witness(3));
+    }
+
+    #[test]
+    fn test_grows_beyond_min_depth() {
+        let tree: NoteTree = (0..=TREE_SIZE).map(commitment).collect();
+        assert_eq!(tree.depth, MIN_DEPTH + 1);
+
+        let last = commitment(TREE_SIZE);
+        let path = tree.witness(TREE_SIZE).unwrap();
+        assert_eq!(path.len(), usize::from(MIN_DEPTH) + 1);
+        assert_eq!(
+            cl::merkle::path_root(cl::merkle::leaf(last.as_bytes()), &path),
+            tree.root()
+        );
+        assert_eq!(tree.index_of(&last), Some(TREE_SIZE));
+    }
+}
@@ -0,0 +1,167 @@
+use cl::balance::{Unit, Value};
+use nomos_proof_statements::stake::StakePublic;
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum StakeProofError {
+    #[error("Invalid stake proof")]
+    InvalidProof,
+    #[error("Note commitment is not in the ledger")]
+    UnknownNote,
+    #[error("Note is already spent")]
+    SpentNote,
+}
+
+/// Proof of ownership of an unspent note, used to back a declaration with
+/// stake.
+///
+/// The proof is bound to a message, usually the id of whoever the stake is
+/// backing, and reveals the nullifier of the note but not its secret.
+pub trait StakeProof {
+    fn verify(&self, public_inputs: &StakePublic) -> bool;
+    // The nullifier of the note used in the proof
+    fn nullifier(&self) -> cl::Nullifier;
+    // The merkle root used for the proof
+    // Like leader proofs, stake proofs may use an old merkle root, so they have
+    // to tell the ledger which one they are using.
+    fn merkle_root(&self) -> [u8; 32];
+}
+
+/// Checks that `proof` is bound to `message` and proves ownership of a note of
+/// at least `min_value` of `unit`, whatever the ledger state.
+///
+/// The note must still be checked to be spendable, see
+/// [`LedgerState::verify_stake_note`](crate::LedgerState::verify_stake_note).
+pub fn verify_stake_proof(
+    proof: &impl StakeProof,
+    message: [u8; 32],
+    unit: Unit,
+    min_value: Value,
+) -> Result<(), StakeProofError> {
+    let public_inputs = StakePublic {
+        cm_root: proof.merkle_root(),
+        nullifier: proof.nullifier(),
+        unit,
+        min_value,
+        message,
+    };
+    if !proof.verify(&public_inputs) {
+        return Err(StakeProofError::InvalidProof);
+    }
+    Ok(())
+}
+
+/// Stake proof checking the statement of the circuit against the witness
+/// instead of proving it.
+#[cfg(any(test, feature = "testing"))]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WitnessStakeProof {
+    pub input: cl::InputWitness,
+    pub message: [u8; 32],
+}
+
+#[cfg(any(test, feature = "testing"))]
+impl StakeProof for WitnessStakeProof {
+    fn verify(&self, public_inputs: &StakePublic) -> bool {
+        self.input.note.unit == public_inputs.unit
+            && self.input.note.value >= public_inputs.min_value
+            && self.merkle_root() == public_inputs.cm_root
+            && self.nullifier() == public_inputs.nullifier
+            && self.message == public_inputs.message
+    }
+
+    fn nullifier(&self) -> cl::Nullifier {
+        self.input.nullifier()
+    }
+
+    fn merkle_root(&self) -> [u8; 32] {
+        cl::merkle::path_root(
+            cl::merkle::leaf(self.input.note_commitment().as_bytes()),
+            &self.input.cm_path,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cl::{balance::Unit, note::NoteWitness, nullifier::NullifierSecret, InputWitness};
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::LedgerState;
+
+    const NF_SK: NullifierSecret = NullifierSecret([1; 16]);
+    const UNIT: Unit = [2; 32];
+    const MESSAGE: [u8; 32] = [3; 32];
+
+    fn stake_proof(ledger_state: &LedgerState, note: NoteWitness) -> WitnessStakeProof {
+        let commitment = note.commit(NF_SK.commit());
+        let index = ledger_state
+            .spend_commitments()
+            .index_of(&commitment)
+            .unwrap();
+        let cm_path = ledger_state.spend_commitments().witness(index).unwrap();
+        WitnessStakeProof {
+            input: InputWitness::new(note, NF_SK, cm_path),
+            message: MESSAGE,
+        }
+    }
+
+    #[test]
+    fn test_verify_stake() {
+        let note = NoteWitness::basic(10, UNIT, &mut thread_rng());
+        let other_note = NoteWitness::basic(10, UNIT, &mut thread_rng());
+        let ledger_state = LedgerState::from_commitments(
+            [
+                other_note.commit(NF_SK.commit()),
+                note.commit(NF_SK.commit()),
+            ],
+            20,
+        );
+        let proof = stake_proof(&ledger_state, note);
+
+        assert_eq!(ledger_state.verify_stake(&proof, MESSAGE, UNIT, 10), Ok(()));
+        assert_eq!(
+            ledger_state.verify_stake(&proof, [4; 32], UNIT, 10),
+            Err(StakeProofError::InvalidProof)
+        );
+        assert_eq!(
+            ledger_state.verify_stake(&proof, MESSAGE, [4; 32], 10),
+            Err(StakeProofError::InvalidProof)
+        );
+        assert_eq!(
+            ledger_state.verify_stake(&proof, MESSAGE, UNIT, 11),
+            Err(StakeProofError::InvalidProof)
+        );
+    }
+
+    #[test]
+    fn test_verify_stake_of_unknown_or_spent_note() {
+        let note = NoteWitness::basic(10, UNIT, &mut thread_rng());
+        let ledger_state = LedgerState::from_commitments([note.commit(NF_SK.commit())], 10);
+        let proof = stake_proof(&ledger_state, note);
+
+        let unknown_proof = WitnessStakeProof {
+            input: InputWitness::new(
+                NoteWitness::basic(10, UNIT, &mut thread_rng()),
+                NF_SK,
+                proof.input.cm_path.clone(),
+            ),
+            message: MESSAGE,
+        };
+        assert_eq!(
+            ledger_state.verify_stake(&unknown_proof, MESSAGE, UNIT, 10),
+            Err(StakeProofError::UnknownNote)
+        );
+
+        let spent_state = LedgerState {
+            nullifiers: ledger_state.nullifiers.insert(proof.nullifier()),
+            ..ledger_state
+        };
+        assert_eq!(
+            spent_state.verify_stake(&proof, MESSAGE, UNIT, 10),
+            Err(StakeProofError::SpentNote)
+        );
+    }
+}
@@ -2,8 +2,14 @@ pub const CL_METRICS: &str = "/cl/metrics";
 pub const CL_STATUS: &str = "/cl/status";
 pub const CRYPTARCHIA_INFO: &str = "/cryptarchia/info";
 pub const CRYPTARCHIA_HEADERS: &str = "/cryptarchia/headers";
+pub const CRYPTARCHIA_LEADER_NOTES: &str = "/cryptarchia/leader/notes";
+pub const CRYPTARCHIA_ADD_LEADER_NOTE: &str = "/cryptarchia/leader/add-note";
+pub const CRYPTARCHIA_REMOVE_LEADER_NOTE: &str = "/cryptarchia/leader/remove-note";
+pub const CRYPTARCHIA_EXPORT_LEDGER_SNAPSHOT: &str = "/cryptarchia/snapshot/export";
 pub const DA_ADD_SHARE: &str = "/da/add-share";
 pub const DA_GET_RANGE: &str = "/da/get-range";
+pub const DA_RETRIEVE_BLOB: &str = "/da/retrieve-blob";
+pub const DA_RETRIEVE_RANGE: &str = "/da/retrieve-range";
 pub const DA_GET_SHARES_COMMITMENTS: &str = "/da/get-commitments";
 pub const DA_GET_LIGHT_SHARE: &str = "/da/get-share";
 pub const DA_GET_SHARES: &str = "/da/sampling/shares";
@@ -14,6 +20,7 @@ pub const DA_BALANCER_STATS: &str = "/da/balancer-stats";
 pub const DA_MONITOR_STATS: &str = "/da/monitor-stats";
 pub const NETWORK_INFO: &str = "/network/info";
 pub const STORAGE_BLOCK: &str = "/storage/block";
+pub const SDP_ACTIVE_PROVIDERS: &str = "/sdp/active-providers";
 pub const MEMPOOL_ADD_TX: &str = "/mempool/add/tx";
 pub const MEMPOOL_ADD_BLOB_INFO: &str = "/mempool/add/blobinfo";
 pub const DISPERSE_DATA: &str = "/disperse-data";
@@ -10,6 +10,7 @@ use nomos_api::{
     http::{
         consensus::Cryptarchia,
         da::{DaDispersal, DaIndexer, DaVerifier},
+        sdp::Sdp,
     },
     Backend,
 };
@@ -29,17 +30,20 @@ use nomos_da_verifier::backend::VerifierBackend;
 use nomos_http_api_common::paths;
 use nomos_libp2p::PeerId;
 use nomos_mempool::{
-    backend::mockpool::MockPool, tx::service::openapi::Status, DaMempoolService, MempoolMetrics,
-    TxMempoolService,
+    backend::priority::PriorityPool, tx::service::openapi::Status, DaMempoolService,
+    MempoolMetrics, TxMempoolService,
 };
 use nomos_node::{
     api::handlers::{
         add_blob_info, add_share, add_tx, balancer_stats, blacklisted_peers, block, block_peer,
-        cl_metrics, cl_status, cryptarchia_headers, cryptarchia_info, da_get_commitments,
-        da_get_light_share, da_get_shares, get_range, libp2p_info, monitor_stats, unblock_peer,
+        cl_metrics, cl_status, cryptarchia_add_leader_note, cryptarchia_export_ledger_snapshot,
+        cryptarchia_headers, cryptarchia_info, cryptarchia_leader_notes,
+        cryptarchia_remove_leader_note, da_get_commitments, da_get_light_share, da_get_shares,
+        get_range, libp2p_info, monitor_stats, sdp_active_providers, unblock_peer,
     },
     RocksBackend,
 };
+use nomos_sdp::consensus::SdpTransaction;
 use nomos_storage::{backends::StorageSerde, StorageService};
 use overwatch::{overwatch::handle::OverwatchHandle, services::AsServiceId};
 use rand::{RngCore, SeedableRng};
@@ -232,6 +236,7 @@ where
         nomos_da_verifier::storage::DaStorageAdapter<RuntimeServiceId> + Send + Sync + 'static,
     DaVerifierStorage::Settings: Clone,
     Tx: Transaction
+        + SdpTransaction
         + Clone
         + Debug
         + Eq
@@ -243,6 +248,8 @@ where
         + 'static,
     <Tx as nomos_core::tx::Transaction>::Hash:
         Serialize + for<'de> Deserialize<'de> + std::cmp::Ord + Debug + Send + Sync + 'static,
+    <Tx as SdpTransaction>::Metadata: Clone + Send + Sync + 'static,
+    <Tx as SdpTransaction>::Proof: Clone + Send + Sync + 'static,
     DaStorageSerializer: StorageSerde + Send + Sync + 'static,
     <DaStorageSerializer as StorageSerde>::Error: Send + Sync,
     DispersalBackend: nomos_da_dispersal::backend::DispersalBackend<
@@ -305,6 +312,23 @@ where
                 SIZE,
             >,
         >
+        + AsServiceId<
+            Sdp<
+                Tx,
+                DaStorageSerializer,
+                SamplingBackend,
+                SamplingNetworkAdapter,
+                SamplingRng,
+                SamplingStorage,
+                DaVerifierBackend,
+                DaVerifierNetwork,
+                DaVerifierStorage,
+                TimeBackend,
+                ApiAdapter,
+                RuntimeServiceId,
+                SIZE,
+            >,
+        >
         + AsServiceId<
             DaVerifier<
                 DaShare,
@@ -353,7 +377,7 @@ where
                     <Tx as Transaction>::Hash,
                     RuntimeServiceId,
                 >,
-                MockPool<HeaderId, Tx, <Tx as Transaction>::Hash>,
+                PriorityPool<HeaderId, Tx, <Tx as Transaction>::Hash>,
                 RuntimeServiceId,
             >,
         >
@@ -364,7 +388,7 @@ where
                     DaVerifiedBlobInfo::BlobId,
                     RuntimeServiceId,
                 >,
-                MockPool<HeaderId, DaVerifiedBlobInfo, DaVerifiedBlobInfo::BlobId>,
+                PriorityPool<HeaderId, DaVerifiedBlobInfo, DaVerifiedBlobInfo::BlobId>,
                 SamplingBackend,
                 SamplingNetworkAdapter,
                 SamplingRng,
@@ -472,6 +496,86 @@ where
                     >,
                 ),
             )
+            .route(
+                paths::CRYPTARCHIA_LEADER_NOTES,
+                routing::get(
+                    cryptarchia_leader_notes::<
+                        Tx,
+                        DaStorageSerializer,
+                        SamplingBackend,
+                        SamplingNetworkAdapter,
+                        SamplingRng,
+                        SamplingStorage,
+                        DaVerifierBackend,
+                        DaVerifierNetwork,
+                        DaVerifierStorage,
+                        TimeBackend,
+                        ApiAdapter,
+                        RuntimeServiceId,
+                        SIZE,
+                    >,
+                ),
+            )
+            .route(
+                paths::CRYPTARCHIA_ADD_LEADER_NOTE,
+                routing::post(
+                    cryptarchia_add_leader_note::<
+                        Tx,
+                        DaStorageSerializer,
+                        SamplingBackend,
+                        SamplingNetworkAdapter,
+                        SamplingRng,
+                        SamplingStorage,
+                        DaVerifierBackend,
+                        DaVerifierNetwork,
+                        DaVerifierStorage,
+                        TimeBackend,
+                        ApiAdapter,
+                        RuntimeServiceId,
+                        SIZE,
+                    >,
+                ),
+            )
+            .route(
+                paths::CRYPTARCHIA_REMOVE_LEADER_NOTE,
+                routing::post(
+                    cryptarchia_remove_leader_note::<
+                        Tx,
+                        DaStorageSerializer,
+                        SamplingBackend,
+                        SamplingNetworkAdapter,
+                        SamplingRng,
+                        SamplingStorage,
+                        DaVerifierBackend,
+                        DaVerifierNetwork,
+                        DaVerifierStorage,
+                        TimeBackend,
+                        ApiAdapter,
+                        RuntimeServiceId,
+                        SIZE,
+                    >,
+                ),
+            )
+            .route(
+                paths::CRYPTARCHIA_EXPORT_LEDGER_SNAPSHOT,
+                routing::post(
+                    cryptarchia_export_ledger_snapshot::<
+                        Tx,
+                        DaStorageSerializer,
+                        SamplingBackend,
+                        SamplingNetworkAdapter,
+                        SamplingRng,
+                        SamplingStorage,
+                        DaVerifierBackend,
+                        DaVerifierNetwork,
+                        DaVerifierStorage,
+                        TimeBackend,
+                        ApiAdapter,
+                        RuntimeServiceId,
+                        SIZE,
+                    >,
+                ),
+            )
             .route(
                 paths::DA_ADD_SHARE,
                 routing::post(
@@ -528,6 +632,26 @@ where
                 paths::STORAGE_BLOCK,
                 routing::post(block::<DaStorageSerializer, Tx, RuntimeServiceId>),
             )
+            .route(
+                paths::SDP_ACTIVE_PROVIDERS,
+                routing::post(
+                    sdp_active_providers::<
+                        Tx,
+                        DaStorageSerializer,
+                        SamplingBackend,
+                        SamplingNetworkAdapter,
+                        SamplingRng,
+                        SamplingStorage,
+                        DaVerifierBackend,
+                        DaVerifierNetwork,
+                        DaVerifierStorage,
+                        TimeBackend,
+                        ApiAdapter,
+                        RuntimeServiceId,
+                        SIZE,
+                    >,
+                ),
+            )
             .route(
                 paths::MEMPOOL_ADD_TX,
                 routing::post(add_tx::<Tx, RuntimeServiceId>),
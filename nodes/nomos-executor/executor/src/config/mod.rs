@@ -1,8 +1,8 @@
 use color_eyre::eyre::Result;
 use nomos_node::{
     config::{
-        mempool::MempoolConfig, update_blend, update_cryptarchia_consensus, update_network,
-        BlendArgs,
+        indexer::IndexerConfig, mempool::MempoolConfig, update_blend, update_cryptarchia_consensus,
+        update_network, BlendArgs,
     },
     CryptarchiaArgs, HttpArgs, LogArgs, NetworkArgs,
 };
@@ -10,9 +10,9 @@ use overwatch::services::ServiceData;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    ApiService, BlendService, CryptarchiaService, DaDispersalService, DaIndexerService,
-    DaNetworkService, DaSamplingService, DaVerifierService, NetworkService, RuntimeServiceId,
-    StorageService, TimeService,
+    ApiService, BlendService, CryptarchiaService, DaDispersalService, DaNetworkService,
+    DaSamplingService, DaVerifierService, KeyManagementService, NetworkService, RuntimeServiceId,
+    SdpService, StorageService, TimeService,
 };
 
 #[derive(Deserialize, Debug, Clone, Serialize)]
@@ -23,11 +23,13 @@ pub struct Config {
     pub blend: <BlendService as ServiceData>::Settings,
     pub da_dispersal: <DaDispersalService as ServiceData>::Settings,
     pub da_network: <DaNetworkService as ServiceData>::Settings,
-    pub da_indexer: <DaIndexerService as ServiceData>::Settings,
+    pub da_indexer: IndexerConfig,
     pub da_verifier: <DaVerifierService as ServiceData>::Settings,
     pub da_sampling: <DaSamplingService as ServiceData>::Settings,
     pub http: <ApiService as ServiceData>::Settings,
     pub cryptarchia: <CryptarchiaService as ServiceData>::Settings,
+    pub sdp: <SdpService as ServiceData>::Settings,
+    pub kms: <KeyManagementService as ServiceData>::Settings,
     pub time: <TimeService as ServiceData>::Settings,
     pub storage: <StorageService as ServiceData>::Settings,
     pub mempool: MempoolConfig,
@@ -51,10 +53,10 @@ impl Config {
     ) -> Result<Self> {
         #[cfg(feature = "tracing")]
         nomos_node::config::update_tracing(&mut self.tracing, log_args)?;
-        update_network::<RuntimeServiceId>(&mut self.network, network_args)?;
-        update_blend(&mut self.blend, blend_args)?;
+        update_network::<RuntimeServiceId>(&mut self.network, &mut self.kms, network_args)?;
+        update_blend(&mut self.blend, &mut self.kms, blend_args)?;
         update_http(&mut self.http, http_args)?;
-        update_cryptarchia_consensus(&mut self.cryptarchia, cryptarchia_args)?;
+        update_cryptarchia_consensus(&mut self.cryptarchia, &mut self.kms, cryptarchia_args)?;
         Ok(self)
     }
 }
@@ -26,7 +26,7 @@ use nomos_da_verifier::{
     network::adapters::executor::Libp2pAdapter as VerifierNetworkAdapter,
     storage::adapters::rocksdb::RocksAdapter as VerifierStorageAdapter,
 };
-use nomos_mempool::backend::mockpool::MockPool;
+use nomos_mempool::backend::priority::PriorityPool;
 #[cfg(feature = "tracing")]
 use nomos_node::Tracing;
 use nomos_node::{
@@ -50,7 +50,7 @@ pub(crate) type BlendService = nomos_blend_service::BlendService<
 
 type DispersalMempoolAdapter = KzgrsMempoolAdapter<
     MempoolNetworkAdapter<BlobInfo, <BlobInfo as DispersedBlobInfo>::BlobId, RuntimeServiceId>,
-    MockPool<HeaderId, BlobInfo, <BlobInfo as DispersedBlobInfo>::BlobId>,
+    PriorityPool<HeaderId, BlobInfo, <BlobInfo as DispersedBlobInfo>::BlobId>,
     KzgrsSamplingBackend<ChaCha20Rng>,
     nomos_da_sampling::network::adapters::executor::Libp2pAdapter<
         NomosDaMembership,
@@ -129,6 +129,25 @@ pub(crate) type CryptarchiaService = nomos_node::generic_services::CryptarchiaSe
 
 pub(crate) type TimeService = nomos_node::generic_services::TimeService<RuntimeServiceId>;
 
+pub(crate) type KeyManagementService =
+    nomos_node::generic_services::KeyManagementService<RuntimeServiceId>;
+
+pub(crate) type SdpService =
+    nomos_node::generic_services::SdpService<CryptarchiaService, RuntimeServiceId>;
+
+pub(crate) type DaMembershipService = nomos_da_membership::DaMembershipService<
+    DaNetworkExecutorBackend<NomosDaMembership>,
+    SdpService,
+    RuntimeServiceId,
+>;
+
+pub(crate) type BlendMembershipService = nomos_blend_membership::BlendMembershipService<
+    BlendBackend,
+    BlendNetworkAdapter<RuntimeServiceId>,
+    SdpService,
+    RuntimeServiceId,
+>;
+
 pub(crate) type ApiService = nomos_api::ApiService<
     AxumBackend<
         (),
@@ -181,6 +200,10 @@ pub struct NomosExecutor {
     cl_mempool: ClMempoolService,
     da_mempool: DaMempoolService,
     cryptarchia: CryptarchiaService,
+    sdp: SdpService,
+    da_membership: DaMembershipService,
+    blend_membership: BlendMembershipService,
+    kms: KeyManagementService,
     time: TimeService,
     http: ApiService,
     storage: StorageService,
@@ -6,8 +6,9 @@ use nomos_executor::{
 };
 use nomos_mempool::tx::settings::TxMempoolSettings;
 use nomos_node::{
-    config::BlendArgs, BlobInfo, CryptarchiaArgs, DaMempoolSettings, HttpArgs, LogArgs,
-    MempoolAdapterSettings, NetworkArgs, Transaction, Tx, CL_TOPIC, DA_TOPIC,
+    config::BlendArgs, size_weight, BlobInfo, CryptarchiaArgs, DaMempoolSettings, HttpArgs,
+    LogArgs, MempoolAdapterSettings, NetworkArgs, PriorityPoolSettings, Transaction, Tx, CL_TOPIC,
+    DA_TOPIC,
 };
 use overwatch::overwatch::OverwatchRunner;
 
@@ -58,7 +59,11 @@ fn main() -> Result<()> {
             tracing: config.tracing,
             http: config.http,
             cl_mempool: TxMempoolSettings {
-                pool: (),
+                pool: PriorityPoolSettings {
+                    max_pending_items: config.mempool.cl_pool.max_pending_items,
+                    item_ttl: config.mempool.cl_pool.item_ttl,
+                    weight: |tx: &Tx| size_weight(tx.as_bytes().len()),
+                },
                 network_adapter: MempoolAdapterSettings {
                     topic: String::from(CL_TOPIC),
                     id: <Tx as Transaction>::hash,
@@ -66,7 +71,11 @@ fn main() -> Result<()> {
                 recovery_path: config.mempool.cl_pool_recovery_path,
             },
             da_mempool: DaMempoolSettings {
-                pool: (),
+                pool: PriorityPoolSettings {
+                    max_pending_items: config.mempool.da_pool.max_pending_items,
+                    item_ttl: config.mempool.da_pool.item_ttl,
+                    weight: |info: &BlobInfo| size_weight(info.size()),
+                },
                 network_adapter: MempoolAdapterSettings {
                     topic: String::from(DA_TOPIC),
                     id: <BlobInfo as DispersedBlobInfo>::blob_id,
@@ -75,10 +84,16 @@ fn main() -> Result<()> {
             },
             da_dispersal: config.da_dispersal,
             da_network: config.da_network,
-            da_indexer: config.da_indexer,
+            da_indexer: config
+                .da_indexer
+                .into_settings(config.cryptarchia.config.epoch_length())?,
             da_sampling: config.da_sampling,
             da_verifier: config.da_verifier,
             cryptarchia: config.cryptarchia,
+            sdp: config.sdp,
+            da_membership: (),
+            blend_membership: (),
+            kms: config.kms,
             time: config.time,
             storage: config.storage,
             system_sig: (),
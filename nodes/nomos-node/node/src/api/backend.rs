@@ -10,6 +10,7 @@ use nomos_api::{
     http::{
         consensus::Cryptarchia,
         da::{DaIndexer, DaVerifier},
+        sdp::Sdp,
     },
     Backend,
 };
@@ -23,14 +24,15 @@ use nomos_core::{
 };
 use nomos_da_network_core::SubnetworkId;
 use nomos_da_network_service::backends::libp2p::validator::DaNetworkValidatorBackend;
-use nomos_da_sampling::backend::DaSamplingServiceBackend;
+use nomos_da_sampling::{backend::DaSamplingServiceBackend, DaSamplingService};
 use nomos_da_verifier::backend::VerifierBackend;
 use nomos_http_api_common::paths;
 use nomos_libp2p::PeerId;
 use nomos_mempool::{
-    backend::mockpool::MockPool, tx::service::openapi::Status, DaMempoolService, MempoolMetrics,
-    TxMempoolService,
+    backend::priority::PriorityPool, tx::service::openapi::Status, DaMempoolService,
+    MempoolMetrics, TxMempoolService,
 };
+use nomos_sdp::consensus::SdpTransaction;
 use nomos_storage::{
     backends::{rocksdb::RocksBackend, StorageSerde},
     StorageService,
@@ -48,8 +50,11 @@ use utoipa_swagger_ui::SwaggerUi;
 
 use super::handlers::{
     add_blob_info, add_share, add_tx, balancer_stats, blacklisted_peers, block, block_peer,
-    cl_metrics, cl_status, cryptarchia_headers, cryptarchia_info, da_get_commitments,
-    da_get_light_share, da_get_shares, get_range, libp2p_info, monitor_stats, unblock_peer,
+    cl_metrics, cl_status, cryptarchia_add_leader_note, cryptarchia_export_ledger_snapshot,
+    cryptarchia_headers, cryptarchia_info, cryptarchia_leader_notes,
+    cryptarchia_remove_leader_note, da_get_commitments, da_get_light_share, da_get_shares,
+    get_range, libp2p_info, monitor_stats, retrieve_blob, retrieve_range, sdp_active_providers,
+    unblock_peer,
 };
 
 /// Configuration for the Http Server
@@ -200,6 +205,7 @@ where
     DaVerifierStorage:
         nomos_da_verifier::storage::DaStorageAdapter<RuntimeServiceId> + Send + Sync + 'static,
     Tx: Transaction
+        + SdpTransaction
         + Clone
         + Debug
         + Eq
@@ -211,6 +217,8 @@ where
         + 'static,
     <Tx as nomos_core::tx::Transaction>::Hash:
         Serialize + for<'de> Deserialize<'de> + std::cmp::Ord + Debug + Send + Sync + 'static,
+    <Tx as SdpTransaction>::Metadata: Clone + Send + Sync + 'static,
+    <Tx as SdpTransaction>::Proof: Clone + Send + Sync + 'static,
     DaStorageSerializer: StorageSerde + Send + Sync + 'static,
     <DaStorageSerializer as StorageSerde>::Error: Send + Sync,
     SamplingRng: SeedableRng + RngCore + Send + 'static,
@@ -264,6 +272,23 @@ where
                 SIZE,
             >,
         >
+        + AsServiceId<
+            Sdp<
+                Tx,
+                DaStorageSerializer,
+                SamplingBackend,
+                SamplingNetworkAdapter,
+                SamplingRng,
+                SamplingStorage,
+                DaVerifierBackend,
+                DaVerifierNetwork,
+                DaVerifierStorage,
+                TimeBackend,
+                ApiAdapter,
+                RuntimeServiceId,
+                SIZE,
+            >,
+        >
         + AsServiceId<
             DaVerifier<
                 DaShare,
@@ -292,6 +317,19 @@ where
                 SIZE,
             >,
         >
+        + AsServiceId<
+            DaSamplingService<
+                SamplingBackend,
+                SamplingNetworkAdapter,
+                SamplingRng,
+                SamplingStorage,
+                DaVerifierBackend,
+                DaVerifierNetwork,
+                DaVerifierStorage,
+                ApiAdapter,
+                RuntimeServiceId,
+            >,
+        >
         + AsServiceId<
             nomos_da_network_service::NetworkService<
                 DaNetworkValidatorBackend<Membership>,
@@ -312,7 +350,7 @@ where
                     <Tx as Transaction>::Hash,
                     RuntimeServiceId,
                 >,
-                MockPool<HeaderId, Tx, <Tx as Transaction>::Hash>,
+                PriorityPool<HeaderId, Tx, <Tx as Transaction>::Hash>,
                 RuntimeServiceId,
             >,
         >
@@ -323,7 +361,7 @@ where
                     DaVerifiedBlobInfo::BlobId,
                     RuntimeServiceId,
                 >,
-                MockPool<HeaderId, DaVerifiedBlobInfo, DaVerifiedBlobInfo::BlobId>,
+                PriorityPool<HeaderId, DaVerifiedBlobInfo, DaVerifiedBlobInfo::BlobId>,
                 SamplingBackend,
                 SamplingNetworkAdapter,
                 SamplingRng,
@@ -436,6 +474,86 @@ where
                     >,
                 ),
             )
+            .route(
+                paths::CRYPTARCHIA_LEADER_NOTES,
+                routing::get(
+                    cryptarchia_leader_notes::<
+                        Tx,
+                        DaStorageSerializer,
+                        SamplingBackend,
+                        SamplingNetworkAdapter,
+                        SamplingRng,
+                        SamplingStorage,
+                        DaVerifierBackend,
+                        DaVerifierNetwork,
+                        DaVerifierStorage,
+                        TimeBackend,
+                        ApiAdapter,
+                        RuntimeServiceId,
+                        SIZE,
+                    >,
+                ),
+            )
+            .route(
+                paths::CRYPTARCHIA_ADD_LEADER_NOTE,
+                routing::post(
+                    cryptarchia_add_leader_note::<
+                        Tx,
+                        DaStorageSerializer,
+                        SamplingBackend,
+                        SamplingNetworkAdapter,
+                        SamplingRng,
+                        SamplingStorage,
+                        DaVerifierBackend,
+                        DaVerifierNetwork,
+                        DaVerifierStorage,
+                        TimeBackend,
+                        ApiAdapter,
+                        RuntimeServiceId,
+                        SIZE,
+                    >,
+                ),
+            )
+            .route(
+                paths::CRYPTARCHIA_REMOVE_LEADER_NOTE,
+                routing::post(
+                    cryptarchia_remove_leader_note::<
+                        Tx,
+                        DaStorageSerializer,
+                        SamplingBackend,
+                        SamplingNetworkAdapter,
+                        SamplingRng,
+                        SamplingStorage,
+                        DaVerifierBackend,
+                        DaVerifierNetwork,
+                        DaVerifierStorage,
+                        TimeBackend,
+                        ApiAdapter,
+                        RuntimeServiceId,
+                        SIZE,
+                    >,
+                ),
+            )
+            .route(
+                paths::CRYPTARCHIA_EXPORT_LEDGER_SNAPSHOT,
+                routing::post(
+                    cryptarchia_export_ledger_snapshot::<
+                        Tx,
+                        DaStorageSerializer,
+                        SamplingBackend,
+                        SamplingNetworkAdapter,
+                        SamplingRng,
+                        SamplingStorage,
+                        DaVerifierBackend,
+                        DaVerifierNetwork,
+                        DaVerifierStorage,
+                        TimeBackend,
+                        ApiAdapter,
+                        RuntimeServiceId,
+                        SIZE,
+                    >,
+                ),
+            )
             .route(
                 paths::DA_ADD_SHARE,
                 routing::post(
@@ -471,6 +589,44 @@ where
                     >,
                 ),
             )
+            .route(
+                paths::DA_RETRIEVE_BLOB,
+                routing::post(
+                    retrieve_blob::<
+                        SamplingBackend,
+                        SamplingNetworkAdapter,
+                        SamplingRng,
+                        SamplingStorage,
+                        DaVerifierBackend,
+                        DaVerifierNetwork,
+                        DaVerifierStorage,
+                        ApiAdapter,
+                        RuntimeServiceId,
+                    >,
+                ),
+            )
+            .route(
+                paths::DA_RETRIEVE_RANGE,
+                routing::post(
+                    retrieve_range::<
+                        Tx,
+                        DaBlobInfo,
+                        DaVerifiedBlobInfo,
+                        DaStorageSerializer,
+                        SamplingBackend,
+                        SamplingNetworkAdapter,
+                        SamplingRng,
+                        SamplingStorage,
+                        DaVerifierBackend,
+                        DaVerifierNetwork,
+                        DaVerifierStorage,
+                        TimeBackend,
+                        ApiAdapter,
+                        RuntimeServiceId,
+                        SIZE,
+                    >,
+                ),
+            )
             .route(
                 paths::DA_BLOCK_PEER,
                 routing::post(
@@ -497,6 +653,26 @@ where
                 paths::STORAGE_BLOCK,
                 routing::post(block::<DaStorageSerializer, Tx, RuntimeServiceId>),
             )
+            .route(
+                paths::SDP_ACTIVE_PROVIDERS,
+                routing::post(
+                    sdp_active_providers::<
+                        Tx,
+                        DaStorageSerializer,
+                        SamplingBackend,
+                        SamplingNetworkAdapter,
+                        SamplingRng,
+                        SamplingStorage,
+                        DaVerifierBackend,
+                        DaVerifierNetwork,
+                        DaVerifierStorage,
+                        TimeBackend,
+                        ApiAdapter,
+                        RuntimeServiceId,
+                        SIZE,
+                    >,
+                ),
+            )
             .route(
                 paths::MEMPOOL_ADD_TX,
                 routing::post(add_tx::<Tx, RuntimeServiceId>),
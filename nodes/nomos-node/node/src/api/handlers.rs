@@ -10,12 +10,15 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
+use cl::{NoteCommitment, NoteWitness};
 use http::{header, StatusCode};
 use nomos_api::http::{
     cl::{self, ClMempoolService},
     consensus::{self, Cryptarchia},
     da::{self, BalancerMessageFactory, DaIndexer, DaVerifier, MonitorMessageFactory},
-    da_shares, libp2p, mempool, storage,
+    da_shares, libp2p, mempool,
+    sdp::{self, Sdp},
+    storage,
 };
 use nomos_core::{
     da::{
@@ -26,18 +29,21 @@ use nomos_core::{
     tx::Transaction,
 };
 use nomos_da_messages::http::da::{
-    DASharesCommitmentsRequest, DaSamplingRequest, GetRangeReq, GetSharesRequest,
+    DASharesCommitmentsRequest, DaSamplingRequest, GetRangeReq, GetSharesRequest, RetrieveBlobReq,
 };
 use nomos_da_network_service::{backends::NetworkBackend, NetworkService};
-use nomos_da_sampling::backend::DaSamplingServiceBackend;
+use nomos_da_sampling::{backend::DaSamplingServiceBackend, DaSamplingService};
 use nomos_da_verifier::backend::VerifierBackend;
 use nomos_http_api_common::paths;
 use nomos_libp2p::PeerId;
 use nomos_mempool::{
-    backend::mockpool::MockPool, network::adapters::libp2p::Libp2pAdapter as MempoolNetworkAdapter,
-    DaMempoolService, TxMempoolService,
+    backend::priority::PriorityPool,
+    network::adapters::libp2p::Libp2pAdapter as MempoolNetworkAdapter, DaMempoolService,
+    TxMempoolService,
 };
 use nomos_network::backends::libp2p::Libp2p as Libp2pNetworkBackend;
+use nomos_sdp::consensus::SdpTransaction;
+use nomos_sdp_core::ServiceType;
 use nomos_storage::{
     backends::{rocksdb::RocksBackend, StorageSerde},
     StorageService,
@@ -306,49 +312,609 @@ where
     >(&store, from, to))
 }
 
+#[utoipa::path(
+    get,
+    path = paths::CRYPTARCHIA_LEADER_NOTES,
+    responses(
+        (status = 200, description = "Query the leader notes evolved on top of each tip", body = Vec<nomos_consensus::LeaderNotesInfo>),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn cryptarchia_leader_notes<
+    Tx,
+    SS,
+    SamplingBackend,
+    SamplingNetworkAdapter,
+    SamplingRng,
+    SamplingStorage,
+    DaVerifierBackend,
+    DaVerifierNetwork,
+    DaVerifierStorage,
+    TimeBackend,
+    ApiAdapter,
+    RuntimeServiceId,
+    const SIZE: usize,
+>(
+    State(handle): State<OverwatchHandle<RuntimeServiceId>>,
+) -> Response
+where
+    Tx: Transaction
+        + Clone
+        + Eq
+        + Debug
+        + Hash
+        + Serialize
+        + DeserializeOwned
+        + Send
+        + Sync
+        + 'static,
+    <Tx as Transaction>::Hash:
+        std::cmp::Ord + Debug + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static,
+    SS: StorageSerde + Send + Sync + 'static,
+    SamplingRng: SeedableRng + RngCore,
+    SamplingBackend: DaSamplingServiceBackend<SamplingRng, BlobId = BlobId> + Send,
+    SamplingBackend::Settings: Clone,
+    SamplingBackend::Share: Debug + 'static,
+    SamplingBackend::BlobId: Debug + 'static,
+    SamplingNetworkAdapter: nomos_da_sampling::network::NetworkAdapter<RuntimeServiceId>,
+    SamplingStorage: nomos_da_sampling::storage::DaStorageAdapter<RuntimeServiceId>,
+    DaVerifierStorage: nomos_da_verifier::storage::DaStorageAdapter<RuntimeServiceId>,
+    DaVerifierBackend: nomos_da_verifier::backend::VerifierBackend + Send + 'static,
+    DaVerifierBackend::Settings: Clone,
+    DaVerifierNetwork: nomos_da_verifier::network::NetworkAdapter<RuntimeServiceId>,
+    DaVerifierNetwork::Settings: Clone,
+    TimeBackend: nomos_time::backends::TimeBackend,
+    TimeBackend::Settings: Clone + Send + Sync,
+    ApiAdapter: nomos_da_sampling::api::ApiAdapter + Send + Sync,
+    RuntimeServiceId: Debug
+        + Sync
+        + Display
+        + 'static
+        + AsServiceId<
+            Cryptarchia<
+                Tx,
+                SS,
+                SamplingBackend,
+                SamplingNetworkAdapter,
+                SamplingRng,
+                SamplingStorage,
+                DaVerifierBackend,
+                DaVerifierNetwork,
+                DaVerifierStorage,
+                TimeBackend,
+                ApiAdapter,
+                RuntimeServiceId,
+                SIZE,
+            >,
+        >,
+{
+    make_request_and_return_response!(consensus::cryptarchia_leader_notes::<
+        Tx,
+        SS,
+        SamplingBackend,
+        SamplingNetworkAdapter,
+        SamplingRng,
+        SamplingStorage,
+        DaVerifierBackend,
+        DaVerifierNetwork,
+        DaVerifierStorage,
+        TimeBackend,
+        ApiAdapter,
+        RuntimeServiceId,
+        SIZE,
+    >(&handle))
+}
+
+#[utoipa::path(
+    post,
+    path = paths::CRYPTARCHIA_ADD_LEADER_NOTE,
+    responses(
+        (status = 200, description = "Whether the note was added to the leader notes", body = bool),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn cryptarchia_add_leader_note<
+    Tx,
+    SS,
+    SamplingBackend,
+    SamplingNetworkAdapter,
+    SamplingRng,
+    SamplingStorage,
+    DaVerifierBackend,
+    DaVerifierNetwork,
+    DaVerifierStorage,
+    TimeBackend,
+    ApiAdapter,
+    RuntimeServiceId,
+    const SIZE: usize,
+>(
+    State(handle): State<OverwatchHandle<RuntimeServiceId>>,
+    Json(note): Json<NoteWitness>,
+) -> Response
+where
+    Tx: Transaction
+        + Clone
+        + Eq
+        + Debug
+        + Hash
+        + Serialize
+        + DeserializeOwned
+        + Send
+        + Sync
+        + 'static,
+    <Tx as Transaction>::Hash:
+        std::cmp::Ord + Debug + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static,
+    SS: StorageSerde + Send + Sync + 'static,
+    SamplingRng: SeedableRng + RngCore,
+    SamplingBackend: DaSamplingServiceBackend<SamplingRng, BlobId = BlobId> + Send,
+    SamplingBackend::Settings: Clone,
+    SamplingBackend::Share: Debug + 'static,
+    SamplingBackend::BlobId: Debug + 'static,
+    SamplingNetworkAdapter: nomos_da_sampling::network::NetworkAdapter<RuntimeServiceId>,
+    SamplingStorage: nomos_da_sampling::storage::DaStorageAdapter<RuntimeServiceId>,
+    DaVerifierStorage: nomos_da_verifier::storage::DaStorageAdapter<RuntimeServiceId>,
+    DaVerifierBackend: nomos_da_verifier::backend::VerifierBackend + Send + 'static,
+    DaVerifierBackend::Settings: Clone,
+    DaVerifierNetwork: nomos_da_verifier::network::NetworkAdapter<RuntimeServiceId>,
+    DaVerifierNetwork::Settings: Clone,
+    TimeBackend: nomos_time::backends::TimeBackend,
+    TimeBackend::Settings: Clone + Send + Sync,
+    ApiAdapter: nomos_da_sampling::api::ApiAdapter + Send + Sync,
+    RuntimeServiceId: Debug
+        + Sync
+        + Display
+        + 'static
+        + AsServiceId<
+            Cryptarchia<
+                Tx,
+                SS,
+                SamplingBackend,
+                SamplingNetworkAdapter,
+                SamplingRng,
+                SamplingStorage,
+                DaVerifierBackend,
+                DaVerifierNetwork,
+                DaVerifierStorage,
+                TimeBackend,
+                ApiAdapter,
+                RuntimeServiceId,
+                SIZE,
+            >,
+        >,
+{
+    make_request_and_return_response!(consensus::cryptarchia_add_leader_note::<
+        Tx,
+        SS,
+        SamplingBackend,
+        SamplingNetworkAdapter,
+        SamplingRng,
+        SamplingStorage,
+        DaVerifierBackend,
+        DaVerifierNetwork,
+        DaVerifierStorage,
+        TimeBackend,
+        ApiAdapter,
+        RuntimeServiceId,
+        SIZE,
+    >(&handle, note))
+}
+
+#[utoipa::path(
+    post,
+    path = paths::CRYPTARCHIA_REMOVE_LEADER_NOTE,
+    responses(
+        (status = 200, description = "Whether the note was removed from the leader notes", body = bool),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn cryptarchia_remove_leader_note<
+    Tx,
+    SS,
+    SamplingBackend,
+    SamplingNetworkAdapter,
+    SamplingRng,
+    SamplingStorage,
+    DaVerifierBackend,
+    DaVerifierNetwork,
+    DaVerifierStorage,
+    TimeBackend,
+    ApiAdapter,
+    RuntimeServiceId,
+    const SIZE: usize,
+>(
+    State(handle): State<OverwatchHandle<RuntimeServiceId>>,
+    Json(commitment): Json<NoteCommitment>,
+) -> Response
+where
+    Tx: Transaction
+        + Clone
+        + Eq
+        + Debug
+        + Hash
+        + Serialize
+        + DeserializeOwned
+        + Send
+        + Sync
+        + 'static,
+    <Tx as Transaction>::Hash:
+        std::cmp::Ord + Debug + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static,
+    SS: StorageSerde + Send + Sync + 'static,
+    SamplingRng: SeedableRng + RngCore,
+    SamplingBackend: DaSamplingServiceBackend<SamplingRng, BlobId = BlobId> + Send,
+    SamplingBackend::Settings: Clone,
+    SamplingBackend::Share: Debug + 'static,
+    SamplingBackend::BlobId: Debug + 'static,
+    SamplingNetworkAdapter: nomos_da_sampling::network::NetworkAdapter<RuntimeServiceId>,
+    SamplingStorage: nomos_da_sampling::storage::DaStorageAdapter<RuntimeServiceId>,
+    DaVerifierStorage: nomos_da_verifier::storage::DaStorageAdapter<RuntimeServiceId>,
+    DaVerifierBackend: nomos_da_verifier::backend::VerifierBackend + Send + 'static,
+    DaVerifierBackend::Settings: Clone,
+    DaVerifierNetwork: nomos_da_verifier::network::NetworkAdapter<RuntimeServiceId>,
+    DaVerifierNetwork::Settings: Clone,
+    TimeBackend: nomos_time::backends::TimeBackend,
+    TimeBackend::Settings: Clone + Send + Sync,
+    ApiAdapter: nomos_da_sampling::api::ApiAdapter + Send + Sync,
+    RuntimeServiceId: Debug
+        + Sync
+        + Display
+        + 'static
+        + AsServiceId<
+            Cryptarchia<
+                Tx,
+                SS,
+                SamplingBackend,
+                SamplingNetworkAdapter,
+                SamplingRng,
+                SamplingStorage,
+                DaVerifierBackend,
+                DaVerifierNetwork,
+                DaVerifierStorage,
+                TimeBackend,
+                ApiAdapter,
+                RuntimeServiceId,
+                SIZE,
+            >,
+        >,
+{
+    make_request_and_return_response!(consensus::cryptarchia_remove_leader_note::<
+        Tx,
+        SS,
+        SamplingBackend,
+        SamplingNetworkAdapter,
+        SamplingRng,
+        SamplingStorage,
+        DaVerifierBackend,
+        DaVerifierNetwork,
+        DaVerifierStorage,
+        TimeBackend,
+        ApiAdapter,
+        RuntimeServiceId,
+        SIZE,
+    >(&handle, commitment))
+}
+
+#[utoipa::path(
+    post,
+    path = paths::CRYPTARCHIA_EXPORT_LEDGER_SNAPSHOT,
+    responses(
+        (status = 200, description = "Path of the signed ledger snapshot written by the node", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn cryptarchia_export_ledger_snapshot<
+    Tx,
+    SS,
+    SamplingBackend,
+    SamplingNetworkAdapter,
+    SamplingRng,
+    SamplingStorage,
+    DaVerifierBackend,
+    DaVerifierNetwork,
+    DaVerifierStorage,
+    TimeBackend,
+    ApiAdapter,
+    RuntimeServiceId,
+    const SIZE: usize,
+>(
+    State(handle): State<OverwatchHandle<RuntimeServiceId>>,
+    Json(id): Json<Option<HeaderId>>,
+) -> Response
+where
+    Tx: Transaction
+        + Clone
+        + Eq
+        + Debug
+        + Hash
+        + Serialize
+        + DeserializeOwned
+        + Send
+        + Sync
+        + 'static,
+    <Tx as Transaction>::Hash:
+        std::cmp::Ord + Debug + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static,
+    SS: StorageSerde + Send + Sync + 'static,
+    SamplingRng: SeedableRng + RngCore,
+    SamplingBackend: DaSamplingServiceBackend<SamplingRng, BlobId = BlobId> + Send,
+    SamplingBackend::Settings: Clone,
+    SamplingBackend::Share: Debug + 'static,
+    SamplingBackend::BlobId: Debug + 'static,
+    SamplingNetworkAdapter: nomos_da_sampling::network::NetworkAdapter<RuntimeServiceId>,
+    SamplingStorage: nomos_da_sampling::storage::DaStorageAdapter<RuntimeServiceId>,
+    DaVerifierStorage: nomos_da_verifier::storage::DaStorageAdapter<RuntimeServiceId>,
+    DaVerifierBackend: nomos_da_verifier::backend::VerifierBackend + Send + 'static,
+    DaVerifierBackend::Settings: Clone,
+    DaVerifierNetwork: nomos_da_verifier::network::NetworkAdapter<RuntimeServiceId>,
+    DaVerifierNetwork::Settings: Clone,
+    TimeBackend: nomos_time::backends::TimeBackend,
+    TimeBackend::Settings: Clone + Send + Sync,
+    ApiAdapter: nomos_da_sampling::api::ApiAdapter + Send + Sync,
+    RuntimeServiceId: Debug
+        + Sync
+        + Display
+        + 'static
+        + AsServiceId<
+            Cryptarchia<
+                Tx,
+                SS,
+                SamplingBackend,
+                SamplingNetworkAdapter,
+                SamplingRng,
+                SamplingStorage,
+                DaVerifierBackend,
+                DaVerifierNetwork,
+                DaVerifierStorage,
+                TimeBackend,
+                ApiAdapter,
+                RuntimeServiceId,
+                SIZE,
+            >,
+        >,
+{
+    make_request_and_return_response!(consensus::cryptarchia_export_ledger_snapshot::<
+        Tx,
+        SS,
+        SamplingBackend,
+        SamplingNetworkAdapter,
+        SamplingRng,
+        SamplingStorage,
+        DaVerifierBackend,
+        DaVerifierNetwork,
+        DaVerifierStorage,
+        TimeBackend,
+        ApiAdapter,
+        RuntimeServiceId,
+        SIZE,
+    >(&handle, id))
+}
+
+#[utoipa::path(
+    post,
+    path = paths::DA_ADD_SHARE,
+    responses(
+        (status = 200, description = "Share to be published received"),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn add_share<A, S, N, VB, SS, RuntimeServiceId>(
+    State(handle): State<OverwatchHandle<RuntimeServiceId>>,
+    Json(share): Json<S>,
+) -> Response
+where
+    A: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+    S: Share + Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+    <S as Share>::BlobId: AsRef<[u8]> + Send + Sync + 'static,
+    <S as Share>::ShareIndex:
+        AsRef<[u8]> + Serialize + DeserializeOwned + Hash + Eq + Send + Sync + 'static,
+    <S as Share>::LightShare: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+    <S as Share>::SharesCommitments: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+    N: nomos_da_verifier::network::NetworkAdapter<RuntimeServiceId>,
+    N::Settings: Clone,
+    VB: VerifierBackend + CoreDaVerifier<DaShare = S>,
+    <VB as VerifierBackend>::Settings: Clone,
+    <VB as CoreDaVerifier>::Error: Error,
+    SS: StorageSerde + Send + Sync + 'static,
+    RuntimeServiceId:
+        Debug + Sync + Display + 'static + AsServiceId<DaVerifier<S, N, VB, SS, RuntimeServiceId>>,
+{
+    make_request_and_return_response!(da::add_share::<A, S, N, VB, SS, RuntimeServiceId>(
+        &handle, share
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = paths::DA_GET_RANGE,
+    responses(
+        (status = 200, description = "Range of blobs", body = Vec<([u8;8], Vec<DaShare>)>),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn get_range<
+    Tx,
+    C,
+    V,
+    SS,
+    SamplingBackend,
+    SamplingNetworkAdapter,
+    SamplingRng,
+    SamplingStorage,
+    DaVerifierBackend,
+    DaVerifierNetwork,
+    DaVerifierStorage,
+    TimeBackend,
+    ApiAdapter,
+    RuntimeServiceId,
+    const SIZE: usize,
+>(
+    State(handle): State<OverwatchHandle<RuntimeServiceId>>,
+    Json(GetRangeReq { app_id, range }): Json<GetRangeReq<V>>,
+) -> Response
+where
+    Tx: Transaction
+        + Eq
+        + Clone
+        + Debug
+        + Hash
+        + Serialize
+        + DeserializeOwned
+        + Send
+        + Sync
+        + 'static,
+    <Tx as Transaction>::Hash:
+        std::cmp::Ord + Debug + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static,
+    C: DispersedBlobInfo<BlobId = [u8; 32]>
+        + Clone
+        + Debug
+        + Serialize
+        + DeserializeOwned
+        + Send
+        + Sync
+        + 'static,
+    <C as DispersedBlobInfo>::BlobId: Clone + Send + Sync,
+    V: DispersedBlobInfo<BlobId = [u8; 32]>
+        + From<C>
+        + Eq
+        + Debug
+        + Metadata
+        + Hash
+        + Clone
+        + Serialize
+        + DeserializeOwned
+        + Send
+        + Sync
+        + 'static,
+    <V as DispersedBlobInfo>::BlobId: Debug + Clone + Ord + Hash,
+    <V as Metadata>::AppId: AsRef<[u8]> + Clone + Serialize + DeserializeOwned + Send + Sync,
+    <V as Metadata>::Index:
+        AsRef<[u8]> + Clone + Serialize + DeserializeOwned + PartialOrd + Send + Sync,
+    SS: StorageSerde + Send + Sync + 'static,
+    SamplingRng: SeedableRng + RngCore,
+    SamplingBackend:
+        DaSamplingServiceBackend<SamplingRng, BlobId = <V as DispersedBlobInfo>::BlobId> + Send,
+    SamplingBackend::Settings: Clone,
+    SamplingBackend::Share: Debug + 'static,
+    SamplingBackend::BlobId: Debug + 'static,
+    SamplingNetworkAdapter: nomos_da_sampling::network::NetworkAdapter<RuntimeServiceId>,
+    SamplingStorage: nomos_da_sampling::storage::DaStorageAdapter<RuntimeServiceId>,
+    DaVerifierStorage: nomos_da_verifier::storage::DaStorageAdapter<RuntimeServiceId>,
+    DaVerifierBackend: nomos_da_verifier::backend::VerifierBackend + Send + 'static,
+    DaVerifierBackend::Settings: Clone,
+    DaVerifierNetwork: nomos_da_verifier::network::NetworkAdapter<RuntimeServiceId>,
+    DaVerifierNetwork::Settings: Clone,
+    TimeBackend: nomos_time::backends::TimeBackend,
+    TimeBackend::Settings: Clone + Send + Sync,
+    ApiAdapter: nomos_da_sampling::api::ApiAdapter + Send + Sync,
+    RuntimeServiceId: Debug
+        + Sync
+        + Display
+        + 'static
+        + AsServiceId<
+            DaIndexer<
+                Tx,
+                C,
+                V,
+                SS,
+                SamplingBackend,
+                SamplingNetworkAdapter,
+                SamplingRng,
+                SamplingStorage,
+                DaVerifierBackend,
+                DaVerifierNetwork,
+                DaVerifierStorage,
+                TimeBackend,
+                ApiAdapter,
+                RuntimeServiceId,
+                SIZE,
+            >,
+        >,
+{
+    make_request_and_return_response!(da::get_range::<
+        Tx,
+        C,
+        V,
+        SS,
+        SamplingBackend,
+        SamplingNetworkAdapter,
+        SamplingRng,
+        SamplingStorage,
+        DaVerifierBackend,
+        DaVerifierNetwork,
+        DaVerifierStorage,
+        TimeBackend,
+        ApiAdapter,
+        RuntimeServiceId,
+        SIZE,
+    >(&handle, app_id, range))
+}
+
 #[utoipa::path(
     post,
-    path = paths::DA_ADD_SHARE,
+    path = paths::DA_RETRIEVE_BLOB,
     responses(
-        (status = 200, description = "Share to be published received"),
+        (status = 200, description = "Data of the blob reconstructed from the network", body = Vec<u8>),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
-pub async fn add_share<A, S, N, VB, SS, RuntimeServiceId>(
+pub async fn retrieve_blob<
+    SamplingBackend,
+    SamplingNetworkAdapter,
+    SamplingRng,
+    SamplingStorage,
+    DaVerifierBackend,
+    DaVerifierNetwork,
+    DaVerifierStorage,
+    ApiAdapter,
+    RuntimeServiceId,
+>(
     State(handle): State<OverwatchHandle<RuntimeServiceId>>,
-    Json(share): Json<S>,
+    Json(RetrieveBlobReq { blob_id }): Json<RetrieveBlobReq>,
 ) -> Response
 where
-    A: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
-    S: Share + Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
-    <S as Share>::BlobId: AsRef<[u8]> + Send + Sync + 'static,
-    <S as Share>::ShareIndex:
-        AsRef<[u8]> + Serialize + DeserializeOwned + Hash + Eq + Send + Sync + 'static,
-    <S as Share>::LightShare: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
-    <S as Share>::SharesCommitments: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
-    N: nomos_da_verifier::network::NetworkAdapter<RuntimeServiceId>,
-    N::Settings: Clone,
-    VB: VerifierBackend + CoreDaVerifier<DaShare = S>,
-    <VB as VerifierBackend>::Settings: Clone,
-    <VB as CoreDaVerifier>::Error: Error,
-    SS: StorageSerde + Send + Sync + 'static,
-    RuntimeServiceId:
-        Debug + Sync + Display + 'static + AsServiceId<DaVerifier<S, N, VB, SS, RuntimeServiceId>>,
+    SamplingRng: SeedableRng + RngCore,
+    SamplingBackend: DaSamplingServiceBackend<SamplingRng, BlobId = BlobId>,
+    SamplingNetworkAdapter: nomos_da_sampling::network::NetworkAdapter<RuntimeServiceId>,
+    SamplingStorage: nomos_da_sampling::storage::DaStorageAdapter<RuntimeServiceId>,
+    ApiAdapter: nomos_da_sampling::api::ApiAdapter,
+    RuntimeServiceId: Debug
+        + Sync
+        + Display
+        + 'static
+        + AsServiceId<
+            DaSamplingService<
+                SamplingBackend,
+                SamplingNetworkAdapter,
+                SamplingRng,
+                SamplingStorage,
+                DaVerifierBackend,
+                DaVerifierNetwork,
+                DaVerifierStorage,
+                ApiAdapter,
+                RuntimeServiceId,
+            >,
+        >,
 {
-    make_request_and_return_response!(da::add_share::<A, S, N, VB, SS, RuntimeServiceId>(
-        &handle, share
-    ))
+    make_request_and_return_response!(da::retrieve_blob::<
+        SamplingBackend,
+        SamplingNetworkAdapter,
+        SamplingRng,
+        SamplingStorage,
+        DaVerifierBackend,
+        DaVerifierNetwork,
+        DaVerifierStorage,
+        ApiAdapter,
+        RuntimeServiceId,
+    >(&handle, blob_id))
 }
 
 #[utoipa::path(
     post,
-    path = paths::DA_GET_RANGE,
+    path = paths::DA_RETRIEVE_RANGE,
     responses(
-        (status = 200, description = "Range of blobs", body = Vec<([u8;8], Vec<DaShare>)>),
+        (status = 200, description = "Data of the blobs in the range reconstructed from the network", body = Vec<([u8;8], Vec<u8>)>),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
-pub async fn get_range<
+pub async fn retrieve_range<
     Tx,
     C,
     V,
@@ -408,8 +974,7 @@ where
         AsRef<[u8]> + Clone + Serialize + DeserializeOwned + PartialOrd + Send + Sync,
     SS: StorageSerde + Send + Sync + 'static,
     SamplingRng: SeedableRng + RngCore,
-    SamplingBackend:
-        DaSamplingServiceBackend<SamplingRng, BlobId = <V as DispersedBlobInfo>::BlobId> + Send,
+    SamplingBackend: DaSamplingServiceBackend<SamplingRng, BlobId = BlobId> + Send,
     SamplingBackend::Settings: Clone,
     SamplingBackend::Share: Debug + 'static,
     SamplingBackend::BlobId: Debug + 'static,
@@ -445,9 +1010,22 @@ where
                 RuntimeServiceId,
                 SIZE,
             >,
+        >
+        + AsServiceId<
+            DaSamplingService<
+                SamplingBackend,
+                SamplingNetworkAdapter,
+                SamplingRng,
+                SamplingStorage,
+                DaVerifierBackend,
+                DaVerifierNetwork,
+                DaVerifierStorage,
+                ApiAdapter,
+                RuntimeServiceId,
+            >,
         >,
 {
-    make_request_and_return_response!(da::get_range::<
+    make_request_and_return_response!(da::retrieve_range::<
         Tx,
         C,
         V,
@@ -580,6 +1158,103 @@ where
     make_request_and_return_response!(storage::block_req::<S, Tx, RuntimeServiceId>(&handle, id))
 }
 
+#[utoipa::path(
+    post,
+    path = paths::SDP_ACTIVE_PROVIDERS,
+    responses(
+        (status = 200, description = "Get the active providers of a service with their locators", body = Vec<(ProviderId, Vec<Locator>)>),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn sdp_active_providers<
+    Tx,
+    SS,
+    SamplingBackend,
+    SamplingNetworkAdapter,
+    SamplingRng,
+    SamplingStorage,
+    DaVerifierBackend,
+    DaVerifierNetwork,
+    DaVerifierStorage,
+    TimeBackend,
+    ApiAdapter,
+    RuntimeServiceId,
+    const SIZE: usize,
+>(
+    State(handle): State<OverwatchHandle<RuntimeServiceId>>,
+    Json(service_type): Json<ServiceType>,
+) -> Response
+where
+    Tx: Transaction
+        + SdpTransaction
+        + Clone
+        + Eq
+        + Debug
+        + Hash
+        + Serialize
+        + DeserializeOwned
+        + Send
+        + Sync
+        + 'static,
+    <Tx as Transaction>::Hash:
+        std::cmp::Ord + Debug + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static,
+    <Tx as SdpTransaction>::Metadata: Clone + Send + Sync + 'static,
+    <Tx as SdpTransaction>::Proof: Clone + Send + Sync + 'static,
+    SS: StorageSerde + Send + Sync + 'static,
+    SamplingRng: SeedableRng + RngCore,
+    SamplingBackend: DaSamplingServiceBackend<SamplingRng, BlobId = BlobId> + Send,
+    SamplingBackend::Settings: Clone,
+    SamplingBackend::Share: Debug + 'static,
+    SamplingBackend::BlobId: Debug + 'static,
+    SamplingNetworkAdapter: nomos_da_sampling::network::NetworkAdapter<RuntimeServiceId>,
+    SamplingStorage: nomos_da_sampling::storage::DaStorageAdapter<RuntimeServiceId>,
+    DaVerifierStorage: nomos_da_verifier::storage::DaStorageAdapter<RuntimeServiceId>,
+    DaVerifierBackend: nomos_da_verifier::backend::VerifierBackend + Send + 'static,
+    DaVerifierBackend::Settings: Clone,
+    DaVerifierNetwork: nomos_da_verifier::network::NetworkAdapter<RuntimeServiceId>,
+    DaVerifierNetwork::Settings: Clone,
+    TimeBackend: nomos_time::backends::TimeBackend,
+    TimeBackend::Settings: Clone + Send + Sync,
+    ApiAdapter: nomos_da_sampling::api::ApiAdapter + Send + Sync,
+    RuntimeServiceId: Debug
+        + Sync
+        + Display
+        + 'static
+        + AsServiceId<
+            Sdp<
+                Tx,
+                SS,
+                SamplingBackend,
+                SamplingNetworkAdapter,
+                SamplingRng,
+                SamplingStorage,
+                DaVerifierBackend,
+                DaVerifierNetwork,
+                DaVerifierStorage,
+                TimeBackend,
+                ApiAdapter,
+                RuntimeServiceId,
+                SIZE,
+            >,
+        >,
+{
+    make_request_and_return_response!(sdp::active_providers::<
+        Tx,
+        SS,
+        SamplingBackend,
+        SamplingNetworkAdapter,
+        SamplingRng,
+        SamplingStorage,
+        DaVerifierBackend,
+        DaVerifierNetwork,
+        DaVerifierStorage,
+        TimeBackend,
+        ApiAdapter,
+        RuntimeServiceId,
+        SIZE,
+    >(&handle, service_type))
+}
+
 #[utoipa::path(
     get,
     path = paths::DA_GET_SHARES_COMMITMENTS,
@@ -757,7 +1432,7 @@ where
         + AsServiceId<
             TxMempoolService<
                 MempoolNetworkAdapter<Tx, <Tx as Transaction>::Hash, RuntimeServiceId>,
-                MockPool<HeaderId, Tx, <Tx as Transaction>::Hash>,
+                PriorityPool<HeaderId, Tx, <Tx as Transaction>::Hash>,
                 RuntimeServiceId,
             >,
         >,
@@ -835,7 +1510,7 @@ where
         + AsServiceId<
             DaMempoolService<
                 MempoolNetworkAdapter<B, <B as DispersedBlobInfo>::BlobId, RuntimeServiceId>,
-                MockPool<HeaderId, B, <B as DispersedBlobInfo>::BlobId>,
+                PriorityPool<HeaderId, B, <B as DispersedBlobInfo>::BlobId>,
                 SamplingBackend,
                 SamplingAdapter,
                 SamplingRng,
@@ -0,0 +1,45 @@
+use std::num::NonZero;
+
+use color_eyre::eyre::{eyre, Result};
+use nomos_da_indexer::{storage::adapters::rocksdb::RocksAdapterSettings, IndexerSettings};
+use nomos_da_storage::retention::RetentionPolicy;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct IndexerConfig {
+    pub storage: RocksAdapterSettings,
+    /// Blobs are kept forever if not set.
+    #[serde(default)]
+    pub retention: Option<RetentionConfig>,
+}
+
+/// How long blobs are kept in storage, see [`RetentionPolicy`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionConfig {
+    /// Keep blobs referenced by the latest `depth` finalized blocks.
+    BlockDepth(u64),
+    /// Keep blobs referenced by blocks of the latest `epochs` epochs of the
+    /// ledger.
+    Epochs(u64),
+}
+
+impl IndexerConfig {
+    /// Builds the indexer settings, given the epoch length in slots of the
+    /// ledger.
+    pub fn into_settings(self, epoch_length: u64) -> Result<IndexerSettings<RocksAdapterSettings>> {
+        let retention = match self.retention {
+            None => None,
+            Some(RetentionConfig::BlockDepth(depth)) => Some(RetentionPolicy::BlockDepth(depth)),
+            Some(RetentionConfig::Epochs(epochs)) => Some(RetentionPolicy::Epochs {
+                epochs,
+                epoch_length: NonZero::new(epoch_length)
+                    .ok_or_else(|| eyre!("Epoch length of the ledger is zero"))?,
+            }),
+        };
+        Ok(IndexerSettings {
+            storage: self.storage,
+            retention,
+        })
+    }
+}
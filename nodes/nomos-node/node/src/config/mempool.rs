@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 
 use serde::{Deserialize, Serialize};
 
@@ -6,4 +6,25 @@ use serde::{Deserialize, Serialize};
 pub struct MempoolConfig {
     pub cl_pool_recovery_path: PathBuf,
     pub da_pool_recovery_path: PathBuf,
+    #[serde(default)]
+    pub cl_pool: PoolConfig,
+    #[serde(default)]
+    pub da_pool: PoolConfig,
+}
+
+/// Limits of a mempool, see
+/// [`PriorityPoolSettings`](nomos_mempool::backend::priority::PriorityPoolSettings).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PoolConfig {
+    pub max_pending_items: usize,
+    pub item_ttl: Duration,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            max_pending_items: 10_000,
+            item_ttl: Duration::from_secs(60 * 60),
+        }
+    }
 }
@@ -1,14 +1,19 @@
 use std::{
     net::{IpAddr, SocketAddr, ToSocketAddrs},
     path::PathBuf,
+    sync::Arc,
 };
 
 use cl::{Nonce, NoteWitness, NullifierSecret};
 use clap::{Parser, ValueEnum};
 use color_eyre::eyre::{eyre, Result};
 use hex::FromHex;
+use key_management_system::{
+    keys::{ed25519::Ed25519Key, nullifier::NullifierSecretKey, Key},
+    NodeKMSBackendSettings,
+};
 use nomos_core::{proofs::covenant::CovenantProof, staking::NMO_UNIT};
-use nomos_libp2p::{ed25519::SecretKey, Multiaddr};
+use nomos_libp2p::Multiaddr;
 use nomos_network::backends::libp2p::Libp2p as NetworkBackend;
 use nomos_tracing::logging::{gelf::GelfConfig, local::FileConfig};
 use nomos_tracing_service::{LoggerLayer, Tracing};
@@ -17,11 +22,13 @@ use serde::{Deserialize, Serialize};
 use tracing::Level;
 
 use crate::{
-    config::mempool::MempoolConfig, ApiService, BlendService, CryptarchiaService, DaIndexerService,
-    DaNetworkService, DaSamplingService, DaVerifierService, NetworkService, RuntimeServiceId,
+    config::{indexer::IndexerConfig, mempool::MempoolConfig},
+    ApiService, BlendService, CryptarchiaService, DaNetworkService, DaSamplingService,
+    DaVerifierService, KeyManagementService, NetworkService, RuntimeServiceId, SdpService,
     StorageService, TimeService,
 };
 
+pub mod indexer;
 pub mod mempool;
 
 #[derive(ValueEnum, Clone, Debug, Default)]
@@ -132,11 +139,13 @@ pub struct Config {
     pub network: <NetworkService as ServiceData>::Settings,
     pub blend: <BlendService as ServiceData>::Settings,
     pub da_network: <DaNetworkService as ServiceData>::Settings,
-    pub da_indexer: <DaIndexerService as ServiceData>::Settings,
+    pub da_indexer: IndexerConfig,
     pub da_verifier: <DaVerifierService as ServiceData>::Settings,
     pub da_sampling: <DaSamplingService as ServiceData>::Settings,
     pub http: <ApiService as ServiceData>::Settings,
     pub cryptarchia: <CryptarchiaService as ServiceData>::Settings,
+    pub sdp: <SdpService as ServiceData>::Settings,
+    pub kms: <KeyManagementService as ServiceData>::Settings,
     pub time: <TimeService as ServiceData>::Settings,
     pub storage: <StorageService as ServiceData>::Settings,
     pub mempool: MempoolConfig,
@@ -152,10 +161,10 @@ impl Config {
         cryptarchia_args: CryptarchiaArgs,
     ) -> Result<Self> {
         update_tracing(&mut self.tracing, log_args)?;
-        update_network::<RuntimeServiceId>(&mut self.network, network_args)?;
-        update_blend(&mut self.blend, blend_args)?;
+        update_network::<RuntimeServiceId>(&mut self.network, &mut self.kms, network_args)?;
+        update_blend(&mut self.blend, &mut self.kms, blend_args)?;
         update_http(&mut self.http, http_args)?;
-        update_cryptarchia_consensus(&mut self.cryptarchia, cryptarchia_args)?;
+        update_cryptarchia_consensus(&mut self.cryptarchia, &mut self.kms, cryptarchia_args)?;
         Ok(self)
     }
 }
@@ -205,6 +214,7 @@ pub fn update_tracing(
 
 pub fn update_network<RuntimeServiceId>(
     network: &mut <nomos_network::NetworkService<NetworkBackend, RuntimeServiceId> as ServiceData>::Settings,
+    kms: &mut <KeyManagementService as ServiceData>::Settings,
     network_args: NetworkArgs,
 ) -> Result<()> {
     let NetworkArgs {
@@ -225,8 +235,12 @@ pub fn update_network<RuntimeServiceId>(
     }
 
     if let Some(node_key) = node_key {
-        let mut key_bytes = hex::decode(node_key)?;
-        network.backend.inner.node_key = SecretKey::try_from_bytes(key_bytes.as_mut_slice())?;
+        let key_bytes = <[u8; 32]>::from_hex(node_key)?;
+        insert_key(
+            kms,
+            &network.backend.node_key_id,
+            Key::Ed25519(Ed25519Key::from_bytes(&key_bytes)),
+        )?;
     }
 
     if let Some(peers) = initial_peers {
@@ -238,6 +252,7 @@ pub fn update_network<RuntimeServiceId>(
 
 pub fn update_blend(
     blend: &mut <BlendService as ServiceData>::Settings,
+    kms: &mut <KeyManagementService as ServiceData>::Settings,
     blend_args: BlendArgs,
 ) -> Result<()> {
     let BlendArgs {
@@ -251,8 +266,12 @@ pub fn update_blend(
     }
 
     if let Some(node_key) = blend_node_key {
-        let mut key_bytes = hex::decode(node_key)?;
-        blend.backend.node_key = SecretKey::try_from_bytes(key_bytes.as_mut_slice())?;
+        let key_bytes = <[u8; 32]>::from_hex(node_key)?;
+        insert_key(
+            kms,
+            &blend.backend.node_key_id,
+            Key::Ed25519(Ed25519Key::from_bytes(&key_bytes)),
+        )?;
     }
 
     if let Some(num_blend_layers) = blend_num_blend_layers {
@@ -284,6 +303,7 @@ pub fn update_http(
 
 pub fn update_cryptarchia_consensus(
     cryptarchia: &mut <CryptarchiaService as ServiceData>::Settings,
+    kms: &mut <KeyManagementService as ServiceData>::Settings,
     consensus_args: CryptarchiaArgs,
 ) -> Result<()> {
     let CryptarchiaArgs {
@@ -305,12 +325,35 @@ pub fn update_cryptarchia_consensus(
 
     if let Some(sk) = note_secret_key {
         let sk = <[u8; 16]>::from_hex(sk)?;
-        cryptarchia.leader_config.nf_sk = NullifierSecret::from_bytes(sk);
+        insert_key(
+            kms,
+            &cryptarchia.leader_config.nf_sk_id,
+            Key::NullifierSecret(NullifierSecretKey::new(NullifierSecret::from_bytes(sk))),
+        )?;
     }
 
     Ok(())
 }
 
+/// Preloads `key` in the key management service under `key_id`, replacing the
+/// configured one.
+///
+/// Keys of a keystore are managed in the keystore itself, they can't be passed
+/// as arguments.
+fn insert_key(
+    kms: &mut <KeyManagementService as ServiceData>::Settings,
+    key_id: &str,
+    key: Key,
+) -> Result<()> {
+    let NodeKMSBackendSettings::Preload(settings) = &mut kms.backend_settings else {
+        return Err(eyre!(
+            "Key {key_id} can only be passed as an argument with the preload KMS backend"
+        ));
+    };
+    settings.keys.insert(key_id.to_owned(), Arc::new(key));
+    Ok(())
+}
+
 pub fn update_time(
     time: &mut <TimeService as ServiceData>::Settings,
     time_args: &TimeArgs,
@@ -4,7 +4,7 @@ use nomos_core::{da::blob::info::DispersedBlobInfo, header::HeaderId, tx::Transa
 use nomos_da_indexer::consensus::adapters::cryptarchia::CryptarchiaConsensusAdapter;
 use nomos_da_sampling::{api::http::HttApiAdapter, backend::kzgrs::KzgrsSamplingBackend};
 use nomos_da_verifier::backend::kzgrs::KzgrsDaVerifier;
-use nomos_mempool::backend::mockpool::MockPool;
+use nomos_mempool::backend::priority::PriorityPool;
 use nomos_storage::backends::rocksdb::RocksBackend;
 use nomos_time::backends::NtpTimeBackend;
 use rand_chacha::ChaCha20Rng;
@@ -17,12 +17,15 @@ pub type TxMempoolService<RuntimeServiceId> = nomos_mempool::TxMempoolService<
         <Tx as Transaction>::Hash,
         RuntimeServiceId,
     >,
-    MockPool<HeaderId, Tx, <Tx as Transaction>::Hash>,
+    PriorityPool<HeaderId, Tx, <Tx as Transaction>::Hash>,
     RuntimeServiceId,
 >;
 
 pub type TimeService<RuntimeServiceId> = nomos_time::TimeService<NtpTimeBackend, RuntimeServiceId>;
 
+pub type KeyManagementService<RuntimeServiceId> =
+    key_management_system::NodeKMSService<RuntimeServiceId>;
+
 pub type DaIndexerService<SamplingAdapter, VerifierNetwork, RuntimeServiceId> =
     nomos_da_indexer::DataIndexerService<
         // Indexer specific.
@@ -41,13 +44,13 @@ pub type DaIndexerService<SamplingAdapter, VerifierNetwork, RuntimeServiceId> =
             BlobInfo,
             RuntimeServiceId,
         >,
-        MockPool<HeaderId, Tx, <Tx as Transaction>::Hash>,
+        PriorityPool<HeaderId, Tx, <Tx as Transaction>::Hash>,
         nomos_mempool::network::adapters::libp2p::Libp2pAdapter<
             Tx,
             <Tx as Transaction>::Hash,
             RuntimeServiceId,
         >,
-        MockPool<HeaderId, BlobInfo, <BlobInfo as DispersedBlobInfo>::BlobId>,
+        PriorityPool<HeaderId, BlobInfo, <BlobInfo as DispersedBlobInfo>::BlobId>,
         nomos_mempool::network::adapters::libp2p::Libp2pAdapter<
             BlobInfo,
             <BlobInfo as DispersedBlobInfo>::BlobId,
@@ -96,7 +99,7 @@ pub type DaMempoolService<DaSamplingNetwork, VerifierNetwork, RuntimeServiceId>
             <BlobInfo as DispersedBlobInfo>::BlobId,
             RuntimeServiceId,
         >,
-        MockPool<HeaderId, BlobInfo, <BlobInfo as DispersedBlobInfo>::BlobId>,
+        PriorityPool<HeaderId, BlobInfo, <BlobInfo as DispersedBlobInfo>::BlobId>,
         KzgrsSamplingBackend<ChaCha20Rng>,
         DaSamplingNetwork,
         ChaCha20Rng,
@@ -121,13 +124,13 @@ pub type CryptarchiaService<SamplingAdapter, VerifierNetwork, RuntimeServiceId>
             BlobInfo,
             RuntimeServiceId,
         >,
-        MockPool<HeaderId, Tx, <Tx as Transaction>::Hash>,
+        PriorityPool<HeaderId, Tx, <Tx as Transaction>::Hash>,
         nomos_mempool::network::adapters::libp2p::Libp2pAdapter<
             Tx,
             <Tx as Transaction>::Hash,
             RuntimeServiceId,
         >,
-        MockPool<HeaderId, BlobInfo, <BlobInfo as DispersedBlobInfo>::BlobId>,
+        PriorityPool<HeaderId, BlobInfo, <BlobInfo as DispersedBlobInfo>::BlobId>,
         nomos_mempool::network::adapters::libp2p::Libp2pAdapter<
             BlobInfo,
             <BlobInfo as DispersedBlobInfo>::BlobId,
@@ -147,3 +150,13 @@ pub type CryptarchiaService<SamplingAdapter, VerifierNetwork, RuntimeServiceId>
         HttApiAdapter<NomosDaMembership>,
         RuntimeServiceId,
     >;
+
+pub type SdpService<Consensus, RuntimeServiceId> = nomos_sdp::SdpService<
+    RocksBackend<Wire>,
+    nomos_sdp::consensus::adapters::cryptarchia::CryptarchiaConsensusAdapter<
+        Consensus,
+        Tx,
+        BlobInfo,
+    >,
+    RuntimeServiceId,
+>;
@@ -1,7 +1,6 @@
 pub mod api;
 pub mod config;
 pub mod generic_services;
-mod tx;
 
 use bytes::Bytes;
 use color_eyre::eyre::Result;
@@ -14,7 +13,7 @@ pub use nomos_blend_service::{
 pub use nomos_core::{
     da::blob::{info::DispersedBlobInfo, select::FillSize as FillSizeWithBlobs},
     header::HeaderId,
-    tx::{select::FillSize as FillSizeWithTx, Transaction},
+    tx::{select::FillSize as FillSizeWithTx, Transaction, Tx},
     wire,
 };
 pub use nomos_da_network_service::backends::libp2p::validator::DaNetworkValidatorBackend;
@@ -29,6 +28,7 @@ use nomos_da_verifier::{
     storage::adapters::rocksdb::RocksAdapter as VerifierStorageAdapter,
 };
 pub use nomos_mempool::{
+    backend::priority::{size_weight, PriorityPoolSettings},
     da::settings::DaMempoolSettings,
     network::adapters::libp2p::{
         Libp2pAdapter as MempoolNetworkAdapter, Settings as MempoolAdapterSettings,
@@ -49,10 +49,7 @@ use serde::{de::DeserializeOwned, Serialize};
 use subnetworks_assignations::versions::v1::FillFromNodeList;
 
 use crate::api::backend::AxumBackend;
-pub use crate::{
-    config::{Config, CryptarchiaArgs, HttpArgs, LogArgs, NetworkArgs},
-    tx::Tx,
-};
+pub use crate::config::{Config, CryptarchiaArgs, HttpArgs, LogArgs, NetworkArgs};
 
 pub const CONSENSUS_TOPIC: &str = "/cryptarchia/proto";
 pub const CL_TOPIC: &str = "cl";
@@ -134,6 +131,23 @@ pub(crate) type CryptarchiaService = generic_services::CryptarchiaService<
 
 pub(crate) type TimeService = generic_services::TimeService<RuntimeServiceId>;
 
+pub(crate) type KeyManagementService = generic_services::KeyManagementService<RuntimeServiceId>;
+
+pub(crate) type SdpService = generic_services::SdpService<CryptarchiaService, RuntimeServiceId>;
+
+pub(crate) type DaMembershipService = nomos_da_membership::DaMembershipService<
+    DaNetworkValidatorBackend<NomosDaMembership>,
+    SdpService,
+    RuntimeServiceId,
+>;
+
+pub(crate) type BlendMembershipService = nomos_blend_membership::BlendMembershipService<
+    BlendBackend,
+    BlendNetworkAdapter<RuntimeServiceId>,
+    SdpService,
+    RuntimeServiceId,
+>;
+
 pub(crate) type ApiService = nomos_api::ApiService<
     AxumBackend<
         (),
@@ -177,6 +191,10 @@ pub struct Nomos {
     cl_mempool: ClMempoolService,
     da_mempool: DaMempoolService,
     cryptarchia: CryptarchiaService,
+    sdp: SdpService,
+    da_membership: DaMembershipService,
+    blend_membership: BlendMembershipService,
+    kms: KeyManagementService,
     time: TimeService,
     http: ApiService,
     storage: StorageService,
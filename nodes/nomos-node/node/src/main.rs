@@ -3,7 +3,9 @@ use color_eyre::eyre::{eyre, Result};
 use kzgrs_backend::dispersal::BlobInfo;
 use nomos_core::{da::blob::info::DispersedBlobInfo, tx::Transaction};
 use nomos_mempool::{
-    network::adapters::libp2p::Settings as AdapterSettings, tx::settings::TxMempoolSettings,
+    backend::priority::{size_weight, PriorityPoolSettings},
+    network::adapters::libp2p::Settings as AdapterSettings,
+    tx::settings::TxMempoolSettings,
 };
 use nomos_node::{
     config::BlendArgs, Config, CryptarchiaArgs, HttpArgs, LogArgs, NetworkArgs, Nomos,
@@ -58,7 +60,11 @@ fn main() -> Result<()> {
             tracing: config.tracing,
             http: config.http,
             cl_mempool: TxMempoolSettings {
-                pool: (),
+                pool: PriorityPoolSettings {
+                    max_pending_items: config.mempool.cl_pool.max_pending_items,
+                    item_ttl: config.mempool.cl_pool.item_ttl,
+                    weight: |tx: &Tx| size_weight(tx.as_bytes().len()),
+                },
                 network_adapter: AdapterSettings {
                     topic: String::from(nomos_node::CL_TOPIC),
                     id: <Tx as Transaction>::hash,
@@ -66,7 +72,11 @@ fn main() -> Result<()> {
                 recovery_path: config.mempool.cl_pool_recovery_path,
             },
             da_mempool: nomos_mempool::DaMempoolSettings {
-                pool: (),
+                pool: PriorityPoolSettings {
+                    max_pending_items: config.mempool.da_pool.max_pending_items,
+                    item_ttl: config.mempool.da_pool.item_ttl,
+                    weight: |info: &BlobInfo| size_weight(info.size()),
+                },
                 network_adapter: AdapterSettings {
                     topic: String::from(nomos_node::DA_TOPIC),
                     id: <BlobInfo as DispersedBlobInfo>::blob_id,
@@ -74,10 +84,16 @@ fn main() -> Result<()> {
                 recovery_path: config.mempool.da_pool_recovery_path,
             },
             da_network: config.da_network,
-            da_indexer: config.da_indexer,
+            da_indexer: config
+                .da_indexer
+                .into_settings(config.cryptarchia.config.epoch_length())?,
             da_sampling: config.da_sampling,
             da_verifier: config.da_verifier,
             cryptarchia: config.cryptarchia,
+            sdp: config.sdp,
+            da_membership: (),
+            blend_membership: (),
+            kms: config.kms,
             time: config.time,
             storage: config.storage,
             system_sig: (),
@@ -25,6 +25,7 @@ pub struct CoverTraffic<EpochStream, SlotStream, Message> {
     settings: CoverTrafficSettings,
     epoch_stream: EpochStream,
     slot_stream: SlotStream,
+    current_epoch: Option<usize>,
     selected_slots: HashSet<u32>,
     _message: PhantomData<Message>,
 }
@@ -45,10 +46,26 @@ where
             settings,
             epoch_stream,
             slot_stream,
+            current_epoch: None,
             selected_slots: HashSet::default(),
             _message: PhantomData,
         }
     }
+
+    /// Updates the size of the blend network, re-selecting the cover traffic
+    /// slots of the current epoch accordingly.
+    pub fn update_network_size(&mut self, network_size: usize) {
+        self.settings.network_size = network_size;
+        if let Some(epoch) = self.current_epoch {
+            self.selected_slots = select_slot(
+                self.settings.node_id,
+                epoch,
+                self.settings.network_size,
+                self.settings.slots_per_epoch,
+                self.winning_probability,
+            );
+        }
+    }
 }
 
 impl<EpochStream, SlotStream, Message> Stream for CoverTraffic<EpochStream, SlotStream, Message>
@@ -65,10 +82,12 @@ where
             settings,
             epoch_stream,
             slot_stream,
+            current_epoch,
             selected_slots,
             ..
         } = &mut *self;
         if let Poll::Ready(Some(epoch)) = epoch_stream.poll_next_unpin(cx) {
+            *current_epoch = Some(epoch);
             *selected_slots = select_slot(
                 settings.node_id,
                 epoch,
@@ -119,7 +138,14 @@ fn winning_probability(number_of_hops: usize) -> f64 {
 
 #[cfg(test)]
 mod tests {
-    use crate::cover_traffic::{generate_ticket, select_slot, winning_probability};
+    use std::task::{Context, Poll};
+
+    use futures::{stream, StreamExt as _};
+    use nomos_blend_message::mock::MockBlendMessage;
+
+    use crate::cover_traffic::{
+        generate_ticket, select_slot, winning_probability, CoverTraffic, CoverTrafficSettings,
+    };
 
     #[test]
     fn test_ticket() {
@@ -129,4 +155,33 @@ mod tests {
             println!("slots = {slots:?}");
         }
     }
+
+    #[test]
+    fn test_update_network_size_reselects_slots() {
+        let settings = CoverTrafficSettings {
+            node_id: [0; 32],
+            number_of_hops: 1,
+            slots_per_epoch: 100,
+            network_size: 100,
+        };
+        let mut cover_traffic: CoverTraffic<_, _, MockBlendMessage> = CoverTraffic::new(
+            settings,
+            stream::iter([7]).chain(stream::pending()),
+            stream::pending(),
+        );
+
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+        assert!(matches!(
+            cover_traffic.poll_next_unpin(&mut cx),
+            Poll::Pending
+        ));
+        assert_eq!(cover_traffic.selected_slots.len(), 1);
+
+        cover_traffic.update_network_size(10);
+        assert_eq!(
+            cover_traffic.selected_slots,
+            select_slot([0; 32], 7, 10, 100, winning_probability(1))
+        );
+        assert_eq!(cover_traffic.selected_slots.len(), 10);
+    }
 }
@@ -80,3 +80,39 @@ where
         self.remote_nodes.len() + 1
     }
 }
+
+impl<NodeId, M> Membership<NodeId, M>
+where
+    NodeId: Clone + Hash + Eq,
+    M: BlendMessage,
+    M::PublicKey: Clone + PartialEq,
+{
+    /// Builds a membership with the same local node and the given remote
+    /// nodes. The local node is skipped if it is part of `nodes`.
+    #[must_use]
+    pub fn with_remote_nodes(&self, nodes: Vec<Node<NodeId, M::PublicKey>>) -> Self {
+        Self {
+            remote_nodes: nodes
+                .into_iter()
+                .filter(|node| node.public_key != self.local_node.public_key)
+                .collect(),
+            local_node: self.local_node.clone(),
+        }
+    }
+
+    pub fn contains_remote_node(&self, id: &NodeId) -> bool {
+        self.remote_nodes.iter().any(|node| node.id == *id)
+    }
+
+    /// Whether both memberships have the same remote nodes, in any order.
+    pub fn has_same_remote_nodes(&self, other: &Self) -> bool {
+        self.remote_nodes.len() == other.remote_nodes.len()
+            && other.remote_nodes.iter().all(|other_node| {
+                self.remote_nodes.iter().any(|node| {
+                    node.id == other_node.id
+                        && node.address == other_node.address
+                        && node.public_key == other_node.public_key
+                })
+            })
+    }
+}
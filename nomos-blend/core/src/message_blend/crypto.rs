@@ -42,6 +42,12 @@ where
         }
     }
 
+    /// Replaces the membership the blend layers of the wrapped messages are
+    /// chosen from. Messages already wrapped are not affected.
+    pub fn update_membership(&mut self, membership: Membership<NodeId, M>) {
+        self.membership = membership;
+    }
+
     pub fn wrap_message(&mut self, message: &[u8]) -> Result<Vec<u8>, M::Error> {
         // TODO: Use the actual Sphinx encoding instead of mock.
         let public_keys = self
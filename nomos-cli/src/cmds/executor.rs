@@ -2,7 +2,7 @@ use std::{path::PathBuf, sync::mpsc::Sender};
 
 use clap::Args;
 use executor_http_client::{BasicAuthCredentials, ExecutorHttpClient};
-use kzgrs_backend::{dispersal::Metadata, encoder::DaEncoderParams};
+use kzgrs_backend::dispersal::Metadata;
 use reqwest::Url;
 
 #[derive(Args, Debug)]
@@ -42,21 +42,13 @@ impl Disseminate {
 
         let client = ExecutorHttpClient::new(basic_auth);
 
-        let mut bytes: Vec<u8> = if let Some(data) = &self.data {
+        let bytes: Vec<u8> = if let Some(data) = &self.data {
             data.clone().into_bytes()
         } else {
             let file_path = self.file.as_ref().unwrap();
             std::fs::read(file_path)?
         };
 
-        let remainder = bytes.len() % DaEncoderParams::MAX_BLS12_381_ENCODING_CHUNK_SIZE;
-        if remainder != 0 {
-            bytes.resize(
-                bytes.len() + (DaEncoderParams::MAX_BLS12_381_ENCODING_CHUNK_SIZE - remainder),
-                0,
-            );
-        }
-
         let app_id: [u8; 32] = hex::decode(&self.app_id)?
             .try_into()
             .map_err(|_| "Invalid app_id")?;
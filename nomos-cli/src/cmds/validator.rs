@@ -1,10 +1,16 @@
-use std::{error::Error, ops::Range, path::PathBuf, sync::mpsc::Sender};
+use std::{collections::BTreeMap, error::Error, ops::Range, path::PathBuf, sync::mpsc::Sender};
 
 use clap::Args;
 use kzgrs_backend::{
-    common::share::DaShare, dispersal::Index, reconstruction::reconstruct_without_missing_data,
+    common::share::{DaShare, DaSharesCommitments},
+    dispersal::Index,
+    global::global_parameters_from_file,
+    reconstruction::reconstruct,
+};
+use nomos_core::da::{
+    blob::{metadata, Share as _},
+    BlobId,
 };
-use nomos_core::da::blob::metadata;
 use nomos_da_messages::http::da::GetRangeReq;
 use nomos_http_api_common::paths;
 use nomos_node::wire;
@@ -49,6 +55,9 @@ pub struct Reconstruct {
     /// File with blobs.
     #[clap(short, long)]
     pub file: Option<PathBuf>,
+    /// Global parameters used to verify the blobs before reconstruction.
+    #[clap(long)]
+    pub global_params_path: String,
 }
 
 impl Retrieve {
@@ -148,20 +157,59 @@ impl Reconstruct {
             let json_string = String::from_utf8(std::fs::read(file_path)?)?;
             parse_app_shares(&json_string)?
         };
-
-        let mut da_shares = vec![];
+        let global_parameters = global_parameters_from_file(&self.global_params_path)?;
 
         for (index, shares) in &app_shares {
             tracing::info!("Index {:?} has {:} shares", (index), shares.len());
             for share in shares {
-                da_shares.push(share.clone());
                 tracing::info!("Index {:?}; DaShare: {share:?}", index.to_u64());
             }
+            let blobs = group_by_blob(shares);
+            if blobs.is_empty() {
+                tracing::error!("Index {:?}; No shares to reconstruct data", index.to_u64());
+                continue;
+            }
+            if blobs.len() > 1 {
+                tracing::warn!(
+                    "Index {:?}; Shares belong to {} different blobs",
+                    index.to_u64(),
+                    blobs.len()
+                );
+            }
+            for (blob_id, (commitments, shares)) in blobs {
+                let blob_id = hex::encode(blob_id);
+                match reconstruct(&shares, &commitments, &global_parameters) {
+                    Ok(reconstructed_data) => {
+                        tracing::info!(
+                            "Index {:?}; Blob {blob_id}; Reconstructed data {:?}",
+                            index.to_u64(),
+                            reconstructed_data
+                        );
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "Index {:?}; Blob {blob_id}; Failed to reconstruct data: {e}",
+                            index.to_u64()
+                        );
+                    }
+                }
+            }
         }
 
-        let reconstructed_data = reconstruct_without_missing_data(&da_shares);
-        tracing::info!("Reconstructed data {:?}", reconstructed_data);
-
         Ok(())
     }
 }
+
+/// Groups the shares by the blob id their commitments hash to, so each share
+/// is only verified against the commitments of its own blob.
+fn group_by_blob(shares: &[DaShare]) -> BTreeMap<BlobId, (DaSharesCommitments, Vec<DaShare>)> {
+    let mut blobs = BTreeMap::new();
+    for share in shares {
+        let (_, commitments) = share.clone().into_share_and_commitments();
+        let (_, blob_shares) = blobs
+            .entry(commitments.blob_id())
+            .or_insert_with(|| (commitments, Vec::new()));
+        blob_shares.push(share.clone());
+    }
+    blobs
+}
@@ -1,16 +1,13 @@
 use std::hash::Hash;
 
-use blake2::digest::Digest;
 use indexmap::IndexSet;
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
-    block::Block,
-    crypto::Blake2b,
+    block::{content_id_and_size, Block},
     da::blob::{info::DispersedBlobInfo, BlobSelect},
     header::Builder,
     tx::{Transaction, TxSelect},
-    wire,
 };
 
 /// Wrapper over a block building `new` method than holds intermediary state and
@@ -87,15 +84,10 @@ where
                 .select_blob_from(blobs)
                 .collect::<IndexSet<_>>();
 
-            let serialized_content = wire::serialize(&(&txs, &blobs)).unwrap();
-            let content_size = u32::try_from(serialized_content.len()).map_err(|_| {
-                format!(
-                    "Content is too big: {} out of {} max",
-                    serialized_content.len(),
-                    u32::MAX
-                )
+            let (content_id, content_size) = content_id_and_size(&txs, &blobs);
+            let content_size = u32::try_from(content_size).map_err(|_| {
+                format!("Content is too big: {content_size} out of {} max", u32::MAX)
             })?;
-            let content_id = <[u8; 32]>::from(Blake2b::digest(&serialized_content)).into();
 
             let header = header_builder.build(content_id, content_size);
 
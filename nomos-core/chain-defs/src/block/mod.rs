@@ -3,10 +3,15 @@ pub mod builder;
 use core::hash::Hash;
 
 use ::serde::{de::DeserializeOwned, Deserialize, Serialize};
+use blake2::Digest;
 use bytes::Bytes;
 use indexmap::IndexSet;
 
-use crate::{header::Header, wire};
+use crate::{
+    crypto::Blake2b,
+    header::{ContentId, Header},
+    wire,
+};
 
 pub type TxHash = [u8; 32];
 
@@ -58,4 +63,24 @@ impl<
     pub fn bl_blobs_len(&self) -> usize {
         self.bl_blobs.len()
     }
+
+    /// Id and size in bytes of the content of the block, as committed to by
+    /// its header.
+    #[must_use]
+    pub fn content_id_and_size(&self) -> (ContentId, usize) {
+        content_id_and_size(&self.cl_transactions, &self.bl_blobs)
+    }
+}
+
+pub(crate) fn content_id_and_size<Tx, BlobCertificate>(
+    txs: &IndexSet<Tx>,
+    blobs: &IndexSet<BlobCertificate>,
+) -> (ContentId, usize)
+where
+    Tx: Serialize,
+    BlobCertificate: Serialize,
+{
+    let serialized_content = wire::serialize(&(txs, blobs)).unwrap();
+    let content_id = <[u8; 32]>::from(Blake2b::digest(&serialized_content)).into();
+    (content_id, serialized_content.len())
 }
@@ -20,6 +20,21 @@ pub trait DaVerifier {
         commitments: &<Self::DaShare as Share>::SharesCommitments,
         light_share: &<Self::DaShare as Share>::LightShare,
     ) -> Result<(), Self::Error>;
+
+    /// Verifies many shares, the result for each of them is returned in the
+    /// same order.
+    fn verify_batch(
+        &self,
+        shares: &[(
+            &<Self::DaShare as Share>::SharesCommitments,
+            &<Self::DaShare as Share>::LightShare,
+        )],
+    ) -> Vec<Result<(), Self::Error>> {
+        shares
+            .iter()
+            .map(|(commitments, light_share)| self.verify(commitments, light_share))
+            .collect()
+    }
 }
 
 #[async_trait::async_trait]
@@ -83,6 +83,11 @@ impl Header {
         self.content_size
     }
 
+    #[must_use]
+    pub const fn content_id(&self) -> ContentId {
+        self.content_id
+    }
+
     #[must_use]
     pub const fn new(
         parent: HeaderId,
@@ -13,6 +13,12 @@ impl BalanceProof {
         self.proof.journal.decode()
     }
 
+    pub(crate) fn verify(&self) -> bool {
+        self.proof
+            .verify(nomos_risc0_proofs::BUNDLE_BALANCE_ID)
+            .is_ok()
+    }
+
     pub(crate) fn prove(
         bundle_witness: &cl::BundleWitness,
         prover: &dyn Prover,
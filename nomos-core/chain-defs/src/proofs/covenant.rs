@@ -36,6 +36,10 @@ impl CovenantProof {
         self.proof.journal.decode()
     }
 
+    pub(crate) fn verify(&self) -> bool {
+        self.proof.verify(self.risc0_id).is_ok()
+    }
+
     #[must_use]
     pub fn nop_constraint() -> Covenant {
         risc0_covenant(nomos_risc0_proofs::COVENANT_NOP_ID)
@@ -103,6 +103,7 @@ impl<'de> Deserialize<'de> for Risc0LeaderProof {
 #[cfg(test)]
 mod test {
     use cl::{note::NoteWitness, nullifier::NullifierSecret};
+    use nomos_ledger::NoteTree;
     use rand::thread_rng;
 
     use super::*;
@@ -141,13 +142,42 @@ mod test {
             cm_path: cl::merkle::path(leaves, 0),
         };
 
+        assert_proves(input, cl::merkle::root(leaves));
+    }
+
+    // The circuit takes paths of any length, so notes added once the ledger
+    // note tree grew beyond its initial depth can still lead.
+    #[test]
+    fn test_leader_prover_deep_note_tree() {
+        const MIN_TREE_SIZE: usize = 1 << 14;
+        let mut rng = thread_rng();
+
+        let note = NoteWitness::basic(32, NMO_UNIT, &mut rng);
+        let nf_sk = NullifierSecret::random(&mut rng);
+
+        let tree: NoteTree = std::iter::repeat_n(cl::NoteCommitment([0; 32]), MIN_TREE_SIZE)
+            .chain([note.commit(nf_sk.commit())])
+            .collect();
+        let cm_path = tree.witness(MIN_TREE_SIZE).unwrap();
+        assert_eq!(cm_path.len(), MIN_TREE_SIZE.ilog2() as usize + 1);
+
+        let input = cl::InputWitness {
+            note,
+            nf_sk,
+            cm_path,
+        };
+
+        assert_proves(input, tree.root());
+    }
+
+    fn assert_proves(input: cl::InputWitness, cm_root: [u8; 32]) {
         let epoch_nonce = [0u8; 32];
         let slot = 0;
         let active_slot_coefficient = 0.05;
         let total_stake = 1000;
 
         let mut expected_public_inputs = LeaderPublic::new(
-            cl::merkle::root(leaves),
+            cm_root,
             epoch_nonce,
             slot,
             active_slot_coefficient,
@@ -2,3 +2,4 @@ pub mod balance;
 pub mod covenant;
 pub mod leader_proof;
 pub mod ptx;
+pub mod stake_proof;
@@ -12,6 +12,10 @@ impl PtxProof {
         self.proof.journal.decode()
     }
 
+    pub(crate) fn verify(&self) -> bool {
+        self.proof.verify(nomos_risc0_proofs::PTX_ID).is_ok()
+    }
+
     pub(crate) fn prove(
         ptx_witness: &cl::PartialTxWitness,
         cm_root: [u8; 32],
@@ -0,0 +1,149 @@
+use nomos_ledger::stake_proof::StakeProof;
+use nomos_proof_statements::stake::{StakePrivate, StakePublic};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::proofs::leader_proof::Error;
+
+#[derive(Debug, Clone)]
+pub struct Risc0StakeProof {
+    public_inputs: StakePublic,
+    risc0_receipt: risc0_zkvm::Receipt,
+}
+
+impl Risc0StakeProof {
+    pub fn prove(
+        public_inputs: StakePublic,
+        private_inputs: &StakePrivate,
+        prover: &dyn risc0_zkvm::Prover,
+    ) -> Result<Self, Error> {
+        let env = risc0_zkvm::ExecutorEnv::builder()
+            .write(&public_inputs)
+            .unwrap()
+            .write(&private_inputs)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let start_t = std::time::Instant::now();
+
+        // ATTENTION: producing a groth16 proof currently requires x86 with docker
+        // support
+        let opts = risc0_zkvm::ProverOpts::groth16();
+        let prove_info =
+            prover.prove_with_opts(env, nomos_risc0_proofs::PROOF_OF_STAKE_ELF, &opts)?;
+
+        tracing::debug!(
+            "STARK prover time: {:.2?}, total_cycles: {}",
+            start_t.elapsed(),
+            prove_info.stats.total_cycles
+        );
+        Ok(Self {
+            public_inputs,
+            risc0_receipt: prove_info.receipt,
+        })
+    }
+
+    #[must_use]
+    pub const fn public_inputs(&self) -> &StakePublic {
+        &self.public_inputs
+    }
+}
+
+impl StakeProof for Risc0StakeProof {
+    fn verify(&self, public_inputs: &StakePublic) -> bool {
+        // The risc0 proof is valid by contract
+        public_inputs == &self.public_inputs
+    }
+
+    fn nullifier(&self) -> cl::Nullifier {
+        self.public_inputs.nullifier
+    }
+
+    fn merkle_root(&self) -> [u8; 32] {
+        self.public_inputs.cm_root
+    }
+}
+
+impl Serialize for Risc0StakeProof {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.risc0_receipt.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Risc0StakeProof {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let risc0_receipt = risc0_zkvm::Receipt::deserialize(deserializer)?;
+        let public_inputs = risc0_receipt
+            .journal
+            .decode()
+            .map_err(|e| D::Error::custom(format!("Invalid public inputs: {e}")))?;
+
+        risc0_receipt
+            .verify(nomos_risc0_proofs::PROOF_OF_STAKE_ID)
+            .map_err(D::Error::custom)?;
+
+        Ok(Self {
+            public_inputs,
+            risc0_receipt,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cl::{note::NoteWitness, nullifier::NullifierSecret};
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::staking::NMO_UNIT;
+
+    const MAX_NOTE_COMMS: usize = 1 << 8;
+
+    #[test]
+    fn test_stake_prover() {
+        let mut rng = thread_rng();
+
+        let note = NoteWitness::basic(32, NMO_UNIT, &mut rng);
+        let nf_sk = NullifierSecret::random(&mut rng);
+
+        let note_comm_bytes = vec![note.commit(nf_sk.commit()).as_bytes().to_vec()];
+        let leaves = cl::merkle::padded_leaves::<MAX_NOTE_COMMS>(&note_comm_bytes);
+
+        let input = cl::InputWitness {
+            note,
+            nf_sk,
+            cm_path: cl::merkle::path(leaves, 0),
+        };
+
+        let public_inputs = StakePublic {
+            cm_root: cl::merkle::root(leaves),
+            nullifier: input.nullifier(),
+            unit: NMO_UNIT,
+            min_value: 32,
+            message: [1; 32],
+        };
+
+        let proof = Risc0StakeProof::prove(
+            public_inputs,
+            &StakePrivate { input },
+            risc0_zkvm::default_prover().as_ref(),
+        )
+        .unwrap();
+        assert!(proof
+            .risc0_receipt
+            .verify(nomos_risc0_proofs::PROOF_OF_STAKE_ID)
+            .is_ok());
+
+        assert!(proof.verify(&public_inputs));
+        assert!(!proof.verify(&StakePublic {
+            message: [2; 32],
+            ..public_inputs
+        }));
+    }
+}
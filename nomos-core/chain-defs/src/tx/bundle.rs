@@ -1,4 +1,6 @@
 use bytes::{Bytes, BytesMut};
+use nomos_ledger::bundle_proof::BundleProof;
+use nomos_proof_statements::{bundle::BundlePublic, covenant::CovenantPublic};
 use risc0_zkvm::Prover;
 
 use super::Error;
@@ -73,11 +75,61 @@ impl Bundle {
         let mut buff = BytesMut::new();
         wire::serializer_into_buffer(&mut buff)
             .serialize_into(&self)
-            .expect("MockTransaction serialization to buffer failed");
+            .expect("Bundle serialization to buffer failed");
         buff.freeze()
     }
 }
 
+impl BundleProof for Bundle {
+    fn verify(&self) -> bool {
+        let ptxs = self.bundle.partial_txs();
+        if self.ptx_proofs.len() != ptxs.len() || self.covenant_proofs.len() != ptxs.len() {
+            return false;
+        }
+
+        let valid_ptxs = ptxs
+            .iter()
+            .zip(&self.ptx_proofs)
+            .zip(&self.covenant_proofs)
+            .all(|((ptx, ptx_proof), covenant_proofs)| {
+                let ptx_root = ptx.root();
+                ptx_proof.verify()
+                    && ptx_proof.public_inputs().is_ok_and(|public| {
+                        &public.ptx == ptx && self.cm_roots.contains(&public.cm_root)
+                    })
+                    // one covenant proof for each input
+                    && covenant_proofs.len() == ptx.inputs.len()
+                    && covenant_proofs.iter().zip(&ptx.inputs).all(|(proof, input)| {
+                        proof.verify()
+                            && proof.covenant() == input.covenant
+                            && proof.public_inputs().is_ok_and(|public| {
+                                public
+                                    == CovenantPublic {
+                                        nf: input.nullifier,
+                                        ptx_root,
+                                    }
+                            })
+                    })
+            });
+
+        let balances = ptxs.iter().map(|ptx| ptx.balance).collect();
+        valid_ptxs
+            && self.balance_proof.verify()
+            && self
+                .balance_proof
+                .public_inputs()
+                .is_ok_and(|public| public == BundlePublic { balances })
+    }
+
+    fn bundle(&self) -> &cl::Bundle {
+        &self.bundle
+    }
+
+    fn cm_roots(&self) -> &[[u8; 32]] {
+        &self.cm_roots
+    }
+}
+
 mod serde {
     use std::collections::HashSet;
 
@@ -175,6 +227,7 @@ mod serde {
 #[cfg(test)]
 mod test {
     use cl::{note::derive_unit, *};
+    use nomos_ledger::{LedgerError, LedgerState};
 
     use super::*;
 
@@ -234,5 +287,73 @@ mod test {
             )
             .unwrap()
         );
+
+        let tx = crate::tx::Tx::from(crate::tx::TxKind::Bundle(bundle));
+        let decoded: crate::tx::Tx = wire::deserialize(&wire::serialize(&tx).unwrap()).unwrap();
+        assert_eq!(tx, decoded);
+        assert_eq!(
+            crate::tx::Transaction::hash(&tx),
+            crate::tx::Transaction::hash(&decoded)
+        );
+    }
+
+    #[test]
+    fn test_apply_bundle_to_ledger() {
+        let nmo = derive_unit("NMO");
+        let mut rng = rand::thread_rng();
+
+        let sender_nf_sk = cl::NullifierSecret::random(&mut rng);
+        let sender_nf_pk = sender_nf_sk.commit();
+        let recipient_nf_pk = cl::NullifierSecret::random(&mut rng).commit();
+
+        let mut utxo = receive_utxo(cl::NoteWitness::basic(10, nmo, &mut rng), sender_nf_pk);
+        utxo.note.covenant = CovenantProof::nop_constraint();
+        let ledger_state = LedgerState::from_commitments([utxo.commit_note()], 10);
+        let notes = ledger_state.spend_commitments();
+        let cm_root = notes.root();
+
+        let recipient_output =
+            cl::OutputWitness::new(cl::NoteWitness::basic(10, nmo, &mut rng), recipient_nf_pk);
+        let ptx_witness = cl::PartialTxWitness {
+            inputs: vec![cl::InputWitness::from_output(
+                utxo,
+                sender_nf_sk,
+                notes.witness(0).unwrap(),
+            )],
+            outputs: vec![recipient_output],
+            balance_blinding: BalanceWitness::random_blinding(&mut rng),
+        };
+
+        // requires RISC0_DEV_MODE=1 on machines that cannot build real proofs
+        let prover = risc0_zkvm::default_prover();
+        let no_op = CovenantProof::prove_nop(
+            ptx_witness.inputs[0].nullifier(),
+            ptx_witness.commit().root(),
+            prover.as_ref(),
+        )
+        .unwrap();
+        let bundle = cl::BundleWitness::new(vec![ptx_witness]);
+        let bundle =
+            super::Bundle::prove(&bundle, cm_root, vec![vec![no_op]], prover.as_ref()).unwrap();
+        assert!(bundle.verify());
+
+        let new_state = ledger_state.try_apply_bundle::<_, ()>(&bundle).unwrap();
+        assert!(new_state.is_nullified(&cl::Nullifier::new(sender_nf_sk, utxo.commit_note())));
+        assert_eq!(
+            new_state
+                .spend_commitments()
+                .index_of(&recipient_output.commit_note()),
+            Some(1)
+        );
+        assert!(matches!(
+            new_state.try_apply_bundle::<_, ()>(&bundle),
+            Err(LedgerError::DoubleSpend(_))
+        ));
+
+        // bundles cannot be applied to ledgers that do not know their roots
+        assert!(matches!(
+            LedgerState::from_commitments([], 10).try_apply_bundle::<_, ()>(&bundle),
+            Err(LedgerError::InvalidBundleRoot)
+        ));
     }
 }
@@ -2,9 +2,10 @@ use std::hash::Hash;
 
 use blake2::Digest;
 use bytes::Bytes;
+use serde::{Deserialize, Serialize, Serializer};
 use thiserror::Error;
 
-use crate::crypto::Blake2b;
+use crate::{crypto::Blake2b, wire};
 
 pub mod bundle;
 #[cfg(feature = "mock")]
@@ -33,21 +34,100 @@ pub trait TxSelect {
     ) -> impl Iterator<Item = Self::Tx> + 'i;
 }
 
-pub enum Tx {
+/// Service declaration protocol message, with opaque metadata and proof of
+/// funds.
+pub type SdpMessage = nomos_sdp_core::SdpMessage<Vec<u8>, Vec<u8>>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum TxKind {
     Bundle(bundle::Bundle),
+    Sdp(SdpMessage),
 }
 
-impl Transaction for Tx {
-    const HASHER: TransactionHasher<Self> = |tx| Blake2b::digest(tx.as_bytes()).into();
-    type Hash = [u8; 32];
+impl TxKind {
+    // Domain tag of each kind, hashed before its bytes so that transactions
+    // of different kinds never share a hash
+    const fn tag(&self) -> &'static [u8] {
+        match self {
+            Self::Bundle(_) => b"NOMOS_TX_BUNDLE",
+            Self::Sdp(_) => b"NOMOS_TX_SDP",
+        }
+    }
 
     fn as_bytes(&self) -> Bytes {
         match self {
             Self::Bundle(bundle) => bundle.as_bytes(),
+            Self::Sdp(message) => wire::serialize(message)
+                .expect("SDP message serialization failed")
+                .into(),
         }
     }
 }
 
+/// Transaction along with its hash, computed once when it is built or
+/// deserialized.
+///
+/// It is serialized as its [`TxKind`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(from = "TxKind")]
+pub struct Tx {
+    kind: TxKind,
+    hash: [u8; 32],
+}
+
+impl Tx {
+    #[must_use]
+    pub const fn kind(&self) -> &TxKind {
+        &self.kind
+    }
+
+    #[must_use]
+    pub fn into_kind(self) -> TxKind {
+        self.kind
+    }
+}
+
+impl From<TxKind> for Tx {
+    fn from(kind: TxKind) -> Self {
+        let hash = Blake2b::new()
+            .chain_update(kind.tag())
+            .chain_update(kind.as_bytes())
+            .finalize()
+            .into();
+        Self { kind, hash }
+    }
+}
+
+impl Serialize for Tx {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.kind.serialize(serializer)
+    }
+}
+
+// Proofs are not comparable, transactions are identified by their hash instead
+impl PartialEq for Tx {
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash
+    }
+}
+
+impl Eq for Tx {}
+
+impl Hash for Tx {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
+    }
+}
+
+impl Transaction for Tx {
+    const HASHER: TransactionHasher<Self> = |tx| tx.hash;
+    type Hash = [u8; 32];
+
+    fn as_bytes(&self) -> Bytes {
+        self.kind.as_bytes()
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("Risc0 failed to prove execution of the zkvm")]
@@ -8,7 +8,7 @@ pub mod serialization;
 // Exports
 pub use deserialization::{deserialize, deserializer};
 pub use errors::Error;
-pub use serialization::{serialize, serializer, serializer_into_buffer};
+pub use serialization::{serialize, serialized_size, serializer, serializer_into_buffer};
 pub type Result<T> = std::result::Result<T, Error>;
 
 #[cfg(test)]
@@ -44,6 +44,11 @@ pub fn serializer_into_buffer(buffer: &mut [u8]) -> Serializer<&'_ mut [u8]> {
     }
 }
 
+/// Size of an object once serialized, without serializing it
+pub fn serialized_size<T: Serialize>(item: &T) -> Result<u64> {
+    OPTIONS.serialized_size(item).map_err(Error::Serialize)
+}
+
 /// Serialize an object directly into a vec
 pub fn serialize<T: Serialize>(item: &T) -> Result<Vec<u8>> {
     let size = OPTIONS.serialized_size(item).map_err(Error::Serialize)?;
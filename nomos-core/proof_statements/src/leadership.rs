@@ -62,6 +62,25 @@ impl LeaderPublic {
         let ticket = ticket(input, self.epoch_nonce, self.slot);
         ticket < threshold
     }
+
+    /// Probability that a note of value `stake` wins the slot, i.e. that its
+    /// ticket is below the threshold used by [`Self::check_winning`].
+    #[must_use]
+    pub fn winning_probability(&self, stake: u64) -> f64 {
+        let stake = U256::from_u64(stake);
+        let threshold: Option<U256> = Option::from(self.scaled_phi_approx.1.checked_mul(&stake))
+            .and_then(|t1_stake| Option::from(self.scaled_phi_approx.0.checked_sub(&t1_stake)))
+            .and_then(|diff| Option::from(stake.checked_mul(&diff)));
+        let Some(threshold) = threshold else {
+            return 1.0;
+        };
+        // tickets are uniformly distributed over the U256 range
+        let threshold = threshold
+            .to_be_bytes()
+            .iter()
+            .fold(0f64, |acc, byte| acc.mul_add(256.0, f64::from(*byte)));
+        (threshold / 2f64.powi(256)).min(1.0)
+    }
 }
 
 fn phi_approx(stake: U256, approx: (U256, U256)) -> U256 {
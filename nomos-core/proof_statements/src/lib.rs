@@ -2,3 +2,4 @@ pub mod bundle;
 pub mod covenant;
 pub mod leadership;
 pub mod ptx;
+pub mod stake;
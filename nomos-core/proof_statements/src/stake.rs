@@ -0,0 +1,19 @@
+use cl::balance::{Unit, Value};
+use serde::{Deserialize, Serialize};
+
+/// Public inputs of a proof of stake, which proves ownership of an unspent
+/// note backing whoever `message` identifies without revealing the note
+/// nullifier secret.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StakePublic {
+    pub cm_root: [u8; 32],
+    pub nullifier: cl::Nullifier,
+    pub unit: Unit,
+    pub min_value: Value,
+    pub message: [u8; 32],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StakePrivate {
+    pub input: cl::InputWitness,
+}
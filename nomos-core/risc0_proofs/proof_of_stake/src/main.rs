@@ -0,0 +1,24 @@
+/// Proof of Stake
+use cl::merkle;
+use nomos_proof_statements::stake::{StakePrivate, StakePublic};
+use risc0_zkvm::guest::env;
+
+fn main() {
+    let public_inputs: StakePublic = env::read();
+
+    let StakePrivate { input } = env::read();
+
+    // Ensure the note is valid and worth the stake
+    assert_eq!(input.note.unit, public_inputs.unit);
+    assert!(input.note.value >= public_inputs.min_value);
+    let note_cm = input.note_commitment();
+    let note_cm_leaf = merkle::leaf(note_cm.as_bytes());
+    let note_cm_root = merkle::path_root(note_cm_leaf, &input.cm_path);
+    assert_eq!(note_cm_root, public_inputs.cm_root);
+
+    // Public input constraints
+    assert_eq!(input.nullifier(), public_inputs.nullifier);
+
+    // The message is committed with the public inputs, binding the proof to it
+    env::commit(&public_inputs);
+}
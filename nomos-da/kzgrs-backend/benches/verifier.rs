@@ -2,7 +2,10 @@ use std::hint::black_box;
 
 use divan::{counter::BytesCount, Bencher};
 use kzgrs_backend::{
-    common::{share::DaShare, Chunk},
+    common::{
+        share::{DaLightShare, DaShare, DaSharesCommitments},
+        Chunk,
+    },
     encoder::{DaEncoder, DaEncoderParams},
     global::GLOBAL_PARAMETERS,
     verifier::DaVerifier,
@@ -48,6 +51,8 @@ fn verify<const SIZE: usize>(bencher: Bencher, column_size: usize) {
                     .iter()
                     .map(|row| row.iter().next().copied().unwrap())
                     .collect(),
+                domain_size: encoded_data.domain_size,
+                data_size: encoded_data.data.len(),
             };
             (verifier, da_share)
         })
@@ -56,6 +61,64 @@ fn verify<const SIZE: usize>(bencher: Bencher, column_size: usize) {
         })
         .bench_values(|(verifier, share)| {
             let (light_share, commitments) = share.into_share_and_commitments();
-            black_box(verifier.verify(&commitments, &light_share, column_size))
+            black_box(verifier.verify(&commitments, &light_share))
+        });
+}
+
+fn encoded_shares(
+    column_size: usize,
+    data_size: usize,
+) -> (DaVerifier, Vec<(DaLightShare, DaSharesCommitments)>) {
+    let params = DaEncoderParams::new(column_size, true, GLOBAL_PARAMETERS.clone());
+    let encoder = DaEncoder::new(params);
+    let data = rand_data(data_size / DaEncoderParams::MAX_BLS12_381_ENCODING_CHUNK_SIZE);
+    let encoded_data = encoder.encode(&data).unwrap();
+    let verifier = DaVerifier::new(GLOBAL_PARAMETERS.clone());
+    let shares = encoded_data
+        .iter()
+        .map(DaShare::into_share_and_commitments)
+        .collect();
+    (verifier, shares)
+}
+
+#[divan::bench(consts = [32, 64, 128, 256, 512, 1024], args = [128, 256, 512, 1024, 2048, 4096], sample_count = 1, sample_size = 1)]
+fn verify_each<const SIZE: usize>(bencher: Bencher, column_size: usize) {
+    bencher
+        .with_inputs(|| encoded_shares(column_size, SIZE * KB))
+        .input_counter(|(_, shares)| {
+            BytesCount::new(
+                shares
+                    .iter()
+                    .map(|(share, _)| share.column.iter().map(Chunk::len).sum::<usize>())
+                    .sum::<usize>(),
+            )
+        })
+        .bench_values(|(verifier, shares)| {
+            black_box(
+                shares
+                    .iter()
+                    .all(|(light_share, commitments)| verifier.verify(commitments, light_share)),
+            )
+        });
+}
+
+#[divan::bench(consts = [32, 64, 128, 256, 512, 1024], args = [128, 256, 512, 1024, 2048, 4096], sample_count = 1, sample_size = 1)]
+fn verify_batch<const SIZE: usize>(bencher: Bencher, column_size: usize) {
+    bencher
+        .with_inputs(|| encoded_shares(column_size, SIZE * KB))
+        .input_counter(|(_, shares)| {
+            BytesCount::new(
+                shares
+                    .iter()
+                    .map(|(share, _)| share.column.iter().map(Chunk::len).sum::<usize>())
+                    .sum::<usize>(),
+            )
+        })
+        .bench_values(|(verifier, shares)| {
+            let shares: Vec<_> = shares
+                .iter()
+                .map(|(light_share, commitments)| (commitments, light_share))
+                .collect();
+            black_box(verifier.verify_batch(&shares))
         });
 }
@@ -158,10 +158,14 @@ pub fn hash_commitment<const HASH_SIZE: usize>(commitment: &Commitment) -> [u8;
         .unwrap_or_else(|_| panic!("Size is guaranteed by constant {HASH_SIZE:?}"))
 }
 
+/// Blob id binding the commitments to the blob dimensions, so they cannot be
+/// changed without changing the id.
 #[must_use]
 pub fn build_blob_id(
     aggregated_column_commitment: &Commitment,
     rows_commitments: &[Commitment],
+    domain_size: usize,
+    data_size: usize,
 ) -> [u8; 32] {
     let mut hasher = Sha3_256::new();
     Digest::update(
@@ -171,6 +175,8 @@ pub fn build_blob_id(
     for c in rows_commitments {
         Digest::update(&mut hasher, commitment_to_bytes(c));
     }
+    Digest::update(&mut hasher, (domain_size as u64).to_le_bytes());
+    Digest::update(&mut hasher, (data_size as u64).to_le_bytes());
     hasher.finalize().into()
 }
 
@@ -38,12 +38,22 @@ pub struct DaShare {
         deserialize_with = "deserialize_vec_canonical"
     )]
     pub rows_proofs: Vec<Proof>,
+    /// Number of columns of the extended blob data.
+    pub domain_size: usize,
+    /// Size in bytes of the original blob data.
+    pub data_size: usize,
 }
 
 impl DaShare {
     #[must_use]
     pub fn blob_id(&self) -> Vec<u8> {
-        build_blob_id(&self.aggregated_column_commitment, &self.rows_commitments).into()
+        build_blob_id(
+            &self.aggregated_column_commitment,
+            &self.rows_commitments,
+            self.domain_size,
+            self.data_size,
+        )
+        .into()
     }
 
     #[must_use]
@@ -66,7 +76,12 @@ impl blob::Share for DaShare {
     type SharesCommitments = DaSharesCommitments;
 
     fn blob_id(&self) -> Self::BlobId {
-        build_blob_id(&self.aggregated_column_commitment, &self.rows_commitments)
+        build_blob_id(
+            &self.aggregated_column_commitment,
+            &self.rows_commitments,
+            self.domain_size,
+            self.data_size,
+        )
     }
 
     fn share_idx(&self) -> Self::ShareIndex {
@@ -85,6 +100,8 @@ impl blob::Share for DaShare {
             DaSharesCommitments {
                 aggregated_column_commitment: self.aggregated_column_commitment,
                 rows_commitments: self.rows_commitments,
+                domain_size: self.domain_size,
+                data_size: self.data_size,
             },
         )
     }
@@ -101,6 +118,8 @@ impl blob::Share for DaShare {
             aggregated_column_proof: light_share.aggregated_column_proof,
             rows_commitments: shares_commitments.rows_commitments,
             rows_proofs: light_share.rows_proofs,
+            domain_size: shares_commitments.domain_size,
+            data_size: shares_commitments.data_size,
         }
     }
 }
@@ -146,4 +165,20 @@ pub struct DaSharesCommitments {
         deserialize_with = "deserialize_vec_canonical"
     )]
     pub rows_commitments: Vec<Commitment>,
+    /// Number of columns of the extended blob data.
+    pub domain_size: usize,
+    /// Size in bytes of the original blob data.
+    pub data_size: usize,
+}
+
+impl DaSharesCommitments {
+    #[must_use]
+    pub fn blob_id(&self) -> [u8; 32] {
+        build_blob_id(
+            &self.aggregated_column_commitment,
+            &self.rows_commitments,
+            self.domain_size,
+            self.data_size,
+        )
+    }
 }
@@ -10,12 +10,31 @@ use serde::{Deserialize, Serialize};
 pub struct BlobInfo {
     id: BlobId,
     metadata: Metadata,
+    /// Number of columns of the extended blob data.
+    domain_size: usize,
+    /// Size in bytes of the original blob data.
+    data_size: usize,
 }
 
 impl BlobInfo {
     #[must_use]
-    pub const fn new(id: BlobId, metadata: Metadata) -> Self {
-        Self { id, metadata }
+    pub const fn new(id: BlobId, metadata: Metadata, domain_size: usize, data_size: usize) -> Self {
+        Self {
+            id,
+            metadata,
+            domain_size,
+            data_size,
+        }
+    }
+
+    #[must_use]
+    pub const fn domain_size(&self) -> usize {
+        self.domain_size
+    }
+
+    #[must_use]
+    pub const fn data_size(&self) -> usize {
+        self.data_size
     }
 }
 
@@ -27,7 +46,10 @@ impl blob::info::DispersedBlobInfo for BlobInfo {
     }
 
     fn size(&self) -> usize {
-        std::mem::size_of_val(&self.id) + self.metadata.size()
+        std::mem::size_of_val(&self.id)
+            + self.metadata.size()
+            + std::mem::size_of_val(&self.domain_size)
+            + std::mem::size_of_val(&self.data_size)
     }
 }
 
@@ -123,7 +145,6 @@ mod tests {
 
     fn attest_encoded_data(encoded_data: &EncodedData, verifiers: &[DaVerifier]) -> Vec<bool> {
         let mut attestations = Vec::new();
-        let domain_size = encoded_data.extended_data.0[0].len();
         for (i, column) in encoded_data.extended_data.columns().enumerate() {
             let verifier = &verifiers[i];
             let da_share = DaShare {
@@ -140,9 +161,11 @@ mod tests {
                     .iter()
                     .map(|proofs| proofs.get(i).copied().unwrap())
                     .collect(),
+                domain_size: encoded_data.domain_size,
+                data_size: encoded_data.data.len(),
             };
             let (light_share, commitments) = da_share.into_share_and_commitments();
-            attestations.push(verifier.verify(&commitments, &light_share, domain_size));
+            attestations.push(verifier.verify(&commitments, &light_share));
         }
         attestations
     }
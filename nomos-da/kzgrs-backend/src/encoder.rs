@@ -1,26 +1,32 @@
 use std::ops::Div;
 
-use ark_ff::{BigInteger, PrimeField};
+use ark_ff::{BigInteger, PrimeField, Zero};
 use ark_poly::EvaluationDomain;
 use kzgrs::{
-    bytes_to_polynomial, commit_polynomial,
+    bytes_to_evaluations, bytes_to_polynomial, commit_polynomial,
     common::bytes_to_polynomial_unchecked,
     encode,
     fk20::{fk20_batch_generate_elements_proofs, Toeplitz1Cache},
-    Commitment, Evaluations, GlobalParameters, KzgRsError, Polynomial, PolynomialEvaluationDomain,
-    Proof, BYTES_PER_FIELD_ELEMENT,
+    rs::interpolate_first_half,
+    Commitment, Evaluations, FieldElement, GlobalParameters, KzgRsError, Polynomial,
+    PolynomialEvaluationDomain, Proof, BYTES_PER_FIELD_ELEMENT,
 };
 #[cfg(feature = "parallel")]
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
 use crate::{
-    common::{hash_commitment, share::DaShare, Chunk, ChunksMatrix, Row},
+    common::{build_blob_id, hash_commitment, share::DaShare, Chunk, ChunksMatrix, Row},
     global::GLOBAL_PARAMETERS,
 };
 
 #[derive(Clone)]
 pub struct DaEncoderParams {
+    /// Maximum number of columns of the extended data, blobs small enough to
+    /// fit in a single row are encoded with fewer columns.
     column_count: usize,
+    /// Minimum number of columns of the extended data, so small blobs are
+    /// still spread over enough subnetworks to be sampled.
+    min_column_count: usize,
     toeplitz1cache: Option<Toeplitz1Cache>,
     global_parameters: GlobalParameters,
 }
@@ -34,6 +40,7 @@ impl DaEncoderParams {
             with_cache.then(|| Toeplitz1Cache::with_size(&global_parameters, column_count));
         Self {
             column_count,
+            min_column_count: 2,
             toeplitz1cache,
             global_parameters,
         }
@@ -42,14 +49,36 @@ impl DaEncoderParams {
     pub fn default_with(column_count: usize) -> Self {
         Self {
             column_count,
+            min_column_count: 2,
             toeplitz1cache: None,
             global_parameters: GLOBAL_PARAMETERS.clone(),
         }
     }
+
+    /// Sets the minimum number of columns blobs are encoded with, it is
+    /// rounded up to a power of two and capped by the maximum.
+    #[must_use]
+    pub const fn with_min_column_count(mut self, min_column_count: usize) -> Self {
+        self.min_column_count = min_column_count;
+        self
+    }
+
+    /// Number of columns of the extended data of a blob of `data_size` bytes.
+    #[must_use]
+    pub fn domain_size(&self, data_size: usize) -> usize {
+        let chunks_count = data_size
+            .div_ceil(Self::MAX_BLS12_381_ENCODING_CHUNK_SIZE)
+            .max(1);
+        (chunks_count * 2)
+            .max(self.min_column_count)
+            .next_power_of_two()
+            .min(self.column_count)
+    }
 }
 
 pub struct EncodedData {
     pub data: Vec<u8>,
+    pub domain_size: usize,
     pub chunked_data: ChunksMatrix,
     pub extended_data: ChunksMatrix,
     pub row_commitments: Vec<Commitment>,
@@ -77,9 +106,21 @@ impl EncodedData {
                 .iter()
                 .map(|proofs| proofs.get(index).copied().unwrap())
                 .collect(),
+            domain_size: self.domain_size,
+            data_size: self.data.len(),
         })
     }
 
+    #[must_use]
+    pub fn blob_id(&self) -> [u8; 32] {
+        build_blob_id(
+            &self.aggregated_column_commitment,
+            &self.row_commitments,
+            self.domain_size,
+            self.data.len(),
+        )
+    }
+
     #[must_use]
     pub const fn iter(&self) -> EncodedDataIterator {
         EncodedDataIterator::new(self)
@@ -144,10 +185,14 @@ impl DaEncoder {
         Self { params: settings }
     }
 
+    /// Splits the data in rows of chunks, zero padding the last row.
     fn chunkify(&self, data: &[u8]) -> ChunksMatrix {
-        let chunk_size =
-            // column count is divided by two, as later on rows are encoded to twice the size
-            self.params.column_count.div(2) * DaEncoderParams::MAX_BLS12_381_ENCODING_CHUNK_SIZE;
+        // column count is divided by two, as later on rows are encoded to twice the size
+        let chunk_size = self.params.domain_size(data.len()).div(2)
+            * DaEncoderParams::MAX_BLS12_381_ENCODING_CHUNK_SIZE;
+        let rows_count = data.len().div_ceil(chunk_size).max(1);
+        let mut data = data.to_vec();
+        data.resize(rows_count * chunk_size, 0);
         data.chunks(chunk_size)
             .map(|d| {
                 d.chunks(DaEncoderParams::MAX_BLS12_381_ENCODING_CHUNK_SIZE)
@@ -162,6 +207,14 @@ impl DaEncoder {
             .collect()
     }
 
+    /// Commit to the polynomials of the rows of `matrix`, which evaluate to the
+    /// row chunks over the first half of `polynomial_evaluation_domain` so
+    /// that their evaluations over the second half extend them.
+    ///
+    /// This systematic encoding is incompatible with earlier versions, which
+    /// took the chunks as evaluations over the whole domain: row commitments,
+    /// and so blob ids, differ for the same data, and shares encoded by
+    /// either version do not verify on the other.
     #[expect(clippy::type_complexity, reason = "TODO: Address this at some point.")]
     fn compute_kzg_row_commitments(
         global_parameters: &GlobalParameters,
@@ -179,14 +232,12 @@ impl DaEncoder {
             }
         }
         .map(|r| {
-            // Using the unchecked version here. Because during the process of chunkifiying
-            // we already make sure to have the chunks of proper elements.
-            // Also, after rs encoding, we are sure all `Fr` elements already fits within
-            // modulus.
-            let (evals, poly) = bytes_to_polynomial_unchecked::<BYTES_PER_FIELD_ELEMENT>(
+            // Chunkifying already makes sure the chunks fit within the modulus.
+            let evals = bytes_to_evaluations::<BYTES_PER_FIELD_ELEMENT>(
                 r.as_bytes().as_ref(),
                 polynomial_evaluation_domain,
             );
+            let poly = interpolate_first_half(&evals.evals, polynomial_evaluation_domain);
             commit_polynomial(&poly, global_parameters)
                 .map(|commitment| ((evals, poly), commitment))
         })
@@ -221,6 +272,7 @@ impl DaEncoder {
     fn compute_rows_proofs(
         global_parameters: &GlobalParameters,
         polynomials: &[Polynomial],
+        polynomial_evaluation_domain: PolynomialEvaluationDomain,
         toeplitz1cache: Option<&Toeplitz1Cache>,
     ) -> Vec<Vec<Proof>> {
         {
@@ -233,7 +285,17 @@ impl DaEncoder {
                 polynomials.par_iter()
             }
         }
-        .map(|poly| fk20_batch_generate_elements_proofs(poly, global_parameters, toeplitz1cache))
+        .map(|poly| {
+            // rows polynomials have a lower degree than the domain, their
+            // coefficients are padded for the proofs to cover the whole domain
+            let mut coeffs = poly.coeffs.clone();
+            coeffs.resize(polynomial_evaluation_domain.size(), FieldElement::zero());
+            fk20_batch_generate_elements_proofs(
+                &Polynomial { coeffs },
+                global_parameters,
+                toeplitz1cache,
+            )
+        })
         .collect()
     }
 
@@ -243,11 +305,30 @@ impl DaEncoder {
         matrix: &ChunksMatrix,
         polynomial_evaluation_domain: PolynomialEvaluationDomain,
     ) -> Result<Vec<((Evaluations, Polynomial), Commitment)>, KzgRsError> {
-        Self::compute_kzg_row_commitments(
-            global_parameters,
-            &matrix.transposed(),
-            polynomial_evaluation_domain,
-        )
+        let columns = matrix.transposed();
+        {
+            #[cfg(not(feature = "parallel"))]
+            {
+                columns.rows()
+            }
+            #[cfg(feature = "parallel")]
+            {
+                columns.par_rows()
+            }
+        }
+        .map(|c| {
+            // Using the unchecked version here. Because during the process of chunkifiying
+            // we already make sure to have the chunks of proper elements.
+            // Also, after rs encoding, we are sure all `Fr` elements already fits within
+            // modulus.
+            let (evals, poly) = bytes_to_polynomial_unchecked::<BYTES_PER_FIELD_ELEMENT>(
+                c.as_bytes().as_ref(),
+                polynomial_evaluation_domain,
+            );
+            commit_polynomial(&poly, global_parameters)
+                .map(|commitment| ((evals, poly), commitment))
+        })
+        .collect()
     }
 
     fn compute_aggregated_column_commitment(
@@ -297,9 +378,16 @@ impl nomos_core::da::DaEncoder for DaEncoder {
 
     fn encode(&self, data: &[u8]) -> Result<EncodedData, KzgRsError> {
         let global_parameters = &self.params.global_parameters;
+        let domain_size = self.params.domain_size(data.len());
+        // the cache is only valid for polynomials of the maximum domain size
+        let toeplitz1cache = self
+            .params
+            .toeplitz1cache
+            .as_ref()
+            .filter(|_| domain_size == self.params.column_count);
         let chunked_data = self.chunkify(data);
-        let row_domain = PolynomialEvaluationDomain::new(self.params.column_count)
-            .expect("Domain should be able to build");
+        let row_domain =
+            PolynomialEvaluationDomain::new(domain_size).expect("Domain should be able to build");
         let column_domain = PolynomialEvaluationDomain::new(chunked_data.len())
             .expect("Domain should be able to build");
         let (row_polynomials, row_commitments): (Vec<_>, Vec<_>) =
@@ -312,7 +400,8 @@ impl nomos_core::da::DaEncoder for DaEncoder {
         let rows_proofs = Self::compute_rows_proofs(
             global_parameters,
             &row_polynomials,
-            self.params.toeplitz1cache.as_ref(),
+            row_domain,
+            toeplitz1cache,
         );
         let (_column_polynomials, column_commitments): (Vec<_>, Vec<_>) =
             Self::compute_kzg_column_commitments(global_parameters, &extended_data, column_domain)?
@@ -327,10 +416,11 @@ impl nomos_core::da::DaEncoder for DaEncoder {
         let aggregated_column_proofs = Self::compute_aggregated_column_proofs(
             global_parameters,
             &aggregated_polynomial,
-            self.params.toeplitz1cache.as_ref(),
+            toeplitz1cache,
         );
         Ok(EncodedData {
             data: data.to_vec(),
+            domain_size,
             chunked_data,
             extended_data,
             row_commitments,
@@ -464,7 +554,7 @@ pub mod test {
         let (_evals, polynomials): (Vec<_>, Vec<_>) = poly_data.into_iter().unzip();
         let extended_evaluations = DaEncoder::rs_encode_rows(&polynomials, domain);
         let extended_matrix = DaEncoder::evals_to_chunk_matrix(&extended_evaluations);
-        let proofs = DaEncoder::compute_rows_proofs(&GLOBAL_PARAMETERS, &polynomials, None);
+        let proofs = DaEncoder::compute_rows_proofs(&GLOBAL_PARAMETERS, &polynomials, domain, None);
 
         let checks = izip!(matrix.iter(), &commitments, &proofs);
         for (row, commitment, proofs) in checks {
@@ -584,11 +674,48 @@ pub mod test {
             0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8,
         ];
         let encoded_data = encoder.encode(&data).unwrap();
+        // a single chunk is extended to two columns
+        assert_eq!(encoded_data.domain_size, 2);
 
         let shares = encoded_data.iter();
-        assert_eq!(shares.count(), 16);
+        assert_eq!(shares.count(), 2);
 
         let shares = encoded_data.iter();
-        assert_eq!(shares.count(), 16);
+        assert_eq!(shares.count(), 2);
+    }
+
+    #[test]
+    fn test_variable_size_encoding() {
+        for (data_size, domain_size, rows_count) in [
+            (0, 2, 1),
+            (31, 2, 1),
+            (32, 4, 1),
+            (31 * 5, 16, 1),
+            (31 * 8, 16, 1),
+            (31 * 8 + 1, 16, 2),
+            (31 * 20, 16, 3),
+        ] {
+            let data = vec![1u8; data_size];
+            let encoded_data = ENCODER.encode(&data).unwrap();
+            assert_eq!(encoded_data.domain_size, domain_size);
+            assert_eq!(encoded_data.data, data);
+            assert_eq!(encoded_data.row_commitments.len(), rows_count);
+            assert_eq!(encoded_data.column_commitments.len(), domain_size);
+            assert_eq!(encoded_data.iter().count(), domain_size);
+        }
+    }
+
+    #[test]
+    fn test_min_column_count() {
+        let params = DaEncoderParams::default_with(DOMAIN_SIZE).with_min_column_count(DOMAIN_SIZE);
+        let encoder = DaEncoder::new(params);
+        for data_size in [0, 31, 31 * 5, 31 * 20] {
+            let encoded_data = encoder.encode(&vec![1u8; data_size]).unwrap();
+            assert_eq!(encoded_data.domain_size, DOMAIN_SIZE);
+            assert_eq!(encoded_data.iter().count(), DOMAIN_SIZE);
+        }
+        let params = DaEncoderParams::default_with(DOMAIN_SIZE).with_min_column_count(5);
+        assert_eq!(params.domain_size(31), 8);
+        assert_eq!(params.domain_size(31 * 5), 16);
     }
 }
@@ -1,12 +1,110 @@
-use kzgrs::BYTES_PER_FIELD_ELEMENT;
+use std::collections::BTreeMap;
 
-use crate::common::{share::DaShare, Chunk};
+use ark_poly::EvaluationDomain as _;
+use kzgrs::{
+    common::field_element_from_bytes_le, decode, rs::points_to_bytes, GlobalParameters,
+    PolynomialEvaluationDomain, BYTES_PER_FIELD_ELEMENT,
+};
+
+use crate::{
+    common::{
+        share::{DaShare, DaSharesCommitments},
+        Chunk, Column, ShareIndex,
+    },
+    encoder::DaEncoderParams,
+    verifier::DaVerifier,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReconstructionError {
+    #[error(
+        "Not enough valid shares to reconstruct the data, required {required} but got {received}"
+    )]
+    NotEnoughShares { required: usize, received: usize },
+}
+
+/// Reconstruct original data from any set of `DaShare` holding at least half
+/// of the blob columns, either original or extended ones.
+///
+/// Shares are verified against the blob `commitments` before being used,
+/// invalid and duplicated shares are ignored.
+pub fn reconstruct(
+    shares: &[DaShare],
+    commitments: &DaSharesCommitments,
+    global_parameters: &GlobalParameters,
+) -> Result<Vec<u8>, ReconstructionError> {
+    let domain = PolynomialEvaluationDomain::new(commitments.domain_size)
+        .expect("Domain should be able to build");
+    let columns = shares
+        .iter()
+        .filter(|share| {
+            (share.share_idx as usize) < domain.size()
+                && DaVerifier::verify_chunks(
+                    global_parameters,
+                    share.column.as_ref(),
+                    &commitments.rows_commitments,
+                    &share.rows_proofs,
+                    share.share_idx as usize,
+                    domain,
+                )
+        })
+        .map(|share| (share.share_idx, &share.column));
+    reconstruct_from_columns(columns, commitments)
+}
+
+/// Reconstruct original data from columns which were already verified, given
+/// at least half of the blob columns. Duplicated columns are ignored.
+pub fn reconstruct_from_columns<'a>(
+    columns: impl IntoIterator<Item = (ShareIndex, &'a Column)>,
+    commitments: &DaSharesCommitments,
+) -> Result<Vec<u8>, ReconstructionError> {
+    let domain = PolynomialEvaluationDomain::new(commitments.domain_size)
+        .expect("Domain should be able to build");
+    let required = domain.size() / 2;
+    let columns: BTreeMap<usize, &Column> = columns
+        .into_iter()
+        .map(|(index, column)| (index as usize, column))
+        .filter(|(index, _)| *index < domain.size())
+        .collect();
+    if columns.len() < required {
+        return Err(ReconstructionError::NotEnoughShares {
+            required,
+            received: columns.len(),
+        });
+    }
+    // rows polynomials have a lower degree than `required`, so that many
+    // points are enough to interpolate them
+    let columns: Vec<_> = columns.into_iter().take(required).collect();
+    let rows_count = columns
+        .iter()
+        .map(|(_, column)| column.len())
+        .min()
+        .unwrap_or_default();
+    let mut data: Vec<u8> = (0..rows_count)
+        .flat_map(|row| {
+            let mut points = vec![None; domain.size()];
+            for (index, column) in &columns {
+                points[*index] = Some(field_element_from_bytes_le(
+                    column.0[row].as_bytes().as_slice(),
+                ));
+            }
+            let evaluations = decode(required, &points, domain);
+            points_to_bytes::<{ DaEncoderParams::MAX_BLS12_381_ENCODING_CHUNK_SIZE }>(
+                &evaluations.evals,
+            )
+        })
+        .collect();
+    // drop the padding of the last row
+    data.truncate(commitments.data_size);
+    Ok(data)
+}
 
 /// Reconstruct original data from a set of `DaShare`
 /// Warning! This does not interpolate so it should not be used on blobs which
 /// doesn't represent the original set of data.
 #[must_use]
 pub fn reconstruct_without_missing_data(shares: &[DaShare]) -> Vec<u8> {
+    let data_size = shares.first().map_or(0, |share| share.data_size);
     // pick positions from columns
     let mut data: Vec<((usize, usize), Vec<u8>)> = shares
         .iter()
@@ -27,18 +125,23 @@ pub fn reconstruct_without_missing_data(shares: &[DaShare]) -> Vec<u8> {
             data.pop();
             data.into_iter()
         })
+        .take(data_size)
         .collect()
 }
 
 #[cfg(test)]
 mod test {
     use kzgrs::{Commitment, Proof};
-    use nomos_core::da::DaEncoder as _;
+    use nomos_core::da::{blob::Share as _, DaEncoder as _};
 
     use crate::{
-        common::{share::DaShare, ShareIndex},
-        encoder::{test::rand_data, DaEncoder, DaEncoderParams, EncodedData},
-        reconstruction::reconstruct_without_missing_data,
+        common::{share::DaShare, Chunk, ShareIndex},
+        encoder::{
+            test::{rand_data, ENCODER},
+            DaEncoder, DaEncoderParams, EncodedData,
+        },
+        global::GLOBAL_PARAMETERS,
+        reconstruction::{reconstruct, reconstruct_without_missing_data, ReconstructionError},
     };
 
     #[test]
@@ -59,8 +162,84 @@ mod test {
                 aggregated_column_proof: Proof::default(),
                 rows_commitments: vec![],
                 rows_proofs: vec![],
+                domain_size: encoded_data.domain_size,
+                data_size: data.len(),
             })
             .collect();
         assert_eq!(data, reconstruct_without_missing_data(&shares));
     }
+
+    #[test]
+    fn test_reconstruct_from_any_half() {
+        let data: Vec<u8> = rand_data(32);
+        let encoder = DaEncoder::new(DaEncoderParams::default_with(16));
+        let encoded_data: EncodedData = encoder.encode(&data).unwrap();
+        let shares: Vec<DaShare> = encoded_data.iter().collect();
+        let (_, commitments) = shares[0].clone().into_share_and_commitments();
+
+        // extended columns only
+        let extended = &shares[8..];
+        assert_eq!(
+            reconstruct(extended, &commitments, &GLOBAL_PARAMETERS).unwrap(),
+            data
+        );
+
+        // every other column, duplicated shares are ignored
+        let mixed: Vec<DaShare> = shares
+            .iter()
+            .step_by(2)
+            .chain(shares.iter().step_by(4))
+            .cloned()
+            .collect();
+        assert_eq!(
+            reconstruct(&mixed, &commitments, &GLOBAL_PARAMETERS).unwrap(),
+            data
+        );
+
+        assert!(matches!(
+            reconstruct(&shares[..7], &commitments, &GLOBAL_PARAMETERS),
+            Err(ReconstructionError::NotEnoughShares {
+                required: 8,
+                received: 7
+            })
+        ));
+    }
+
+    #[test]
+    fn test_reconstruct_ignores_invalid_shares() {
+        let data: Vec<u8> = rand_data(32);
+        let encoder = DaEncoder::new(DaEncoderParams::default_with(16));
+        let encoded_data: EncodedData = encoder.encode(&data).unwrap();
+        let mut shares: Vec<DaShare> = encoded_data.iter().skip(7).collect();
+        let (_, commitments) = shares[0].clone().into_share_and_commitments();
+        shares[0].column.0[0] = Chunk(vec![0; 32]);
+
+        assert_eq!(
+            reconstruct(&shares, &commitments, &GLOBAL_PARAMETERS).unwrap(),
+            data
+        );
+        assert!(matches!(
+            reconstruct(&shares[..8], &commitments, &GLOBAL_PARAMETERS),
+            Err(ReconstructionError::NotEnoughShares {
+                required: 8,
+                received: 7
+            })
+        ));
+    }
+
+    #[test]
+    fn test_reconstruct_variable_size() {
+        let data = vec![7u8; 100];
+        let encoded_data: EncodedData = ENCODER.encode(&data).unwrap();
+        let shares: Vec<DaShare> = encoded_data.iter().collect();
+        assert_eq!(shares.len(), 8);
+        let (_, commitments) = shares[0].clone().into_share_and_commitments();
+
+        // the padding of the row is not part of the data
+        assert_eq!(
+            reconstruct(&shares[4..], &commitments, &GLOBAL_PARAMETERS).unwrap(),
+            data
+        );
+        assert_eq!(reconstruct_without_missing_data(&shares[..4]), data);
+    }
 }
@@ -32,6 +32,8 @@ pub fn get_da_share(data: Option<Vec<u8>>) -> DaShare {
             .iter()
             .map(|proofs| proofs.get(index).copied().unwrap())
             .collect(),
+        domain_size: encoded_data.domain_size,
+        data_size: data.len(),
     };
 
     da_share
@@ -2,9 +2,11 @@ use ark_poly::EvaluationDomain;
 use itertools::{izip, Itertools};
 use kzgrs::{
     bytes_to_polynomial, commit_polynomial, common::field_element_from_bytes_le,
-    verify_element_proof, Commitment, GlobalParameters, PolynomialEvaluationDomain, Proof,
-    BYTES_PER_FIELD_ELEMENT,
+    verify_element_proofs_batch, Commitment, ElementOpening, GlobalParameters,
+    PolynomialEvaluationDomain, Proof, BYTES_PER_FIELD_ELEMENT,
 };
+#[cfg(feature = "parallel")]
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
 use crate::{
     common::{
@@ -25,69 +27,95 @@ impl DaVerifier {
         Self { global_parameters }
     }
 
-    fn verify_column(
+    /// Checks the column matches its commitment, and returns the opening of
+    /// the commitment hash over the aggregated column commitment.
+    fn column_opening<'a>(
         global_parameters: &GlobalParameters,
         column: &Column,
         column_commitment: &Commitment,
-        aggregated_column_commitment: &Commitment,
-        aggregated_column_proof: &Proof,
+        aggregated_column_commitment: &'a Commitment,
+        aggregated_column_proof: &'a Proof,
         index: usize,
         rows_domain: PolynomialEvaluationDomain,
-    ) -> bool {
+    ) -> Option<ElementOpening<'a>> {
         let column_domain =
             PolynomialEvaluationDomain::new(column.len()).expect("Domain should be able to build");
         // 1. compute commitment for column
-        let Ok((_, polynomial)) = bytes_to_polynomial::<BYTES_PER_FIELD_ELEMENT>(
+        let (_, polynomial) = bytes_to_polynomial::<BYTES_PER_FIELD_ELEMENT>(
             column.as_bytes().as_slice(),
             column_domain,
-        ) else {
-            return false;
-        };
-        let Ok(computed_column_commitment) = commit_polynomial(&polynomial, global_parameters)
-        else {
-            return false;
-        };
+        )
+        .ok()?;
+        let computed_column_commitment = commit_polynomial(&polynomial, global_parameters).ok()?;
         // 2. if computed column commitment != column commitment, fail
         if &computed_column_commitment != column_commitment {
-            return false;
+            return None;
         }
         // 3. compute column hash
         let commitment_hash = hash_commitment::<
             { DaEncoderParams::MAX_BLS12_381_ENCODING_CHUNK_SIZE },
         >(column_commitment);
-        // 4. check proof with commitment and proof over the aggregated column
-        //    commitment
-        let element = field_element_from_bytes_le(commitment_hash.as_slice());
-        verify_element_proof(
-            index,
-            &element,
+        // 4. open the hash over the aggregated column commitment
+        Some(ElementOpening {
+            element_index: index,
+            element: field_element_from_bytes_le(commitment_hash.as_slice()),
+            commitment: aggregated_column_commitment,
+            proof: aggregated_column_proof,
+            domain: rows_domain,
+        })
+    }
+
+    #[cfg(test)]
+    fn verify_column(
+        global_parameters: &GlobalParameters,
+        column: &Column,
+        column_commitment: &Commitment,
+        aggregated_column_commitment: &Commitment,
+        aggregated_column_proof: &Proof,
+        index: usize,
+        rows_domain: PolynomialEvaluationDomain,
+    ) -> bool {
+        Self::column_opening(
+            global_parameters,
+            column,
+            column_commitment,
             aggregated_column_commitment,
             aggregated_column_proof,
+            index,
             rows_domain,
-            global_parameters,
         )
+        .is_some_and(|opening| verify_element_proofs_batch(&[opening], global_parameters))
     }
 
-    fn verify_chunk(
-        global_parameters: &GlobalParameters,
-        chunk: &Chunk,
-        commitment: &Commitment,
-        proof: &Proof,
+    /// Openings of the chunks over the rows commitments, `None` if the number
+    /// of chunks, commitments and proofs differ.
+    fn chunks_openings<'a>(
+        chunks: &[Chunk],
+        commitments: &'a [Commitment],
+        proofs: &'a [Proof],
         index: usize,
         domain: PolynomialEvaluationDomain,
-    ) -> bool {
-        let element = field_element_from_bytes_le(chunk.as_bytes().as_slice());
-        verify_element_proof(
-            index,
-            &element,
-            commitment,
-            proof,
-            domain,
-            global_parameters,
+    ) -> Option<Vec<ElementOpening<'a>>> {
+        if ![chunks.len(), commitments.len(), proofs.len()]
+            .iter()
+            .all_equal()
+        {
+            return None;
+        }
+        Some(
+            izip!(chunks, commitments, proofs)
+                .map(|(chunk, commitment, proof)| ElementOpening {
+                    element_index: index,
+                    element: field_element_from_bytes_le(chunk.as_bytes().as_slice()),
+                    commitment,
+                    proof,
+                    domain,
+                })
+                .collect(),
         )
     }
 
-    fn verify_chunks(
+    pub(crate) fn verify_chunks(
         global_parameters: &GlobalParameters,
         chunks: &[Chunk],
         commitments: &[Commitment],
@@ -95,57 +123,120 @@ impl DaVerifier {
         index: usize,
         domain: PolynomialEvaluationDomain,
     ) -> bool {
-        if ![chunks.len(), commitments.len(), proofs.len()]
-            .iter()
-            .all_equal()
-        {
+        Self::chunks_openings(chunks, commitments, proofs, index, domain)
+            .is_some_and(|openings| verify_element_proofs_batch(&openings, global_parameters))
+    }
+
+    /// Checks the blob dimensions in the commitments match each other and the
+    /// share.
+    fn verify_dimensions(commitments: &DaSharesCommitments, light_share: &DaLightShare) -> bool {
+        let domain_size = commitments.domain_size;
+        if domain_size < 2 || !domain_size.is_power_of_two() {
             return false;
         }
-        for (chunk, commitment, proof) in izip!(chunks, commitments, proofs) {
-            if !Self::verify_chunk(global_parameters, chunk, commitment, proof, index, domain) {
-                return false;
-            }
-        }
-        true
+        let row_size = domain_size / 2 * DaEncoderParams::MAX_BLS12_381_ENCODING_CHUNK_SIZE;
+        usize::from(light_share.share_idx) < domain_size
+            && commitments.rows_commitments.len() == commitments.data_size.div_ceil(row_size).max(1)
     }
 
-    #[must_use]
-    pub fn verify(
-        &self,
-        commitments: &DaSharesCommitments,
-        light_share: &DaLightShare,
-        rows_domain_size: usize,
-    ) -> bool {
-        let rows_domain = PolynomialEvaluationDomain::new(rows_domain_size)
+    /// Openings proving the share, `None` if the share does not match the
+    /// commitments.
+    fn share_openings<'a>(
+        global_parameters: &GlobalParameters,
+        commitments: &'a DaSharesCommitments,
+        light_share: &'a DaLightShare,
+    ) -> Option<Vec<ElementOpening<'a>>> {
+        if !Self::verify_dimensions(commitments, light_share) {
+            return None;
+        }
+        let rows_domain = PolynomialEvaluationDomain::new(commitments.domain_size)
             .expect("Domain should be able to build");
-        let share_col_idx = &light_share.share_idx;
-        let index = share_col_idx;
+        let index = usize::from(light_share.share_idx);
 
-        let is_column_verified = Self::verify_column(
-            &self.global_parameters,
+        let column_opening = Self::column_opening(
+            global_parameters,
             &light_share.column,
             &light_share.column_commitment,
             &commitments.aggregated_column_commitment,
             &light_share.aggregated_column_proof,
-            *index as usize,
+            index,
             rows_domain,
-        );
-        if !is_column_verified {
-            return false;
-        }
-
-        let are_chunks_verified = Self::verify_chunks(
-            &self.global_parameters,
+        )?;
+        let mut openings = Self::chunks_openings(
             light_share.column.as_ref(),
             &commitments.rows_commitments,
             &light_share.rows_proofs,
-            *index as usize,
+            index,
             rows_domain,
-        );
-        if !are_chunks_verified {
-            return false;
+        )?;
+        openings.push(column_opening);
+        Some(openings)
+    }
+
+    /// Verifies the share against the commitments, the domain of the blob is
+    /// taken from the commitments.
+    #[must_use]
+    pub fn verify(&self, commitments: &DaSharesCommitments, light_share: &DaLightShare) -> bool {
+        Self::share_openings(&self.global_parameters, commitments, light_share)
+            .is_some_and(|openings| verify_element_proofs_batch(&openings, &self.global_parameters))
+    }
+
+    /// Verifies many shares at once, the result for each of them is returned
+    /// in the same order.
+    ///
+    /// Proofs of all the shares are checked with a single multi-pairing. If
+    /// that fails, the batch is split in halves until the invalid shares are
+    /// isolated, so a few invalid shares only cost a logarithmic number of
+    /// extra checks.
+    #[must_use]
+    pub fn verify_batch(&self, shares: &[(&DaSharesCommitments, &DaLightShare)]) -> Vec<bool> {
+        let openings: Vec<Option<Vec<ElementOpening>>> = {
+            #[cfg(not(feature = "parallel"))]
+            {
+                shares.iter()
+            }
+            #[cfg(feature = "parallel")]
+            {
+                shares.par_iter()
+            }
+        }
+        .map(|&(commitments, light_share)| {
+            Self::share_openings(&self.global_parameters, commitments, light_share)
+        })
+        .collect();
+
+        let mut results = vec![false; shares.len()];
+        self.verify_openings(&openings, &mut results);
+        results
+    }
+
+    /// Checks the openings of the shares with a single multi-pairing, and
+    /// bisects the shares when it fails.
+    fn verify_openings(&self, openings: &[Option<Vec<ElementOpening>>], results: &mut [bool]) {
+        let batch: Vec<ElementOpening> = openings.iter().flatten().flatten().copied().collect();
+        if verify_element_proofs_batch(&batch, &self.global_parameters) {
+            for (result, openings) in results.iter_mut().zip(openings) {
+                *result = openings.is_some();
+            }
+            return;
+        }
+        if openings.len() == 1 {
+            results[0] = false;
+            return;
+        }
+        let middle = openings.len() / 2;
+        let (left_openings, right_openings) = openings.split_at(middle);
+        let (left_results, right_results) = results.split_at_mut(middle);
+        #[cfg(not(feature = "parallel"))]
+        {
+            self.verify_openings(left_openings, left_results);
+            self.verify_openings(right_openings, right_results);
         }
-        true
+        #[cfg(feature = "parallel")]
+        rayon::join(
+            || self.verify_openings(left_openings, left_results),
+            || self.verify_openings(right_openings, right_results),
+        );
     }
 }
 
@@ -163,7 +254,11 @@ mod test {
     use nomos_core::da::{blob::Share, DaEncoder};
 
     use crate::{
-        common::{hash_commitment, share::DaShare, Chunk, Column},
+        common::{
+            hash_commitment,
+            share::{DaLightShare, DaShare, DaSharesCommitments},
+            Chunk, Column,
+        },
         encoder::{
             test::{rand_data, ENCODER},
             DaEncoderParams,
@@ -311,6 +406,8 @@ mod test {
                 .iter()
                 .map(|proofs| proofs.get(index).copied().unwrap())
                 .collect(),
+            domain_size: encoded_data.domain_size,
+            data_size: data.len(),
         };
         // Happy case
         let chunks_verified = DaVerifier::verify_chunks(
@@ -370,7 +467,6 @@ mod test {
     fn test_verify() {
         let encoder = &ENCODER;
         let data = rand_data(32);
-        let domain_size = 16usize;
         let verifiers: Vec<DaVerifier> = (0..16)
             .map(|_| DaVerifier::new(GLOBAL_PARAMETERS.clone()))
             .collect();
@@ -392,9 +488,70 @@ mod test {
                     .iter()
                     .map(|proofs| proofs.get(i).copied().unwrap())
                     .collect(),
+                domain_size: encoded_data.domain_size,
+                data_size: data.len(),
             };
             let (light_share, commitments) = da_share.into_share_and_commitments();
-            assert!(verifier.verify(&commitments, &light_share, domain_size));
+            assert!(verifier.verify(&commitments, &light_share));
+        }
+    }
+
+    #[test]
+    fn test_verify_variable_size() {
+        let verifier = DaVerifier::new(GLOBAL_PARAMETERS.clone());
+        // a few chunks fit in a single row with fewer columns
+        let data = vec![1u8; 100];
+        let encoded_data = ENCODER.encode(&data).unwrap();
+        assert_eq!(encoded_data.domain_size, 8);
+        assert_eq!(encoded_data.row_commitments.len(), 1);
+        for share in &encoded_data {
+            let (light_share, commitments) = share.into_share_and_commitments();
+            assert!(verifier.verify(&commitments, &light_share));
+        }
+
+        // the dimensions are part of the commitments
+        let (light_share, mut commitments) = encoded_data
+            .to_da_share(0)
+            .unwrap()
+            .into_share_and_commitments();
+        commitments.data_size = 200;
+        assert!(!verifier.verify(&commitments, &light_share));
+        commitments.data_size = data.len();
+        commitments.domain_size = 16;
+        assert!(!verifier.verify(&commitments, &light_share));
+    }
+
+    #[test]
+    fn test_verify_batch() {
+        let verifier = DaVerifier::new(GLOBAL_PARAMETERS.clone());
+        let first_blob = ENCODER.encode(&rand_data(32)).unwrap();
+        let second_blob = ENCODER.encode(&vec![1u8; 100]).unwrap();
+        let mut shares: Vec<_> = first_blob
+            .iter()
+            .chain(second_blob.iter())
+            .map(DaShare::into_share_and_commitments)
+            .collect();
+        let batch = |shares: &[(DaLightShare, DaSharesCommitments)]| {
+            let shares: Vec<_> = shares
+                .iter()
+                .map(|(light_share, commitments)| (commitments, light_share))
+                .collect();
+            verifier.verify_batch(&shares)
+        };
+        assert!(batch(&shares).into_iter().all(|verified| verified));
+
+        // invalid shares do not fail the rest of the batch
+        shares[3].0.rows_proofs.swap(0, 1);
+        shares[20].0.column_commitment = Commitment::default();
+        let verified = batch(&shares);
+        assert_eq!(verified.len(), 16 + 8);
+        for (index, verified) in verified.into_iter().enumerate() {
+            assert_eq!(verified, index != 3 && index != 20);
         }
+
+        // a single invalid share is isolated as well
+        let verified = batch(&shares[..4]);
+        assert_eq!(verified, vec![true, true, true, false]);
+        assert!(batch(&[]).is_empty());
     }
 }
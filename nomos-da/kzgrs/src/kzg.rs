@@ -4,12 +4,14 @@ use std::{
 };
 
 use ark_bls12_381::{Bls12_381, Fr};
-use ark_ec::pairing::Pairing;
+use ark_ec::{pairing::Pairing, VariableBaseMSM};
+use ark_ff::UniformRand;
 use ark_poly::{
     univariate::DensePolynomial, DenseUVPolynomial, EvaluationDomain, GeneralEvaluationDomain,
 };
 use ark_poly_commit::kzg10::{Commitment, Powers, Proof, UniversalParams, KZG10};
 use num_traits::{One, Zero};
+use rand::thread_rng;
 
 use crate::{common::KzgRsError, Evaluations};
 
@@ -74,6 +76,57 @@ pub fn verify_element_proof(
     lhs == rhs
 }
 
+/// Opening of a committed polynomial at the `element_index` point of `domain`.
+#[derive(Clone, Copy, Debug)]
+pub struct ElementOpening<'a> {
+    pub element_index: usize,
+    pub element: Fr,
+    pub commitment: &'a Commitment<Bls12_381>,
+    pub proof: &'a Proof<Bls12_381>,
+    pub domain: GeneralEvaluationDomain<Fr>,
+}
+
+/// Verify proofs for many elements with a single multi-pairing
+///
+/// Each opening holds when `e(C - vG, H) = e(w, βH - uH)`, that is
+/// `e(C - vG + uw, H) = e(w, βH)`. Openings are combined with random scalars
+/// `r`, so `e(Σr(C - vG + uw), H) = e(Σrw, βH)` only holds if all of them do,
+/// except with negligible probability.
+#[must_use]
+pub fn verify_element_proofs_batch(
+    openings: &[ElementOpening],
+    global_parameters: &UniversalParams<Bls12_381>,
+) -> bool {
+    if openings.is_empty() {
+        return true;
+    }
+    let mut rng = thread_rng();
+    let randomness: Vec<Fr> = openings.iter().map(|_| Fr::rand(&mut rng)).collect();
+    let mut bases = Vec::with_capacity(openings.len() * 2 + 1);
+    let mut scalars = Vec::with_capacity(openings.len() * 2 + 1);
+    let mut elements = Fr::zero();
+    for (opening, r) in openings.iter().zip(&randomness) {
+        let u = opening.domain.element(opening.element_index);
+        bases.push(opening.commitment.0);
+        scalars.push(*r);
+        bases.push(opening.proof.w);
+        scalars.push(*r * u);
+        elements += *r * opening.element;
+    }
+    bases.push(global_parameters.powers_of_g[0]);
+    scalars.push(-elements);
+    let proofs: Vec<_> = openings.iter().map(|opening| opening.proof.w).collect();
+    let commitments_check_g1 = <Bls12_381 as Pairing>::G1::msm(&bases, &scalars)
+        .expect("Bases and scalars have the same length");
+    let proofs_check_g1 = <Bls12_381 as Pairing>::G1::msm(&proofs, &randomness)
+        .expect("Bases and scalars have the same length");
+    Bls12_381::multi_pairing(
+        [commitments_check_g1, -proofs_check_g1],
+        [global_parameters.h, global_parameters.beta_h],
+    )
+    .is_zero()
+}
+
 #[cfg(test)]
 mod test {
     use std::sync::LazyLock;
@@ -91,7 +144,10 @@ mod test {
 
     use crate::{
         common::bytes_to_polynomial,
-        kzg::{commit_polynomial, generate_element_proof, verify_element_proof},
+        kzg::{
+            commit_polynomial, generate_element_proof, verify_element_proof,
+            verify_element_proofs_batch, ElementOpening,
+        },
     };
 
     const COEFFICIENTS_SIZE: usize = 16;
@@ -154,4 +210,35 @@ mod test {
                 }
             });
     }
+
+    #[test]
+    fn batch_verify_proofs() {
+        let mut bytes: [u8; 310] = [0; 310];
+        let mut rng = thread_rng();
+        bytes.try_fill(&mut rng).unwrap();
+        let (eval, poly) = bytes_to_polynomial::<31>(&bytes, *DOMAIN).unwrap();
+        let commitment = commit_polynomial(&poly, &GLOBAL_PARAMETERS).unwrap();
+        let proofs: Vec<_> = (0..10)
+            .map(|i| generate_element_proof(i, &poly, &eval, &GLOBAL_PARAMETERS, *DOMAIN).unwrap())
+            .collect();
+        let mut openings: Vec<_> = proofs
+            .iter()
+            .enumerate()
+            .map(|(i, proof)| ElementOpening {
+                element_index: i,
+                element: eval.evals[i],
+                commitment: &commitment,
+                proof,
+                domain: *DOMAIN,
+            })
+            .collect();
+        assert!(verify_element_proofs_batch(&openings, &GLOBAL_PARAMETERS));
+
+        // a single wrong opening fails the whole batch
+        openings[3].element_index = 4;
+        assert!(!verify_element_proofs_batch(&openings, &GLOBAL_PARAMETERS));
+        openings[3].element_index = 3;
+        openings[7].element = eval.evals[8];
+        assert!(!verify_element_proofs_batch(&openings, &GLOBAL_PARAMETERS));
+    }
 }
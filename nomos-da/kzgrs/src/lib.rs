@@ -11,7 +11,10 @@ use ark_poly::{univariate::DensePolynomial, GeneralEvaluationDomain};
 use ark_poly_commit::{kzg10, sonic_pc::UniversalParams};
 pub use common::{bytes_to_evaluations, bytes_to_polynomial, KzgRsError};
 pub use global_parameters::{global_parameters_from_file, global_parameters_from_randomness};
-pub use kzg::{commit_polynomial, generate_element_proof, verify_element_proof};
+pub use kzg::{
+    commit_polynomial, generate_element_proof, verify_element_proof, verify_element_proofs_batch,
+    ElementOpening,
+};
 pub use rs::{decode, encode};
 
 pub type Commitment = kzg10::Commitment<Bls12_381>;
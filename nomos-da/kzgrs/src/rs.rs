@@ -1,7 +1,5 @@
-use std::ops::{Mul, Neg};
-
 use ark_bls12_381::Fr;
-use ark_ff::{BigInteger, Field, PrimeField};
+use ark_ff::{batch_inversion, BigInteger, Field, PrimeField};
 use ark_poly::{
     univariate::DensePolynomial, DenseUVPolynomial, EvaluationDomain, Evaluations,
     GeneralEvaluationDomain,
@@ -57,26 +55,71 @@ pub fn decode(
 #[must_use]
 pub fn lagrange_interpolate(points: &[Fr], roots_of_unity: &[Fr]) -> DensePolynomial<Fr> {
     assert_eq!(points.len(), roots_of_unity.len());
-    let mut result = DensePolynomial::from_coefficients_vec(vec![Fr::zero()]);
-    for i in 0..roots_of_unity.len() {
-        let mut summand = DensePolynomial::from_coefficients_vec(vec![points[i]]);
-        for j in 0..points.len() {
-            if i != j {
-                let weight_adjustment =
-                    (roots_of_unity[i] - roots_of_unity[j])
-                        .inverse()
-                        .expect(
-                            "Roots of unity are/should not repeated. If this panics it means we have no coefficients enough in the evaluation domain"
-                        );
-                summand = summand.naive_mul(&DensePolynomial::from_coefficients_vec(vec![
-                    weight_adjustment.mul(roots_of_unity[j]).neg(),
-                    weight_adjustment,
-                ]));
-            }
+    // vanishing polynomial of the roots, `prod(x - w_j)`
+    let mut vanishing = vec![Fr::ONE];
+    for root in roots_of_unity {
+        vanishing.insert(0, Fr::zero());
+        for i in 0..vanishing.len() - 1 {
+            let next = vanishing[i + 1];
+            vanishing[i] -= next * root;
         }
-        result = result + summand;
     }
-    result
+    // barycentric weights `1 / prod(w_i - w_j)` for `j != i`
+    let mut weights: Vec<Fr> = roots_of_unity
+        .iter()
+        .enumerate()
+        .map(|(i, root_i)| {
+            roots_of_unity
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, root_j)| *root_i - root_j)
+                .product()
+        })
+        .collect();
+    assert!(
+        weights.iter().all(|weight| !weight.is_zero()),
+        "Roots of unity are/should not repeated. If this panics it means we have no coefficients enough in the evaluation domain"
+    );
+    batch_inversion(&mut weights);
+
+    let mut coefficients = vec![Fr::zero(); roots_of_unity.len()];
+    for ((point, root), weight) in points.iter().zip(roots_of_unity).zip(&weights) {
+        // `vanishing / (x - w_i)` by synthetic division, scaled by `p_i`
+        let scale = *weight * point;
+        let mut quotient = Fr::zero();
+        for (coefficient, vanishing_coefficient) in
+            coefficients.iter_mut().zip(&vanishing[1..]).rev()
+        {
+            quotient = quotient * root + vanishing_coefficient;
+            *coefficient += quotient * scale;
+        }
+    }
+    DensePolynomial::from_coefficients_vec(coefficients)
+}
+
+/// Interpolate the polynomial of degree lower than half the `domain` size
+/// which evaluates to `points` over the first half of the domain.
+///
+/// Missing trailing points are considered to be zero. Evaluating the
+/// polynomial over the whole domain with [`encode`] keeps the original points
+/// as the first half of the evaluations, and any half of the evaluations is
+/// enough to recover them with [`decode`].
+#[must_use]
+pub fn interpolate_first_half(
+    points: &[Fr],
+    domain: GeneralEvaluationDomain<Fr>,
+) -> DensePolynomial<Fr> {
+    let half = domain.size() / 2;
+    assert!(points.len() <= half);
+    let points: Vec<Fr> = points
+        .iter()
+        .copied()
+        .chain(std::iter::repeat(Fr::zero()))
+        .take(half)
+        .collect();
+    let roots_of_unity: Vec<Fr> = domain.elements().take(half).collect();
+    lagrange_interpolate(&points, &roots_of_unity)
 }
 
 /// Reconstruct bytes from the polynomial evaluation points using original chunk
@@ -101,7 +144,7 @@ mod test {
 
     use crate::{
         common::bytes_to_polynomial,
-        rs::{decode, encode, points_to_bytes},
+        rs::{decode, encode, interpolate_first_half, points_to_bytes},
     };
 
     const COEFFICIENTS_SIZE: usize = 32;
@@ -132,4 +175,30 @@ mod test {
         let decoded_bytes = points_to_bytes::<31>(&decoded.evals);
         assert_eq!(decoded_bytes, bytes);
     }
+
+    #[test]
+    fn test_decode_from_any_half() {
+        let points: Vec<Fr> = (0..COEFFICIENTS_SIZE as u64 / 2).map(Fr::from).collect();
+        let polynomial = interpolate_first_half(&points, *DOMAIN);
+        let encoded = encode(&polynomial, *DOMAIN);
+        assert_eq!(&encoded.evals[..points.len()], points.as_slice());
+
+        // only the extended points
+        let extended: Vec<Option<Fr>> = encoded
+            .evals
+            .iter()
+            .enumerate()
+            .map(|(i, e)| (i >= points.len()).then_some(*e))
+            .collect();
+        assert_eq!(decode(points.len(), &extended, *DOMAIN).evals, points);
+
+        // half of the original points and half of the extended ones
+        let mixed: Vec<Option<Fr>> = encoded
+            .evals
+            .iter()
+            .enumerate()
+            .map(|(i, e)| (i % 2 == 0).then_some(*e))
+            .collect();
+        assert_eq!(decode(points.len(), &mixed, *DOMAIN).evals, points);
+    }
 }
@@ -69,12 +69,18 @@ where
         }
     }
 
-    pub fn update_membership(&mut self, membership: Membership) {
+    pub fn update_membership(&mut self, membership: Membership)
+    where
+        Balancer: ConnectionBalancer<Membership = Membership>,
+    {
         // TODO: share membership
         self.sampling.update_membership(membership.clone());
         self.executor_dispersal
             .update_membership(membership.clone());
-        self.replication.update_membership(membership);
+        self.validator_dispersal
+            .update_membership(membership.clone());
+        self.replication.update_membership(membership.clone());
+        self.balancer.update_membership(membership);
     }
 
     pub const fn sampling_behaviour(&self) -> &SamplingBehaviour<Membership> {
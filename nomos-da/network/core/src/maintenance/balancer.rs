@@ -31,10 +31,12 @@ pub enum ConnectionEvent {
 
 pub trait ConnectionBalancer {
     type Stats;
+    type Membership;
 
     fn record_event(&mut self, event: ConnectionEvent);
     fn poll(&mut self, cx: &mut Context<'_>) -> Poll<VecDeque<PeerId>>;
     fn stats(&self) -> Self::Stats;
+    fn update_membership(&mut self, membership: Self::Membership);
 }
 
 #[derive(Debug)]
@@ -88,6 +90,24 @@ where
     }
 }
 
+impl<Balancer, Membership> ConnectionBalancerBehaviour<Balancer, Membership>
+where
+    Balancer: ConnectionBalancer<Membership = Membership>,
+    Membership: MembershipHandler + Clone,
+{
+    /// Replace the membership used to resolve peer addresses and to select
+    /// the peers to connect to. Pending dials are dropped, as they were
+    /// selected from the previous membership.
+    pub fn update_membership(&mut self, membership: Membership) {
+        self.peers_to_dial.clear();
+        self.balancer.update_membership(membership.clone());
+        self.membership = membership;
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
 impl<Balancer, Membership> NetworkBehaviour for ConnectionBalancerBehaviour<Balancer, Membership>
 where
     Balancer: ConnectionBalancer + 'static,
@@ -212,6 +232,7 @@ mod tests {
 
     impl ConnectionBalancer for MockBalancer {
         type Stats = ();
+        type Membership = AllNeighbours;
 
         fn record_event(&mut self, event: ConnectionEvent) {
             match event {
@@ -235,6 +256,8 @@ mod tests {
         fn stats(&self) {
             unimplemented!()
         }
+
+        fn update_membership(&mut self, _membership: Self::Membership) {}
     }
 
     #[tokio::test]
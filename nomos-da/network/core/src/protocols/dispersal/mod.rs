@@ -100,6 +100,8 @@ pub mod test {
                         aggregated_column_proof: Proof::default(),
                         rows_commitments: vec![],
                         rows_proofs: vec![],
+                        domain_size: 2,
+                        data_size: 0,
                     },
                 ))
                 .unwrap();
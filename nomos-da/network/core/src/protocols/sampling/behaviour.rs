@@ -29,6 +29,7 @@ use nomos_da_messages::{
     packing::{pack_to_writer, unpack_from_reader},
     sampling,
 };
+use rand::seq::IteratorRandom;
 use subnetworks_assignations::MembershipHandler;
 use thiserror::Error;
 use tokio::sync::{mpsc, mpsc::UnboundedSender};
@@ -55,6 +56,7 @@ pub enum SamplingError {
     OpenStream {
         peer_id: PeerId,
         error: OpenStreamError,
+        message: sampling::SampleRequest,
     },
     #[error("Unable to deserialize blob response: {error}")]
     Deserialize {
@@ -101,6 +103,28 @@ impl SamplingError {
             Self::BlobNotFound { blob_id, .. } => blob_id.as_slice().try_into().ok(),
             Self::Deserialize { blob_id, .. } => Some(blob_id),
             Self::Protocol { error, .. } => Some(&error.blob_id),
+            Self::OpenStream { message, .. }
+            | Self::Io {
+                message: Some(message),
+                ..
+            } => Some(&message.blob_id),
+            _ => None,
+        }
+    }
+
+    /// Subnetwork of the sample request that failed, if the error comes
+    /// from one.
+    #[must_use]
+    pub const fn subnetwork_id(&self) -> Option<SubnetworkId> {
+        match self {
+            Self::BlobNotFound { subnetwork_id, .. }
+            | Self::Deserialize { subnetwork_id, .. }
+            | Self::Protocol { subnetwork_id, .. } => Some(*subnetwork_id),
+            Self::OpenStream { message, .. }
+            | Self::Io {
+                message: Some(message),
+                ..
+            } => Some(message.share_idx),
             _ => None,
         }
     }
@@ -127,8 +151,13 @@ impl Clone for SamplingError {
                 peer_id: *peer_id,
                 error: error.clone(),
             },
-            Self::OpenStream { peer_id, error } => Self::OpenStream {
+            Self::OpenStream {
+                peer_id,
+                error,
+                message,
+            } => Self::OpenStream {
                 peer_id: *peer_id,
+                message: *message,
                 error: match error {
                     OpenStreamError::UnsupportedProtocol(protocol) => {
                         OpenStreamError::UnsupportedProtocol(protocol.clone())
@@ -327,15 +356,21 @@ where
         self.membership = membership;
     }
 
-    /// Open a new stream from the underlying control to the provided peer
+    /// Open a new stream from the underlying control to the provided peer, in
+    /// order to send it the `message` sample request
     async fn open_stream(
         peer_id: PeerId,
         mut control: Control,
+        message: sampling::SampleRequest,
     ) -> Result<SampleStream, SamplingError> {
         let stream = control
             .open_stream(peer_id, SAMPLING_PROTOCOL)
             .await
-            .map_err(|error| SamplingError::OpenStream { peer_id, error })?;
+            .map_err(|error| SamplingError::OpenStream {
+                peer_id,
+                error,
+                message,
+            })?;
         Ok(SampleStream { stream, peer_id })
     }
 
@@ -526,14 +561,13 @@ impl<Membership: MembershipHandler<Id = PeerId, NetworkId = SubnetworkId> + 'sta
         control: &Control,
     ) {
         let members = membership.members_of(&subnetwork_id);
-        // TODO: peer selection for sampling should be randomly selected (?) filtering
-        // ourselves currently we assume optimal setup which is one peer per
-        // blob
+        // Peers are picked randomly, so sampling the subnetwork again after a
+        // failure likely asks a different member.
         let peer = members
             .iter()
             .filter(|&id| id != &local_peer_id)
             .copied()
-            .next()
+            .choose(&mut rand::thread_rng())
             .expect("At least a single node should be a member of the subnetwork");
         // If its connected means we are already working on some other sample, enqueue
         // message, stream behaviour will dial peer if connection is not
@@ -545,7 +579,7 @@ impl<Membership: MembershipHandler<Id = PeerId, NetworkId = SubnetworkId> + 'sta
             // return `ConnectionReset` io error. To handle that, we need to queue
             // `sample_request` for a peer and try again when the connection is
             // established.
-            let stream = Self::open_stream(peer, control)
+            let stream = Self::open_stream(peer, control, sample_request)
                 .await
                 .map_err(|err| (err, None))?;
             Self::stream_sample(stream, sample_request).await
@@ -563,7 +597,7 @@ impl<Membership: MembershipHandler<Id = PeerId, NetworkId = SubnetworkId> + 'sta
             let control = self.control.clone();
             let sample_request = sampling::SampleRequest::new(blob_id, subnetwork_id);
             let open_stream_task: SamplingStreamFuture = async move {
-                let stream = Self::open_stream(peer_id, control)
+                let stream = Self::open_stream(peer_id, control, sample_request)
                     .await
                     .map_err(|err| (err, None))?;
                 Self::stream_sample(stream, sample_request).await
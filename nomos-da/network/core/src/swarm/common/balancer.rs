@@ -35,11 +35,15 @@ pub struct SubnetworkDeviation {
 }
 
 pub trait SubnetworkConnectionPolicy {
+    type Membership;
+
     fn connection_number_deviation(
         &self,
         subnetwork_id: SubnetworkId,
         stats: &SubnetworkStats,
     ) -> SubnetworkDeviation;
+
+    fn update_membership(&mut self, membership: Self::Membership);
 }
 
 pub struct DAConnectionBalancer<Membership, Policy> {
@@ -48,7 +52,8 @@ pub struct DAConnectionBalancer<Membership, Policy> {
     policy: Policy,
     interval: Pin<Box<dyn futures::Stream<Item = ()> + Send>>,
     subnetwork_stats: BalancerStats,
-    connected_peers: HashSet<PeerId>,
+    inbound_peers: HashSet<PeerId>,
+    outbound_peers: HashSet<PeerId>,
 }
 
 impl<Membership, Policy> DAConnectionBalancer<Membership, Policy>
@@ -68,7 +73,8 @@ where
             policy,
             interval: Box::pin(interval),
             subnetwork_stats: HashMap::new(),
-            connected_peers: HashSet::new(),
+            inbound_peers: HashSet::new(),
+            outbound_peers: HashSet::new(),
         }
     }
 
@@ -98,40 +104,67 @@ where
         let candidates = self.membership.members_of(&subnetwork_id);
         candidates
             .into_iter()
-            .filter(|peer| !self.connected_peers.contains(peer) && *peer != self.local_peer_id)
+            .filter(|peer| !self.is_connected(peer) && *peer != self.local_peer_id)
             .choose_multiple(&mut rand::thread_rng(), missing_count)
     }
+
+    fn is_connected(&self, peer: &PeerId) -> bool {
+        self.inbound_peers.contains(peer) || self.outbound_peers.contains(peer)
+    }
+
+    /// Recompute the subnetwork stats of the currently connected peers, as
+    /// their subnetworks depend on the membership.
+    fn recompute_subnetwork_stats(&mut self) {
+        self.subnetwork_stats.clear();
+        let inbound_subnetworks: Vec<_> = self
+            .inbound_peers
+            .iter()
+            .flat_map(|peer| self.membership.membership(peer))
+            .collect();
+        let outbound_subnetworks: Vec<_> = self
+            .outbound_peers
+            .iter()
+            .flat_map(|peer| self.membership.membership(peer))
+            .collect();
+        for subnetwork in inbound_subnetworks {
+            self.update_subnetwork_stats(subnetwork, 1, 0);
+        }
+        for subnetwork in outbound_subnetworks {
+            self.update_subnetwork_stats(subnetwork, 0, 1);
+        }
+    }
 }
 
 impl<Membership, Policy> ConnectionBalancer for DAConnectionBalancer<Membership, Policy>
 where
-    Membership: MembershipHandler<NetworkId = SubnetworkId, Id = PeerId>,
-    Policy: SubnetworkConnectionPolicy,
+    Membership: MembershipHandler<NetworkId = SubnetworkId, Id = PeerId> + Clone,
+    Policy: SubnetworkConnectionPolicy<Membership = Membership>,
 {
     type Stats = BalancerStats;
+    type Membership = Membership;
 
     fn record_event(&mut self, event: ConnectionEvent) {
         match event {
             ConnectionEvent::OpenInbound(peer) => {
-                self.connected_peers.insert(peer);
+                self.inbound_peers.insert(peer);
                 for subnetwork in self.membership.membership(&peer) {
                     self.update_subnetwork_stats(subnetwork, 1, 0);
                 }
             }
             ConnectionEvent::OpenOutbound(peer) => {
-                self.connected_peers.insert(peer);
+                self.outbound_peers.insert(peer);
                 for subnetwork in self.membership.membership(&peer) {
                     self.update_subnetwork_stats(subnetwork, 0, 1);
                 }
             }
             ConnectionEvent::CloseInbound(peer) => {
-                self.connected_peers.remove(&peer);
+                self.inbound_peers.remove(&peer);
                 for subnetwork in self.membership.membership(&peer) {
                     self.update_subnetwork_stats(subnetwork, -1, 0);
                 }
             }
             ConnectionEvent::CloseOutbound(peer) => {
-                self.connected_peers.remove(&peer);
+                self.outbound_peers.remove(&peer);
                 for subnetwork in self.membership.membership(&peer) {
                     self.update_subnetwork_stats(subnetwork, 0, -1);
                 }
@@ -176,6 +209,12 @@ where
     fn stats(&self) -> Self::Stats {
         self.subnetwork_stats.clone()
     }
+
+    fn update_membership(&mut self, membership: Self::Membership) {
+        self.policy.update_membership(membership.clone());
+        self.membership = membership;
+        self.recompute_subnetwork_stats();
+    }
 }
 
 #[cfg(test)]
@@ -196,6 +235,8 @@ mod tests {
     }
 
     impl SubnetworkConnectionPolicy for MockPolicy {
+        type Membership = MockMembership;
+
         fn connection_number_deviation(
             &self,
             _id: SubnetworkId,
@@ -205,8 +246,11 @@ mod tests {
                 outbound: ConnectionDeviation::Missing(self.missing),
             }
         }
+
+        fn update_membership(&mut self, _membership: Self::Membership) {}
     }
 
+    #[derive(Clone)]
     struct MockMembership {
         subnetwork: SubnetworkId,
         members: HashSet<PeerId>,
@@ -332,4 +376,40 @@ mod tests {
 
         assert!(matches!(poll_result, Poll::Pending));
     }
+
+    #[tokio::test]
+    async fn test_balancer_selects_peers_from_updated_membership() {
+        let subnetwork_id = SubnetworkId::default();
+        let peer1 = PeerId::random();
+        let peer2 = PeerId::random();
+        let peer3 = PeerId::random();
+
+        let membership = MockMembership {
+            subnetwork: subnetwork_id,
+            members: HashSet::from([peer1, peer2]),
+            last_subnet_id: 0,
+        };
+
+        let policy = MockPolicy { missing: 1 };
+
+        let interval = stream::once(async {}).chain(stream::pending());
+        let mut balancer = DAConnectionBalancer::new(peer1, membership, policy, interval);
+        balancer.record_event(ConnectionEvent::OpenOutbound(peer2));
+        assert_eq!(balancer.stats()[&subnetwork_id].outbound, 1);
+
+        balancer.update_membership(MockMembership {
+            subnetwork: subnetwork_id,
+            members: HashSet::from([peer1, peer3]),
+            last_subnet_id: 0,
+        });
+        // peer2 left the subnetwork, its connection is not counted anymore
+        assert!(!balancer.stats().contains_key(&subnetwork_id));
+
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+        let Poll::Ready(peers) = balancer.poll(&mut cx) else {
+            panic!("Expected Poll::Ready with peers")
+        };
+
+        assert_eq!(peers, VecDeque::from([peer3]));
+    }
 }
@@ -70,6 +70,8 @@ impl<Membership> SubnetworkConnectionPolicy for DAConnectionPolicy<Membership>
 where
     Membership: MembershipHandler<NetworkId = SubnetworkId, Id = PeerId> + Clone,
 {
+    type Membership = Membership;
+
     fn connection_number_deviation(
         &self,
         subnetwork_id: SubnetworkId,
@@ -96,4 +98,8 @@ where
             outbound: ConnectionDeviation::Missing(total_missing),
         }
     }
+
+    fn update_membership(&mut self, membership: Self::Membership) {
+        self.membership = membership;
+    }
 }
@@ -12,7 +12,7 @@ use log::debug;
 use nomos_core::da::BlobId;
 use subnetworks_assignations::MembershipHandler;
 use tokio::{
-    sync::mpsc::{unbounded_channel, UnboundedSender},
+    sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
     time::interval,
 };
 use tokio_stream::wrappers::{IntervalStream, UnboundedReceiverStream};
@@ -62,6 +62,8 @@ pub struct ValidatorSwarm<
     >,
     sampling_events_sender: UnboundedSender<SamplingEvent>,
     validation_events_sender: UnboundedSender<DaShare>,
+    membership_updates_sender: UnboundedSender<Membership>,
+    membership_updates_receiver: UnboundedReceiver<Membership>,
 }
 
 impl<Membership> ValidatorSwarm<Membership>
@@ -82,6 +84,7 @@ where
 
         let sampling_events_receiver = UnboundedReceiverStream::new(sampling_events_receiver);
         let validation_events_receiver = UnboundedReceiverStream::new(validation_events_receiver);
+        let (membership_updates_sender, membership_updates_receiver) = unbounded_channel();
         let local_peer_id = PeerId::from_public_key(&key.public());
 
         let policy = DAConnectionPolicy::new(policy_settings, membership.clone(), local_peer_id);
@@ -114,6 +117,8 @@ where
                 ),
                 sampling_events_sender,
                 validation_events_sender,
+                membership_updates_sender,
+                membership_updates_receiver,
             },
             ValidatorEventsStream {
                 sampling_events_receiver,
@@ -189,6 +194,16 @@ where
         self.swarm.behaviour().monitor_behavior().command_channel()
     }
 
+    /// Channel to replace the membership of the running swarm, e.g. when
+    /// the set of DA providers changes at a session boundary.
+    pub fn membership_update_channel(&self) -> UnboundedSender<Membership> {
+        self.membership_updates_sender.clone()
+    }
+
+    pub fn update_membership(&mut self, membership: Membership) {
+        self.swarm.behaviour_mut().update_membership(membership);
+    }
+
     pub fn local_peer_id(&self) -> &PeerId {
         self.swarm.local_peer_id()
     }
@@ -284,30 +299,36 @@ where
 
     pub async fn run(mut self) {
         loop {
-            if let Some(event) = self.swarm.next().await {
-                debug!("Da swarm event received: {event:?}");
-                match event {
-                    SwarmEvent::Behaviour(behaviour_event) => {
-                        self.handle_behaviour_event(behaviour_event).await;
-                    }
-                    SwarmEvent::ConnectionEstablished { .. }
-                    | SwarmEvent::ConnectionClosed { .. }
-                    | SwarmEvent::IncomingConnection { .. }
-                    | SwarmEvent::IncomingConnectionError { .. }
-                    | SwarmEvent::OutgoingConnectionError { .. }
-                    | SwarmEvent::NewListenAddr { .. }
-                    | SwarmEvent::ExpiredListenAddr { .. }
-                    | SwarmEvent::ListenerClosed { .. }
-                    | SwarmEvent::ListenerError { .. }
-                    | SwarmEvent::Dialing { .. }
-                    | SwarmEvent::NewExternalAddrCandidate { .. }
-                    | SwarmEvent::ExternalAddrConfirmed { .. }
-                    | SwarmEvent::ExternalAddrExpired { .. }
-                    | SwarmEvent::NewExternalAddrOfPeer { .. } => {}
-                    event => {
-                        debug!("Unsupported validator swarm event: {event:?}");
+            tokio::select! {
+                Some(event) = self.swarm.next() => {
+                    debug!("Da swarm event received: {event:?}");
+                    match event {
+                        SwarmEvent::Behaviour(behaviour_event) => {
+                            self.handle_behaviour_event(behaviour_event).await;
+                        }
+                        SwarmEvent::ConnectionEstablished { .. }
+                        | SwarmEvent::ConnectionClosed { .. }
+                        | SwarmEvent::IncomingConnection { .. }
+                        | SwarmEvent::IncomingConnectionError { .. }
+                        | SwarmEvent::OutgoingConnectionError { .. }
+                        | SwarmEvent::NewListenAddr { .. }
+                        | SwarmEvent::ExpiredListenAddr { .. }
+                        | SwarmEvent::ListenerClosed { .. }
+                        | SwarmEvent::ListenerError { .. }
+                        | SwarmEvent::Dialing { .. }
+                        | SwarmEvent::NewExternalAddrCandidate { .. }
+                        | SwarmEvent::ExternalAddrConfirmed { .. }
+                        | SwarmEvent::ExternalAddrExpired { .. }
+                        | SwarmEvent::NewExternalAddrOfPeer { .. } => {}
+                        event => {
+                            debug!("Unsupported validator swarm event: {event:?}");
+                        }
                     }
                 }
+                Some(membership) = self.membership_updates_receiver.recv() => {
+                    debug!("Updating DA swarm membership");
+                    self.update_membership(membership);
+                }
             }
         }
     }
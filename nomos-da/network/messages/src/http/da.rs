@@ -1,6 +1,9 @@
 use std::{collections::HashSet, hash::Hash, ops::Range};
 
-use nomos_core::da::blob::{metadata::Metadata, Share};
+use nomos_core::da::{
+    blob::{metadata::Metadata, Share},
+    BlobId,
+};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 // Shared types for http requests. Probably better part of upcoming `nomos-lib`
@@ -15,6 +18,11 @@ where
     pub range: Range<<V as Metadata>::Index>,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct RetrieveBlobReq {
+    pub blob_id: BlobId,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct DASharesCommitmentsRequest<S: Share> {
     pub blob_id: S::BlobId,
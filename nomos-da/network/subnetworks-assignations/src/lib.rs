@@ -1,6 +1,9 @@
 pub mod versions;
 
-use std::{collections::HashSet, hash::Hash};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
 
 use libp2p::Multiaddr;
 use libp2p_identity::PeerId;
@@ -33,6 +36,14 @@ pub trait MembershipHandler {
     fn get_address(&self, peer_id: &PeerId) -> Option<Multiaddr>;
 }
 
+pub trait MembershipCreator: MembershipHandler {
+    /// Creates a new membership from the given members and their addresses,
+    /// keeping the assignation parameters of `self`. The set of members must
+    /// not be empty.
+    #[must_use]
+    fn init(&self, addressbook: HashMap<Self::Id, Multiaddr>) -> Self;
+}
+
 use std::sync::Arc;
 
 impl<T> MembershipHandler for Arc<T>
@@ -4,7 +4,7 @@ use libp2p::Multiaddr;
 use libp2p_identity::PeerId;
 use serde::{Deserialize, Serialize};
 
-use crate::MembershipHandler;
+use crate::{MembershipCreator, MembershipHandler};
 
 /// Fill a `N` sized set of "subnetworks" from a list of peer ids members
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
@@ -107,13 +107,27 @@ impl MembershipHandler for FillFromNodeList {
     }
 }
 
+impl MembershipCreator for FillFromNodeList {
+    fn init(&self, addressbook: HashMap<Self::Id, Multiaddr>) -> Self {
+        let peers: Vec<PeerId> = addressbook.keys().copied().collect();
+        Self::new(
+            &peers,
+            addressbook,
+            self.subnetwork_size,
+            self.dispersal_factor,
+        )
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::collections::HashMap;
 
     use libp2p_identity::PeerId;
 
-    use crate::versions::v1::FillFromNodeList;
+    use libp2p::Multiaddr;
+
+    use crate::{versions::v1::FillFromNodeList, MembershipCreator as _, MembershipHandler as _};
 
     #[test]
     fn test_distribution_fill_from_node_list() {
@@ -131,4 +145,28 @@ mod test {
             assert_eq!(subnetwork.len(), dispersal_factor);
         }
     }
+
+    #[test]
+    fn test_init_keeps_assignation_parameters() {
+        let nodes: Vec<_> = std::iter::repeat_with(PeerId::random).take(10).collect();
+        let distribution = FillFromNodeList::new(&nodes, HashMap::default(), 16, 2);
+
+        let new_node = PeerId::random();
+        let address: Multiaddr = "/ip4/127.0.0.1/udp/8716/quic-v1".parse().unwrap();
+        let addressbook = nodes
+            .iter()
+            .skip(1)
+            .map(|peer| (*peer, address.clone()))
+            .chain(std::iter::once((new_node, address.clone())))
+            .collect();
+        let updated = distribution.init(addressbook);
+
+        assert_eq!(updated.assignations.len(), 16);
+        for subnetwork in &updated.assignations {
+            assert_eq!(subnetwork.len(), 2);
+        }
+        assert!(!updated.members().contains(&nodes[0]));
+        assert!(updated.members().contains(&new_node));
+        assert_eq!(updated.get_address(&new_node), Some(address));
+    }
 }
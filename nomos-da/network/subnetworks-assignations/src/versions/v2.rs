@@ -4,7 +4,7 @@ use libp2p::Multiaddr;
 use libp2p_identity::PeerId;
 use serde::{Deserialize, Serialize};
 
-use crate::MembershipHandler;
+use crate::{MembershipCreator, MembershipHandler};
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct FillWithOriginalReplication {
@@ -114,6 +114,20 @@ impl MembershipHandler for FillWithOriginalReplication {
     }
 }
 
+impl MembershipCreator for FillWithOriginalReplication {
+    fn init(&self, addressbook: HashMap<Self::Id, Multiaddr>) -> Self {
+        let peers: Vec<PeerId> = addressbook.keys().copied().collect();
+        Self::new(
+            &peers,
+            addressbook,
+            self.subnetwork_size,
+            self.dispersal_factor,
+            self.original_replication,
+            self.pivot,
+        )
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::collections::HashMap;
@@ -0,0 +1,122 @@
+use std::num::NonZero;
+
+use serde::{Deserialize, Serialize};
+
+/// Position of a block in the finalized chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockReference {
+    pub height: u64,
+    pub slot: u64,
+}
+
+/// Retention record of a blob, stored under
+/// [`DA_BLOB_RETENTION_PREFIX`](crate::rocksdb::DA_BLOB_RETENTION_PREFIX).
+///
+/// Records are only written by the indexer. Blobs with a reference are also
+/// queued under
+/// [`DA_BLOB_RETENTION_QUEUE_PREFIX`](crate::rocksdb::DA_BLOB_RETENTION_QUEUE_PREFIX)
+/// by height, the verifier marks new blobs under
+/// [`DA_BLOB_RETENTION_PENDING_PREFIX`](crate::rocksdb::DA_BLOB_RETENTION_PENDING_PREFIX).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BlobRetention<BlobId> {
+    pub blob_id: BlobId,
+    /// Latest finalized block referencing the blob. It is `None` until the
+    /// blob is first considered for pruning, when it is set to the finalized
+    /// tip so blobs never included in a block are dropped after a full window.
+    pub referenced_at: Option<BlockReference>,
+    /// Indexer keys pointing to the blob.
+    pub vid_keys: Vec<Vec<u8>>,
+}
+
+impl<BlobId> BlobRetention<BlobId> {
+    pub const fn new(blob_id: BlobId) -> Self {
+        Self {
+            blob_id,
+            referenced_at: None,
+            vid_keys: Vec::new(),
+        }
+    }
+
+    /// Keeps the most recent of the blob references.
+    pub fn reference(&mut self, block: BlockReference) {
+        if self
+            .referenced_at
+            .is_none_or(|referenced_at| referenced_at.height < block.height)
+        {
+            self.referenced_at = Some(block);
+        }
+    }
+}
+
+/// How long blobs are kept in storage after the last finalized block
+/// referencing them.
+///
+/// Blobs not included in a finalized block yet are kept for a full window
+/// after they are first considered for pruning, so the window should be longer
+/// than the time blobs take to be finalized.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionPolicy {
+    /// Keep blobs referenced by the latest `depth` finalized blocks.
+    BlockDepth(u64),
+    /// Keep blobs referenced by blocks of the latest `epochs` epochs, each
+    /// `epoch_length` slots long.
+    Epochs {
+        epochs: u64,
+        epoch_length: NonZero<u64>,
+    },
+}
+
+impl RetentionPolicy {
+    /// Whether a blob referenced at `reference` falls out of the window ending
+    /// at the finalized `tip`.
+    #[must_use]
+    pub const fn is_expired(&self, reference: BlockReference, tip: BlockReference) -> bool {
+        match *self {
+            Self::BlockDepth(depth) => reference.height.saturating_add(depth) <= tip.height,
+            Self::Epochs {
+                epochs,
+                epoch_length,
+            } => {
+                let epoch = reference.slot / epoch_length.get();
+                let tip_epoch = tip.slot / epoch_length.get();
+                epoch.saturating_add(epochs) <= tip_epoch
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn block(height: u64, slot: u64) -> BlockReference {
+        BlockReference { height, slot }
+    }
+
+    #[test]
+    fn block_depth_window() {
+        let policy = RetentionPolicy::BlockDepth(10);
+        assert!(!policy.is_expired(block(5, 50), block(14, 200)));
+        assert!(policy.is_expired(block(5, 50), block(15, 60)));
+    }
+
+    #[test]
+    fn epochs_window() {
+        let policy = RetentionPolicy::Epochs {
+            epochs: 2,
+            epoch_length: NonZero::new(100).unwrap(),
+        };
+        // epoch 1 is kept while the tip is in epochs 1 and 2
+        assert!(!policy.is_expired(block(5, 199), block(500, 299)));
+        assert!(policy.is_expired(block(5, 199), block(6, 300)));
+    }
+
+    #[test]
+    fn latest_reference_is_kept() {
+        let mut retention = BlobRetention::new([0u8; 32]);
+        retention.reference(block(10, 100));
+        retention.reference(block(8, 80));
+        assert_eq!(retention.referenced_at, Some(block(10, 100)));
+    }
+}
@@ -8,6 +8,13 @@ pub const DA_BLOB_SHARES_INDEX_PREFIX: &str = concat!("da/verified/", "si");
 pub const DA_SHARED_COMMITMENTS_PREFIX: &str = concat!("da/verified/", "sc");
 pub const DA_SHARE_PREFIX: &str = concat!("da/verified/", "bl");
 
+// "DA/RETENTION/" PREFIX
+pub const DA_BLOB_RETENTION_PREFIX: &str = "da/retention/";
+// Blobs ordered by the height of their latest reference.
+pub const DA_BLOB_RETENTION_QUEUE_PREFIX: &str = "da/retention-queue/";
+// Blobs stored by the verifier, not tracked by the indexer yet.
+pub const DA_BLOB_RETENTION_PENDING_PREFIX: &str = "da/retention-pending/";
+
 pub fn key_bytes(prefix: &str, id: impl AsRef<[u8]>) -> Bytes {
     let mut buffer = BytesMut::new();
 
@@ -0,0 +1,140 @@
+//! Request-response protocol used by consensus nodes to fetch blocks from
+//! their peers.
+//!
+//! Requests and responses are opaque byte payloads prefixed by their length,
+//! it's up to the consensus layer to give them a meaning.
+
+use std::io;
+
+use futures::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use libp2p::{request_response, StreamProtocol};
+
+/// Upper bound for requests, which are expected to be small.
+const MAX_REQUEST_SIZE: usize = 1 << 16; // 64 KiB
+/// Upper bound for responses, which may carry several blocks. Consensus keeps
+/// the blocks of a response below it.
+const MAX_RESPONSE_SIZE: usize = 1 << 24; // 16 MiB
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Codec;
+
+#[async_trait::async_trait]
+impl request_response::Codec for Codec {
+    type Protocol = StreamProtocol;
+    type Request = Vec<u8>;
+    type Response = Vec<u8>;
+
+    async fn read_request<T>(&mut self, _: &Self::Protocol, io: &mut T) -> io::Result<Vec<u8>>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        read_length_prefixed(io, MAX_REQUEST_SIZE).await
+    }
+
+    async fn read_response<T>(&mut self, _: &Self::Protocol, io: &mut T) -> io::Result<Vec<u8>>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        read_length_prefixed(io, MAX_RESPONSE_SIZE).await
+    }
+
+    async fn write_request<T>(
+        &mut self,
+        _: &Self::Protocol,
+        io: &mut T,
+        request: Vec<u8>,
+    ) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        write_length_prefixed(io, &request, MAX_REQUEST_SIZE).await
+    }
+
+    async fn write_response<T>(
+        &mut self,
+        _: &Self::Protocol,
+        io: &mut T,
+        response: Vec<u8>,
+    ) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        write_length_prefixed(io, &response, MAX_RESPONSE_SIZE).await
+    }
+}
+
+async fn read_length_prefixed<T>(io: &mut T, max_size: usize) -> io::Result<Vec<u8>>
+where
+    T: AsyncRead + Unpin + Send,
+{
+    let mut length = [0; 4];
+    io.read_exact(&mut length).await?;
+    let length = u32::from_be_bytes(length) as usize;
+    if length > max_size {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("message of {length} bytes exceeds the limit of {max_size} bytes"),
+        ));
+    }
+    let mut data = vec![0; length];
+    io.read_exact(&mut data).await?;
+    Ok(data)
+}
+
+async fn write_length_prefixed<T>(io: &mut T, data: &[u8], max_size: usize) -> io::Result<()>
+where
+    T: AsyncWrite + Unpin + Send,
+{
+    if data.len() > max_size {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "message of {} bytes exceeds the limit of {max_size} bytes",
+                data.len()
+            ),
+        ));
+    }
+    let length = u32::try_from(data.len())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?
+        .to_be_bytes();
+    io.write_all(&length).await?;
+    io.write_all(data).await
+}
+
+#[must_use]
+pub fn behaviour(protocol: StreamProtocol) -> request_response::Behaviour<Codec> {
+    request_response::Behaviour::with_codec(
+        Codec,
+        std::iter::once((protocol, request_response::ProtocolSupport::Full)),
+        request_response::Config::default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{executor::block_on, io::Cursor};
+
+    use super::*;
+
+    #[test]
+    fn test_length_prefixed_roundtrip() {
+        let data = b"cryptarchia".to_vec();
+        let mut buffer = Cursor::new(Vec::new());
+        block_on(write_length_prefixed(&mut buffer, &data, MAX_REQUEST_SIZE)).unwrap();
+
+        let mut buffer = Cursor::new(buffer.into_inner());
+        let read = block_on(read_length_prefixed(&mut buffer, MAX_REQUEST_SIZE)).unwrap();
+        assert_eq!(read, data);
+    }
+
+    #[test]
+    fn test_oversized_message_is_rejected() {
+        let data = vec![0; 16];
+        let mut buffer = Cursor::new(Vec::new());
+        assert!(block_on(write_length_prefixed(&mut buffer, &data, 8)).is_err());
+
+        block_on(write_length_prefixed(&mut buffer, &data, 16)).unwrap();
+        let mut buffer = Cursor::new(buffer.into_inner());
+        assert!(block_on(read_length_prefixed(&mut buffer, 8)).is_err());
+    }
+}
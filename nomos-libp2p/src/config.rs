@@ -1,10 +1,6 @@
 use std::{num::NonZeroUsize, time::Duration};
 
-use libp2p::{
-    gossipsub, identify,
-    identity::{self, ed25519},
-    kad, StreamProtocol,
-};
+use libp2p::{gossipsub, identify, identity, kad, StreamProtocol};
 use serde::{Deserialize, Serialize};
 
 use crate::protocol_name::ProtocolName;
@@ -15,9 +11,6 @@ pub struct SwarmConfig {
     pub host: std::net::Ipv4Addr,
     // TCP listening port. Use 0 for random
     pub port: u16,
-    // Secp256k1 private key in Hex format (`0x123...abc`). Default random
-    #[serde(with = "secret_key_serde", default = "ed25519::SecretKey::generate")]
-    pub node_key: ed25519::SecretKey,
     // Gossipsub config
     #[serde(with = "GossipsubConfigDef", default = "gossipsub::Config::default")]
     pub gossipsub_config: gossipsub::Config,
@@ -53,7 +46,6 @@ impl Default for SwarmConfig {
         Self {
             host: std::net::Ipv4Addr::new(0, 0, 0, 0),
             port: 60000,
-            node_key: ed25519::SecretKey::generate(),
             gossipsub_config: gossipsub::Config::default(),
             protocol_name_env: ProtocolName::default(),
             kademlia_config: None,
@@ -430,6 +422,5 @@ mod tests {
         let deserialized: SwarmConfig = serde_json::from_str(serialized.as_str()).unwrap();
         assert_eq!(deserialized.host, config.host);
         assert_eq!(deserialized.port, config.port);
-        assert_eq!(deserialized.node_key.as_ref(), config.node_key.as_ref());
     }
 }
@@ -1,3 +1,4 @@
+pub mod chain_sync;
 mod config;
 
 pub mod protocol_name;
@@ -27,7 +28,9 @@ use libp2p::{
     gossipsub::{Message, MessageId, TopicHash},
     identify,
     kad::{self, QueryId},
+    request_response::{OutboundRequestId, ResponseChannel},
     swarm::{behaviour::toggle::Toggle, ConnectionId},
+    StreamProtocol,
 };
 pub use multiaddr::{multiaddr, Multiaddr, Protocol};
 use protocol_name::ProtocolName;
@@ -53,6 +56,7 @@ pub struct Behaviour {
     // todo: support persistent store if needed
     kademlia: Toggle<kad::Behaviour<kad::store::MemoryStore>>,
     identify: Toggle<identify::Behaviour>,
+    chain_sync: libp2p::request_response::Behaviour<chain_sync::Codec>,
 }
 
 impl Behaviour {
@@ -93,10 +97,15 @@ impl Behaviour {
             },
         );
 
+        let chain_sync = chain_sync::behaviour(StreamProtocol::new(
+            protocol_name.chain_sync_protocol_name(),
+        ));
+
         Ok(Self {
             gossipsub,
             kademlia,
             identify,
+            chain_sync,
         })
     }
 
@@ -173,12 +182,14 @@ const IDLE_CONN_TIMEOUT: Duration = Duration::from_secs(300);
 
 impl Swarm {
     /// Builds a [`Swarm`] configured for use with Nomos on top of a tokio
-    /// executor.
+    /// executor, with `node_key` as its identity.
     //
     // TODO: define error types
-    pub fn build(config: SwarmConfig) -> Result<Self, Box<dyn Error>> {
-        let keypair =
-            libp2p::identity::Keypair::from(ed25519::Keypair::from(config.node_key.clone()));
+    pub fn build(
+        config: SwarmConfig,
+        node_key: ed25519::SecretKey,
+    ) -> Result<Self, Box<dyn Error>> {
+        let keypair = libp2p::identity::Keypair::from(ed25519::Keypair::from(node_key));
         let peer_id = PeerId::from(keypair.public());
         tracing::info!("libp2p peer_id:{}", peer_id);
 
@@ -300,6 +311,35 @@ impl Swarm {
         self.swarm.behaviour().get_kademlia_protocol_names()
     }
 
+    /// Sends a chain sync request to a peer
+    ///
+    /// The response will be notified as a
+    /// [`BehaviourEvent::ChainSync`] event.
+    pub fn send_chain_sync_request(
+        &mut self,
+        peer_id: &PeerId,
+        request: Vec<u8>,
+    ) -> OutboundRequestId {
+        self.swarm
+            .behaviour_mut()
+            .chain_sync
+            .send_request(peer_id, request)
+    }
+
+    /// Answers a chain sync request received from a peer
+    ///
+    /// Returns the response back if the channel was already closed.
+    pub fn send_chain_sync_response(
+        &mut self,
+        channel: ResponseChannel<Vec<u8>>,
+        response: Vec<u8>,
+    ) -> Result<(), Vec<u8>> {
+        self.swarm
+            .behaviour_mut()
+            .chain_sync
+            .send_response(channel, response)
+    }
+
     #[must_use]
     pub fn topic_hash(topic: &str) -> TopicHash {
         gossipsub::IdentTopic::new(topic).hash()
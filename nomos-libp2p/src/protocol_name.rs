@@ -2,15 +2,19 @@ use serde::{Deserialize, Serialize};
 
 pub const MAINNET_KAD_PROTOCOL_NAME: &str = "/nomos/kad/1.0.0";
 pub const MAINNET_IDENTIFY_PROTOCOL_NAME: &str = "/nomos/identify/1.0.0";
+pub const MAINNET_CHAIN_SYNC_PROTOCOL_NAME: &str = "/nomos/chainsync/1.0.0";
 
 pub const TESTNET_KAD_PROTOCOL_NAME: &str = "/testnet/nomos/kad/1.0.0";
 pub const TESTNET_IDENTIFY_PROTOCOL_NAME: &str = "/testnet/nomos/identify/1.0.0";
+pub const TESTNET_CHAIN_SYNC_PROTOCOL_NAME: &str = "/testnet/nomos/chainsync/1.0.0";
 
 pub const UNITTEST_KAD_PROTOCOL_NAME: &str = "/unittest/nomos/kad/1.0.0";
 pub const UNITTEST_IDENTIFY_PROTOCOL_NAME: &str = "/unittest/nomos/identify/1.0.0";
+pub const UNITTEST_CHAIN_SYNC_PROTOCOL_NAME: &str = "/unittest/nomos/chainsync/1.0.0";
 
 pub const INTEGRATION_KAD_PROTOCOL_NAME: &str = "/integration/nomos/kad/1.0.0";
 pub const INTEGRATION_IDENTIFY_PROTOCOL_NAME: &str = "/integration/nomos/identify/1.0.0";
+pub const INTEGRATION_CHAIN_SYNC_PROTOCOL_NAME: &str = "/integration/nomos/chainsync/1.0.0";
 
 /// Network environment type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -42,6 +46,16 @@ impl ProtocolName {
             Self::Integration => INTEGRATION_IDENTIFY_PROTOCOL_NAME,
         }
     }
+
+    #[must_use]
+    pub const fn chain_sync_protocol_name(self) -> &'static str {
+        match self {
+            Self::Mainnet => MAINNET_CHAIN_SYNC_PROTOCOL_NAME,
+            Self::Testnet => TESTNET_CHAIN_SYNC_PROTOCOL_NAME,
+            Self::Unittest => UNITTEST_CHAIN_SYNC_PROTOCOL_NAME,
+            Self::Integration => INTEGRATION_CHAIN_SYNC_PROTOCOL_NAME,
+        }
+    }
 }
 
 impl Default for ProtocolName {
@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, hash_map::Entry},
+    collections::{HashMap, HashSet, hash_map::Entry},
     error::Error,
     fmt::Debug,
     marker::PhantomData,
@@ -9,8 +9,8 @@ use async_trait::async_trait;
 
 use crate::{
     BlockNumber, Declaration, DeclarationId, DeclarationMessage, DeclarationUpdate, EventType,
-    Nonce, ProviderId, ProviderInfo, RewardId, RewardMessage, SdpMessage, ServiceParameters,
-    ServiceType, WithdrawMessage,
+    Locator, Nonce, ProviderId, ProviderInfo, RewardId, RewardMessage, SdpMessage,
+    ServiceParameters, ServiceType, StakeId, WithdrawMessage,
     state::{ProviderState, ProviderStateError},
 };
 
@@ -22,10 +22,21 @@ pub enum DeclarationsRepositoryError {
     DeclarationNotFound(DeclarationId),
     #[error("Duplicate nonce")]
     DuplicateNonce,
+    #[error("Stake already backs a declaration")]
+    DuplicateStake,
     #[error(transparent)]
     Other(Box<dyn Error + Send>),
 }
 
+/// Updates of a block, applied at once when the block is marked in block.
+#[derive(Clone, Debug, Default)]
+pub struct BlockUpdate {
+    pub providers: Vec<ProviderInfo>,
+    pub declarations: Vec<DeclarationUpdate>,
+    pub nonces: Vec<(ProviderId, Nonce)>,
+    pub stakes: Vec<StakeId>,
+}
+
 #[async_trait]
 pub trait DeclarationsRepository {
     async fn get_provider_info(
@@ -36,19 +47,26 @@ pub trait DeclarationsRepository {
         &self,
         declaration_id: DeclarationId,
     ) -> Result<Declaration, DeclarationsRepositoryError>;
-    async fn update_provider_info(
-        &self,
-        provider_info: ProviderInfo,
-    ) -> Result<(), DeclarationsRepositoryError>;
-    async fn update_declaration(
+    /// Applies all the updates of a block atomically, either all of them are
+    /// persisted or none is.
+    async fn apply_block_update(
         &self,
-        declaration_update: DeclarationUpdate,
+        block_number: BlockNumber,
+        block_update: BlockUpdate,
     ) -> Result<(), DeclarationsRepositoryError>;
+    /// Checks that the nonce was not used by the provider in an applied
+    /// block.
     async fn check_nonce(
         &self,
         provider_id: ProviderId,
         nonce: Nonce,
     ) -> Result<(), DeclarationsRepositoryError>;
+    /// Checks that the stake does not back a declaration of an applied block.
+    async fn check_stake(&self, stake_id: StakeId) -> Result<(), DeclarationsRepositoryError>;
+    async fn get_declarations(
+        &self,
+        service_type: ServiceType,
+    ) -> Result<Vec<Declaration>, DeclarationsRepositoryError>;
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -105,11 +123,13 @@ pub enum StakesVerifierError {
 pub trait StakesVerifier {
     type Proof;
 
+    /// Verifies the proof of funds of the provider, returning the stake it
+    /// locks.
     async fn verify(
         &self,
         provider_id: ProviderId,
         proof: Self::Proof,
-    ) -> Result<(), StakesVerifierError>;
+    ) -> Result<StakeId, StakesVerifierError>;
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -148,6 +168,8 @@ where
     stake_verifier: Stakes,
     pending_providers: HashMap<BlockNumber, HashMap<ProviderId, ProviderInfo>>,
     pending_declarations: HashMap<BlockNumber, HashMap<ProviderId, DeclarationUpdate>>,
+    pending_nonces: HashMap<BlockNumber, HashSet<(ProviderId, Nonce)>>,
+    pending_stakes: HashMap<BlockNumber, HashSet<StakeId>>,
     pending_rewards: HashMap<ProviderId, RewardId>,
     _phantom: PhantomData<(Proof, Metadata, ContractAddress)>,
 }
@@ -161,6 +183,26 @@ where
     Stakes: StakesVerifier<Proof = Proof> + Send + Sync,
     ContractAddress: Debug,
 {
+    pub fn new(
+        declaration_repo: Declarations,
+        reward_request_sender: Rewards,
+        services_repo: Services,
+        stake_verifier: Stakes,
+    ) -> Self {
+        Self {
+            declaration_repo,
+            reward_request_sender,
+            services_repo,
+            stake_verifier,
+            pending_providers: HashMap::new(),
+            pending_declarations: HashMap::new(),
+            pending_nonces: HashMap::new(),
+            pending_stakes: HashMap::new(),
+            pending_rewards: HashMap::new(),
+            _phantom: PhantomData,
+        }
+    }
+
     async fn process_declare(
         &mut self,
         block_number: BlockNumber,
@@ -200,10 +242,26 @@ where
         let proof_of_funds = declaration_message.proof_of_funds;
 
         // Provider stake needs to be checked by verifying proof.
-        self.stake_verifier
+        let stake_id = self
+            .stake_verifier
             .verify(provider_id, proof_of_funds)
             .await?;
 
+        // Each stake backs a single declaration, for as long as the ledger
+        // keeps it.
+        if self
+            .pending_stakes
+            .get(&block_number)
+            .is_some_and(|stakes| stakes.contains(&stake_id))
+        {
+            return Err(DeclarationsRepositoryError::DuplicateStake.into());
+        }
+        self.declaration_repo.check_stake(stake_id).await?;
+        self.pending_stakes
+            .entry(block_number)
+            .or_default()
+            .insert(stake_id);
+
         let entry = self.pending_declarations.entry(block_number).or_default();
         entry.insert(provider_id, declaration_update);
 
@@ -222,9 +280,11 @@ where
         let provider_id = reward_message.provider_id;
         let declaration_id = reward_message.declaration_id;
         let service_type = reward_message.service_type;
+        let nonce = reward_message.nonce;
 
+        self.check_pending_nonce(block_number, provider_id, nonce)?;
         self.declaration_repo
-            .check_nonce(provider_id, reward_message.nonce)
+            .check_nonce(provider_id, nonce)
             .await?;
 
         // One declaration can be for multiple services, and each service could have
@@ -247,6 +307,7 @@ where
             entry.insert(reward_id);
         }
 
+        self.insert_pending_nonce(block_number, provider_id, nonce);
         Ok(pending_state)
     }
 
@@ -265,9 +326,11 @@ where
         let provider_id = withdraw_message.provider_id;
         let declaration_id = withdraw_message.declaration_id;
         let service_type = withdraw_message.service_type;
+        let nonce = withdraw_message.nonce;
 
+        self.check_pending_nonce(block_number, provider_id, nonce)?;
         self.declaration_repo
-            .check_nonce(provider_id, withdraw_message.nonce)
+            .check_nonce(provider_id, nonce)
             .await?;
 
         // One declaration can be for multiple services, and each service could have
@@ -300,9 +363,40 @@ where
             }
         }
 
+        self.insert_pending_nonce(block_number, provider_id, nonce);
         Ok(pending_state)
     }
 
+    /// Nonces are single use, a nonce can't be used twice in the same block.
+    /// Nonces used in applied blocks are checked by the repository.
+    fn check_pending_nonce(
+        &self,
+        block_number: BlockNumber,
+        provider_id: ProviderId,
+        nonce: Nonce,
+    ) -> Result<(), SdpLedgerError<ContractAddress>> {
+        if self
+            .pending_nonces
+            .get(&block_number)
+            .is_some_and(|nonces| nonces.contains(&(provider_id, nonce)))
+        {
+            return Err(DeclarationsRepositoryError::DuplicateNonce.into());
+        }
+        Ok(())
+    }
+
+    fn insert_pending_nonce(
+        &mut self,
+        block_number: BlockNumber,
+        provider_id: ProviderId,
+        nonce: Nonce,
+    ) {
+        self.pending_nonces
+            .entry(block_number)
+            .or_default()
+            .insert((provider_id, nonce));
+    }
+
     pub async fn process_sdp_message(
         &mut self,
         block_number: BlockNumber,
@@ -369,79 +463,122 @@ where
         Ok(())
     }
 
-    async fn mark_declaration_in_block(
-        &mut self,
-        block_number: BlockNumber,
-        provider_info: &ProviderInfo,
-    ) -> Result<(), SdpLedgerError<ContractAddress>> {
-        let provider_id = provider_info.provider_id;
-
-        if let Err(err) = self
-            .declaration_repo
-            .update_provider_info(*provider_info)
-            .await
-        {
-            // If provider update failed, discard declaration.
-            self.pending_declarations
-                .get_mut(&block_number)
-                .and_then(|updates| updates.remove(&provider_id));
-            return Err(err.into());
-        }
-
-        // One provider id can declare only one service in one declaration.
-        if let Some(declaration_update) = self
-            .pending_declarations
-            .get_mut(&block_number)
-            .and_then(|updates| updates.remove(&provider_id))
-        {
-            if let Err(err) = self
-                .declaration_repo
-                .update_declaration(declaration_update)
-                .await
-            {
-                tracing::error!("Declaration could not be updated: {err}");
-            }
-        }
-
-        Ok(())
-    }
-
+    /// Applies the pending updates of the block. Updates are applied
+    /// atomically, if they can't be persisted the block is discarded.
     pub async fn mark_in_block(
         &mut self,
         block_number: BlockNumber,
     ) -> Result<(), SdpLedgerError<ContractAddress>> {
-        let Some(updates) = self.pending_providers.remove(&block_number) else {
-            return Ok(());
+        let providers = self
+            .pending_providers
+            .remove(&block_number)
+            .unwrap_or_default();
+        let mut declarations = self
+            .pending_declarations
+            .remove(&block_number)
+            .unwrap_or_default();
+        let nonces = self
+            .pending_nonces
+            .remove(&block_number)
+            .unwrap_or_default();
+        let stakes = self
+            .pending_stakes
+            .remove(&block_number)
+            .unwrap_or_default();
+
+        let mut block_update = BlockUpdate {
+            nonces: nonces.into_iter().collect(),
+            stakes: stakes.into_iter().collect(),
+            ..BlockUpdate::default()
         };
-
-        for info in updates.values() {
-            let id = info.provider_id;
+        for (provider_id, provider_info) in providers {
             // Rewards are expected to be marked in block by entity that manages rewards.
-            self.pending_rewards.remove(&id);
-
-            if let Err(err) = self.mark_declaration_in_block(block_number, info).await {
-                tracing::error!("Provider information couldn't be updated: {err}");
+            self.pending_rewards.remove(&provider_id);
+            // One provider id can declare only one service in one declaration.
+            if let Some(declaration_update) = declarations.remove(&provider_id) {
+                block_update.declarations.push(declaration_update);
             }
+            block_update.providers.push(provider_info);
         }
 
+        self.declaration_repo
+            .apply_block_update(block_number, block_update)
+            .await?;
         Ok(())
     }
 
+    pub const fn stake_verifier_mut(&mut self) -> &mut Stakes {
+        &mut self.stake_verifier
+    }
+
+    /// Clears the pending updates of a block whose messages were processed
+    /// but which won't be marked in block, none of them is applied.
+    ///
+    /// This is not a rollback of applied blocks: updates marked in block are
+    /// final, the ledger being meant to be fed finalized blocks only. Blocks
+    /// failing in [`Self::mark_in_block`] need no clearing either, their
+    /// pending updates are dropped there whether they are persisted or not.
+    ///
+    /// Reward requests already sent for the block are not revoked, but the
+    /// providers can be rewarded again in another block.
     pub fn discard_block(&mut self, block_number: BlockNumber) {
         self.pending_declarations.remove(&block_number);
+        self.pending_nonces.remove(&block_number);
+        self.pending_stakes.remove(&block_number);
         if let Some(updates) = self.pending_providers.remove(&block_number) {
-            for ProviderInfo { provider_id, .. } in updates.values() {
+            for provider_id in updates.keys() {
                 self.pending_rewards.remove(provider_id);
             }
         }
     }
 }
 
+impl<Declarations, Rewards, Services, Stakes, Proof, Metadata, ContractAddress>
+    SdpLedger<Declarations, Rewards, Services, Stakes, Proof, Metadata, ContractAddress>
+where
+    Declarations: DeclarationsRepository + Send + Sync,
+    Rewards: RewardsRequestSender<Metadata = Metadata, ContractAddress = ContractAddress> + Sync,
+    Services: ServicesRepository<ContractAddress = ContractAddress> + Sync,
+    Stakes: Sync,
+    Proof: Sync,
+    Metadata: Sync,
+    ContractAddress: Debug + Send + Sync,
+{
+    /// Providers of `service_type` which are active at `block_number`, with
+    /// the locators of their declarations.
+    ///
+    /// Only updates of blocks already marked in block are taken into account.
+    pub async fn active_providers(
+        &self,
+        block_number: BlockNumber,
+        service_type: ServiceType,
+    ) -> Result<HashMap<ProviderId, Vec<Locator>>, SdpLedgerError<ContractAddress>> {
+        let service_params = self.services_repo.get_parameters(service_type).await?;
+        let mut providers = HashMap::new();
+        for declaration in self.declaration_repo.get_declarations(service_type).await? {
+            let Some(provider_ids) = declaration.services.get(&service_type) else {
+                continue;
+            };
+            for provider_id in provider_ids {
+                let provider_info = self
+                    .declaration_repo
+                    .get_provider_info(*provider_id)
+                    .await?;
+                if let Ok(ProviderState::Active(_)) =
+                    ProviderState::try_from_info(block_number, &provider_info, &service_params)
+                {
+                    providers.insert(*provider_id, declaration.locators.clone());
+                }
+            }
+        }
+        Ok(providers)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{
-        collections::HashMap,
-        marker::PhantomData,
+        collections::{HashMap, HashSet},
         sync::{Arc, Mutex},
     };
 
@@ -481,7 +618,7 @@ mod tests {
                 SdpMessage::Declare(DeclarationMessage {
                     service_type,
                     locators,
-                    proof_of_funds: [0u8; 32],
+                    proof_of_funds: provider_id.0,
                     provider_id,
                 }),
                 should_pass,
@@ -573,6 +710,8 @@ mod tests {
     struct MockDeclarationsRepository {
         providers: Arc<Mutex<HashMap<ProviderId, ProviderInfo>>>,
         declarations: Arc<Mutex<HashMap<DeclarationId, Declaration>>>,
+        nonces: Arc<Mutex<HashSet<(ProviderId, Nonce)>>>,
+        stakes: Arc<Mutex<HashSet<StakeId>>>,
     }
 
     impl MockDeclarationsRepository {
@@ -612,43 +751,35 @@ mod tests {
                 .ok_or(DeclarationsRepositoryError::DeclarationNotFound(id))
         }
 
-        async fn update_provider_info(
+        async fn apply_block_update(
             &self,
-            provider_info: ProviderInfo,
+            _block_number: BlockNumber,
+            block_update: BlockUpdate,
         ) -> Result<(), DeclarationsRepositoryError> {
-            self.providers
-                .lock()
-                .unwrap()
-                .insert(provider_info.provider_id, provider_info);
-            Ok(())
-        }
+            let mut providers = self.providers.lock().unwrap();
+            for provider_info in block_update.providers {
+                providers.insert(provider_info.provider_id, provider_info);
+            }
+            drop(providers);
 
-        async fn update_declaration(
-            &self,
-            declaration_update: DeclarationUpdate,
-        ) -> Result<(), DeclarationsRepositoryError> {
             let mut declarations = self.declarations.lock().unwrap();
-            if let Some(declaration) = declarations.get_mut(&declaration_update.declaration_id) {
-                declaration.insert_service_provider(
-                    declaration_update.provider_id,
-                    declaration_update.service_type,
-                );
-            } else {
-                let mut services = HashMap::new();
-                services.insert(
-                    declaration_update.service_type,
-                    [declaration_update.provider_id].into(),
-                );
-                declarations.insert(
-                    declaration_update.declaration_id,
-                    Declaration {
+            for declaration_update in block_update.declarations {
+                declarations
+                    .entry(declaration_update.declaration_id)
+                    .or_insert_with(|| Declaration {
                         declaration_id: declaration_update.declaration_id,
                         locators: declaration_update.locators,
-                        services,
-                    },
-                );
-                drop(declarations);
+                        services: HashMap::new(),
+                    })
+                    .insert_service_provider(
+                        declaration_update.provider_id,
+                        declaration_update.service_type,
+                    );
             }
+            drop(declarations);
+
+            self.nonces.lock().unwrap().extend(block_update.nonces);
+            self.stakes.lock().unwrap().extend(block_update.stakes);
             Ok(())
         }
 
@@ -659,6 +790,27 @@ mod tests {
         ) -> Result<(), DeclarationsRepositoryError> {
             Ok(())
         }
+
+        async fn check_stake(&self, stake_id: StakeId) -> Result<(), DeclarationsRepositoryError> {
+            if self.stakes.lock().unwrap().contains(&stake_id) {
+                return Err(DeclarationsRepositoryError::DuplicateStake);
+            }
+            Ok(())
+        }
+
+        async fn get_declarations(
+            &self,
+            service_type: ServiceType,
+        ) -> Result<Vec<Declaration>, DeclarationsRepositoryError> {
+            Ok(self
+                .declarations
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|declaration| declaration.services.contains_key(&service_type))
+                .cloned()
+                .collect())
+        }
     }
 
     #[derive(Default, Clone)]
@@ -690,9 +842,9 @@ mod tests {
         async fn verify(
             &self,
             _provider_id: ProviderId,
-            _proof: Self::Proof,
-        ) -> Result<(), StakesVerifierError> {
-            Ok(())
+            proof: Self::Proof,
+        ) -> Result<StakeId, StakesVerifierError> {
+            Ok(StakeId(proof))
         }
     }
 
@@ -745,16 +897,12 @@ mod tests {
             params.insert(ServiceType::DataAvailability, default_service_params());
         };
 
-        let ledger = SdpLedger {
-            declaration_repo: declaration_repo.clone(),
-            reward_request_sender: rewards_sender.clone(),
-            services_repo: service_repo.clone(),
+        let ledger = SdpLedger::new(
+            declaration_repo.clone(),
+            rewards_sender.clone(),
+            service_repo.clone(),
             stake_verifier,
-            pending_providers: HashMap::new(),
-            pending_declarations: HashMap::new(),
-            pending_rewards: HashMap::new(),
-            _phantom: PhantomData,
-        };
+        );
 
         (ledger, declaration_repo, rewards_sender, service_repo)
     }
@@ -872,6 +1020,42 @@ mod tests {
         assert!(result2.is_err());
     }
 
+    #[tokio::test]
+    async fn test_duplicate_stake() {
+        let (mut ledger, _, _, _) = setup_ledger();
+        let declaration = |provider_id| {
+            SdpMessage::Declare(DeclarationMessage {
+                service_type: ServiceType::BlendNetwork,
+                locators: vec![],
+                proof_of_funds: [0u8; 32],
+                provider_id,
+            })
+        };
+
+        ledger
+            .process_sdp_message(100, declaration(ProviderId([0u8; 32])))
+            .await
+            .unwrap();
+        assert!(matches!(
+            ledger
+                .process_sdp_message(100, declaration(ProviderId([1u8; 32])))
+                .await,
+            Err(SdpLedgerError::DeclarationsRepository(
+                DeclarationsRepositoryError::DuplicateStake
+            ))
+        ));
+
+        ledger.mark_in_block(100).await.unwrap();
+        assert!(matches!(
+            ledger
+                .process_sdp_message(101, declaration(ProviderId([2u8; 32])))
+                .await,
+            Err(SdpLedgerError::DeclarationsRepository(
+                DeclarationsRepositoryError::DuplicateStake
+            ))
+        ));
+    }
+
     #[tokio::test]
     async fn test_valid_blocks() {
         let (mut ledger, declarations_repo, _, _) = setup_ledger();
@@ -1035,4 +1219,119 @@ mod tests {
             }
         );
     }
+
+    #[tokio::test]
+    async fn test_active_providers() {
+        let (mut ledger, _, _, _) = setup_ledger();
+        let (pid1, pid2, pid3) = (
+            ProviderId([0; 32]),
+            ProviderId([1; 32]),
+            ProviderId([2; 32]),
+        );
+        let locators = vec![Locator::new(multiaddr!(Ip4([1, 2, 3, 4]), Udp(5678u16)))];
+        let did = declaration_id(&locators);
+        let blocks = [
+            (
+                0,
+                vec![
+                    (BOp::Dec(pid1, St::DataAvailability, locators.clone()), true),
+                    (BOp::Dec(pid2, St::BlendNetwork, locators.clone()), true),
+                    (BOp::Dec(pid3, St::DataAvailability, locators.clone()), true),
+                ],
+            ),
+            (
+                15,
+                vec![(BOp::Wit(pid3, did, St::DataAvailability, false), true)],
+            ),
+        ]
+        .into();
+        for (block_number, block) in gen_blocks(blocks) {
+            for (message, should_pass) in block.messages {
+                let res = ledger.process_sdp_message(block_number, message).await;
+                assert_eq!(res.is_ok(), should_pass);
+            }
+            ledger.mark_in_block(block_number).await.unwrap();
+        }
+
+        let providers = ledger
+            .active_providers(15, ServiceType::DataAvailability)
+            .await
+            .unwrap();
+        assert_eq!(providers, HashMap::from([(pid1, locators.clone())]));
+        let providers = ledger
+            .active_providers(15, ServiceType::BlendNetwork)
+            .await
+            .unwrap();
+        assert_eq!(providers, HashMap::from([(pid2, locators)]));
+
+        // providers become inactive if they are not rewarded
+        let providers = ledger
+            .active_providers(25, ServiceType::DataAvailability)
+            .await
+            .unwrap();
+        assert!(providers.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_discard_block() {
+        let (mut ledger, declarations_repo, _, _) = setup_ledger();
+        let pid = ProviderId([0; 32]);
+        let locators = vec![Locator::new(multiaddr!(Ip4([1, 2, 3, 4]), Udp(5678u16)))];
+        let did = declaration_id(&locators);
+        let blocks = [
+            (
+                0,
+                vec![(BOp::Dec(pid, St::DataAvailability, locators.clone()), true)],
+            ),
+            (
+                10,
+                vec![
+                    (BOp::Rew(pid, did, St::DataAvailability), true),
+                    // nonce already used in the block
+                    (BOp::Rew(pid, did, St::DataAvailability), false),
+                ],
+            ),
+        ];
+        let blocks = gen_blocks(blocks.into());
+        let (_, reward_block) = &blocks[1];
+        let reward = reward_block.messages[0].0.clone();
+        for (block_number, block) in blocks {
+            for (message, should_pass) in block.messages {
+                let res = ledger.process_sdp_message(block_number, message).await;
+                assert_eq!(res.is_ok(), should_pass);
+            }
+            if block_number == 0 {
+                ledger.mark_in_block(block_number).await.unwrap();
+            }
+        }
+
+        ledger.discard_block(10);
+        assert!(ledger.pending_providers.is_empty());
+        assert!(ledger.pending_nonces.is_empty());
+        assert!(ledger.pending_rewards.is_empty());
+        assert_eq!(
+            declarations_repo
+                .dump_providers()
+                .get(&pid)
+                .unwrap()
+                .rewarded,
+            None
+        );
+
+        // the nonce of the discarded block can be used again
+        ledger.process_sdp_message(10, reward).await.unwrap();
+        ledger.mark_in_block(10).await.unwrap();
+        assert_eq!(
+            declarations_repo
+                .dump_providers()
+                .get(&pid)
+                .unwrap()
+                .rewarded,
+            Some(10)
+        );
+        assert_eq!(
+            *declarations_repo.nonces.lock().unwrap(),
+            HashSet::from([(pid, [0; 16])])
+        );
+    }
 }
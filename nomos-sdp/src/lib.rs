@@ -8,6 +8,7 @@ use std::{
 
 use blake2::{Blake2b, Digest};
 use multiaddr::Multiaddr;
+use serde::{Deserialize, Serialize};
 
 pub type StakeThreshold = u64;
 pub type BlockNumber = u64;
@@ -17,7 +18,7 @@ pub struct MinStake {
     pub timestamp: BlockNumber,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ServiceParameters<ContractAddress> {
     pub lock_period: u64,
     pub inactivity_period: u64,
@@ -26,12 +27,24 @@ pub struct ServiceParameters<ContractAddress> {
     pub timestamp: BlockNumber,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Locator {
     addr: Multiaddr,
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+impl Locator {
+    #[must_use]
+    pub const fn new(addr: Multiaddr) -> Self {
+        Self { addr }
+    }
+
+    #[must_use]
+    pub const fn addr(&self) -> &Multiaddr {
+        &self.addr
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum ServiceType {
     BlendNetwork,
     DataAvailability,
@@ -41,16 +54,20 @@ pub enum ServiceType {
 
 pub type Nonce = [u8; 16];
 
-#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
 pub struct ProviderId(pub [u8; 32]);
 
-#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
 pub struct DeclarationId(pub [u8; 32]);
 
-#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
 pub struct RewardId(pub [u8; 32]);
 
-#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+/// Identifies the funds backing a declaration, which can only back one.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct StakeId(pub [u8; 32]);
+
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
 pub struct ProviderInfo {
     pub provider_id: ProviderId,
     pub declaration_id: DeclarationId,
@@ -76,7 +93,7 @@ impl ProviderInfo {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Declaration {
     pub declaration_id: DeclarationId,
     pub locators: Vec<Locator>,
@@ -99,7 +116,7 @@ impl Declaration {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DeclarationUpdate {
     pub declaration_id: DeclarationId,
     pub provider_id: ProviderId,
@@ -118,7 +135,7 @@ impl<Proof> From<&DeclarationMessage<Proof>> for DeclarationUpdate {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DeclarationMessage<Proof> {
     pub service_type: ServiceType,
     pub locators: Vec<Locator>,
@@ -136,7 +153,7 @@ impl<Proof> DeclarationMessage<Proof> {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct WithdrawMessage<Metadata> {
     pub declaration_id: DeclarationId,
     pub service_type: ServiceType,
@@ -145,7 +162,7 @@ pub struct WithdrawMessage<Metadata> {
     pub metadata: Option<Metadata>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RewardMessage<Metadata> {
     pub declaration_id: DeclarationId,
     pub service_type: ServiceType,
@@ -202,6 +219,7 @@ pub struct Event {
     pub timestamp: BlockNumber,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SdpMessage<Metadata, Proof> {
     Declare(DeclarationMessage<Proof>),
     Reward(RewardMessage<Metadata>),
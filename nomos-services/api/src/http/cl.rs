@@ -3,7 +3,8 @@ use std::fmt::Display;
 
 use nomos_core::{header::HeaderId, tx::Transaction};
 use nomos_mempool::{
-    backend::mockpool::MockPool, network::adapters::libp2p::Libp2pAdapter as MempoolNetworkAdapter,
+    backend::priority::PriorityPool,
+    network::adapters::libp2p::Libp2pAdapter as MempoolNetworkAdapter,
     tx::service::openapi::Status, MempoolMetrics, MempoolMsg, TxMempoolService,
 };
 use overwatch::services::AsServiceId;
@@ -14,7 +15,7 @@ use crate::wait_with_timeout;
 
 pub type ClMempoolService<T, RuntimeServiceId> = TxMempoolService<
     MempoolNetworkAdapter<T, <T as Transaction>::Hash, RuntimeServiceId>,
-    MockPool<HeaderId, T, <T as Transaction>::Hash>,
+    PriorityPool<HeaderId, T, <T as Transaction>::Hash>,
     RuntimeServiceId,
 >;
 
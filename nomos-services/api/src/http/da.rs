@@ -32,14 +32,17 @@ use nomos_da_network_service::{
     },
     DaNetworkMsg, NetworkService,
 };
-use nomos_da_sampling::backend::DaSamplingServiceBackend;
+use nomos_da_sampling::{
+    backend::DaSamplingServiceBackend, DaSamplingService, DaSamplingServiceMsg,
+};
 use nomos_da_verifier::{
     backend::VerifierBackend, storage::adapters::rocksdb::RocksAdapter as VerifierStorageAdapter,
     DaVerifierMsg, DaVerifierService,
 };
 use nomos_libp2p::PeerId;
 use nomos_mempool::{
-    backend::mockpool::MockPool, network::adapters::libp2p::Libp2pAdapter as MempoolNetworkAdapter,
+    backend::priority::PriorityPool,
+    network::adapters::libp2p::Libp2pAdapter as MempoolNetworkAdapter,
 };
 use nomos_storage::backends::{rocksdb::RocksBackend, StorageSerde};
 use overwatch::{overwatch::handle::OverwatchHandle, services::AsServiceId, DynError};
@@ -79,9 +82,9 @@ pub type DaIndexer<
         V,
         RuntimeServiceId,
     >,
-    MockPool<HeaderId, Tx, <Tx as Transaction>::Hash>,
+    PriorityPool<HeaderId, Tx, <Tx as Transaction>::Hash>,
     MempoolNetworkAdapter<Tx, <Tx as Transaction>::Hash, RuntimeServiceId>,
-    MockPool<HeaderId, V, [u8; 32]>,
+    PriorityPool<HeaderId, V, [u8; 32]>,
     MempoolNetworkAdapter<C, <C as DispersedBlobInfo>::BlobId, RuntimeServiceId>,
     FillSizeWithTx<SIZE, Tx>,
     FillSizeWithBlobs<SIZE, V>,
@@ -271,6 +274,332 @@ where
     wait_with_timeout(receiver, "Timeout while waiting for get range".to_owned()).await
 }
 
+/// Resolves the blob ids indexed in the range, `None` for the indexes no blob
+/// is indexed at.
+pub async fn get_blob_ids<
+    Tx,
+    C,
+    V,
+    SS,
+    SamplingBackend,
+    SamplingNetworkAdapter,
+    SamplingRng,
+    SamplingStorage,
+    DaVerifierBackend,
+    DaVerifierNetwork,
+    DaVerifierStorage,
+    TimeBackend,
+    ApiAdapter,
+    RuntimeServiceId,
+    const SIZE: usize,
+>(
+    handle: &OverwatchHandle<RuntimeServiceId>,
+    app_id: <V as metadata::Metadata>::AppId,
+    range: Range<<V as metadata::Metadata>::Index>,
+) -> Result<Vec<(<V as metadata::Metadata>::Index, Option<BlobId>)>, DynError>
+where
+    Tx: Transaction
+        + Eq
+        + Clone
+        + Debug
+        + Hash
+        + Serialize
+        + DeserializeOwned
+        + Send
+        + Sync
+        + 'static,
+    <Tx as Transaction>::Hash:
+        std::cmp::Ord + Debug + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static,
+    C: DispersedBlobInfo<BlobId = [u8; 32]>
+        + Clone
+        + Debug
+        + Serialize
+        + DeserializeOwned
+        + Send
+        + Sync
+        + 'static,
+    <C as DispersedBlobInfo>::BlobId: Clone + Send + Sync,
+    V: DispersedBlobInfo<BlobId = [u8; 32]>
+        + From<C>
+        + Eq
+        + Debug
+        + metadata::Metadata
+        + Hash
+        + Clone
+        + Serialize
+        + DeserializeOwned
+        + Send
+        + Sync
+        + 'static,
+    <V as DispersedBlobInfo>::BlobId: Debug + Clone + Ord + Hash,
+    <V as metadata::Metadata>::AppId: AsRef<[u8]> + Serialize + Clone + Send + Sync,
+    <V as metadata::Metadata>::Index:
+        AsRef<[u8]> + Serialize + DeserializeOwned + Clone + PartialOrd + Send + Sync,
+    SS: StorageSerde + Send + Sync + 'static,
+    SamplingRng: SeedableRng + RngCore,
+    SamplingBackend: DaSamplingServiceBackend<SamplingRng, BlobId = BlobId> + Send,
+    SamplingBackend::Settings: Clone,
+    SamplingBackend::Share: Debug + 'static,
+    SamplingBackend::BlobId: Debug + 'static,
+    SamplingNetworkAdapter: nomos_da_sampling::network::NetworkAdapter<RuntimeServiceId>,
+    SamplingStorage: nomos_da_sampling::storage::DaStorageAdapter<RuntimeServiceId>,
+    DaVerifierStorage: nomos_da_verifier::storage::DaStorageAdapter<RuntimeServiceId>,
+    DaVerifierBackend: nomos_da_verifier::backend::VerifierBackend + Send + 'static,
+    DaVerifierBackend::Settings: Clone,
+    DaVerifierNetwork: nomos_da_verifier::network::NetworkAdapter<RuntimeServiceId>,
+    DaVerifierNetwork::Settings: Clone,
+    TimeBackend: nomos_time::backends::TimeBackend,
+    TimeBackend::Settings: Clone + Send + Sync,
+    ApiAdapter: nomos_da_sampling::api::ApiAdapter + Send + Sync,
+    RuntimeServiceId: Debug
+        + Sync
+        + Display
+        + 'static
+        + AsServiceId<
+            DaIndexer<
+                Tx,
+                C,
+                V,
+                SS,
+                SamplingBackend,
+                SamplingNetworkAdapter,
+                SamplingRng,
+                SamplingStorage,
+                DaVerifierBackend,
+                DaVerifierNetwork,
+                DaVerifierStorage,
+                TimeBackend,
+                ApiAdapter,
+                RuntimeServiceId,
+                SIZE,
+            >,
+        >,
+{
+    let relay = handle.relay().await?;
+    let (sender, receiver) = oneshot::channel();
+    relay
+        .send(DaMsg::GetBlobIds {
+            app_id,
+            range,
+            reply_channel: sender,
+        })
+        .await
+        .map_err(|(e, _)| e)?;
+
+    wait_with_timeout(receiver, "Timeout while waiting for blob ids".to_owned()).await?
+}
+
+pub async fn retrieve_blob<
+    SamplingBackend,
+    SamplingNetworkAdapter,
+    SamplingRng,
+    SamplingStorage,
+    DaVerifierBackend,
+    DaVerifierNetwork,
+    DaVerifierStorage,
+    ApiAdapter,
+    RuntimeServiceId,
+>(
+    handle: &OverwatchHandle<RuntimeServiceId>,
+    blob_id: BlobId,
+) -> Result<Vec<u8>, DynError>
+where
+    SamplingRng: SeedableRng + RngCore,
+    SamplingBackend: DaSamplingServiceBackend<SamplingRng, BlobId = BlobId>,
+    SamplingNetworkAdapter: nomos_da_sampling::network::NetworkAdapter<RuntimeServiceId>,
+    SamplingStorage: nomos_da_sampling::storage::DaStorageAdapter<RuntimeServiceId>,
+    ApiAdapter: nomos_da_sampling::api::ApiAdapter,
+    RuntimeServiceId: Debug
+        + Sync
+        + Display
+        + AsServiceId<
+            DaSamplingService<
+                SamplingBackend,
+                SamplingNetworkAdapter,
+                SamplingRng,
+                SamplingStorage,
+                DaVerifierBackend,
+                DaVerifierNetwork,
+                DaVerifierStorage,
+                ApiAdapter,
+                RuntimeServiceId,
+            >,
+        >,
+{
+    let relay = handle.relay().await?;
+    let (sender, receiver) = oneshot::channel();
+    relay
+        .send(DaSamplingServiceMsg::RetrieveBlob {
+            blob_id,
+            reply_channel: sender,
+        })
+        .await
+        .map_err(|(e, _)| e)?;
+
+    wait_with_timeout(
+        receiver,
+        "Timeout while waiting for blob retrieval".to_owned(),
+    )
+    .await?
+}
+
+/// Retrieves the data of the blobs indexed in the range, sampling the shares
+/// this node does not hold from the network. Fails if any index in the range
+/// has no blob indexed.
+pub async fn retrieve_range<
+    Tx,
+    C,
+    V,
+    SS,
+    SamplingBackend,
+    SamplingNetworkAdapter,
+    SamplingRng,
+    SamplingStorage,
+    DaVerifierBackend,
+    DaVerifierNetwork,
+    DaVerifierStorage,
+    TimeBackend,
+    ApiAdapter,
+    RuntimeServiceId,
+    const SIZE: usize,
+>(
+    handle: &OverwatchHandle<RuntimeServiceId>,
+    app_id: <V as metadata::Metadata>::AppId,
+    range: Range<<V as metadata::Metadata>::Index>,
+) -> Result<Vec<(<V as metadata::Metadata>::Index, Vec<u8>)>, DynError>
+where
+    Tx: Transaction
+        + Eq
+        + Clone
+        + Debug
+        + Hash
+        + Serialize
+        + DeserializeOwned
+        + Send
+        + Sync
+        + 'static,
+    <Tx as Transaction>::Hash:
+        std::cmp::Ord + Debug + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static,
+    C: DispersedBlobInfo<BlobId = [u8; 32]>
+        + Clone
+        + Debug
+        + Serialize
+        + DeserializeOwned
+        + Send
+        + Sync
+        + 'static,
+    <C as DispersedBlobInfo>::BlobId: Clone + Send + Sync,
+    V: DispersedBlobInfo<BlobId = [u8; 32]>
+        + From<C>
+        + Eq
+        + Debug
+        + metadata::Metadata
+        + Hash
+        + Clone
+        + Serialize
+        + DeserializeOwned
+        + Send
+        + Sync
+        + 'static,
+    <V as DispersedBlobInfo>::BlobId: Debug + Clone + Ord + Hash,
+    <V as metadata::Metadata>::AppId: AsRef<[u8]> + Serialize + Clone + Send + Sync,
+    <V as metadata::Metadata>::Index:
+        AsRef<[u8]> + Serialize + DeserializeOwned + Clone + PartialOrd + Send + Sync,
+    SS: StorageSerde + Send + Sync + 'static,
+    SamplingRng: SeedableRng + RngCore,
+    SamplingBackend: DaSamplingServiceBackend<SamplingRng, BlobId = BlobId> + Send,
+    SamplingBackend::Settings: Clone,
+    SamplingBackend::Share: Debug + 'static,
+    SamplingBackend::BlobId: Debug + 'static,
+    SamplingNetworkAdapter: nomos_da_sampling::network::NetworkAdapter<RuntimeServiceId>,
+    SamplingStorage: nomos_da_sampling::storage::DaStorageAdapter<RuntimeServiceId>,
+    DaVerifierStorage: nomos_da_verifier::storage::DaStorageAdapter<RuntimeServiceId>,
+    DaVerifierBackend: nomos_da_verifier::backend::VerifierBackend + Send + 'static,
+    DaVerifierBackend::Settings: Clone,
+    DaVerifierNetwork: nomos_da_verifier::network::NetworkAdapter<RuntimeServiceId>,
+    DaVerifierNetwork::Settings: Clone,
+    TimeBackend: nomos_time::backends::TimeBackend,
+    TimeBackend::Settings: Clone + Send + Sync,
+    ApiAdapter: nomos_da_sampling::api::ApiAdapter + Send + Sync,
+    RuntimeServiceId: Debug
+        + Sync
+        + Display
+        + 'static
+        + AsServiceId<
+            DaIndexer<
+                Tx,
+                C,
+                V,
+                SS,
+                SamplingBackend,
+                SamplingNetworkAdapter,
+                SamplingRng,
+                SamplingStorage,
+                DaVerifierBackend,
+                DaVerifierNetwork,
+                DaVerifierStorage,
+                TimeBackend,
+                ApiAdapter,
+                RuntimeServiceId,
+                SIZE,
+            >,
+        >
+        + AsServiceId<
+            DaSamplingService<
+                SamplingBackend,
+                SamplingNetworkAdapter,
+                SamplingRng,
+                SamplingStorage,
+                DaVerifierBackend,
+                DaVerifierNetwork,
+                DaVerifierStorage,
+                ApiAdapter,
+                RuntimeServiceId,
+            >,
+        >,
+{
+    let blob_ids = get_blob_ids::<
+        Tx,
+        C,
+        V,
+        SS,
+        SamplingBackend,
+        SamplingNetworkAdapter,
+        SamplingRng,
+        SamplingStorage,
+        DaVerifierBackend,
+        DaVerifierNetwork,
+        DaVerifierStorage,
+        TimeBackend,
+        ApiAdapter,
+        RuntimeServiceId,
+        SIZE,
+    >(handle, app_id, range)
+    .await?;
+
+    let mut blobs = Vec::with_capacity(blob_ids.len());
+    for (index, blob_id) in blob_ids {
+        let Some(blob_id) = blob_id else {
+            return Err(format!("No blob indexed at index {:?}", index.as_ref()).into());
+        };
+        let data = retrieve_blob::<
+            SamplingBackend,
+            SamplingNetworkAdapter,
+            SamplingRng,
+            SamplingStorage,
+            DaVerifierBackend,
+            DaVerifierNetwork,
+            DaVerifierStorage,
+            ApiAdapter,
+            RuntimeServiceId,
+        >(handle, blob_id)
+        .await?;
+        blobs.push((index, data));
+    }
+
+    Ok(blobs)
+}
+
 pub async fn disperse_data<
     Backend,
     NetworkAdapter,
@@ -7,7 +7,7 @@ use nomos_da_sampling::{
 };
 use nomos_da_verifier::backend::VerifierBackend;
 use nomos_mempool::{
-    backend::mockpool::MockPool, network::NetworkAdapter, DaMempoolService, MempoolMsg,
+    backend::priority::PriorityPool, network::NetworkAdapter, DaMempoolService, MempoolMsg,
     TxMempoolService,
 };
 use nomos_network::backends::NetworkBackend;
@@ -35,7 +35,7 @@ where
     RuntimeServiceId: Debug
         + Sync
         + Display
-        + AsServiceId<TxMempoolService<A, MockPool<HeaderId, Item, Key>, RuntimeServiceId>>,
+        + AsServiceId<TxMempoolService<A, PriorityPool<HeaderId, Item, Key>, RuntimeServiceId>>,
 {
     let relay = handle.relay().await?;
     let (sender, receiver) = oneshot::channel();
@@ -99,7 +99,7 @@ where
         + AsServiceId<
             DaMempoolService<
                 A,
-                MockPool<HeaderId, Item, Key>,
+                PriorityPool<HeaderId, Item, Key>,
                 SamplingBackend,
                 SamplingAdapter,
                 SamplingRng,
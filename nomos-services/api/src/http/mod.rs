@@ -5,4 +5,5 @@ pub mod da;
 pub mod da_shares;
 pub mod libp2p;
 pub mod mempool;
+pub mod sdp;
 pub mod storage;
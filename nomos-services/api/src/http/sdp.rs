@@ -0,0 +1,145 @@
+use std::{
+    fmt::{Debug, Display},
+    hash::Hash,
+};
+
+use kzgrs_backend::dispersal::BlobInfo;
+use nomos_core::{da::BlobId, tx::Transaction};
+use nomos_da_sampling::backend::DaSamplingServiceBackend;
+use nomos_sdp::{
+    consensus::{adapters::cryptarchia::CryptarchiaConsensusAdapter, SdpTransaction},
+    SdpMsg, SdpService,
+};
+use nomos_sdp_core::{Locator, ProviderId, ServiceType};
+use nomos_storage::backends::{rocksdb::RocksBackend, StorageSerde};
+use overwatch::{overwatch::handle::OverwatchHandle, services::AsServiceId};
+use rand::{RngCore, SeedableRng};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tokio::sync::oneshot;
+
+use crate::http::{consensus::Cryptarchia, DynError};
+
+pub type Sdp<
+    Tx,
+    SS,
+    SamplingBackend,
+    SamplingNetworkAdapter,
+    SamplingRng,
+    SamplingStorage,
+    DaVerifierBackend,
+    DaVerifierNetwork,
+    DaVerifierStorage,
+    TimeBackend,
+    ApiAdapter,
+    RuntimeServiceId,
+    const SIZE: usize,
+> = SdpService<
+    RocksBackend<SS>,
+    CryptarchiaConsensusAdapter<
+        Cryptarchia<
+            Tx,
+            SS,
+            SamplingBackend,
+            SamplingNetworkAdapter,
+            SamplingRng,
+            SamplingStorage,
+            DaVerifierBackend,
+            DaVerifierNetwork,
+            DaVerifierStorage,
+            TimeBackend,
+            ApiAdapter,
+            RuntimeServiceId,
+            SIZE,
+        >,
+        Tx,
+        BlobInfo,
+    >,
+    RuntimeServiceId,
+>;
+
+pub async fn active_providers<
+    'a,
+    Tx,
+    SS,
+    SamplingBackend,
+    SamplingNetworkAdapter,
+    SamplingRng,
+    SamplingStorage,
+    DaVerifierBackend,
+    DaVerifierNetwork,
+    DaVerifierStorage,
+    TimeBackend,
+    ApiAdapter,
+    RuntimeServiceId,
+    const SIZE: usize,
+>(
+    handle: &'a OverwatchHandle<RuntimeServiceId>,
+    service_type: ServiceType,
+) -> Result<Vec<(ProviderId, Vec<Locator>)>, DynError>
+where
+    Tx: Transaction
+        + SdpTransaction
+        + Eq
+        + Clone
+        + Debug
+        + Hash
+        + Serialize
+        + DeserializeOwned
+        + Send
+        + Sync
+        + 'static,
+    <Tx as Transaction>::Hash:
+        std::cmp::Ord + Debug + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static,
+    <Tx as SdpTransaction>::Metadata: Clone + Send + Sync + 'static,
+    <Tx as SdpTransaction>::Proof: Clone + Send + Sync + 'static,
+    SS: StorageSerde + Send + Sync + 'static,
+    SamplingRng: SeedableRng + RngCore,
+    SamplingBackend: DaSamplingServiceBackend<SamplingRng, BlobId = BlobId> + Send,
+    SamplingBackend::Settings: Clone,
+    SamplingBackend::Share: Debug + 'static,
+    SamplingBackend::BlobId: Debug + 'static,
+    SamplingNetworkAdapter: nomos_da_sampling::network::NetworkAdapter<RuntimeServiceId>,
+    SamplingStorage: nomos_da_sampling::storage::DaStorageAdapter<RuntimeServiceId>,
+    DaVerifierStorage: nomos_da_verifier::storage::DaStorageAdapter<RuntimeServiceId>,
+    DaVerifierBackend: nomos_da_verifier::backend::VerifierBackend + Send + 'static,
+    DaVerifierBackend::Settings: Clone,
+    DaVerifierNetwork: nomos_da_verifier::network::NetworkAdapter<RuntimeServiceId>,
+    DaVerifierNetwork::Settings: Clone,
+    TimeBackend: nomos_time::backends::TimeBackend,
+    TimeBackend::Settings: Clone + Send + Sync,
+    ApiAdapter: nomos_da_sampling::api::ApiAdapter + Send + Sync,
+    RuntimeServiceId: Debug
+        + Sync
+        + Display
+        + 'static
+        + AsServiceId<
+            Sdp<
+                Tx,
+                SS,
+                SamplingBackend,
+                SamplingNetworkAdapter,
+                SamplingRng,
+                SamplingStorage,
+                DaVerifierBackend,
+                DaVerifierNetwork,
+                DaVerifierStorage,
+                TimeBackend,
+                ApiAdapter,
+                RuntimeServiceId,
+                SIZE,
+            >,
+        >,
+{
+    let relay = handle.relay().await?;
+    let (reply_channel, receiver) = oneshot::channel();
+    relay
+        .send(SdpMsg::GetActiveProviders {
+            service_type,
+            reply_channel,
+        })
+        .await
+        .map_err(|(e, _)| e)?;
+
+    // provider ids are not valid map keys in every format
+    Ok(receiver.await??.into_iter().collect())
+}
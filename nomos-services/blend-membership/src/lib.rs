@@ -0,0 +1,125 @@
+use std::{collections::HashMap, marker::PhantomData};
+
+use libp2p::{identity::ed25519, PeerId};
+use nomos_blend_service::{
+    backends::BlendBackend, encryption_public_key, network::NetworkAdapter, BlendService,
+    DeclaredNode, ServiceMessage,
+};
+use nomos_sdp::membership::{MembershipAdapter, MembershipRelay, SessionMembershipService};
+use nomos_sdp_core::{Locator, ProviderId, ServiceType};
+use overwatch::{services::ServiceData, DynError};
+use tracing::warn;
+
+/// Service keeping the blend network membership in sync with the
+/// `BlendNetwork` providers declared through SDP.
+///
+/// The membership is the providers active at the start of the current
+/// session, also when the service starts or catches up in the middle of it,
+/// so all nodes choose the blend layers among the same nodes.
+pub type BlendMembershipService<Backend, Network, Sdp, RuntimeServiceId> =
+    SessionMembershipService<BlendMembershipAdapter<Backend, Network>, Sdp, RuntimeServiceId>;
+
+/// Applies the `BlendNetwork` providers to the blend network membership.
+pub struct BlendMembershipAdapter<Backend, Network> {
+    _phantom: PhantomData<fn() -> (Backend, Network)>,
+}
+
+#[async_trait::async_trait]
+impl<Backend, Network, RuntimeServiceId> MembershipAdapter<RuntimeServiceId>
+    for BlendMembershipAdapter<Backend, Network>
+where
+    Backend: BlendBackend<RuntimeServiceId> + Send + 'static,
+    Network: NetworkAdapter<RuntimeServiceId> + Send + 'static,
+    Network::BroadcastSettings: Send,
+    BlendService<Backend, Network, RuntimeServiceId>:
+        ServiceData<Message = ServiceMessage<Network::BroadcastSettings>>,
+    RuntimeServiceId: Send + Sync + 'static,
+{
+    type Service = BlendService<Backend, Network, RuntimeServiceId>;
+
+    const SERVICE_TYPE: ServiceType = ServiceType::BlendNetwork;
+
+    /// Replaces the blend network membership with the given providers. The
+    /// membership is left untouched if none of the providers can be reached.
+    async fn update_membership(
+        blend_relay: &MembershipRelay<Self, RuntimeServiceId>,
+        providers: &HashMap<ProviderId, Vec<Locator>>,
+    ) -> Result<(), DynError> {
+        let nodes = declared_nodes(providers);
+        if nodes.is_empty() {
+            warn!("No active blend providers, keeping the current membership");
+            return Ok(());
+        }
+
+        blend_relay
+            .send(ServiceMessage::UpdateMembership(nodes))
+            .await
+            .map_err(|(e, _)| Box::new(e) as DynError)
+    }
+}
+
+/// Maps the providers to blend nodes.
+///
+/// Provider ids are the ed25519 public keys of the nodes, which their peer id
+/// and the x25519 public key used for the blend message encryption are derived
+/// from. Their first locator is the address the node listens on. Providers
+/// with an invalid key or without locators are skipped.
+fn declared_nodes(providers: &HashMap<ProviderId, Vec<Locator>>) -> Vec<DeclaredNode> {
+    providers
+        .iter()
+        .filter_map(|(provider_id, locators)| {
+            let (Ok(node_key), Some(public_key)) = (
+                ed25519::PublicKey::try_from_bytes(&provider_id.0),
+                encryption_public_key(&provider_id.0),
+            ) else {
+                warn!("Invalid blend provider id {provider_id:?}");
+                return None;
+            };
+            let peer_id = PeerId::from_public_key(&node_key.into());
+            let Ok(address) = locators.first()?.addr().clone().with_p2p(peer_id) else {
+                warn!("Blend provider {provider_id:?} locator belongs to another node");
+                return None;
+            };
+            Some(DeclaredNode {
+                address,
+                public_key,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use libp2p::Multiaddr;
+    use nomos_blend_service::encryption_private_key;
+
+    use super::*;
+
+    #[test]
+    fn test_declared_nodes() {
+        let secret_key = [1; 32];
+        let keypair =
+            ed25519::Keypair::from(ed25519::SecretKey::try_from_bytes(secret_key).unwrap());
+        let peer_id = PeerId::from_public_key(&keypair.public().into());
+        let address: Multiaddr = "/ip4/127.0.0.1/udp/3000/quic-v1".parse().unwrap();
+        let providers = HashMap::from([
+            (
+                ProviderId(keypair.public().to_bytes()),
+                vec![Locator::new(address.clone())],
+            ),
+            // provider without locators
+            (ProviderId([2; 32]), Vec::new()),
+        ]);
+
+        let nodes = declared_nodes(&providers);
+
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0].address, address.with_p2p(peer_id).unwrap());
+        // the node decrypts messages with the key derived from its node key
+        let private_key = x25519_dalek::StaticSecret::from(encryption_private_key(&secret_key));
+        assert_eq!(
+            nodes[0].public_key,
+            x25519_dalek::PublicKey::from(&private_key).to_bytes()
+        );
+    }
+}
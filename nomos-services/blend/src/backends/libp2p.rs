@@ -2,18 +2,20 @@ use std::{collections::HashSet, pin::Pin, time::Duration};
 
 use async_trait::async_trait;
 use futures::{Stream, StreamExt};
+use key_management_system::{retrieve_ed25519_key, NodeKMSRelay};
 use libp2p::{
     allow_block_list::BlockedPeers,
     connection_limits::ConnectionLimits,
     identity::{ed25519, Keypair},
+    multiaddr::Protocol,
     swarm::SwarmEvent,
     Multiaddr, PeerId, Swarm, SwarmBuilder,
 };
 use nomos_blend::{conn_maintenance::ConnectionMonitorSettings, membership::Membership};
 use nomos_blend_message::sphinx::SphinxMessage;
 use nomos_blend_network::TokioIntervalStreamProvider;
-use nomos_libp2p::{secret_key_serde, NetworkBehaviour};
-use overwatch::overwatch::handle::OverwatchHandle;
+use nomos_libp2p::NetworkBehaviour;
+use overwatch::{overwatch::handle::OverwatchHandle, DynError};
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 use tokio::sync::{broadcast, mpsc};
@@ -30,9 +32,9 @@ pub struct Libp2pBlendBackend {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Libp2pBlendBackendSettings {
     pub listening_address: Multiaddr,
-    // A key for deriving PeerId and establishing secure connections (TLS 1.3 by QUIC)
-    #[serde(with = "secret_key_serde", default = "ed25519::SecretKey::generate")]
-    pub node_key: ed25519::SecretKey,
+    /// Id of the ed25519 key in the key management service, for deriving
+    /// PeerId and establishing secure connections (TLS 1.3 by QUIC)
+    pub node_key_id: String,
     pub peering_degree: u16,
     pub max_peering_degree: u16,
     pub conn_monitor: Option<ConnectionMonitorSettings>,
@@ -45,34 +47,37 @@ impl<RuntimeServiceId> BlendBackend<RuntimeServiceId> for Libp2pBlendBackend {
     type Settings = Libp2pBlendBackendSettings;
     type NodeId = PeerId;
 
-    fn new<R>(
+    async fn new<R>(
         config: Self::Settings,
         overwatch_handle: OverwatchHandle<RuntimeServiceId>,
         membership: Membership<Self::NodeId, SphinxMessage>,
         rng: R,
-    ) -> Self
+        kms: NodeKMSRelay,
+    ) -> Result<Self, DynError>
     where
         R: RngCore + Send + 'static,
     {
+        let mut node_key = retrieve_ed25519_key(&kms, config.node_key_id.clone()).await?;
+        let node_key = ed25519::SecretKey::try_from_bytes(node_key.as_mut_slice())?;
         let (swarm_message_sender, swarm_message_receiver) = mpsc::channel(CHANNEL_SIZE);
         let (incoming_message_sender, _) = broadcast::channel(CHANNEL_SIZE);
 
         let mut swarm = BlendSwarm::new(
             config,
+            node_key,
             membership,
             rng,
             swarm_message_receiver,
             incoming_message_sender.clone(),
         );
-
         overwatch_handle.runtime().spawn(async move {
             swarm.run().await;
         });
 
-        Self {
+        Ok(Self {
             swarm_message_sender,
             incoming_message_sender,
-        }
+        })
     }
 
     async fn publish(&self, msg: Vec<u8>) {
@@ -85,6 +90,23 @@ impl<RuntimeServiceId> BlendBackend<RuntimeServiceId> for Libp2pBlendBackend {
         }
     }
 
+    async fn update_membership(&self, membership: Membership<Self::NodeId, SphinxMessage>) {
+        if let Err(e) = self
+            .swarm_message_sender
+            .send(BlendSwarmMessage::UpdateMembership(membership))
+            .await
+        {
+            tracing::error!("Failed to send membership update to BlendSwarm: {e}");
+        }
+    }
+
+    fn node_id(address: &Multiaddr) -> Option<Self::NodeId> {
+        address.iter().find_map(|protocol| match protocol {
+            Protocol::P2p(peer_id) => Some(peer_id),
+            _ => None,
+        })
+    }
+
     fn listen_to_incoming_messages(&mut self) -> Pin<Box<dyn Stream<Item = Vec<u8>> + Send>> {
         Box::pin(
             BroadcastStream::new(self.incoming_message_sender.subscribe())
@@ -136,6 +158,7 @@ impl BlendBehaviour {
 #[derive(Debug)]
 pub enum BlendSwarmMessage {
     Publish(Vec<u8>),
+    UpdateMembership(Membership<PeerId, SphinxMessage>),
 }
 
 impl<R> BlendSwarm<R>
@@ -144,12 +167,13 @@ where
 {
     fn new(
         config: Libp2pBlendBackendSettings,
+        node_key: ed25519::SecretKey,
         membership: Membership<PeerId, SphinxMessage>,
         mut rng: R,
         swarm_messages_receiver: mpsc::Receiver<BlendSwarmMessage>,
         incoming_message_sender: broadcast::Sender<Vec<u8>>,
     ) -> Self {
-        let keypair = Keypair::from(ed25519::Keypair::from(config.node_key.clone()));
+        let keypair = Keypair::from(ed25519::Keypair::from(node_key));
         let mut swarm = SwarmBuilder::with_existing_identity(keypair)
             .with_tokio()
             .with_quic()
@@ -218,7 +242,30 @@ where
                     tracing::info!(histogram.sent_data = msg_size as u64);
                 }
             }
+            BlendSwarmMessage::UpdateMembership(membership) => {
+                self.update_membership(membership);
+            }
+        }
+    }
+
+    /// Replace the membership, disconnecting from the peers that left it.
+    /// New peers are dialed as the connections are closed, to maintain the
+    /// peering degree.
+    fn update_membership(&mut self, membership: Membership<PeerId, SphinxMessage>) {
+        self.membership = membership;
+        let removed_peers: Vec<PeerId> = self
+            .swarm
+            .connected_peers()
+            .filter(|peer_id| !self.membership.contains_remote_node(peer_id))
+            .copied()
+            .collect();
+        for peer_id in removed_peers {
+            tracing::debug!("Disconnecting from peer {peer_id} which left the blend network");
+            if self.swarm.disconnect_peer_id(peer_id).is_err() {
+                tracing::debug!("Peer {peer_id} was already disconnected");
+            }
         }
+        self.check_and_dial_new_peers();
     }
 
     #[expect(
@@ -4,9 +4,11 @@ pub mod libp2p;
 use std::{fmt::Debug, pin::Pin};
 
 use futures::Stream;
+use key_management_system::NodeKMSRelay;
+use libp2p::Multiaddr;
 use nomos_blend::membership::Membership;
 use nomos_blend_message::sphinx::SphinxMessage;
-use overwatch::overwatch::handle::OverwatchHandle;
+use overwatch::{overwatch::handle::OverwatchHandle, DynError};
 use rand::RngCore;
 
 /// A trait for blend backends that send messages to the blend network.
@@ -15,16 +17,25 @@ pub trait BlendBackend<RuntimeServiceId> {
     type Settings: Clone + Debug + Send + Sync + 'static;
     type NodeId: Clone + Debug + Send + Sync + 'static;
 
-    fn new<R>(
+    /// Creates the backend, which retrieves the keys it needs through `kms`.
+    async fn new<R>(
         config: Self::Settings,
         overwatch_handle: OverwatchHandle<RuntimeServiceId>,
         membership: Membership<Self::NodeId, SphinxMessage>,
         rng: R,
-    ) -> Self
+        kms: NodeKMSRelay,
+    ) -> Result<Self, DynError>
     where
-        R: RngCore + Send + 'static;
+        R: RngCore + Send + 'static,
+        Self: Sized;
     /// Publish a message to the blend network.
     async fn publish(&self, msg: Vec<u8>);
+    /// Replace the membership used to maintain connections with the blend
+    /// network.
+    async fn update_membership(&self, membership: Membership<Self::NodeId, SphinxMessage>);
+    /// Identifier of the node listening on the given address, if the address
+    /// carries one.
+    fn node_id(address: &Multiaddr) -> Option<Self::NodeId>;
     /// Listen to messages received from the blend network.
     fn listen_to_incoming_messages(&mut self) -> Pin<Box<dyn Stream<Item = Vec<u8>> + Send>>;
 }
@@ -4,12 +4,15 @@ pub mod network;
 use std::{
     fmt::{Debug, Display},
     hash::Hash,
+    marker::PhantomData,
     time::Duration,
 };
 
 use async_trait::async_trait;
 use backends::BlendBackend;
 use futures::StreamExt;
+use key_management_system::{retrieve_ed25519_key, NodeKMSRelay, NodeKMSService};
+use libp2p::Multiaddr;
 use network::NetworkAdapter;
 use nomos_blend::{
     cover_traffic::{CoverTraffic, CoverTrafficSettings},
@@ -17,6 +20,7 @@ use nomos_blend::{
     message_blend::{
         crypto::CryptographicProcessor, temporal::TemporalScheduler,
         CryptographicProcessorSettings, MessageBlendExt, MessageBlendSettings,
+        TemporalSchedulerSettings,
     },
     persistent_transmission::{
         PersistentTransmissionExt, PersistentTransmissionSettings, PersistentTransmissionStream,
@@ -32,7 +36,7 @@ use overwatch::{
         state::{NoOperator, NoState},
         AsServiceId, ServiceCore, ServiceData,
     },
-    OpaqueServiceStateHandle,
+    DynError, OpaqueServiceStateHandle,
 };
 use rand::SeedableRng;
 use rand_chacha::ChaCha12Rng;
@@ -53,9 +57,8 @@ where
     Backend: BlendBackend<RuntimeServiceId> + 'static,
     Network: NetworkAdapter<RuntimeServiceId>,
 {
-    backend: Backend,
     service_state: OpaqueServiceStateHandle<Self, RuntimeServiceId>,
-    membership: Membership<Backend::NodeId, SphinxMessage>,
+    _backend: PhantomData<Backend>,
 }
 
 impl<Backend, Network, RuntimeServiceId> ServiceData
@@ -83,6 +86,7 @@ where
     Network::BroadcastSettings:
         Clone + Debug + Serialize + DeserializeOwned + Send + Sync + 'static,
     RuntimeServiceId: AsServiceId<NetworkService<Network::Backend, RuntimeServiceId>>
+        + AsServiceId<NodeKMSService<RuntimeServiceId>>
         + AsServiceId<Self>
         + Clone
         + Debug
@@ -95,28 +99,31 @@ where
         service_state: OpaqueServiceStateHandle<Self, RuntimeServiceId>,
         _init_state: Self::State,
     ) -> Result<Self, overwatch::DynError> {
-        let blend_config = service_state.settings_reader.get_updated_settings();
         Ok(Self {
-            backend: <Backend as BlendBackend<RuntimeServiceId>>::new(
-                service_state.settings_reader.get_updated_settings().backend,
-                service_state.overwatch_handle.clone(),
-                blend_config.membership(),
-                ChaCha12Rng::from_entropy(),
-            ),
             service_state,
-            membership: blend_config.membership(),
+            _backend: PhantomData,
         })
     }
 
-    async fn run(mut self) -> Result<(), overwatch::DynError> {
-        let Self {
-            service_state,
-            mut backend,
-            membership,
-        } = self;
+    async fn run(self) -> Result<(), overwatch::DynError> {
+        let Self { service_state, .. } = self;
         let blend_config = service_state.settings_reader.get_updated_settings();
+        let kms = service_state
+            .overwatch_handle
+            .relay::<NodeKMSService<RuntimeServiceId>>()
+            .await?;
+        let message_blend_settings = blend_config.message_blend.settings(&kms).await?;
+        let mut membership = blend_config.membership(&message_blend_settings);
+        let mut backend = <Backend as BlendBackend<RuntimeServiceId>>::new(
+            blend_config.backend.clone(),
+            service_state.overwatch_handle.clone(),
+            membership.clone(),
+            ChaCha12Rng::from_entropy(),
+            kms,
+        )
+        .await?;
         let mut cryptographic_processor = CryptographicProcessor::new(
-            blend_config.message_blend.cryptographic_processor.clone(),
+            message_blend_settings.cryptographic_processor.clone(),
             membership.clone(),
             ChaCha12Rng::from_entropy(),
         );
@@ -141,11 +148,11 @@ where
 
         // tier 2 blend
         let temporal_scheduler = TemporalScheduler::new(
-            blend_config.message_blend.temporal_processor,
+            message_blend_settings.temporal_processor,
             ChaCha12Rng::from_entropy(),
         );
         let mut blend_messages = backend.listen_to_incoming_messages().blend(
-            blend_config.message_blend.clone(),
+            message_blend_settings.clone(),
             membership.clone(),
             temporal_scheduler,
             ChaCha12Rng::from_entropy(),
@@ -155,20 +162,14 @@ where
         let mut cover_traffic: CoverTraffic<_, _, SphinxMessage> = CoverTraffic::new(
             blend_config.cover_traffic.cover_traffic_settings(
                 &membership,
-                &blend_config.message_blend.cryptographic_processor,
+                &message_blend_settings.cryptographic_processor,
             ),
             blend_config.cover_traffic.epoch_stream(),
             blend_config.cover_traffic.slot_stream(),
         );
 
         // local messages, are bypassed and send immediately
-        let mut local_messages =
-            service_state
-                .inbound_relay
-                .map(|ServiceMessage::Blend(message)| {
-                    wire::serialize(&message)
-                        .expect("Message from internal services should not fail to serialize")
-                });
+        let mut inbound_relay = service_state.inbound_relay;
 
         let mut lifecycle_stream = service_state.lifecycle_handle.message_stream();
         loop {
@@ -200,8 +201,34 @@ where
                 Some(msg) = cover_traffic.next() => {
                     Self::wrap_and_send_to_persistent_transmission(&msg, &mut cryptographic_processor, &persistent_sender);
                 }
-                Some(msg) = local_messages.next() => {
-                    Self::wrap_and_send_to_persistent_transmission(&msg, &mut cryptographic_processor, &persistent_sender);
+                Some(msg) = inbound_relay.next() => {
+                    match msg {
+                        ServiceMessage::Blend(message) => {
+                            let msg = wire::serialize(&message)
+                                .expect("Message from internal services should not fail to serialize");
+                            Self::wrap_and_send_to_persistent_transmission(&msg, &mut cryptographic_processor, &persistent_sender);
+                        }
+                        // Messages already wrapped or being blended are not affected by the
+                        // update, as the membership is only used to choose the blend layers of
+                        // new messages.
+                        ServiceMessage::UpdateMembership(nodes) => {
+                            let Some(new_membership) = Self::declared_membership(&membership, nodes) else {
+                                tracing::warn!("No remote blend nodes declared, keeping the current membership");
+                                continue;
+                            };
+                            // the providers of the current session are sent again when the
+                            // membership service restarts or misses session updates
+                            if new_membership.has_same_remote_nodes(&membership) {
+                                tracing::debug!("Blend membership unchanged");
+                                continue;
+                            }
+                            tracing::info!("Updating blend membership to {} nodes", new_membership.size());
+                            membership = new_membership;
+                            cryptographic_processor.update_membership(membership.clone());
+                            cover_traffic.update_network_size(membership.size());
+                            backend.update_membership(membership.clone()).await;
+                        }
+                    }
                 }
                 Some(msg) = lifecycle_stream.next() => {
                     if lifecycle::should_stop_service::<Self, RuntimeServiceId>(&msg) {
@@ -224,6 +251,37 @@ where
     Network: NetworkAdapter<RuntimeServiceId>,
     Network::BroadcastSettings: Clone + Debug + Serialize + DeserializeOwned,
 {
+    /// Membership with the remote nodes replaced by the declared ones, or
+    /// `None` if no remote node is left. Declared nodes whose address does
+    /// not identify them in the backend are skipped.
+    fn declared_membership(
+        membership: &Membership<Backend::NodeId, SphinxMessage>,
+        nodes: Vec<DeclaredNode>,
+    ) -> Option<Membership<Backend::NodeId, SphinxMessage>> {
+        let nodes = nodes
+            .into_iter()
+            .filter_map(
+                |DeclaredNode {
+                     address,
+                     public_key,
+                 }| {
+                    let Some(id) = <Backend as BlendBackend<RuntimeServiceId>>::node_id(&address)
+                    else {
+                        tracing::warn!("Blend node address {address} does not identify the node");
+                        return None;
+                    };
+                    Some(Node {
+                        id,
+                        address,
+                        public_key,
+                    })
+                },
+            )
+            .collect();
+        let membership = membership.with_remote_nodes(nodes);
+        (membership.size() > 1).then_some(membership)
+    }
+
     fn wrap_and_send_to_persistent_transmission(
         message: &[u8],
         cryptographic_processor: &mut CryptographicProcessor<
@@ -249,12 +307,38 @@ where
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct BlendConfig<BackendSettings, BackendNodeId> {
     pub backend: BackendSettings,
-    pub message_blend: MessageBlendSettings<SphinxMessage>,
+    pub message_blend: MessageBlendConfig,
     pub persistent_transmission: PersistentTransmissionSettings,
     pub cover_traffic: CoverTrafficExtSettings,
     pub membership: Vec<Node<BackendNodeId, <SphinxMessage as BlendMessage>::PublicKey>>,
 }
 
+/// [`MessageBlendSettings`] with the id of the ed25519 node key in the key
+/// management service in place of the x25519 private key, which is derived
+/// from it with [`encryption_private_key`].
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct MessageBlendConfig {
+    pub cryptographic_processor: CryptographicProcessorSettings<String>,
+    pub temporal_processor: TemporalSchedulerSettings,
+}
+
+impl MessageBlendConfig {
+    async fn settings(
+        &self,
+        kms: &NodeKMSRelay,
+    ) -> Result<MessageBlendSettings<SphinxMessage>, DynError> {
+        let node_key =
+            retrieve_ed25519_key(kms, self.cryptographic_processor.private_key.clone()).await?;
+        Ok(MessageBlendSettings {
+            cryptographic_processor: CryptographicProcessorSettings {
+                private_key: encryption_private_key(&node_key),
+                num_blend_layers: self.cryptographic_processor.num_blend_layers,
+            },
+            temporal_processor: self.temporal_processor,
+        })
+    }
+}
+
 #[serde_with::serde_as]
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct CoverTrafficExtSettings {
@@ -317,9 +401,12 @@ impl<BackendSettings, BackendNodeId> BlendConfig<BackendSettings, BackendNodeId>
 where
     BackendNodeId: Clone + Hash + Eq,
 {
-    fn membership(&self) -> Membership<BackendNodeId, SphinxMessage> {
+    fn membership(
+        &self,
+        message_blend_settings: &MessageBlendSettings<SphinxMessage>,
+    ) -> Membership<BackendNodeId, SphinxMessage> {
         let public_key = x25519_dalek::PublicKey::from(&x25519_dalek::StaticSecret::from(
-            self.message_blend.cryptographic_processor.private_key,
+            message_blend_settings.cryptographic_processor.private_key,
         ))
         .to_bytes();
         Membership::new(self.membership.clone(), &public_key)
@@ -332,6 +419,42 @@ pub enum ServiceMessage<BroadcastSettings> {
     /// To send a message to the blend network and eventually broadcast it to
     /// the [`NetworkService`].
     Blend(NetworkMessage<BroadcastSettings>),
+    /// To replace the remote nodes of the blend network with the ones
+    /// declared for a new session.
+    UpdateMembership(Vec<DeclaredNode>),
+}
+
+/// The x25519 private key blend messages are decrypted with, derived from the
+/// ed25519 secret key identifying the node.
+///
+/// Nodes are declared on chain with their ed25519 public key, so peers derive
+/// the matching public key with [`encryption_public_key`].
+#[must_use]
+pub fn encryption_private_key(
+    node_secret_key: &[u8; 32],
+) -> <SphinxMessage as BlendMessage>::PrivateKey {
+    ed25519_dalek::SigningKey::from_bytes(node_secret_key).to_scalar_bytes()
+}
+
+/// The x25519 public key blend messages are encrypted with for the node
+/// identified by the ed25519 `node_public_key`, or `None` if it is not a
+/// valid key.
+#[must_use]
+pub fn encryption_public_key(
+    node_public_key: &[u8; 32],
+) -> Option<<SphinxMessage as BlendMessage>::PublicKey> {
+    ed25519_dalek::VerifyingKey::from_bytes(node_public_key)
+        .ok()
+        .map(|key| key.to_montgomery().to_bytes())
+}
+
+/// A blend node declared on chain.
+#[derive(Clone, Debug)]
+pub struct DeclaredNode {
+    /// A listening address, which must identify the node in the blend backend
+    pub address: Multiaddr,
+    /// A public key used for the blend message encryption
+    pub public_key: <SphinxMessage as BlendMessage>::PublicKey,
 }
 
 /// A message that is sent to the blend network.
@@ -1,19 +1,32 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use cl::{
-    note::NoteWitness,
+    note::{NoteCommitment, NoteWitness},
     nullifier::{Nullifier, NullifierSecret},
     InputWitness,
 };
-use cryptarchia_engine::Slot;
-use nomos_core::{header::HeaderId, proofs::leader_proof::Risc0LeaderProof};
-use nomos_ledger::{EpochState, NoteTree};
+use cryptarchia_engine::{Branch, Branches, Epoch, Slot};
+use key_management_system::{retrieve_nullifier_secret, NodeKMSRelay};
+use nomos_core::{
+    header::{Header, HeaderId},
+    proofs::leader_proof::Risc0LeaderProof,
+};
+use nomos_ledger::{leader_proof::LeaderProof as _, EpochState, LedgerState};
 use nomos_proof_statements::leadership::{LeaderPrivate, LeaderPublic};
+use overwatch::DynError;
 use serde::{Deserialize, Serialize};
 
 pub struct Leader {
     // for each block, the indexes in the note tree of the notes we control
     notes: HashMap<HeaderId, Vec<NoteWitness>>,
+    // for each block spending our notes, its slot and the nullifiers it
+    // reveals, which must not be reused in the other branches
+    nullified: HashMap<HeaderId, (Slot, Vec<Nullifier>)>,
+    // headers seen so far, whose leader proofs can be imported as orphan proofs
+    // in the branches that did not adopt them
+    headers: HashMap<HeaderId, Header>,
+    // notes added and removed at runtime, on top of the configured ones
+    notes_updates: LeaderNotesUpdates,
     nf_sk: NullifierSecret,
     config: nomos_ledger::Config,
 }
@@ -21,10 +34,67 @@ pub struct Leader {
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct LeaderConfig {
     pub notes: Vec<NoteWitness>,
-    // this is common to every note
+    /// Id of the nullifier secret in the key management service, which is
+    /// common to every note
+    pub nf_sk_id: String,
+}
+
+impl LeaderConfig {
+    /// Retrieves the nullifier secret of the notes from the key management
+    /// service.
+    pub async fn resolve(self, kms: &NodeKMSRelay) -> Result<LeaderKeys, DynError> {
+        Ok(LeaderKeys {
+            notes: self.notes,
+            nf_sk: retrieve_nullifier_secret(kms, self.nf_sk_id).await?,
+        })
+    }
+}
+
+/// The leader notes, with the nullifier secret they are controlled by.
+pub struct LeaderKeys {
+    pub notes: Vec<NoteWitness>,
     pub nf_sk: NullifierSecret,
 }
 
+/// Leader notes evolved on top of a tip, with the expected leadership in the
+/// epoch of the tip.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct LeaderNotesInfo {
+    pub tip: HeaderId,
+    pub epoch: Epoch,
+    pub notes: Vec<LeaderNoteInfo>,
+    /// Probability of winning a slot with at least one of the notes.
+    pub slot_leadership_probability: f64,
+    /// Expected number of slots won in the epoch.
+    pub expected_slots_per_epoch: f64,
+    /// Probability of winning at least one slot of the epoch.
+    pub epoch_leadership_probability: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct LeaderNoteInfo {
+    pub commitment: NoteCommitment,
+    pub value: u64,
+    /// Whether the note is part of the stake distribution of the epoch and
+    /// was not spent in any branch.
+    pub eligible: bool,
+    /// Probability of winning a slot with the note, `0` if not eligible.
+    pub slot_win_probability: f64,
+}
+
+/// Leader notes added and removed at runtime, which have to be applied again
+/// when the leader is recovered.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LeaderNotesUpdates {
+    /// Notes added, as they were provided.
+    pub added: Vec<NoteWitness>,
+    /// Commitments of the notes removed, including the evolutions known at
+    /// removal time.
+    pub removed: HashSet<NoteCommitment>,
+}
+
 impl Leader {
     pub fn new(
         header_id: HeaderId,
@@ -34,6 +104,9 @@ impl Leader {
     ) -> Self {
         Self {
             notes: HashMap::from([(header_id, header_notes)]),
+            nullified: HashMap::new(),
+            headers: HashMap::new(),
+            notes_updates: LeaderNotesUpdates::default(),
             nf_sk,
             config,
         }
@@ -41,48 +114,144 @@ impl Leader {
 
     pub fn from_genesis(
         genesis: HeaderId,
-        LeaderConfig { notes, nf_sk }: LeaderConfig,
+        LeaderKeys { notes, nf_sk }: LeaderKeys,
         config: nomos_ledger::Config,
     ) -> Self {
         Self::new(genesis, notes, nf_sk, config)
     }
 
-    // Signal that the chain extended with a new header, possibly evolving a leader
-    // notes in the process FIXME: this implementation does not delete old coins
-    // and will attempt to re-use a note in different forks,        we should
-    // use the orphan proofs mechanism to handle this.
-    pub fn follow_chain(&mut self, parent_id: HeaderId, id: HeaderId, to_evolve: Nullifier) {
-        if let Some(notes) = self.notes.get(&parent_id) {
-            let notes = notes
+    // Signal that the chain extended with a new header, evolving the leader notes
+    // spent by its leader proof or by the orphan proofs it imports.
+    // Notes spent in a branch are not used anymore in the other ones, until the
+    // proof spending them is imported there as well.
+    pub fn follow_chain(&mut self, header: &Header) {
+        let id = header.id();
+        if let Some(notes) = self.notes.get(&header.parent()) {
+            let mut notes = notes.clone();
+            let mut nullified = Vec::new();
+            // orphan proofs are applied before the leader proof of the block
+            let spent = header
+                .orphaned_proofs()
                 .iter()
-                .map(|note| {
+                .chain(std::iter::once(header))
+                .map(|header| header.leader_proof().nullifier());
+            for to_evolve in spent {
+                for note in &mut notes {
                     let note_cm = note.commit(self.nf_sk.commit());
                     if Nullifier::new(self.nf_sk, note_cm) == to_evolve {
-                        evolve(note, self.nf_sk)
-                    } else {
-                        *note
+                        nullified.push(to_evolve);
+                        *note = evolve(note, self.nf_sk);
                     }
-                })
-                .collect();
+                }
+            }
+            if !nullified.is_empty() {
+                self.nullified.insert(id, (header.slot(), nullified));
+            }
+            // evolutions of removed notes are not followed
+            notes.retain(|note| {
+                !self
+                    .notes_updates
+                    .removed
+                    .contains(&note.commit(self.nf_sk.commit()))
+            });
             self.notes.insert(id, notes);
         }
+        // headers importing orphan proofs cannot be imported themselves
+        if header.orphaned_proofs().is_empty() {
+            self.headers.insert(id, header.clone());
+        }
+    }
+
+    /// Headers from other branches whose leader proofs can be imported as
+    /// orphan proofs in a block proposed on top of `parent` at `slot`, in the
+    /// order they have to be applied.
+    ///
+    /// `state` is the ledger state of `parent`. Proofs already imported in it
+    /// are skipped, as well as proofs that depend on a block which cannot be
+    /// imported.
+    pub fn orphan_proofs_for(
+        &self,
+        branches: &Branches<HeaderId>,
+        parent: HeaderId,
+        slot: Slot,
+        state: &LedgerState,
+    ) -> Vec<Header> {
+        let mut candidates: Vec<&Header> = self
+            .headers
+            .values()
+            .filter(|header| header.slot() < slot)
+            .collect();
+        candidates.sort_by_key(|header| header.slot());
+        let Some(oldest) = candidates
+            .iter()
+            .filter_map(|header| branches.get(&header.parent()))
+            .map(Branch::slot)
+            .min()
+        else {
+            return Vec::new();
+        };
+
+        // blocks on the chain of the parent cannot be orphans, slots decrease
+        // along the chain so there is no need to go past the oldest parent of
+        // the candidates
+        let mut ancestors = HashSet::new();
+        let mut id = parent;
+        while let Some(branch) = branches.get(&id) {
+            ancestors.insert(id);
+            if branch.parent() == id || branch.slot() < oldest {
+                break;
+            }
+            id = branch.parent();
+        }
+
+        let mut orphans = Vec::new();
+        let mut imported = HashSet::new();
+        for header in candidates {
+            let id = header.id();
+            if ancestors.contains(&id) || state.is_nullified(&header.leader_proof().nullifier()) {
+                continue;
+            }
+            // the proof spends a note created by its parent, which must be known
+            // in the chain of the new block
+            let parent = header.parent();
+            let parent_known = ancestors.contains(&parent)
+                || imported.contains(&parent)
+                || self
+                    .headers
+                    .get(&parent)
+                    .is_some_and(|parent| state.is_nullified(&parent.leader_proof().nullifier()));
+            if parent_known {
+                imported.insert(id);
+                orphans.push(header.clone());
+            }
+        }
+        orphans
     }
 
+    /// Proves the leadership of `slot` on top of `parent`, whose ledger state
+    /// is `state`, with the first winning note tracked there.
     pub async fn build_proof_for(
         &self,
-        note_tree: &NoteTree,
+        state: &LedgerState,
         epoch_state: &EpochState,
         slot: Slot,
         parent: HeaderId,
     ) -> Option<Risc0LeaderProof> {
+        let note_tree = state.lead_commitments();
         let notes = self.notes.get(&parent)?;
         for note in notes {
             let note_commit = note.commit(self.nf_sk.commit());
-            let Some(index) = note_tree
-                .commitments()
-                .iter()
-                .position(|cm| cm == &note_commit)
-            else {
+            let nullifier = Nullifier::new(self.nf_sk, note_commit);
+            if self.is_nullified(&nullifier) {
+                tracing::debug!("skipping note already spent in another branch");
+                continue;
+            }
+            // e.g. spent by a transaction, which the leader does not follow
+            if state.is_nullified(&nullifier) {
+                tracing::debug!("skipping note already spent in the chain of the parent");
+                continue;
+            }
+            let Some(index) = note_tree.index_of(&note_commit) else {
                 continue;
             };
 
@@ -134,9 +303,212 @@ impl Leader {
         None
     }
 
+    /// Whether the note revealing `nullifier` was spent by a block in any
+    /// branch.
+    fn is_nullified(&self, nullifier: &Nullifier) -> bool {
+        self.nullified
+            .values()
+            .any(|(_, nullifiers)| nullifiers.contains(nullifier))
+    }
+
+    /// Forget the notes, headers and nullifiers tracked for blocks that have
+    /// been pruned. Notes spent only in pruned blocks can be used again.
+    pub fn prune_notes_at(&mut self, header_ids: &[HeaderId]) {
+        for header_id in header_ids {
+            self.notes.remove(header_id);
+            self.headers.remove(header_id);
+            self.nullified.remove(header_id);
+        }
+    }
+
+    /// Forget the headers and nullifiers up to `slot`, which is the slot of
+    /// the security block. Older blocks are either part of every branch or
+    /// pruned, so their proofs cannot be imported anymore and the notes they
+    /// spend are spent in the ledger state of every branch.
+    pub fn prune_headers_until(&mut self, slot: Slot) {
+        self.headers.retain(|_, header| header.slot() > slot);
+        self.nullified.retain(|_, (spent_at, _)| *spent_at > slot);
+    }
+
     pub(crate) fn notes(&self, header_id: &HeaderId) -> Option<&[NoteWitness]> {
         self.notes.get(header_id).map(Vec::as_slice)
     }
+
+    /// Notes tracked on top of `tip`, whose ledger state is `state`, with
+    /// their chances of winning the slots of the epoch of the tip.
+    #[must_use]
+    pub fn notes_info(&self, tip: HeaderId, state: &LedgerState) -> Option<LeaderNotesInfo> {
+        let notes = self.notes.get(&tip)?;
+        let epoch_state = state.epoch_state();
+        let note_tree = state.lead_commitments();
+        let notes: Vec<_> = notes
+            .iter()
+            .map(|note| {
+                let commitment = note.commit(self.nf_sk.commit());
+                let nullifier = Nullifier::new(self.nf_sk, commitment);
+                let eligible = note_tree.index_of(&commitment).is_some()
+                    && !self.is_nullified(&nullifier)
+                    && !state.is_nullified(&nullifier);
+                let slot_win_probability = if eligible {
+                    LeaderPublic::new(
+                        note_tree.root(),
+                        *epoch_state.nonce(),
+                        state.slot().into(),
+                        self.config.consensus_config.active_slot_coeff,
+                        epoch_state.total_stake(),
+                        nullifier,
+                        commitment,
+                    )
+                    .winning_probability(note.value)
+                } else {
+                    0.0
+                };
+                LeaderNoteInfo {
+                    commitment,
+                    value: note.value,
+                    eligible,
+                    slot_win_probability,
+                }
+            })
+            .collect();
+        // notes win slots independently of each other
+        let slot_leadership_probability = 1.0
+            - notes
+                .iter()
+                .map(|note| 1.0 - note.slot_win_probability)
+                .product::<f64>();
+        let epoch_length = self.config.epoch_length() as f64;
+        Some(LeaderNotesInfo {
+            tip,
+            epoch: epoch_state.epoch(),
+            notes,
+            slot_leadership_probability,
+            expected_slots_per_epoch: slot_leadership_probability * epoch_length,
+            epoch_leadership_probability: 1.0
+                - (1.0 - slot_leadership_probability).powf(epoch_length),
+        })
+    }
+
+    pub(crate) const fn notes_updates(&self) -> &LeaderNotesUpdates {
+        &self.notes_updates
+    }
+
+    /// Start leading with `note` on top of every known block, from its first
+    /// evolution not spent in the ledger state of the block given by
+    /// `states`. Returns `false` if the note was already tracked everywhere.
+    pub fn add_note<'state>(
+        &mut self,
+        note: NoteWitness,
+        states: impl Fn(&HeaderId) -> Option<&'state LedgerState>,
+    ) -> bool {
+        let nf_sk = self.nf_sk;
+        let commitment = note.commit(nf_sk.commit());
+        // the note and its future evolutions are not removed anymore
+        let mut evolved = note;
+        while self
+            .notes_updates
+            .removed
+            .remove(&evolved.commit(self.nf_sk.commit()))
+        {
+            evolved = evolve(&evolved, self.nf_sk);
+        }
+        let mut added = false;
+        for (id, notes) in &mut self.notes {
+            let note = states(id).map_or(note, |state| unspent_evolution(note, nf_sk, state));
+            let note_commitment = note.commit(nf_sk.commit());
+            if !notes
+                .iter()
+                .any(|tracked| tracked.commit(nf_sk.commit()) == note_commitment)
+            {
+                notes.push(note);
+                added = true;
+            }
+        }
+        if !self
+            .notes_updates
+            .added
+            .iter()
+            .any(|tracked| tracked.commit(self.nf_sk.commit()) == commitment)
+        {
+            self.notes_updates.added.push(note);
+        }
+        added
+    }
+
+    /// Stop leading with the note committed to by `commitment`, together with
+    /// its ancestors and evolutions tracked in any block. Returns `false` if
+    /// the note is not tracked.
+    pub fn remove_note(&mut self, commitment: &NoteCommitment) -> bool {
+        let pk = self.nf_sk.commit();
+        let tracked: HashSet<NoteCommitment> = self
+            .notes
+            .values()
+            .flatten()
+            .chain(&self.notes_updates.added)
+            .map(|note| note.commit(pk))
+            .collect();
+        if !tracked.contains(commitment) {
+            return false;
+        }
+        // a note and its evolutions form a chain, which is followed from every
+        // tracked note as far as the evolutions are tracked
+        let mut removed = HashSet::new();
+        for note in self
+            .notes
+            .values()
+            .flatten()
+            .chain(&self.notes_updates.added)
+        {
+            let mut lineage = Vec::new();
+            let mut note = *note;
+            loop {
+                let note_cm = note.commit(pk);
+                if !tracked.contains(&note_cm) || lineage.contains(&note_cm) {
+                    break;
+                }
+                lineage.push(note_cm);
+                note = evolve(&note, self.nf_sk);
+            }
+            if lineage.contains(commitment) {
+                removed.extend(lineage);
+            }
+        }
+        self.notes_updates
+            .added
+            .retain(|note| !removed.contains(&note.commit(pk)));
+        self.notes_updates.removed.extend(removed);
+        self.strip_removed_notes();
+        true
+    }
+
+    /// Apply again the notes updates of a previous run. Added notes are
+    /// expected to be part of the notes the leader was created with.
+    pub fn restore_notes_updates(&mut self, notes_updates: LeaderNotesUpdates) {
+        self.notes_updates = notes_updates;
+        self.strip_removed_notes();
+    }
+
+    /// Apply again the notes updates of a previous run to a leader recovered
+    /// at a block whose ledger state is `state`. Added notes are tracked from
+    /// their first evolution not spent in `state`, unless the leader already
+    /// tracks them.
+    pub fn restore_notes_updates_at(
+        &mut self,
+        notes_updates: LeaderNotesUpdates,
+        state: &LedgerState,
+    ) {
+        for note in &notes_updates.added {
+            self.add_note(*note, |_| Some(state));
+        }
+        self.restore_notes_updates(notes_updates);
+    }
+
+    fn strip_removed_notes(&mut self) {
+        let pk = self.nf_sk.commit();
+        for notes in self.notes.values_mut() {
+            notes.retain(|note| !self.notes_updates.removed.contains(&note.commit(pk)));
+        }
+    }
 }
 
 fn evolve(note: &NoteWitness, nf_sk: NullifierSecret) -> NoteWitness {
@@ -145,3 +517,202 @@ fn evolve(note: &NoteWitness, nf_sk: NullifierSecret) -> NoteWitness {
         ..*note
     }
 }
+
+// first evolution of `note` which is not spent in `state`
+fn unspent_evolution(
+    mut note: NoteWitness,
+    nf_sk: NullifierSecret,
+    state: &LedgerState,
+) -> NoteWitness {
+    while state.is_nullified(&Nullifier::new(nf_sk, note.commit(nf_sk.commit()))) {
+        note = evolve(&note, nf_sk);
+    }
+    note
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZero;
+
+    use nomos_core::staking::NMO_UNIT;
+    use nomos_ledger::leader_proof::LeaderProof;
+    use rand::thread_rng;
+
+    use super::*;
+
+    const NF_SK: NullifierSecret = NullifierSecret([1; 16]);
+
+    fn config() -> nomos_ledger::Config {
+        nomos_ledger::Config {
+            epoch_config: cryptarchia_engine::EpochConfig {
+                epoch_stake_distribution_stabilization: NonZero::new(3).unwrap(),
+                epoch_period_nonce_buffer: NonZero::new(3).unwrap(),
+                epoch_period_nonce_stabilization: NonZero::new(4).unwrap(),
+            },
+            consensus_config: cryptarchia_engine::Config {
+                security_param: NonZero::new(10).unwrap(),
+                active_slot_coeff: 0.05,
+            },
+        }
+    }
+
+    fn commit(note: &NoteWitness) -> NoteCommitment {
+        note.commit(NF_SK.commit())
+    }
+
+    #[test]
+    fn test_add_and_remove_notes() {
+        let header_id = HeaderId::from([0; 32]);
+        let note = NoteWitness::basic(10, NMO_UNIT, &mut thread_rng());
+        let added = NoteWitness::basic(20, NMO_UNIT, &mut thread_rng());
+        let mut leader = Leader::new(header_id, vec![note], NF_SK, config());
+
+        assert!(leader.add_note(added, |_| None));
+        assert!(!leader.add_note(added, |_| None));
+        assert_eq!(leader.notes(&header_id), Some([note, added].as_slice()));
+        assert_eq!(leader.notes_updates().added, vec![added]);
+
+        // evolutions of the removed note are removed as well
+        let evolved_header_id = HeaderId::from([1; 32]);
+        leader
+            .notes
+            .insert(evolved_header_id, vec![evolve(&note, NF_SK), added]);
+        assert!(leader.remove_note(&commit(&note)));
+        assert!(!leader.remove_note(&commit(&note)));
+        assert_eq!(leader.notes(&header_id), Some([added].as_slice()));
+        assert_eq!(leader.notes(&evolved_header_id), Some([added].as_slice()));
+        assert!(leader
+            .notes_updates()
+            .removed
+            .contains(&commit(&evolve(&note, NF_SK))));
+
+        assert!(leader.remove_note(&commit(&added)));
+        assert!(leader.notes_updates().added.is_empty());
+        assert_eq!(leader.notes(&header_id), Some([].as_slice()));
+
+        // the updates are applied again to a leader created from the config
+        let updates = leader.notes_updates().clone();
+        let mut leader = Leader::new(header_id, vec![note, added], NF_SK, config());
+        leader.restore_notes_updates(updates);
+        assert_eq!(leader.notes(&header_id), Some([].as_slice()));
+
+        assert!(leader.add_note(note, |_| None));
+        assert_eq!(leader.notes(&header_id), Some([note].as_slice()));
+        assert!(!leader.notes_updates().removed.contains(&commit(&note)));
+    }
+
+    // spends `note`, with any leadership conditions
+    struct SpendingProof {
+        note: NoteWitness,
+        cm_root: [u8; 32],
+    }
+
+    impl LeaderProof for SpendingProof {
+        fn verify(&self, _public_inputs: &LeaderPublic) -> bool {
+            true
+        }
+
+        fn nullifier(&self) -> Nullifier {
+            Nullifier::new(NF_SK, commit(&self.note))
+        }
+
+        fn evolved_commitment(&self) -> NoteCommitment {
+            commit(&evolve(&self.note, NF_SK))
+        }
+
+        fn merke_root(&self) -> [u8; 32] {
+            self.cm_root
+        }
+    }
+
+    #[test]
+    fn test_restore_notes_updates_at_recovered_block() {
+        let genesis = HeaderId::from([0; 32]);
+        let security_block = HeaderId::from([1; 32]);
+        let spent = NoteWitness::basic(10, NMO_UNIT, &mut thread_rng());
+        let unspent = NoteWitness::basic(20, NMO_UNIT, &mut thread_rng());
+        let genesis_state = LedgerState::from_commitments([commit(&spent), commit(&unspent)], 30);
+        let proof = SpendingProof {
+            note: spent,
+            cm_root: genesis_state.lead_commitments().root(),
+        };
+        let ledger = nomos_ledger::Ledger::new(genesis, genesis_state, config())
+            .try_update(security_block, genesis, 1.into(), &proof, [])
+            .unwrap();
+        let updates = LeaderNotesUpdates {
+            added: vec![spent, unspent],
+            removed: HashSet::new(),
+        };
+
+        // the notes of the security block are missing the added notes
+        let mut leader = Leader::new(security_block, vec![], NF_SK, config());
+        leader.restore_notes_updates_at(updates.clone(), ledger.state(&security_block).unwrap());
+        assert_eq!(
+            leader.notes(&security_block),
+            Some([evolve(&spent, NF_SK), unspent].as_slice())
+        );
+        assert_eq!(leader.notes_updates(), &updates);
+
+        // notes already tracked are not added again
+        let mut leader = Leader::new(
+            security_block,
+            vec![evolve(&spent, NF_SK), unspent],
+            NF_SK,
+            config(),
+        );
+        leader.restore_notes_updates_at(updates, ledger.state(&security_block).unwrap());
+        assert_eq!(
+            leader.notes(&security_block),
+            Some([evolve(&spent, NF_SK), unspent].as_slice())
+        );
+    }
+
+    #[test]
+    fn test_add_note_spent_in_some_blocks() {
+        let genesis = HeaderId::from([0; 32]);
+        let block = HeaderId::from([1; 32]);
+        let note = NoteWitness::basic(10, NMO_UNIT, &mut thread_rng());
+        let genesis_state = LedgerState::from_commitments([commit(&note)], 10);
+        let proof = SpendingProof {
+            note,
+            cm_root: genesis_state.lead_commitments().root(),
+        };
+        let ledger = nomos_ledger::Ledger::new(genesis, genesis_state, config())
+            .try_update(block, genesis, 1.into(), &proof, [])
+            .unwrap();
+        let mut leader = Leader::new(genesis, vec![], NF_SK, config());
+        leader.notes.insert(block, vec![]);
+
+        // the note is already spent on top of `block`, where its evolution leads
+        assert!(leader.add_note(note, |id| ledger.state(id)));
+        assert_eq!(leader.notes(&genesis), Some([note].as_slice()));
+        assert_eq!(
+            leader.notes(&block),
+            Some([evolve(&note, NF_SK)].as_slice())
+        );
+        assert_eq!(leader.notes_updates().added, vec![note]);
+        assert!(!leader.add_note(note, |id| ledger.state(id)));
+    }
+
+    #[test]
+    fn test_notes_info() {
+        let header_id = HeaderId::from([0; 32]);
+        let note = NoteWitness::basic(10, NMO_UNIT, &mut thread_rng());
+        let unknown = NoteWitness::basic(10, NMO_UNIT, &mut thread_rng());
+        let leader = Leader::new(header_id, vec![note, unknown], NF_SK, config());
+        let state = LedgerState::from_commitments([commit(&note)], 10);
+
+        let info = leader.notes_info(header_id, &state).unwrap();
+        assert_eq!(info.tip, header_id);
+        assert_eq!(info.notes.len(), 2);
+        assert!(info.notes[0].eligible);
+        assert!((info.notes[0].slot_win_probability - 0.05).abs() < 1e-3);
+        assert!(!info.notes[1].eligible);
+        assert!(info.notes[1].slot_win_probability == 0.0);
+        assert!((info.slot_leadership_probability - 0.05).abs() < 1e-3);
+        assert!(info.expected_slots_per_epoch > info.slot_leadership_probability);
+        assert!(info.epoch_leadership_probability > info.slot_leadership_probability);
+
+        assert!(leader.notes_info(HeaderId::from([1; 32]), &state).is_none());
+    }
+}
@@ -1,19 +1,25 @@
 pub mod blend;
 mod leadership;
-mod messages;
+pub mod messages;
 pub mod network;
+mod orphans;
 mod relays;
+pub mod snapshot;
 mod states;
 pub mod storage;
+mod sync;
+pub mod validation;
 
 use core::fmt::Debug;
 use std::{collections::BTreeSet, fmt::Display, hash::Hash, path::PathBuf};
 
 use bytes::Bytes;
+use cl::{NoteCommitment, NoteWitness};
 use cryptarchia_engine::Slot;
 use futures::StreamExt;
-pub use leadership::LeaderConfig;
-use network::NetworkAdapter;
+use key_management_system::{NodeKMSRelay, NodeKMSService};
+pub use leadership::{LeaderConfig, LeaderKeys, LeaderNoteInfo, LeaderNotesInfo};
+use network::{IncomingChainSyncRequest, NetworkAdapter};
 use nomos_blend_service::BlendService;
 use nomos_core::{
     block::{builder::BlockBuilder, Block},
@@ -27,8 +33,8 @@ use nomos_da_sampling::{
 };
 use nomos_ledger::{leader_proof::LeaderProof, LedgerState};
 use nomos_mempool::{
-    backend::RecoverableMempool, network::NetworkAdapter as MempoolAdapter, DaMempoolService,
-    MempoolMsg, TxMempoolService,
+    backend::RecoverableMempool, network::NetworkAdapter as MempoolAdapter,
+    verify::LedgerVerifiable, DaMempoolService, MempoolMsg, TxMempoolService,
 };
 use nomos_network::NetworkService;
 use nomos_storage::{backends::StorageBackend, StorageMsg, StorageService};
@@ -43,26 +49,45 @@ use serde_with::serde_as;
 use services_utils::overwatch::{
     lifecycle, recovery::backends::FileBackendSettings, JsonFileBackend, RecoveryOperator,
 };
+pub use sync::SyncStatus;
 use thiserror::Error;
-use tokio::sync::{broadcast, oneshot, oneshot::Sender};
+use tokio::sync::{broadcast, mpsc, oneshot, oneshot::Sender};
 use tracing::{error, info, instrument, span, Level};
 use tracing_futures::Instrument;
 
 use crate::{
-    leadership::Leader,
+    leadership::{Leader, LeaderNotesUpdates},
+    messages::{ChainSyncRequest, ChainSyncResponse, ChainTip},
+    orphans::OrphanBlocks,
     relays::CryptarchiaConsensusRelays,
+    snapshot::{
+        LedgerSnapshot, SignedLedgerSnapshot, SnapshotError, SnapshotExportSettings,
+        SnapshotSettings,
+    },
     states::{
         CryptarchiaConsensusState, CryptarchiaInitialisationStrategy, GenesisRecoveryStrategy,
         SecurityRecoveryStrategy,
     },
     storage::{adapters::StorageAdapter, StorageAdapter as _},
+    sync::{SyncEvent, MAX_SYNC_BATCH_SIZE},
+    validation::{BlockValidationSettings, BlockValidator},
 };
 
 type MempoolRelay<Payload, Item, Key> = OutboundRelay<MempoolMsg<HeaderId, Payload, Item, Key>>;
 type SamplingRelay<BlobId> = OutboundRelay<DaSamplingServiceMsg<BlobId>>;
+type FinalizedBlockSender = mpsc::Sender<FinalizedBranch>;
 
 // Limit the number of blocks returned by GetHeaders
 const HEADERS_LIMIT: usize = 512;
+// Limit the number of blocks buffered while waiting for their parents
+const MAX_ORPHAN_BLOCKS: usize = 256;
+// Number of peers asked for a missing block before giving up
+const MISSING_BLOCK_REQUEST_ATTEMPTS: usize = 3;
+// Finalized blocks buffered in each subscriber channel
+const FINALIZED_BLOCKS_CHANNEL_SIZE: usize = 16;
+// Finalized blocks waiting to be forwarded to a subscriber, subscribers lagging
+// further behind are dropped
+const FINALIZED_BLOCKS_BUFFER_SIZE: usize = 256;
 const CRYPTARCHIA_ID: &str = "Cryptarchia";
 
 #[derive(Debug, Clone, Error)]
@@ -107,11 +132,60 @@ impl Cryptarchia {
         self.consensus.genesis()
     }
 
-    fn try_apply_header(&self, header: &Header) -> Result<Self, Error> {
+    // Snapshots default to the security block, which is not expected to be
+    // reverted
+    fn snapshot_block(&self, id: Option<HeaderId>) -> HeaderId {
+        id.or_else(|| self.consensus.get_security_block_header_id())
+            .unwrap_or_else(|| self.genesis())
+    }
+
+    fn ledger_snapshot(&self, id: HeaderId) -> Option<LedgerSnapshot> {
+        self.ledger
+            .state(&id)
+            .map(|state| LedgerSnapshot::new(id, state.clone()))
+    }
+
+    fn chain_tip(&self) -> ChainTip {
+        let id = self.tip();
+        ChainTip {
+            id,
+            slot: self.tip_state().slot(),
+            height: self
+                .consensus
+                .branches()
+                .get(&id)
+                .expect("tip branch not available")
+                .length(),
+        }
+    }
+
+    /// Block locator of the local chain, used to find the common ancestor
+    /// with the chains of peers.
+    fn block_locator(&self) -> Vec<HeaderId> {
+        let branches = self.consensus.branches();
+        let chain = std::iter::successors(branches.get(&self.tip()), |branch| {
+            (branch.parent() != branch.id())
+                .then(|| branches.get(&branch.parent()))
+                .flatten()
+        });
+        sync::block_locator(chain.map(cryptarchia_engine::Branch::id))
+    }
+
+    /// Applies the header of the block and its transactions, in order, on top
+    /// of the ledger state of its parent.
+    fn try_apply_block<Tx, BlobCertificate>(
+        &self,
+        block: &Block<Tx, BlobCertificate>,
+    ) -> Result<Self, Error>
+    where
+        Tx: LedgerVerifiable + Clone + Eq + Hash,
+        BlobCertificate: Clone + Eq + Hash,
+    {
+        let header = block.header();
         let id = header.id();
         let parent = header.parent();
         let slot = header.slot();
-        let ledger = self.ledger.try_update(
+        let ledger = self.ledger.try_update_with_transactions(
             id,
             parent,
             slot,
@@ -122,12 +196,65 @@ impl Cryptarchia {
                     imported_header.leader_proof().to_orphan_proof(),
                 )
             }),
+            block.transactions(),
+            |state, tx| tx.try_apply(state),
         )?;
         let consensus = self.consensus.receive_block(id, parent, slot)?;
 
         Ok(Self { ledger, consensus })
     }
 
+    /// Branches of the local chain finalized after the first one matching
+    /// `stop`, from the oldest one to the security block.
+    ///
+    /// The root of the chain, i.e. genesis or the block the node was
+    /// bootstrapped from, is never included.
+    fn finalized_branches(
+        &self,
+        stop: impl Fn(&cryptarchia_engine::Branch<HeaderId>) -> bool,
+    ) -> Vec<cryptarchia_engine::Branch<HeaderId>> {
+        let branches = self.consensus.branches();
+        let mut finalized = Vec::new();
+        let mut id = self.consensus.get_security_block_header_id();
+        while let Some(branch) = id.and_then(|id| branches.get(&id)) {
+            if stop(branch) || branch.parent() == branch.id() {
+                break;
+            }
+            finalized.push(branch.clone());
+            id = Some(branch.parent());
+        }
+        finalized.reverse();
+        finalized
+    }
+
+    /// The finalized block of `branch` with the ledger state after it, if the
+    /// state is still available.
+    fn finalized_branch(
+        &self,
+        branch: &cryptarchia_engine::Branch<HeaderId>,
+    ) -> Option<FinalizedBranch> {
+        let Some(ledger_state) = self.ledger.state(&branch.id()) else {
+            tracing::error!("finalized block {} has no ledger state", branch.id());
+            return None;
+        };
+        Some(FinalizedBranch {
+            id: branch.id(),
+            height: branch.length(),
+            ledger_state: ledger_state.clone(),
+        })
+    }
+
+    /// Prunes the forks that diverged from the local chain deeper than the
+    /// security parameter, together with their ledger states.
+    ///
+    /// Returns the ids of the pruned blocks.
+    fn prune_forks(&mut self) -> Vec<HeaderId> {
+        let depth = self.ledger.config().consensus_config.security_param.get();
+        let pruned = self.consensus.prune_forks(depth.into());
+        self.ledger.prune_state_at(pruned.iter().copied());
+        pruned
+    }
+
     fn epoch_state_for_slot(&self, slot: Slot) -> Option<&nomos_ledger::EpochState> {
         let tip = self.tip();
         let state = self.ledger.state(&tip).expect("no state for tip");
@@ -154,6 +281,16 @@ pub struct CryptarchiaSettings<Ts, Bs, NetworkAdapterSettings, BlendAdapterSetti
     pub network_adapter_settings: NetworkAdapterSettings,
     pub blend_adapter_settings: BlendAdapterSettings,
     pub recovery_file: PathBuf,
+    /// Snapshot to start from instead of genesis when there is no state to
+    /// recover from
+    #[serde(default)]
+    pub snapshot: Option<SnapshotSettings>,
+    /// Where and how to export signed snapshots, exporting is disabled if not
+    /// set
+    #[serde(default)]
+    pub snapshot_export: Option<SnapshotExportSettings>,
+    #[serde(default)]
+    pub block_validation: BlockValidationSettings,
 }
 
 impl<Ts, Bs, NetworkAdapterSettings, BlendAdapterSettings> FileBackendSettings
@@ -227,6 +364,7 @@ pub struct CryptarchiaConsensus<
 {
     service_state: OpaqueServiceStateHandle<Self, RuntimeServiceId>,
     block_subscription_sender: broadcast::Sender<Block<ClPool::Item, DaPool::Item>>,
+    finalized_block_subscribers: Vec<FinalizedBlockSender>,
     initial_state: <Self as ServiceData>::State,
 }
 
@@ -388,6 +526,7 @@ where
     ClPool::RecoveryState: Serialize + for<'de> Deserialize<'de>,
     ClPool::Settings: Clone + Send + Sync + 'static,
     ClPool::Item: Transaction<Hash = ClPool::Key>
+        + LedgerVerifiable
         + Debug
         + Clone
         + Eq
@@ -480,7 +619,8 @@ where
             >,
         >
         + AsServiceId<StorageService<Storage, RuntimeServiceId>>
-        + AsServiceId<TimeService<TimeBackend, RuntimeServiceId>>,
+        + AsServiceId<TimeService<TimeBackend, RuntimeServiceId>>
+        + AsServiceId<NodeKMSService<RuntimeServiceId>>,
 {
     fn init(
         service_state: OpaqueServiceStateHandle<Self, RuntimeServiceId>,
@@ -491,6 +631,7 @@ where
         Ok(Self {
             service_state,
             block_subscription_sender,
+            finalized_block_subscribers: Vec::new(),
             initial_state,
         })
     }
@@ -524,21 +665,44 @@ where
             leader_config,
             network_adapter_settings,
             blend_adapter_settings,
+            snapshot: snapshot_settings,
+            snapshot_export,
+            block_validation,
             ..
         } = self.service_state.settings_reader.get_updated_settings();
 
         let genesis_id = HeaderId::from([0; 32]);
 
+        let kms_relay = self
+            .service_state
+            .overwatch_handle
+            .relay::<NodeKMSService<RuntimeServiceId>>()
+            .await?;
+        let leader_keys = leader_config.resolve(&kms_relay).await?;
+
         let (mut cryptarchia, mut leader) = Self::build_cryptarchia(
             self.initial_state,
             genesis_id,
             genesis_state,
+            snapshot_settings,
             ledger_config,
-            leader_config,
+            leader_keys,
             &relays,
             &mut self.block_subscription_sender,
         )
         .await;
+        update_mempool_tip(
+            relays.cl_mempool_relay().clone(),
+            cryptarchia.tip(),
+            cryptarchia.tip_state().clone(),
+        )
+        .await;
+        update_mempool_tip(
+            relays.da_mempool_relay().clone(),
+            cryptarchia.tip(),
+            cryptarchia.tip_state().clone(),
+        )
+        .await;
 
         let network_adapter =
             NetAdapter::new(network_adapter_settings, relays.network_relay().clone()).await;
@@ -546,6 +710,37 @@ where
         let blob_selector = BS::new(blob_selector_settings);
 
         let mut incoming_blocks = network_adapter.blocks_stream().await?;
+        let mut chain_sync_requests = network_adapter.chain_sync_requests_stream().await?;
+        let mut orphan_blocks = OrphanBlocks::new(MAX_ORPHAN_BLOCKS);
+        let (fetched_blocks_sender, mut fetched_blocks) = mpsc::channel(MAX_ORPHAN_BLOCKS);
+
+        let mut sync_status = SyncStatus::Syncing {
+            target_height: cryptarchia.chain_tip().height,
+        };
+        let (sync_events_sender, mut sync_events) = mpsc::channel(1);
+        tokio::spawn(sync::sync_with_peers::<_, RuntimeServiceId>(
+            network_adapter.clone(),
+            cryptarchia.block_locator(),
+            sync_events_sender.clone(),
+        ));
+        let mut sync_running = true;
+        let mut resync_timer = tokio::time::interval_at(
+            tokio::time::Instant::now() + sync::RESYNC_INTERVAL,
+            sync::RESYNC_INTERVAL,
+        );
+        resync_timer.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut chain_index = sync::ChainIndex::new(cryptarchia.genesis());
+
+        let current_slot = {
+            let (sender, receiver) = oneshot::channel();
+            relays
+                .time_relay()
+                .send(TimeServiceMessage::CurrentSlot { sender })
+                .await
+                .expect("Request current slot to time service should succeed");
+            receiver.await?.slot
+        };
+        let mut block_validator = BlockValidator::new(block_validation, current_slot);
 
         let mut slot_timer = {
             let (sender, receiver) = oneshot::channel();
@@ -567,10 +762,15 @@ where
                 tokio::select! {
                     Some(block) = incoming_blocks.next() => {
                         Self::log_received_block(&block);
-                        cryptarchia = Self::process_block(
+                        cryptarchia = Self::process_block_and_orphans(
                             cryptarchia,
                             &mut leader,
                             block,
+                            &mut orphan_blocks,
+                            &block_validator,
+                            &mut self.finalized_block_subscribers,
+                            &network_adapter,
+                            &fetched_blocks_sender,
                             &relays,
                             &mut self.block_subscription_sender,
                         )
@@ -581,35 +781,157 @@ where
                         tracing::info!(counter.consensus_processed_blocks = 1);
                     }
 
+                    Some((id, block)) = fetched_blocks.recv() => {
+                        orphan_blocks.request_completed(&id);
+                        let Some(block) = block else {
+                            tracing::debug!("could not fetch missing block {id} from peers");
+                            continue;
+                        };
+                        cryptarchia = Self::process_block_and_orphans(
+                            cryptarchia,
+                            &mut leader,
+                            block,
+                            &mut orphan_blocks,
+                            &block_validator,
+                            &mut self.finalized_block_subscribers,
+                            &network_adapter,
+                            &fetched_blocks_sender,
+                            &relays,
+                            &mut self.block_subscription_sender,
+                        )
+                        .await;
+
+                        self.service_state.state_updater.update(Self::State::from_cryptarchia(&cryptarchia, &leader));
+                    }
+
+                    Some(request) = chain_sync_requests.next() => {
+                        chain_index.update(cryptarchia.consensus.branches(), cryptarchia.tip());
+                        Self::handle_chain_sync_request(request, &cryptarchia, &chain_index, relays.storage_adapter()).await;
+                    }
+
+                    _ = resync_timer.tick(), if !sync_running => {
+                        sync_running = true;
+                        tokio::spawn(sync::sync_with_peers::<_, RuntimeServiceId>(
+                            network_adapter.clone(),
+                            cryptarchia.block_locator(),
+                            sync_events_sender.clone(),
+                        ));
+                    }
+
+                    Some(event) = sync_events.recv() => {
+                        match event {
+                            // only a node falling behind stops proposing blocks
+                            SyncEvent::Target(target_height) if target_height > cryptarchia.chain_tip().height => {
+                                sync_status = SyncStatus::Syncing { target_height };
+                            }
+                            SyncEvent::Target(_) => {}
+                            SyncEvent::Blocks(blocks) => {
+                                for block in blocks {
+                                    cryptarchia = Self::process_block_and_orphans(
+                                        cryptarchia,
+                                        &mut leader,
+                                        block,
+                                        &mut orphan_blocks,
+                                        &block_validator,
+                                        &mut self.finalized_block_subscribers,
+                                        &network_adapter,
+                                        &fetched_blocks_sender,
+                                        &relays,
+                                        &mut self.block_subscription_sender,
+                                    )
+                                    .await;
+                                }
+                                self.service_state.state_updater.update(Self::State::from_cryptarchia(&cryptarchia, &leader));
+                            }
+                            SyncEvent::Completed => {
+                                if !sync_status.is_synced() {
+                                    tracing::info!("chain sync completed at height {}", cryptarchia.chain_tip().height);
+                                }
+                                sync_status = SyncStatus::Synced;
+                                sync_running = false;
+                            }
+                        }
+                    }
+
                     Some(SlotTick { slot, .. }) = slot_timer.next() => {
+                        block_validator.update_slot(slot);
                         let parent = cryptarchia.tip();
-                        let note_tree = cryptarchia.tip_state().lead_commitments();
                         tracing::debug!("ticking for slot {}", u64::from(slot));
+                        if !sync_status.is_synced() {
+                            tracing::debug!("skipping block proposal while syncing");
+                            continue;
+                        }
 
                         let Some(epoch_state) = cryptarchia.epoch_state_for_slot(slot) else {
                             tracing::error!("trying to propose a block for slot {} but epoch state is not available", u64::from(slot));
                             continue;
                         };
-                        if let Some(proof) = leader.build_proof_for(note_tree, epoch_state, slot, parent).await {
+                        if let Some(proof) = leader.build_proof_for(cryptarchia.tip_state(), epoch_state, slot, parent).await {
                             tracing::debug!("proposing block...");
+                            let orphan_proofs = leader.orphan_proofs_for(
+                                cryptarchia.consensus.branches(),
+                                parent,
+                                slot,
+                                cryptarchia.tip_state(),
+                            );
+                            let ledger_state = cryptarchia.tip_state().clone();
                             // TODO: spawn as a separate task?
-                            let block = Self::propose_block(
+                            let block = match Self::propose_block(
                                 parent,
                                 slot,
-                                proof,
+                                proof.clone(),
+                                orphan_proofs,
+                                ledger_state.clone(),
                                 tx_selector.clone(),
                                 blob_selector.clone(),
                                 &relays
-                            ).await;
+                            ).await {
+                                // orphan proofs whose roots are too old are rejected by the ledger
+                                Some(block) if !block.header().orphaned_proofs().is_empty()
+                                    && cryptarchia.try_apply_block(&block).is_err() => {
+                                    tracing::warn!("orphan proofs cannot be imported on top of {parent}, proposing without them");
+                                    Self::propose_block(
+                                        parent,
+                                        slot,
+                                        proof,
+                                        Vec::new(),
+                                        ledger_state,
+                                        tx_selector.clone(),
+                                        blob_selector.clone(),
+                                        &relays
+                                    ).await
+                                }
+                                block => block,
+                            };
 
                             if let Some(block) = block {
+                                // peers would reject it anyway
+                                if let Err(e) = cryptarchia.try_apply_block(&block) {
+                                    tracing::error!("proposed block is not valid on top of {parent}: {e}");
+                                    continue;
+                                }
                                 blend_adapter.blend(block).await;
                             }
                         }
                     }
 
                     Some(msg) = self.service_state.inbound_relay.next() => {
-                        Self::process_message(&cryptarchia, &self.block_subscription_sender, msg);
+                        let updates_notes = matches!(msg, ConsensusMsg::AddLeaderNote { .. } | ConsensusMsg::RemoveLeaderNote { .. });
+                        Self::process_message(
+                            &cryptarchia,
+                            &mut leader,
+                            sync_status,
+                            &self.block_subscription_sender,
+                            &mut self.finalized_block_subscribers,
+                            relays.storage_adapter(),
+                            snapshot_export.as_ref(),
+                            &kms_relay,
+                            msg,
+                        )
+                        .await;
+                        if updates_notes {
+                            self.service_state.state_updater.update(Self::State::from_cryptarchia(&cryptarchia, &leader));
+                        }
                     }
                     Some(msg) = lifecycle_stream.next() => {
                         if lifecycle::should_stop_service::<Self, RuntimeServiceId>(&msg) {
@@ -669,7 +991,11 @@ impl<
         RuntimeServiceId,
     >
 where
-    NetAdapter: NetworkAdapter<RuntimeServiceId> + Clone + Send + Sync + 'static,
+    NetAdapter: NetworkAdapter<RuntimeServiceId, Tx = ClPool::Item, BlobCertificate = DaPool::Item>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
     NetAdapter::Settings: Send,
     BlendAdapter: blend::BlendAdapter<RuntimeServiceId> + Clone + Send + Sync + 'static,
     BlendAdapter::Settings: Send,
@@ -677,6 +1003,7 @@ where
     ClPool::RecoveryState: Serialize + for<'de> Deserialize<'de>,
     ClPool::Settings: Clone + Send + Sync + 'static,
     ClPool::Item: Transaction<Hash = ClPool::Key>
+        + LedgerVerifiable
         + Debug
         + Clone
         + Eq
@@ -731,36 +1058,73 @@ where
     TimeBackend::Settings: Clone + Send + Sync,
     ApiAdapter: nomos_da_sampling::api::ApiAdapter + Send + Sync,
 {
-    fn process_message(
+    #[expect(clippy::too_many_arguments)]
+    async fn process_message(
         cryptarchia: &Cryptarchia,
+        leader: &mut Leader,
+        sync_status: SyncStatus,
         block_channel: &broadcast::Sender<Block<ClPool::Item, DaPool::Item>>,
+        finalized_block_subscribers: &mut Vec<FinalizedBlockSender>,
+        storage_adapter: &StorageAdapter<Storage, TxS::Tx, BS::BlobId, RuntimeServiceId>,
+        snapshot_export: Option<&SnapshotExportSettings>,
+        kms_relay: &NodeKMSRelay,
         msg: ConsensusMsg<Block<ClPool::Item, DaPool::Item>>,
     ) {
         match msg {
             ConsensusMsg::Info { tx } => {
+                let ChainTip { id, slot, height } = cryptarchia.chain_tip();
                 let info = CryptarchiaInfo {
-                    tip: cryptarchia.tip(),
-                    slot: cryptarchia
-                        .ledger
-                        .state(&cryptarchia.tip())
-                        .expect("tip state not available")
-                        .slot(),
-                    height: cryptarchia
-                        .consensus
-                        .branches()
-                        .get(&cryptarchia.tip())
-                        .expect("tip branch not available")
-                        .length(),
+                    tip: id,
+                    slot,
+                    height,
+                    sync_status,
                 };
                 tx.send(info).unwrap_or_else(|e| {
                     tracing::error!("Could not send consensus info through channel: {:?}", e);
                 });
             }
+            ConsensusMsg::GetLedgerSnapshot { id, tx } => {
+                tx.send(cryptarchia.ledger_snapshot(cryptarchia.snapshot_block(id)))
+                    .unwrap_or_else(|_| {
+                        tracing::error!("Could not send ledger snapshot through channel");
+                    });
+            }
+            ConsensusMsg::ExportLedgerSnapshot { id, tx } => {
+                let result = async {
+                    let settings = snapshot_export.ok_or(SnapshotError::ExportDisabled)?;
+                    let id = cryptarchia.snapshot_block(id);
+                    let snapshot = cryptarchia
+                        .ledger_snapshot(id)
+                        .ok_or(SnapshotError::StateNotFound(id))?;
+                    settings.export(kms_relay, snapshot).await
+                }
+                .await;
+                if let Err(e) = &result {
+                    tracing::error!("Could not export ledger snapshot: {e}");
+                }
+                tx.send(result.map_err(|e| e.to_string()))
+                    .unwrap_or_else(|_| {
+                        tracing::error!("Could not send exported snapshot path through channel");
+                    });
+            }
             ConsensusMsg::BlockSubscribe { sender } => {
                 sender.send(block_channel.subscribe()).unwrap_or_else(|_| {
                     tracing::error!("Could not subscribe to block subscription channel");
                 });
             }
+            ConsensusMsg::FinalizedBlockSubscribe {
+                from_height,
+                sender,
+            } => {
+                Self::subscribe_finalized_blocks(
+                    cryptarchia,
+                    from_height,
+                    sender,
+                    storage_adapter,
+                    finalized_block_subscribers,
+                )
+                .await;
+            }
             ConsensusMsg::GetHeaders { from, to, tx } => {
                 // default to tip block if not present
                 let from = from.unwrap_or_else(|| cryptarchia.tip());
@@ -783,6 +1147,294 @@ where
                 tx.send(res)
                     .unwrap_or_else(|_| tracing::error!("could not send blocks through channel"));
             }
+            ConsensusMsg::AddLeaderNote { note, tx } => {
+                tx.send(leader.add_note(note, |id| cryptarchia.ledger.state(id)))
+                    .unwrap_or_else(|_| {
+                        tracing::error!(
+                            "Could not send leader note addition result through channel"
+                        );
+                    });
+            }
+            ConsensusMsg::RemoveLeaderNote { commitment, tx } => {
+                tx.send(leader.remove_note(&commitment))
+                    .unwrap_or_else(|_| {
+                        tracing::error!(
+                            "Could not send leader note removal result through channel"
+                        );
+                    });
+            }
+            ConsensusMsg::GetLeaderNotes { tx } => {
+                let mut tips = cryptarchia.consensus.branches().branches();
+                // longest chains first
+                tips.sort_by_key(|branch| std::cmp::Reverse(branch.length()));
+                let info = tips
+                    .into_iter()
+                    .filter_map(|branch| {
+                        let state = cryptarchia.ledger.state(&branch.id())?;
+                        leader.notes_info(branch.id(), state)
+                    })
+                    .collect();
+                tx.send(info).unwrap_or_else(|_| {
+                    tracing::error!("Could not send leader notes through channel");
+                });
+            }
+        }
+    }
+
+    /// Processes a block, buffering it if its parent is not known yet and
+    /// requesting the missing parent from peers.
+    ///
+    /// Blocks are validated with `block_validator` before being applied. Once
+    /// a block is applied, any buffered descendant is processed as well.
+    /// Blocks finalized as a result are sent to `finalized_block_subscribers`.
+    #[expect(clippy::type_complexity)]
+    #[expect(clippy::too_many_arguments)]
+    async fn process_block_and_orphans(
+        mut cryptarchia: Cryptarchia,
+        leader: &mut leadership::Leader,
+        block: Block<ClPool::Item, DaPool::Item>,
+        orphan_blocks: &mut OrphanBlocks<HeaderId, Block<ClPool::Item, DaPool::Item>>,
+        block_validator: &BlockValidator,
+        finalized_block_subscribers: &mut Vec<FinalizedBlockSender>,
+        network_adapter: &NetAdapter,
+        fetched_blocks_sender: &mpsc::Sender<(HeaderId, Option<Block<ClPool::Item, DaPool::Item>>)>,
+        relays: &CryptarchiaConsensusRelays<
+            BlendAdapter,
+            BS,
+            ClPool,
+            ClPoolAdapter,
+            DaPool,
+            DaPoolAdapter,
+            NetAdapter,
+            SamplingBackend,
+            SamplingRng,
+            Storage,
+            TxS,
+            DaVerifierBackend,
+            RuntimeServiceId,
+        >,
+        block_broadcaster: &mut broadcast::Sender<Block<ClPool::Item, DaPool::Item>>,
+    ) -> Cryptarchia {
+        let finalized = cryptarchia.consensus.get_security_block_header_id();
+        let mut pending_blocks = vec![block];
+        while let Some(block) = pending_blocks.pop() {
+            let id = block.header().id();
+            let parent = block.header().parent();
+            let branches = cryptarchia.consensus.branches();
+            if branches.get(&id).is_some() {
+                tracing::debug!("block {id} was already processed");
+                continue;
+            }
+            if branches.get(&parent).is_none() {
+                if orphan_blocks.insert(id, parent, block) {
+                    tracing::debug!(
+                        "buffered orphan block {id}, {} orphans pending",
+                        orphan_blocks.len()
+                    );
+                    if orphan_blocks.mark_requested(parent) {
+                        Self::request_missing_block(
+                            parent,
+                            network_adapter.clone(),
+                            fetched_blocks_sender.clone(),
+                        );
+                    }
+                }
+                continue;
+            }
+
+            let sampled_blobs = match get_sampled_blobs(relays.sampling_relay().clone()).await {
+                Ok(sampled_blobs) => sampled_blobs,
+                Err(error) => {
+                    error!("Unable to retrieved sampled blobs: {error}");
+                    continue;
+                }
+            };
+            if let Err(e) = block_validator.validate(&block, &sampled_blobs) {
+                error!("Invalid block {id}: {e}");
+                continue;
+            }
+
+            cryptarchia =
+                Self::process_block(cryptarchia, leader, block, relays, block_broadcaster).await;
+
+            if cryptarchia.consensus.branches().get(&id).is_some() {
+                pending_blocks.extend(orphan_blocks.take_children(&id));
+            }
+        }
+
+        if cryptarchia.consensus.get_security_block_header_id() != finalized {
+            let branches = cryptarchia.finalized_branches(|branch| Some(branch.id()) == finalized);
+            Self::send_finalized_blocks(&cryptarchia, branches, finalized_block_subscribers);
+        }
+        cryptarchia
+    }
+
+    /// Hands the blocks of `branches`, in order, to the task forwarding them
+    /// to each subscriber, without waiting for any of them. Subscribers which
+    /// are gone or lag more than `FINALIZED_BLOCKS_BUFFER_SIZE` blocks behind
+    /// are dropped, closing their channels.
+    ///
+    /// If the ledger state of a block is not available anymore, all the
+    /// subscribers are dropped, as they could not get every finalized block
+    /// otherwise.
+    fn send_finalized_blocks(
+        cryptarchia: &Cryptarchia,
+        branches: Vec<cryptarchia_engine::Branch<HeaderId>>,
+        subscribers: &mut Vec<FinalizedBlockSender>,
+    ) {
+        for branch in branches {
+            if subscribers.is_empty() {
+                return;
+            }
+            let Some(finalized_branch) = cryptarchia.finalized_branch(&branch) else {
+                subscribers.clear();
+                return;
+            };
+            subscribers.retain(
+                |subscriber| match subscriber.try_send(finalized_branch.clone()) {
+                    Ok(()) => true,
+                    Err(mpsc::error::TrySendError::Full(_)) => {
+                        tracing::warn!("dropping finalized blocks subscriber lagging behind");
+                        false
+                    }
+                    Err(mpsc::error::TrySendError::Closed(_)) => false,
+                },
+            );
+        }
+    }
+
+    /// Subscribes to the finalized blocks starting from height `from_height`,
+    /// or to the ones finalized from now on if not set.
+    ///
+    /// Blocks are read from storage and sent by a task of the subscriber's
+    /// own, starting with the ones already finalized. The channel is closed
+    /// right away if some of them are not available, e.g. because they
+    /// precede the block the node was bootstrapped from.
+    async fn subscribe_finalized_blocks(
+        cryptarchia: &Cryptarchia,
+        from_height: Option<u64>,
+        sender: oneshot::Sender<mpsc::Receiver<FinalizedBlock<Block<ClPool::Item, DaPool::Item>>>>,
+        storage_adapter: &StorageAdapter<Storage, TxS::Tx, BS::BlobId, RuntimeServiceId>,
+        subscribers: &mut Vec<FinalizedBlockSender>,
+    ) {
+        let (block_sender, block_receiver) = mpsc::channel(FINALIZED_BLOCKS_CHANNEL_SIZE);
+        if sender.send(block_receiver).is_err() {
+            tracing::error!("Could not subscribe to finalized blocks");
+            return;
+        }
+        let mut backlog = Vec::new();
+        if let Some(from_height) = from_height {
+            let branches = cryptarchia.finalized_branches(|branch| branch.length() < from_height);
+            if branches
+                .first()
+                .is_some_and(|branch| branch.length() != from_height)
+            {
+                tracing::error!("finalized blocks from height {from_height} are not available");
+                return;
+            }
+            let Some(branches) = branches
+                .iter()
+                .map(|branch| cryptarchia.finalized_branch(branch))
+                .collect::<Option<Vec<_>>>()
+            else {
+                return;
+            };
+            backlog = branches;
+        }
+        let (branch_sender, branch_receiver) = mpsc::channel(FINALIZED_BLOCKS_BUFFER_SIZE);
+        let storage_adapter: StorageAdapter<Storage, TxS::Tx, BS::BlobId, RuntimeServiceId> =
+            StorageAdapter::new(storage_adapter.storage_relay.clone()).await;
+        tokio::spawn(forward_finalized_blocks(
+            backlog,
+            branch_receiver,
+            block_sender,
+            storage_adapter,
+        ));
+        subscribers.push(branch_sender);
+    }
+
+    /// Fetches a missing block from peers in the background, notifying the
+    /// outcome through `fetched_blocks_sender`.
+    fn request_missing_block(
+        id: HeaderId,
+        network_adapter: NetAdapter,
+        fetched_blocks_sender: mpsc::Sender<(HeaderId, Option<Block<ClPool::Item, DaPool::Item>>)>,
+    ) {
+        tracing::debug!("requesting missing block {id} from peers");
+        tokio::spawn(async move {
+            let mut fetched_block = None;
+            for attempt in 1..=MISSING_BLOCK_REQUEST_ATTEMPTS {
+                match network_adapter.request_block(id).await {
+                    Ok(Some(block)) if block.header().id() == id => {
+                        fetched_block = Some(block);
+                        break;
+                    }
+                    Ok(Some(block)) => tracing::warn!(
+                        "peer answered with block {} when {id} was requested",
+                        block.header().id()
+                    ),
+                    Ok(None) => {
+                        tracing::debug!("peer does not know block {id} (attempt {attempt})")
+                    }
+                    Err(e) => {
+                        tracing::debug!("failed to request block {id} (attempt {attempt}): {e}")
+                    }
+                }
+            }
+            if fetched_blocks_sender
+                .send((id, fetched_block))
+                .await
+                .is_err()
+            {
+                tracing::error!("could not notify fetched block {id}");
+            }
+        });
+    }
+
+    /// Answers a chain sync request received from a peer from the local chain
+    /// and storage.
+    async fn handle_chain_sync_request(
+        IncomingChainSyncRequest {
+            request,
+            reply_channel,
+        }: IncomingChainSyncRequest<ClPool::Item, DaPool::Item>,
+        cryptarchia: &Cryptarchia,
+        chain_index: &sync::ChainIndex<HeaderId>,
+        storage_adapter: &StorageAdapter<Storage, TxS::Tx, BS::BlobId, RuntimeServiceId>,
+    ) {
+        let response = match request {
+            ChainSyncRequest::Block(id) => {
+                ChainSyncResponse::Block(storage_adapter.get_block(&id).await)
+            }
+            ChainSyncRequest::Tip => ChainSyncResponse::Tip(cryptarchia.chain_tip()),
+            ChainSyncRequest::Blocks {
+                locator,
+                tip,
+                limit,
+            } => {
+                let ids = sync::blocks_after_locator(
+                    cryptarchia.consensus.branches(),
+                    chain_index,
+                    &locator,
+                    tip,
+                    limit.min(MAX_SYNC_BATCH_SIZE),
+                );
+                let mut blocks = sync::ResponseBlocks::with_capacity(ids.len());
+                for id in ids {
+                    let Some(block) = storage_adapter.get_block(&id).await else {
+                        tracing::error!("block {id} is part of the chain but not in storage");
+                        break;
+                    };
+                    // the peer requests the following blocks next
+                    if !blocks.push(block) {
+                        break;
+                    }
+                }
+                ChainSyncResponse::Blocks(blocks.into_blocks())
+            }
+        };
+        if reply_channel.send(response).is_err() {
+            tracing::debug!("could not send chain sync response, request was dropped");
         }
     }
 
@@ -812,38 +1464,27 @@ where
     ) -> Cryptarchia {
         tracing::debug!("received proposal {:?}", block);
 
-        // TODO: filter on time?
-
         let header = block.header();
         let id = header.id();
-        let sampled_blobs = match get_sampled_blobs(relays.sampling_relay().clone()).await {
-            Ok(sampled_blobs) => sampled_blobs,
-            Err(error) => {
-                error!("Unable to retrieved sampled blobs: {error}");
-                return cryptarchia;
-            }
-        };
-        if !Self::validate_block(&block, &sampled_blobs) {
-            error!("Invalid block: {block:?}");
-            return cryptarchia;
-        }
 
-        match cryptarchia.try_apply_header(header) {
+        match cryptarchia.try_apply_block(&block) {
             Ok(new_state) => {
                 // update leader
-                leader.follow_chain(header.parent(), id, header.leader_proof().nullifier());
+                leader.follow_chain(header);
 
                 // remove included content from mempool
                 mark_in_block(
                     relays.cl_mempool_relay().clone(),
                     block.transactions().map(Transaction::hash),
                     id,
+                    header.parent(),
                 )
                 .await;
                 mark_in_block(
                     relays.da_mempool_relay().clone(),
                     block.blobs().map(DispersedBlobInfo::blob_id),
                     id,
+                    header.parent(),
                 )
                 .await;
 
@@ -865,14 +1506,52 @@ where
                     tracing::error!("Could not notify block to services {e}");
                 }
 
+                let previous_tip = cryptarchia.tip();
+                let previous_finalized = cryptarchia.consensus.get_security_block_header_id();
                 cryptarchia = new_state;
+                if cryptarchia.tip() != previous_tip {
+                    // pending transactions may conflict with the new tip
+                    update_mempool_tip(
+                        relays.cl_mempool_relay().clone(),
+                        cryptarchia.tip(),
+                        cryptarchia.tip_state().clone(),
+                    )
+                    .await;
+                    update_mempool_tip(
+                        relays.da_mempool_relay().clone(),
+                        cryptarchia.tip(),
+                        cryptarchia.tip_state().clone(),
+                    )
+                    .await;
+                }
+
+                let pruned_blocks = cryptarchia.prune_forks();
+                if !pruned_blocks.is_empty() {
+                    tracing::debug!("pruned {} blocks from stale forks", pruned_blocks.len());
+                    leader.prune_notes_at(&pruned_blocks);
+                    Self::delete_pruned_blocks(&pruned_blocks, relays.storage_adapter()).await;
+                }
+                if let Some(security_block) = cryptarchia
+                    .consensus
+                    .get_security_block_header_id()
+                    .and_then(|id| cryptarchia.consensus.branches().get(&id))
+                {
+                    leader.prune_headers_until(security_block.slot());
+                    if Some(security_block.id()) != previous_finalized {
+                        mark_finalized(relays.cl_mempool_relay().clone(), security_block.id())
+                            .await;
+                        mark_finalized(relays.da_mempool_relay().clone(), security_block.id())
+                            .await;
+                    }
+                }
             }
             Err(
                 Error::Ledger(nomos_ledger::LedgerError::ParentNotFound(parent))
                 | Error::Consensus(cryptarchia_engine::Error::ParentMissing(parent)),
             ) => {
+                // Blocks with unknown parents are buffered before reaching this point, so
+                // this only happens if the parent state was pruned
                 tracing::debug!("missing parent {:?}", parent);
-                // TODO: request parent block
             }
             Err(e) => tracing::debug!("invalid block {:?}: {e:?}", block),
         }
@@ -880,13 +1559,23 @@ where
         cryptarchia
     }
 
+    /// Builds a block on top of `parent` with the content of the mempools.
+    ///
+    /// Transactions that cannot be applied, in order, on top of the ledger
+    /// state of the parent are left out.
     #[expect(clippy::allow_attributes_without_reason)]
     #[expect(clippy::type_complexity)]
-    #[instrument(level = "debug", skip(tx_selector, blob_selector, relays))]
+    #[expect(clippy::too_many_arguments)]
+    #[instrument(
+        level = "debug",
+        skip(ledger_state, tx_selector, blob_selector, relays)
+    )]
     async fn propose_block(
         parent: HeaderId,
         slot: Slot,
         proof: Risc0LeaderProof,
+        orphan_proofs: Vec<Header>,
+        mut ledger_state: LedgerState,
         tx_selector: TxS,
         blob_selector: BS,
         relays: &CryptarchiaConsensusRelays<
@@ -906,17 +1595,30 @@ where
         >,
     ) -> Option<Block<ClPool::Item, DaPool::Item>> {
         let mut output = None;
-        let cl_txs = get_mempool_contents(relays.cl_mempool_relay().clone());
-        let da_certs = get_mempool_contents(relays.da_mempool_relay().clone());
+        let cl_txs = get_mempool_contents(relays.cl_mempool_relay().clone(), parent);
+        let da_certs = get_mempool_contents(relays.da_mempool_relay().clone(), parent);
         let blobs_ids = get_sampled_blobs(relays.sampling_relay().clone());
         match futures::join!(cl_txs, da_certs, blobs_ids) {
             (Ok(cl_txs), Ok(da_blobs_info), Ok(blobs_ids)) => {
                 let block = BlockBuilder::new(
                     tx_selector,
                     blob_selector,
-                    Builder::new(parent, slot, proof),
+                    Builder::new(parent, slot, proof).with_orphaned_proofs(orphan_proofs),
                 )
-                .with_transactions(cl_txs)
+                .with_transactions(cl_txs.filter(move |tx| {
+                    match tx.try_apply::<HeaderId>(ledger_state.clone()) {
+                        Ok(state) => {
+                            ledger_state = state;
+                            true
+                        }
+                        Err(e) => {
+                            tracing::debug!(
+                                "leaving out transaction not valid on top of {parent}: {e}"
+                            );
+                            false
+                        }
+                    }
+                }))
                 .with_blobs_info(
                     da_blobs_info.filter(move |info| blobs_ids.contains(&info.blob_id())),
                 )
@@ -940,16 +1642,6 @@ where
         output
     }
 
-    fn validate_block(
-        block: &Block<ClPool::Item, DaPool::Item>,
-        sampled_blobs_ids: &BTreeSet<DaPool::Key>,
-    ) -> bool {
-        let validated_blobs = block
-            .blobs()
-            .all(|blob| sampled_blobs_ids.contains(&blob.blob_id()));
-        validated_blobs
-    }
-
     fn log_received_block(block: &Block<ClPool::Item, DaPool::Item>) {
         let content_size = block.header().content_size();
         let transactions = block.cl_transactions_len();
@@ -1016,6 +1708,18 @@ where
         blocks.collect::<Vec<_>>().await.into_iter().rev().collect()
     }
 
+    /// Removes the blocks belonging to pruned forks from the storage.
+    async fn delete_pruned_blocks(
+        pruned_blocks: &[HeaderId],
+        storage_adapter: &StorageAdapter<Storage, TxS::Tx, BS::BlobId, RuntimeServiceId>,
+    ) {
+        for header_id in pruned_blocks {
+            if storage_adapter.remove_block(header_id).await.is_none() {
+                tracing::debug!("pruned block {header_id} was not found in storage");
+            }
+        }
+    }
+
     /// Builds cryptarchia
     /// The build process is determined by the initial state passed:
     /// - If the initial state doesn't contain any recovery information,
@@ -1049,8 +1753,9 @@ where
         >,
         genesis_id: HeaderId,
         genesis_state: LedgerState,
+        snapshot_settings: Option<SnapshotSettings>,
         ledger_config: nomos_ledger::Config,
-        leader_config: LeaderConfig,
+        leader_keys: LeaderKeys,
         relays: &CryptarchiaConsensusRelays<
             BlendAdapter,
             BS,
@@ -1068,10 +1773,27 @@ where
         >,
         block_subscription_sender: &mut broadcast::Sender<Block<ClPool::Item, DaPool::Item>>,
     ) -> (Cryptarchia, Leader) {
+        let notes_updates = initial_state.take_leader_notes_updates();
         match initial_state.recovery_strategy() {
             CryptarchiaInitialisationStrategy::Genesis => {
+                if let Some(snapshot) = snapshot_settings {
+                    match Self::load_snapshot(&snapshot, relays.storage_adapter()).await {
+                        Ok(LedgerSnapshot { header_id, state }) => {
+                            info!("Building Cryptarchia from snapshot at {header_id}.");
+                            return Self::build_from_genesis(
+                                header_id,
+                                state,
+                                leader_keys,
+                                ledger_config,
+                            );
+                        }
+                        Err(e) => {
+                            error!("Could not load snapshot {:?}: {e}", snapshot.path);
+                        }
+                    }
+                }
                 info!("Building Cryptarchia from genesis.");
-                Self::build_from_genesis(genesis_id, genesis_state, leader_config, ledger_config)
+                Self::build_from_genesis(genesis_id, genesis_state, leader_keys, ledger_config)
             }
             CryptarchiaInitialisationStrategy::RecoveryFromGenesis(strategy) => {
                 info!("Recovering Cryptarchia with Genesis strategy.");
@@ -1079,7 +1801,8 @@ where
                     strategy,
                     genesis_id,
                     genesis_state,
-                    leader_config,
+                    leader_keys,
+                    notes_updates,
                     ledger_config,
                     relays,
                     block_subscription_sender,
@@ -1090,7 +1813,8 @@ where
                 info!("Recovering Cryptarchia with Security strategy.");
                 Self::recover_from_security(
                     *strategy,
-                    leader_config,
+                    leader_keys,
+                    notes_updates,
                     ledger_config,
                     relays,
                     block_subscription_sender,
@@ -1164,13 +1888,41 @@ where
         cryptarchia
     }
 
+    /// Loads a trusted snapshot, checking it against the local block store.
+    ///
+    /// A snapshot whose block is not stored locally is accepted, since a node
+    /// bootstrapping from a snapshot is not expected to have it.
+    async fn load_snapshot(
+        SnapshotSettings {
+            path,
+            trusted_signers,
+        }: &SnapshotSettings,
+        storage_adapter: &StorageAdapter<Storage, TxS::Tx, BS::BlobId, RuntimeServiceId>,
+    ) -> Result<LedgerSnapshot, SnapshotError> {
+        let snapshot = SignedLedgerSnapshot::load(path)?.verify(trusted_signers)?;
+        let block_slot = storage_adapter
+            .get_block(&snapshot.header_id)
+            .await
+            .map(|block| block.header().slot());
+        match snapshot.check_block_slot(block_slot) {
+            Ok(()) => {}
+            Err(SnapshotError::BlockNotFound(id)) => {
+                tracing::warn!(
+                    "snapshot block {id} is not stored locally, skipping integrity check"
+                );
+            }
+            Err(e) => return Err(e),
+        }
+        Ok(snapshot)
+    }
+
     fn build_from_genesis(
         genesis_id: HeaderId,
         genesis_state: LedgerState,
-        leader_config: LeaderConfig,
+        leader_keys: LeaderKeys,
         ledger_config: nomos_ledger::Config,
     ) -> (Cryptarchia, Leader) {
-        let leader = Leader::from_genesis(genesis_id, leader_config, ledger_config);
+        let leader = Leader::from_genesis(genesis_id, leader_keys, ledger_config);
         let cryptarchia = Cryptarchia::new(genesis_id, genesis_state, ledger_config);
 
         (cryptarchia, leader)
@@ -1184,7 +1936,8 @@ where
         GenesisRecoveryStrategy { tip }: GenesisRecoveryStrategy,
         genesis_id: HeaderId,
         genesis_state: LedgerState,
-        leader_config: LeaderConfig,
+        mut leader_keys: LeaderKeys,
+        notes_updates: LeaderNotesUpdates,
         ledger_config: nomos_ledger::Config,
         relays: &CryptarchiaConsensusRelays<
             BlendAdapter,
@@ -1203,7 +1956,15 @@ where
         >,
         block_subscription_sender: &mut broadcast::Sender<Block<ClPool::Item, DaPool::Item>>,
     ) -> (Cryptarchia, Leader) {
-        let mut leader = Leader::from_genesis(genesis_id, leader_config, ledger_config);
+        // notes added at runtime are followed from genesis like the configured
+        // ones
+        for note in &notes_updates.added {
+            if !leader_keys.notes.contains(note) {
+                leader_keys.notes.push(*note);
+            }
+        }
+        let mut leader = Leader::from_genesis(genesis_id, leader_keys, ledger_config);
+        leader.restore_notes_updates(notes_updates);
         let cryptarchia = Self::recover_cryptarchia(
             genesis_id,
             genesis_state,
@@ -1229,7 +1990,8 @@ where
             security_ledger_state,
             security_leader_notes,
         }: SecurityRecoveryStrategy,
-        leader_config: LeaderConfig,
+        leader_keys: LeaderKeys,
+        notes_updates: LeaderNotesUpdates,
         ledger_config: nomos_ledger::Config,
         relays: &CryptarchiaConsensusRelays<
             BlendAdapter,
@@ -1251,9 +2013,12 @@ where
         let mut leader = Leader::new(
             security_block_id,
             security_leader_notes,
-            leader_config.nf_sk,
+            leader_keys.nf_sk,
             ledger_config,
         );
+        // notes added at runtime are followed from the security block like the
+        // recovered ones
+        leader.restore_notes_updates_at(notes_updates, &security_ledger_state);
 
         let cryptarchia = Self::recover_cryptarchia(
             security_block_id,
@@ -1278,11 +2043,111 @@ pub enum ConsensusMsg<Block> {
     BlockSubscribe {
         sender: oneshot::Sender<broadcast::Receiver<Block>>,
     },
+    /// Subscribe to the blocks of the local chain which became final, i.e.
+    /// deeper than the security parameter, in chain order.
+    ///
+    /// Blocks already finalized are sent first, starting from height
+    /// `from_height` if set. Consensus never waits for subscribers, the
+    /// channel is closed if the subscriber lags too far behind or some block
+    /// cannot be delivered.
+    FinalizedBlockSubscribe {
+        from_height: Option<u64>,
+        sender: oneshot::Sender<mpsc::Receiver<FinalizedBlock<Block>>>,
+    },
     GetHeaders {
         from: Option<HeaderId>,
         to: Option<HeaderId>,
         tx: Sender<Vec<HeaderId>>,
     },
+    /// Snapshot of the ledger state at block `id`, or at the security block if
+    /// not specified
+    GetLedgerSnapshot {
+        id: Option<HeaderId>,
+        tx: Sender<Option<LedgerSnapshot>>,
+    },
+    /// Sign the snapshot of the ledger state at block `id`, or at the security
+    /// block if not specified, and write it to the export directory. Returns
+    /// the path of the snapshot file.
+    ExportLedgerSnapshot {
+        id: Option<HeaderId>,
+        tx: Sender<Result<PathBuf, String>>,
+    },
+    /// Start leading with a note, returns `false` if it was already tracked
+    AddLeaderNote {
+        note: NoteWitness,
+        tx: Sender<bool>,
+    },
+    /// Stop leading with a note and its evolutions, returns `false` if it is
+    /// not tracked
+    RemoveLeaderNote {
+        commitment: NoteCommitment,
+        tx: Sender<bool>,
+    },
+    /// Leader notes evolved on top of each tip
+    GetLeaderNotes {
+        tx: Sender<Vec<LeaderNotesInfo>>,
+    },
+}
+
+/// A block which cannot be reverted anymore, with its height in the chain.
+#[derive(Debug, Clone)]
+pub struct FinalizedBlock<Block> {
+    pub height: u64,
+    pub block: Block,
+    /// Ledger state after the block, the same on every node
+    pub ledger_state: LedgerState,
+}
+
+/// A finalized block of the local chain waiting to be forwarded to a
+/// subscriber, which reads it back from storage.
+#[derive(Clone)]
+struct FinalizedBranch {
+    id: HeaderId,
+    height: u64,
+    ledger_state: LedgerState,
+}
+
+/// Forwards finalized blocks to `subscriber`, the ones in `backlog` first and
+/// then the ones received from consensus, reading them back from storage.
+///
+/// Stops, closing the subscriber channel, once the subscriber is gone,
+/// consensus dropped it or a block is missing from storage.
+async fn forward_finalized_blocks<Adapter, RuntimeServiceId>(
+    backlog: Vec<FinalizedBranch>,
+    mut branches: mpsc::Receiver<FinalizedBranch>,
+    subscriber: mpsc::Sender<FinalizedBlock<Adapter::Block>>,
+    storage_adapter: Adapter,
+) where
+    Adapter: storage::StorageAdapter<RuntimeServiceId> + Send + Sync,
+    Adapter::Block: Send,
+{
+    let mut backlog = backlog.into_iter();
+    loop {
+        let next = match backlog.next() {
+            Some(branch) => Some(branch),
+            None => branches.recv().await,
+        };
+        let Some(FinalizedBranch {
+            id,
+            height,
+            ledger_state,
+        }) = next
+        else {
+            return;
+        };
+        let Some(block) = storage_adapter.get_block(&id).await else {
+            tracing::error!("finalized block {id} is not in storage");
+            return;
+        };
+        let finalized_block = FinalizedBlock {
+            height,
+            block,
+            ledger_state,
+        };
+        if subscriber.send(finalized_block).await.is_err() {
+            return;
+        }
+    }
 }
 
 #[serde_as]
@@ -1292,10 +2157,12 @@ pub struct CryptarchiaInfo {
     pub tip: HeaderId,
     pub slot: Slot,
     pub height: u64,
+    pub sync_status: SyncStatus,
 }
 
 async fn get_mempool_contents<Payload, Item, Key>(
     mempool: OutboundRelay<MempoolMsg<HeaderId, Payload, Item, Key>>,
+    ancestor_hint: HeaderId,
 ) -> Result<Box<dyn Iterator<Item = Item> + Send>, tokio::sync::oneshot::error::RecvError>
 where
     Key: Send,
@@ -1305,7 +2172,7 @@ where
 
     mempool
         .send(MempoolMsg::View {
-            ancestor_hint: [0; 32].into(),
+            ancestor_hint,
             reply_channel,
         })
         .await
@@ -1318,6 +2185,7 @@ async fn mark_in_block<Payload, Item, Key>(
     mempool: OutboundRelay<MempoolMsg<HeaderId, Payload, Item, Key>>,
     ids: impl Iterator<Item = Key>,
     block: HeaderId,
+    parent: HeaderId,
 ) where
     Key: Send,
     Payload: Send,
@@ -1326,11 +2194,39 @@ async fn mark_in_block<Payload, Item, Key>(
         .send(MempoolMsg::MarkInBlock {
             ids: ids.collect(),
             block,
+            parent,
         })
         .await
         .unwrap_or_else(|(e, _)| tracing::error!("Could not mark items in block: {e}"));
 }
 
+async fn mark_finalized<Payload, Item, Key>(
+    mempool: OutboundRelay<MempoolMsg<HeaderId, Payload, Item, Key>>,
+    block: HeaderId,
+) where
+    Key: Send,
+    Payload: Send,
+{
+    mempool
+        .send(MempoolMsg::MarkFinalized { block })
+        .await
+        .unwrap_or_else(|(e, _)| tracing::error!("Could not mark block finalized: {e}"));
+}
+
+async fn update_mempool_tip<Payload, Item, Key>(
+    mempool: OutboundRelay<MempoolMsg<HeaderId, Payload, Item, Key>>,
+    block: HeaderId,
+    state: LedgerState,
+) where
+    Key: Send,
+    Payload: Send,
+{
+    mempool
+        .send(MempoolMsg::UpdateTip { block, state })
+        .await
+        .unwrap_or_else(|(e, _)| tracing::error!("Could not update mempool tip: {e}"));
+}
+
 async fn mark_blob_in_block<BlobId: Debug + Send>(
     sampling_relay: SamplingRelay<BlobId>,
     blobs_id: Vec<BlobId>,
@@ -1358,3 +2254,295 @@ where
         .map_err(|(error, _)| Box::new(error) as DynError)?;
     receiver.await.map_err(|error| Box::new(error) as DynError)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZero;
+
+    use kzgrs_backend::dispersal::BlobInfo;
+    use nomos_core::{
+        da::blob::select::FillSize as FillSizeWithBlobs,
+        proofs::covenant::CovenantProof,
+        staking::NMO_UNIT,
+        tx::{bundle::Bundle, select::FillSize as FillSizeWithTx, Tx, TxKind},
+    };
+    use nomos_ledger::LedgerError;
+    use rand::thread_rng;
+
+    use super::*;
+
+    const NF_SK: cl::NullifierSecret = cl::NullifierSecret([1; 16]);
+
+    fn config() -> nomos_ledger::Config {
+        nomos_ledger::Config {
+            epoch_config: cryptarchia_engine::EpochConfig {
+                epoch_stake_distribution_stabilization: NonZero::new(3).unwrap(),
+                epoch_period_nonce_buffer: NonZero::new(3).unwrap(),
+                epoch_period_nonce_stabilization: NonZero::new(4).unwrap(),
+            },
+            consensus_config: cryptarchia_engine::Config {
+                security_param: NonZero::new(10).unwrap(),
+                // so that the leader wins most slots
+                active_slot_coeff: 0.9,
+            },
+        }
+    }
+
+    // spends `utxo`, whose covenant must be the no-op one
+    fn spend(utxo: cl::OutputWitness, ledger_state: &LedgerState) -> Tx {
+        let notes = ledger_state.spend_commitments();
+        let index = notes.index_of(&utxo.commit_note()).unwrap();
+        let ptx_witness = cl::PartialTxWitness {
+            inputs: vec![cl::InputWitness::from_output(
+                utxo,
+                NF_SK,
+                notes.witness(index).unwrap(),
+            )],
+            outputs: vec![cl::OutputWitness::new(utxo.note, NF_SK.commit())],
+            balance_blinding: cl::BalanceWitness::random_blinding(&mut thread_rng()),
+        };
+        // requires RISC0_DEV_MODE=1 on machines that cannot build real proofs
+        let prover = risc0_zkvm::default_prover();
+        let no_op = CovenantProof::prove_nop(
+            ptx_witness.inputs[0].nullifier(),
+            ptx_witness.commit().root(),
+            prover.as_ref(),
+        )
+        .unwrap();
+        let bundle = Bundle::prove(
+            &cl::BundleWitness::new(vec![ptx_witness]),
+            notes.root(),
+            vec![vec![no_op]],
+            prover.as_ref(),
+        )
+        .unwrap();
+        Tx::from(TxKind::Bundle(bundle))
+    }
+
+    // proposes a block on top of the tip at the first slot `leader` wins
+    async fn propose(
+        cryptarchia: &Cryptarchia,
+        leader: &Leader,
+        txs: Vec<Tx>,
+    ) -> Block<Tx, BlobInfo> {
+        let parent = cryptarchia.tip();
+        let mut slot = cryptarchia.tip_state().slot();
+        let proof = loop {
+            slot = slot + 1u64;
+            let epoch_state = cryptarchia.epoch_state_for_slot(slot).unwrap();
+            if let Some(proof) = leader
+                .build_proof_for(cryptarchia.tip_state(), epoch_state, slot, parent)
+                .await
+            {
+                break proof;
+            }
+        };
+        BlockBuilder::new(
+            FillSizeWithTx::<{ usize::MAX }, Tx>::new(),
+            FillSizeWithBlobs::<{ usize::MAX }, BlobInfo>::new(),
+            Builder::new(parent, slot, proof),
+        )
+        .with_transactions(txs.into_iter())
+        .with_blobs_info(std::iter::empty())
+        .build()
+        .unwrap()
+    }
+
+    // proposes a block on top of `parent` at the first slot after the tip
+    // `leader` wins, importing the orphan proofs available to it
+    async fn propose_on(
+        cryptarchia: &Cryptarchia,
+        leader: &Leader,
+        parent: HeaderId,
+    ) -> Block<Tx, BlobInfo> {
+        let state = cryptarchia.ledger.state(&parent).unwrap();
+        let mut slot = cryptarchia.tip_state().slot();
+        let proof = loop {
+            slot = slot + 1u64;
+            if let Some(proof) = leader
+                .build_proof_for(state, state.epoch_state(), slot, parent)
+                .await
+            {
+                break proof;
+            }
+        };
+        let orphans =
+            leader.orphan_proofs_for(cryptarchia.consensus.branches(), parent, slot, state);
+        BlockBuilder::new(
+            FillSizeWithTx::<{ usize::MAX }, Tx>::new(),
+            FillSizeWithBlobs::<{ usize::MAX }, BlobInfo>::new(),
+            Builder::new(parent, slot, proof).with_orphaned_proofs(orphans),
+        )
+        .with_transactions(std::iter::empty())
+        .with_blobs_info(std::iter::empty())
+        .build()
+        .unwrap()
+    }
+
+    fn eligible_notes(
+        cryptarchia: &Cryptarchia,
+        leader: &Leader,
+        id: HeaderId,
+    ) -> Vec<cl::NoteCommitment> {
+        leader
+            .notes_info(id, cryptarchia.ledger.state(&id).unwrap())
+            .unwrap()
+            .notes
+            .into_iter()
+            .filter(|note| note.eligible)
+            .map(|note| note.commitment)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_notes_spent_in_other_branches() {
+        let genesis = HeaderId::from([0; 32]);
+        let notes = [
+            cl::NoteWitness::basic(100, NMO_UNIT, &mut thread_rng()),
+            cl::NoteWitness::basic(100, NMO_UNIT, &mut thread_rng()),
+        ];
+        let commitments = notes.map(|note| note.commit(NF_SK.commit()));
+        let genesis_state = LedgerState::from_commitments(commitments, 200);
+        let mut cryptarchia = Cryptarchia::new(genesis, genesis_state, config());
+        let mut leader = Leader::new(genesis, notes.to_vec(), NF_SK, config());
+
+        let block_a = propose_on(&cryptarchia, &leader, genesis).await;
+        assert!(block_a.header().orphaned_proofs().is_empty());
+        cryptarchia = cryptarchia.try_apply_block(&block_a).unwrap();
+        leader.follow_chain(block_a.header());
+        let spent_a = block_a.header().leader_proof().nullifier();
+        // only the note spent by A evolves
+        let tracked: Vec<_> = leader
+            .notes(&block_a.header().id())
+            .unwrap()
+            .iter()
+            .map(|note| note.commit(NF_SK.commit()))
+            .collect();
+        assert_eq!(tracked.len(), 2);
+        for cm in commitments {
+            assert_eq!(
+                tracked.contains(&cm),
+                cl::Nullifier::new(NF_SK, cm) != spent_a
+            );
+        }
+
+        // the note spent by A is not used in the fork on top of genesis, which
+        // imports the proof of A instead
+        let unspent: Vec<_> = commitments
+            .into_iter()
+            .filter(|cm| cl::Nullifier::new(NF_SK, *cm) != spent_a)
+            .collect();
+        assert_eq!(eligible_notes(&cryptarchia, &leader, genesis), unspent);
+        let block_b = propose_on(&cryptarchia, &leader, genesis).await;
+        assert_ne!(block_b.header().leader_proof().nullifier(), spent_a);
+        assert_eq!(
+            block_b
+                .header()
+                .orphaned_proofs()
+                .iter()
+                .map(Header::id)
+                .collect::<Vec<_>>(),
+            vec![block_a.header().id()]
+        );
+        cryptarchia = cryptarchia.try_apply_block(&block_b).unwrap();
+        leader.follow_chain(block_b.header());
+        let evolved = leader.notes(&block_b.header().id()).unwrap();
+        assert_eq!(evolved.len(), 2);
+        assert!(evolved
+            .iter()
+            .all(|note| !commitments.contains(&note.commit(NF_SK.commit()))));
+
+        // proofs already imported are not imported again
+        let state_b = cryptarchia.ledger.state(&block_b.header().id()).unwrap();
+        assert!(leader
+            .orphan_proofs_for(
+                cryptarchia.consensus.branches(),
+                block_b.header().id(),
+                block_b.header().slot() + 1u64,
+                state_b,
+            )
+            .is_empty());
+
+        // notes spent only in pruned blocks can be used again
+        leader.prune_notes_at(&[block_a.header().id(), block_b.header().id()]);
+        assert_eq!(
+            eligible_notes(&cryptarchia, &leader, genesis),
+            commitments.to_vec()
+        );
+    }
+
+    #[test]
+    fn test_finalized_branches() {
+        let genesis = HeaderId::from([0; 32]);
+        let mut cryptarchia =
+            Cryptarchia::new(genesis, LedgerState::from_commitments([], 0), config());
+        let mut parent = genesis;
+        for height in 1..=15 {
+            let id = HeaderId::from([height; 32]);
+            cryptarchia.consensus = cryptarchia
+                .consensus
+                .receive_block(id, parent, u64::from(height).into())
+                .unwrap();
+            parent = id;
+        }
+        let heights = |branches: Vec<cryptarchia_engine::Branch<HeaderId>>| {
+            branches
+                .iter()
+                .map(cryptarchia_engine::Branch::length)
+                .collect::<Vec<_>>()
+        };
+
+        // blocks deeper than the security parameter, genesis excluded
+        assert_eq!(
+            heights(cryptarchia.finalized_branches(|_| false)),
+            vec![1, 2, 3, 4, 5]
+        );
+        assert_eq!(
+            heights(cryptarchia.finalized_branches(|branch| branch.length() < 3)),
+            vec![3, 4, 5]
+        );
+        assert_eq!(
+            heights(
+                cryptarchia.finalized_branches(|branch| branch.id() == HeaderId::from([4; 32]))
+            ),
+            vec![5]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_block_spending_spent_note_is_rejected() {
+        let genesis = HeaderId::from([0; 32]);
+        let leader_note = cl::NoteWitness::basic(100, NMO_UNIT, &mut thread_rng());
+        let mut utxo = cl::OutputWitness::new(
+            cl::NoteWitness::basic(10, NMO_UNIT, &mut thread_rng()),
+            NF_SK.commit(),
+        );
+        utxo.note.covenant = CovenantProof::nop_constraint();
+        let genesis_state = LedgerState::from_commitments(
+            [leader_note.commit(NF_SK.commit()), utxo.commit_note()],
+            100,
+        );
+        let tx = spend(utxo, &genesis_state);
+
+        let mut cryptarchia = Cryptarchia::new(genesis, genesis_state, config());
+        let mut leader = Leader::new(genesis, vec![leader_note], NF_SK, config());
+
+        let block = propose(&cryptarchia, &leader, vec![tx.clone()]).await;
+        cryptarchia = cryptarchia.try_apply_block(&block).unwrap();
+        leader.follow_chain(block.header());
+        assert!(cryptarchia
+            .tip_state()
+            .is_nullified(&cl::Nullifier::new(NF_SK, utxo.commit_note())));
+
+        // the same spend is rejected on top of the block including it
+        let block = propose(&cryptarchia, &leader, vec![tx]).await;
+        assert!(matches!(
+            cryptarchia.try_apply_block(&block),
+            Err(Error::Ledger(LedgerError::DoubleSpend(_)))
+        ));
+
+        // while the block is valid without it
+        let block = propose(&cryptarchia, &leader, vec![]).await;
+        assert!(cryptarchia.try_apply_block(&block).is_ok());
+    }
+}
@@ -1,6 +1,7 @@
 use std::hash::Hash;
 
-use nomos_core::block::Block;
+use cryptarchia_engine::Slot;
+use nomos_core::{block::Block, header::HeaderId};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize)]
@@ -11,3 +12,44 @@ where
 {
     Block(Block<Tx, Blob>),
 }
+
+/// Requests exchanged between peers to fetch blocks they are missing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ChainSyncRequest {
+    /// Request a single block by its header id
+    Block(HeaderId),
+    /// Request the tip of the peer's local chain
+    Tip,
+    /// Request the blocks leading to `tip`, starting right after the most
+    /// recent block in `locator` that is an ancestor of `tip`.
+    ///
+    /// `locator` is a list of block ids known to the requester, ordered from
+    /// the most recent one.
+    Blocks {
+        locator: Vec<HeaderId>,
+        tip: HeaderId,
+        limit: usize,
+    },
+}
+
+/// Tip of the local chain of a peer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainTip {
+    pub id: HeaderId,
+    pub slot: Slot,
+    pub height: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ChainSyncResponse<Tx, Blob>
+where
+    Tx: Clone + Eq + Hash,
+    Blob: Clone + Eq + Hash,
+{
+    /// The requested block, if known by the peer
+    Block(Option<Block<Tx, Blob>>),
+    Tip(ChainTip),
+    /// Blocks ordered from the oldest one, empty if `tip` is not known by the
+    /// peer
+    Blocks(Vec<Block<Tx, Blob>>),
+}
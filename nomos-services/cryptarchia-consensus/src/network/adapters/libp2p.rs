@@ -1,8 +1,11 @@
 use std::{hash::Hash, marker::PhantomData};
 
-use nomos_core::{block::Block, wire};
+use nomos_core::{block::Block, header::HeaderId, wire};
 use nomos_network::{
-    backends::libp2p::{Command, Event, EventKind, Libp2p, PubSubCommand::Subscribe},
+    backends::libp2p::{
+        ChainSyncCommand, Command, Event, EventKind, InboundRequestId, Libp2p, PeerId,
+        PubSubCommand::Subscribe,
+    },
     NetworkMsg, NetworkService,
 };
 use overwatch::{
@@ -10,16 +13,19 @@ use overwatch::{
     DynError,
 };
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tokio::sync::oneshot;
 use tokio_stream::{
     wrappers::{errors::BroadcastStreamRecvError, BroadcastStream},
     StreamExt,
 };
 
 use crate::{
-    messages::NetworkMessage,
-    network::{BoxedStream, NetworkAdapter},
+    messages::{ChainSyncRequest, ChainSyncResponse, ChainTip, NetworkMessage},
+    network::{BoxedStream, IncomingChainSyncRequest, NetworkAdapter},
 };
 
+const UNEXPECTED_RESPONSE: &str = "unexpected chain sync response";
+
 type Relay<T, RuntimeServiceId> =
     OutboundRelay<<NetworkService<T, RuntimeServiceId> as ServiceData>::Message>;
 
@@ -55,6 +61,61 @@ where
             tracing::error!("error subscribing to {topic}: {e}");
         };
     }
+
+    async fn chain_sync_request(
+        &self,
+        peer_id: Option<PeerId>,
+        request: &ChainSyncRequest,
+    ) -> Result<ChainSyncResponse<Tx, BlobCert>, DynError>
+    where
+        Tx: DeserializeOwned,
+        BlobCert: DeserializeOwned,
+    {
+        let (reply, receiver) = oneshot::channel();
+        self.network_relay
+            .send(NetworkMsg::Process(Command::ChainSync(
+                ChainSyncCommand::Request {
+                    peer_id,
+                    request: wire::serialize(request)?.into_boxed_slice(),
+                    reply,
+                },
+            )))
+            .await
+            .map_err(|(e, _)| Box::new(e))?;
+        let response = receiver.await??;
+        Ok(wire::deserialize(&response)?)
+    }
+
+    async fn respond(
+        relay: Relay<Libp2p, RuntimeServiceId>,
+        request_id: InboundRequestId,
+        response: oneshot::Receiver<ChainSyncResponse<Tx, BlobCert>>,
+    ) {
+        // If the request is not answered the network backend will eventually time it
+        // out and notify the failure to the requesting peer
+        let Ok(response) = response.await else {
+            tracing::debug!("chain sync request {request_id} was dropped without a response");
+            return;
+        };
+        let response = match wire::serialize(&response) {
+            Ok(response) => response.into_boxed_slice(),
+            Err(e) => {
+                tracing::error!("failed to serialize chain sync response: {e}");
+                return;
+            }
+        };
+        if let Err((e, _)) = relay
+            .send(NetworkMsg::Process(Command::ChainSync(
+                ChainSyncCommand::Respond {
+                    request_id,
+                    response,
+                },
+            )))
+            .await
+        {
+            tracing::error!("failed to send chain sync response: {e}");
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -63,11 +124,13 @@ impl<Tx, BlobCert, RuntimeServiceId> NetworkAdapter<RuntimeServiceId>
 where
     Tx: Serialize + DeserializeOwned + Clone + Eq + Hash + Send + Sync + 'static,
     BlobCert: Serialize + DeserializeOwned + Clone + Eq + Hash + Send + Sync + 'static,
+    RuntimeServiceId: Send + Sync + 'static,
 {
     type Backend = Libp2p;
     type Settings = LibP2pAdapterSettings;
     type Tx = Tx;
     type BlobCertificate = BlobCert;
+    type PeerId = PeerId;
 
     async fn new(settings: Self::Settings, network_relay: Relay<Libp2p, RuntimeServiceId>) -> Self {
         let relay = network_relay.clone();
@@ -113,6 +176,7 @@ where
                             }
                         },
                     ),
+                    Ok(Event::ChainSyncRequest { .. }) => None,
                     Err(BroadcastStreamRecvError::Lagged(n)) => {
                         tracing::error!("lagged messages: {n}");
                         None
@@ -121,4 +185,104 @@ where
             }),
         ))
     }
+
+    async fn request_block(
+        &self,
+        id: HeaderId,
+    ) -> Result<Option<Block<Self::Tx, Self::BlobCertificate>>, DynError> {
+        match self
+            .chain_sync_request(None, &ChainSyncRequest::Block(id))
+            .await?
+        {
+            ChainSyncResponse::Block(block) => Ok(block),
+            _ => Err(UNEXPECTED_RESPONSE.into()),
+        }
+    }
+
+    async fn connected_peers(&self) -> Result<Vec<Self::PeerId>, DynError> {
+        let (reply, receiver) = oneshot::channel();
+        self.network_relay
+            .send(NetworkMsg::Process(Command::ChainSync(
+                ChainSyncCommand::ConnectedPeers { reply },
+            )))
+            .await
+            .map_err(|(e, _)| Box::new(e))?;
+        Ok(receiver.await?)
+    }
+
+    async fn request_tip(&self, peer: Self::PeerId) -> Result<ChainTip, DynError> {
+        match self
+            .chain_sync_request(Some(peer), &ChainSyncRequest::Tip)
+            .await?
+        {
+            ChainSyncResponse::Tip(tip) => Ok(tip),
+            _ => Err(UNEXPECTED_RESPONSE.into()),
+        }
+    }
+
+    async fn request_blocks(
+        &self,
+        peer: Self::PeerId,
+        locator: Vec<HeaderId>,
+        tip: HeaderId,
+        limit: usize,
+    ) -> Result<Vec<Block<Self::Tx, Self::BlobCertificate>>, DynError> {
+        let request = ChainSyncRequest::Blocks {
+            locator,
+            tip,
+            limit,
+        };
+        match self.chain_sync_request(Some(peer), &request).await? {
+            ChainSyncResponse::Blocks(blocks) => Ok(blocks),
+            _ => Err(UNEXPECTED_RESPONSE.into()),
+        }
+    }
+
+    async fn chain_sync_requests_stream(
+        &self,
+    ) -> Result<BoxedStream<IncomingChainSyncRequest<Self::Tx, Self::BlobCertificate>>, DynError>
+    {
+        let (sender, receiver) = tokio::sync::oneshot::channel();
+        if let Err((e, _)) = self
+            .network_relay
+            .send(NetworkMsg::Subscribe {
+                kind: EventKind::ChainSyncRequest,
+                sender,
+            })
+            .await
+        {
+            return Err(Box::new(e));
+        }
+        let relay = self.network_relay.clone();
+        Ok(Box::new(
+            BroadcastStream::new(receiver.await.map_err(Box::new)?).filter_map(move |event| {
+                match event {
+                    Ok(Event::ChainSyncRequest {
+                        peer_id,
+                        request_id,
+                        request,
+                    }) => match wire::deserialize::<ChainSyncRequest>(&request) {
+                        Ok(request) => {
+                            tracing::debug!("received chain sync request from {peer_id}");
+                            let (reply_channel, response) = oneshot::channel();
+                            tokio::spawn(Self::respond(relay.clone(), request_id, response));
+                            Some(IncomingChainSyncRequest {
+                                request,
+                                reply_channel,
+                            })
+                        }
+                        Err(e) => {
+                            tracing::debug!("unrecognized chain sync request from {peer_id}: {e}");
+                            None
+                        }
+                    },
+                    Ok(Event::Message(_)) => None,
+                    Err(BroadcastStreamRecvError::Lagged(n)) => {
+                        tracing::error!("lagged chain sync requests: {n}");
+                        None
+                    }
+                }
+            }),
+        ))
+    }
 }
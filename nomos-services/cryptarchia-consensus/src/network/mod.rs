@@ -1,24 +1,39 @@
 pub mod adapters;
 
-use std::hash::Hash;
+use std::{fmt::Display, hash::Hash};
 
 use futures::Stream;
-use nomos_core::block::Block;
+use nomos_core::{block::Block, header::HeaderId};
 use nomos_network::{backends::NetworkBackend, NetworkService};
 use overwatch::{
     services::{relay::OutboundRelay, ServiceData},
     DynError,
 };
 use serde::{de::DeserializeOwned, Serialize};
+use tokio::sync::oneshot;
+
+use crate::messages::{ChainSyncRequest, ChainSyncResponse, ChainTip};
 
 type BoxedStream<T> = Box<dyn Stream<Item = T> + Send + Sync + Unpin>;
 
+/// A chain sync request received from a peer, which is answered by sending
+/// the response through `reply_channel`.
+pub struct IncomingChainSyncRequest<Tx, BlobCertificate>
+where
+    Tx: Clone + Eq + Hash,
+    BlobCertificate: Clone + Eq + Hash,
+{
+    pub request: ChainSyncRequest,
+    pub reply_channel: oneshot::Sender<ChainSyncResponse<Tx, BlobCertificate>>,
+}
+
 #[async_trait::async_trait]
 pub trait NetworkAdapter<RuntimeServiceId> {
     type Backend: NetworkBackend<RuntimeServiceId> + 'static;
     type Settings: Clone + 'static;
     type Tx: Serialize + DeserializeOwned + Clone + Eq + Hash + 'static;
     type BlobCertificate: Serialize + DeserializeOwned + Clone + Eq + Hash + 'static;
+    type PeerId: Clone + Eq + Hash + Display + Send + Sync + 'static;
     async fn new(
         settings: Self::Settings,
         network_relay: OutboundRelay<
@@ -28,4 +43,35 @@ pub trait NetworkAdapter<RuntimeServiceId> {
     async fn blocks_stream(
         &self,
     ) -> Result<BoxedStream<Block<Self::Tx, Self::BlobCertificate>>, DynError>;
+
+    /// Fetches a block by its header id from one of the connected peers.
+    ///
+    /// Returns `None` if the peer does not know about the block.
+    async fn request_block(
+        &self,
+        id: HeaderId,
+    ) -> Result<Option<Block<Self::Tx, Self::BlobCertificate>>, DynError>;
+
+    /// Peers that can currently be asked for blocks.
+    async fn connected_peers(&self) -> Result<Vec<Self::PeerId>, DynError>;
+
+    /// Fetches the tip of the local chain of `peer`.
+    async fn request_tip(&self, peer: Self::PeerId) -> Result<ChainTip, DynError>;
+
+    /// Fetches from `peer` up to `limit` blocks of the chain ending at `tip`,
+    /// starting after the most recent block in `locator` it belongs to.
+    ///
+    /// Blocks are returned ordered from the oldest one.
+    async fn request_blocks(
+        &self,
+        peer: Self::PeerId,
+        locator: Vec<HeaderId>,
+        tip: HeaderId,
+        limit: usize,
+    ) -> Result<Vec<Block<Self::Tx, Self::BlobCertificate>>, DynError>;
+
+    /// Stream of chain sync requests received from peers.
+    async fn chain_sync_requests_stream(
+        &self,
+    ) -> Result<BoxedStream<IncomingChainSyncRequest<Self::Tx, Self::BlobCertificate>>, DynError>;
 }
@@ -0,0 +1,140 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+/// Blocks received before their parent, waiting for the missing ancestors to
+/// be fetched from peers.
+///
+/// The buffer is bounded: once full, the oldest orphans are evicted to make
+/// room for new ones.
+pub struct OrphanBlocks<Id, Block> {
+    // orphans indexed by the id of their (missing) parent
+    by_parent: HashMap<Id, Vec<(Id, Block)>>,
+    // (parent, id) of the buffered orphans, in insertion order
+    insertion_order: VecDeque<(Id, Id)>,
+    // blocks that have been requested to peers and are yet to be received
+    requested: HashSet<Id>,
+    capacity: usize,
+}
+
+impl<Id, Block> OrphanBlocks<Id, Block>
+where
+    Id: Copy + Eq + Hash,
+{
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            by_parent: HashMap::new(),
+            insertion_order: VecDeque::new(),
+            requested: HashSet::new(),
+            capacity,
+        }
+    }
+
+    /// Buffers a block whose parent is not known yet.
+    ///
+    /// Returns `false` if the block was already buffered.
+    pub fn insert(&mut self, id: Id, parent: Id, block: Block) -> bool {
+        if self.contains(&id, &parent) {
+            return false;
+        }
+        while self.insertion_order.len() >= self.capacity {
+            let Some((oldest_parent, oldest_id)) = self.insertion_order.pop_front() else {
+                break;
+            };
+            self.remove(&oldest_id, &oldest_parent);
+        }
+        if self.capacity == 0 {
+            return false;
+        }
+        self.by_parent.entry(parent).or_default().push((id, block));
+        self.insertion_order.push_back((parent, id));
+        true
+    }
+
+    /// Takes all the buffered blocks that are children of `parent`.
+    pub fn take_children(&mut self, parent: &Id) -> Vec<Block> {
+        let children = self.by_parent.remove(parent).unwrap_or_default();
+        self.insertion_order
+            .retain(|(orphan_parent, _)| orphan_parent != parent);
+        children.into_iter().map(|(_, block)| block).collect()
+    }
+
+    /// Marks a block as requested from peers.
+    ///
+    /// Returns `false` if a request for the block is already in flight.
+    pub fn mark_requested(&mut self, id: Id) -> bool {
+        self.requested.insert(id)
+    }
+
+    /// Marks a request as completed, successfully or not, so that the block
+    /// can be requested again if needed.
+    pub fn request_completed(&mut self, id: &Id) {
+        self.requested.remove(id);
+    }
+
+    pub fn len(&self) -> usize {
+        self.insertion_order.len()
+    }
+
+    fn contains(&self, id: &Id, parent: &Id) -> bool {
+        self.by_parent
+            .get(parent)
+            .is_some_and(|children| children.iter().any(|(child, _)| child == id))
+    }
+
+    fn remove(&mut self, id: &Id, parent: &Id) {
+        if let Some(children) = self.by_parent.get_mut(parent) {
+            children.retain(|(child, _)| child != id);
+            if children.is_empty() {
+                self.by_parent.remove(parent);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take_children() {
+        let mut orphans = OrphanBlocks::new(10);
+        assert!(orphans.insert(2, 1, "b2"));
+        assert!(orphans.insert(3, 1, "b3"));
+        assert!(orphans.insert(4, 2, "b4"));
+        // duplicates are ignored
+        assert!(!orphans.insert(2, 1, "b2"));
+        assert_eq!(orphans.len(), 3);
+
+        let mut children = orphans.take_children(&1);
+        children.sort_unstable();
+        assert_eq!(children, vec!["b2", "b3"]);
+        assert_eq!(orphans.len(), 1);
+        assert!(orphans.take_children(&1).is_empty());
+        assert_eq!(orphans.take_children(&2), vec!["b4"]);
+        assert_eq!(orphans.len(), 0);
+    }
+
+    #[test]
+    fn test_capacity() {
+        let mut orphans = OrphanBlocks::new(2);
+        assert!(orphans.insert(2, 1, "b2"));
+        assert!(orphans.insert(3, 2, "b3"));
+        // the oldest orphan is evicted
+        assert!(orphans.insert(4, 3, "b4"));
+        assert_eq!(orphans.len(), 2);
+        assert!(orphans.take_children(&1).is_empty());
+        assert_eq!(orphans.take_children(&2), vec!["b3"]);
+        assert_eq!(orphans.take_children(&3), vec!["b4"]);
+    }
+
+    #[test]
+    fn test_requests() {
+        let mut orphans = OrphanBlocks::<u8, ()>::new(2);
+        assert!(orphans.mark_requested(1));
+        assert!(!orphans.mark_requested(1));
+        orphans.request_completed(&1);
+        assert!(orphans.mark_requested(1));
+    }
+}
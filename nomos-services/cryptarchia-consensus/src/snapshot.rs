@@ -0,0 +1,377 @@
+use std::path::{Path, PathBuf};
+
+use bytes::Bytes;
+use cryptarchia_engine::Slot;
+use ed25519_dalek::{Signature, Signer as _, SigningKey, VerifyingKey};
+use key_management_system::{keys::SupportedKeyTypes, public_key, sign, NodeKMSRelay};
+use nomos_core::{header::HeaderId, wire};
+use nomos_ledger::LedgerState;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+// Prefix of every snapshot file, used to tell them apart from arbitrary data
+const SNAPSHOT_MAGIC: [u8; 4] = *b"NLSN";
+pub const SNAPSHOT_FORMAT_VERSION: u16 = 1;
+const SNAPSHOT_HEADER_SIZE: usize = SNAPSHOT_MAGIC.len() + size_of::<u16>();
+
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] wire::Error),
+    #[error("Not a ledger snapshot")]
+    InvalidFormat,
+    #[error("Unsupported snapshot format version {0}")]
+    UnsupportedVersion(u16),
+    #[error("Snapshot signer is not trusted")]
+    UntrustedSigner,
+    #[error("Invalid snapshot signature")]
+    InvalidSignature,
+    #[error("Snapshot block {0} not found in the block store")]
+    BlockNotFound(HeaderId),
+    #[error("Snapshot slot {snapshot:?} does not match the slot {block:?} of the stored block")]
+    SlotMismatch { snapshot: Slot, block: Slot },
+    #[error("No ledger state for block {0}")]
+    StateNotFound(HeaderId),
+    #[error("Invalid snapshot signing key")]
+    InvalidSigningKey,
+    #[error("Could not sign snapshot: {0}")]
+    Signing(String),
+    #[error("Snapshot export is not configured")]
+    ExportDisabled,
+}
+
+/// Settings to bootstrap the ledger from a snapshot instead of genesis.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotSettings {
+    pub path: PathBuf,
+    /// Keys whose signed snapshots are accepted
+    pub trusted_signers: Vec<VerifyingKey>,
+}
+
+/// Settings to export signed snapshots of the local ledger.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotExportSettings {
+    /// Directory where exported snapshots are written
+    pub dir: PathBuf,
+    /// Id of the ed25519 key signing exported snapshots in the key
+    /// management service
+    pub signing_key_id: String,
+}
+
+impl SnapshotExportSettings {
+    /// Signs `snapshot` through the key management service and writes it to
+    /// `dir`, returning the path of the snapshot file.
+    pub async fn export(
+        &self,
+        kms: &NodeKMSRelay,
+        snapshot: LedgerSnapshot,
+    ) -> Result<PathBuf, SnapshotError> {
+        let signer = public_key(kms, self.signing_key_id.clone(), SupportedKeyTypes::Ed25519)
+            .await
+            .map_err(|e| SnapshotError::Signing(e.to_string()))?;
+        let signer = VerifyingKey::try_from(signer.as_ref())
+            .map_err(|_| SnapshotError::InvalidSigningKey)?;
+        let signature = sign(
+            kms,
+            self.signing_key_id.clone(),
+            SupportedKeyTypes::Ed25519,
+            Bytes::from(snapshot.signed_bytes()?),
+        )
+        .await
+        .map_err(|e| SnapshotError::Signing(e.to_string()))?;
+        let signature =
+            Signature::from_slice(&signature).map_err(|_| SnapshotError::InvalidSignature)?;
+
+        let path = self.dir.join(format!("{}.snapshot", snapshot.header_id));
+        snapshot.with_signature(signer, signature).save(&path)?;
+        Ok(path)
+    }
+}
+
+/// The ledger state right after applying block `header_id`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LedgerSnapshot {
+    pub header_id: HeaderId,
+    pub state: LedgerState,
+}
+
+impl LedgerSnapshot {
+    #[must_use]
+    pub const fn new(header_id: HeaderId, state: LedgerState) -> Self {
+        Self { header_id, state }
+    }
+
+    pub fn sign(self, signing_key: &SigningKey) -> Result<SignedLedgerSnapshot, SnapshotError> {
+        let signature = signing_key.sign(&self.signed_bytes()?);
+        Ok(self.with_signature(signing_key.verifying_key(), signature))
+    }
+
+    /// Attaches a `signature` of the snapshot made by `signer` elsewhere, e.g.
+    /// by the key management service.
+    #[must_use]
+    pub const fn with_signature(
+        self,
+        signer: VerifyingKey,
+        signature: Signature,
+    ) -> SignedLedgerSnapshot {
+        SignedLedgerSnapshot {
+            snapshot: self,
+            signer,
+            signature,
+        }
+    }
+
+    /// Checks the snapshot against the slot of block `header_id` in the local
+    /// block store, if any.
+    pub fn check_block_slot(&self, block_slot: Option<Slot>) -> Result<(), SnapshotError> {
+        match block_slot {
+            None => Err(SnapshotError::BlockNotFound(self.header_id)),
+            Some(block) if block != self.state.slot() => Err(SnapshotError::SlotMismatch {
+                snapshot: self.state.slot(),
+                block,
+            }),
+            Some(_) => Ok(()),
+        }
+    }
+
+    // The signature also covers the format version, so that a snapshot cannot be
+    // reinterpreted under a different format
+    fn signed_bytes(&self) -> Result<Vec<u8>, SnapshotError> {
+        let mut bytes = snapshot_header().to_vec();
+        bytes.extend(wire::serialize(self)?);
+        Ok(bytes)
+    }
+}
+
+/// A [`LedgerSnapshot`] signed by the node that produced it.
+///
+/// On disk, snapshots are stored as a magic prefix and a big endian format
+/// version, followed by the serialized signed snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedLedgerSnapshot {
+    snapshot: LedgerSnapshot,
+    signer: VerifyingKey,
+    signature: Signature,
+}
+
+impl SignedLedgerSnapshot {
+    #[must_use]
+    pub const fn signer(&self) -> &VerifyingKey {
+        &self.signer
+    }
+
+    /// Returns the snapshot if it was signed by one of `trusted_signers`.
+    pub fn verify(self, trusted_signers: &[VerifyingKey]) -> Result<LedgerSnapshot, SnapshotError> {
+        if !trusted_signers.contains(&self.signer) {
+            return Err(SnapshotError::UntrustedSigner);
+        }
+        self.signer
+            .verify_strict(&self.snapshot.signed_bytes()?, &self.signature)
+            .map_err(|_| SnapshotError::InvalidSignature)?;
+        Ok(self.snapshot)
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SnapshotError> {
+        let mut bytes = snapshot_header().to_vec();
+        bytes.extend(wire::serialize(self)?);
+        Ok(bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SnapshotError> {
+        let (header, body) = bytes
+            .split_at_checked(SNAPSHOT_HEADER_SIZE)
+            .ok_or(SnapshotError::InvalidFormat)?;
+        let (magic, version) = header.split_at(SNAPSHOT_MAGIC.len());
+        if magic != SNAPSHOT_MAGIC {
+            return Err(SnapshotError::InvalidFormat);
+        }
+        let version = u16::from_be_bytes(version.try_into().expect("version is two bytes long"));
+        if version != SNAPSHOT_FORMAT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+        Ok(wire::deserialize(body)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), SnapshotError> {
+        Ok(std::fs::write(path, self.to_bytes()?)?)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, SnapshotError> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+}
+
+fn snapshot_header() -> [u8; SNAPSHOT_HEADER_SIZE] {
+    let mut header = [0; SNAPSHOT_HEADER_SIZE];
+    let (magic, version) = header.split_at_mut(SNAPSHOT_MAGIC.len());
+    magic.copy_from_slice(&SNAPSHOT_MAGIC);
+    version.copy_from_slice(&SNAPSHOT_FORMAT_VERSION.to_be_bytes());
+    header
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use cl::note::NoteCommitment;
+    use key_management_system::{
+        backend::{
+            preload::{PreloadKMSBackend, PreloadKMSBackendSettings},
+            KMSBackend as _,
+        },
+        keys::{ed25519::Ed25519Key, nullifier::NullifierSecretKey, Key},
+        KMSMessage, NodeKMSBackend,
+    };
+    use overwatch::services::relay::relay;
+
+    use super::*;
+
+    fn snapshot() -> LedgerSnapshot {
+        let state = LedgerState::from_commitments([NoteCommitment([1; 32])], 1);
+        LedgerSnapshot::new([2; 32].into(), state)
+    }
+
+    #[test]
+    fn test_signed_snapshot_roundtrip() {
+        let signing_key = SigningKey::from_bytes(&[3; 32]);
+        let signed = snapshot().sign(&signing_key).unwrap();
+
+        let decoded = SignedLedgerSnapshot::from_bytes(&signed.to_bytes().unwrap()).unwrap();
+        assert_eq!(decoded, signed);
+        assert_eq!(
+            decoded.verify(&[signing_key.verifying_key()]).unwrap(),
+            snapshot()
+        );
+    }
+
+    #[test]
+    fn test_untrusted_or_tampered_snapshot() {
+        let signing_key = SigningKey::from_bytes(&[3; 32]);
+        let signed = snapshot().sign(&signing_key).unwrap();
+
+        let other_key = SigningKey::from_bytes(&[4; 32]).verifying_key();
+        assert!(matches!(
+            signed.clone().verify(&[other_key]),
+            Err(SnapshotError::UntrustedSigner)
+        ));
+
+        let mut tampered = signed;
+        tampered.snapshot.header_id = [5; 32].into();
+        assert!(matches!(
+            tampered.verify(&[signing_key.verifying_key()]),
+            Err(SnapshotError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn test_invalid_format() {
+        let signed = snapshot().sign(&SigningKey::from_bytes(&[3; 32])).unwrap();
+        let mut bytes = signed.to_bytes().unwrap();
+
+        bytes[SNAPSHOT_MAGIC.len() + 1] += 1;
+        assert!(matches!(
+            SignedLedgerSnapshot::from_bytes(&bytes),
+            Err(SnapshotError::UnsupportedVersion(2))
+        ));
+
+        bytes[0] = 0;
+        assert!(matches!(
+            SignedLedgerSnapshot::from_bytes(&bytes),
+            Err(SnapshotError::InvalidFormat)
+        ));
+        assert!(matches!(
+            SignedLedgerSnapshot::from_bytes(&[]),
+            Err(SnapshotError::InvalidFormat)
+        ));
+    }
+
+    // serves the requests of the snapshot export the way the key management
+    // service does, with the keys preloaded
+    fn kms_relay(keys: HashMap<String, Arc<Key>>) -> NodeKMSRelay {
+        let (mut inbound_relay, outbound_relay) = relay::<KMSMessage<NodeKMSBackend>>(16);
+        let mut backend = NodeKMSBackend::Preload(
+            PreloadKMSBackend::new(PreloadKMSBackendSettings { keys }).unwrap(),
+        );
+        tokio::spawn(async move {
+            while let Some(msg) = inbound_relay.recv().await {
+                match msg {
+                    KMSMessage::Register {
+                        key_id,
+                        key_type,
+                        reply_channel,
+                    } => {
+                        // dropping the reply channel tells the requester
+                        if let Ok(key_id) = backend.register(key_id, key_type) {
+                            reply_channel.send(key_id).unwrap();
+                        }
+                    }
+                    KMSMessage::PublicKey {
+                        key_id,
+                        reply_channel,
+                    } => {
+                        reply_channel
+                            .send(backend.public_key(key_id).unwrap())
+                            .unwrap();
+                    }
+                    KMSMessage::Sign {
+                        key_id,
+                        data,
+                        reply_channel,
+                    } => {
+                        reply_channel
+                            .send(backend.sign(key_id, data).unwrap())
+                            .unwrap();
+                    }
+                    KMSMessage::Execute { .. } => panic!("snapshot keys are not exported"),
+                }
+            }
+        });
+        outbound_relay
+    }
+
+    #[tokio::test]
+    async fn test_export_snapshot() {
+        let dir = std::env::temp_dir().join(format!("snapshot-export-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let kms = kms_relay(HashMap::from([
+            (
+                "snapshot".to_owned(),
+                Arc::new(Key::Ed25519(Ed25519Key::from_bytes(&[3; 32]))),
+            ),
+            (
+                "leader".to_owned(),
+                Arc::new(Key::NullifierSecret(NullifierSecretKey::new(
+                    cl::NullifierSecret([3; 16]),
+                ))),
+            ),
+        ]));
+
+        // not an ed25519 key
+        let settings = SnapshotExportSettings {
+            dir: dir.clone(),
+            signing_key_id: "leader".to_owned(),
+        };
+        assert!(matches!(
+            settings.export(&kms, snapshot()).await,
+            Err(SnapshotError::Signing(_))
+        ));
+
+        let settings = SnapshotExportSettings {
+            dir: dir.clone(),
+            signing_key_id: "snapshot".to_owned(),
+        };
+        let path = settings.export(&kms, snapshot()).await.unwrap();
+        let signer = SigningKey::from_bytes(&[3; 32]).verifying_key();
+        assert_eq!(
+            SignedLedgerSnapshot::load(&path)
+                .unwrap()
+                .verify(&[signer])
+                .unwrap(),
+            snapshot()
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -6,7 +6,10 @@ use nomos_ledger::LedgerState;
 use overwatch::services::state::ServiceState;
 use serde::{Deserialize, Serialize};
 
-use crate::{leadership::Leader, Cryptarchia, CryptarchiaSettings, Error};
+use crate::{
+    leadership::{Leader, LeaderNotesUpdates},
+    Cryptarchia, CryptarchiaSettings, Error,
+};
 
 /// Indicates that there's stored data so [`Cryptarchia`] should be recovered.
 /// However, the number of stored epochs is fewer than
@@ -25,6 +28,9 @@ pub struct GenesisRecoveryStrategy {
 /// As a result, a [`Cryptarchia`](cryptarchia_engine::Cryptarchia) instance
 /// must first be built from the security state and then recovered up to the
 /// `tip` epoch.
+///
+/// Until the chain is deeper than the security parameter, the block it was
+/// built from (genesis or a snapshot) is used as the security block.
 pub struct SecurityRecoveryStrategy {
     pub tip: HeaderId,
     pub security_block_id: HeaderId,
@@ -52,6 +58,9 @@ pub struct CryptarchiaConsensusState<
     security_block: Option<HeaderId>,
     security_ledger_state: Option<LedgerState>,
     security_leader_notes: Option<Vec<NoteWitness>>,
+    // notes added and removed at runtime, applied again on recovery
+    #[serde(default)]
+    leader_notes_updates: LeaderNotesUpdates,
     _txs: PhantomData<TxS>,
     _bxs: PhantomData<BxS>,
     _network_adapter_settings: PhantomData<NetworkAdapterSettings>,
@@ -68,7 +77,7 @@ impl<TxS, BxS, NetworkAdapterSettings, BlendAdapterSettings, TimeBackendSettings
         TimeBackendSettings,
     >
 {
-    pub const fn new(
+    pub fn new(
         tip: Option<HeaderId>,
         security_block: Option<HeaderId>,
         security_ledger_state: Option<LedgerState>,
@@ -79,6 +88,7 @@ impl<TxS, BxS, NetworkAdapterSettings, BlendAdapterSettings, TimeBackendSettings
             security_block,
             security_ledger_state,
             security_leader_notes,
+            leader_notes_updates: LeaderNotesUpdates::default(),
             _txs: PhantomData,
             _bxs: PhantomData,
             _network_adapter_settings: PhantomData,
@@ -88,7 +98,14 @@ impl<TxS, BxS, NetworkAdapterSettings, BlendAdapterSettings, TimeBackendSettings
     }
 
     pub(crate) fn from_cryptarchia(cryptarchia: &Cryptarchia, leader: &Leader) -> Self {
-        let security_block_header = cryptarchia.consensus.get_security_block_header_id();
+        // Until the chain is deeper than the security parameter, recover from the
+        // block it was built from: genesis, the snapshot it was bootstrapped from
+        // or the security block of a previous recovery. Blocks before it may not
+        // be stored locally, so the chain cannot be recovered from genesis.
+        let security_block_header = cryptarchia
+            .consensus
+            .get_security_block_header_id()
+            .or_else(|| Some(cryptarchia.genesis()));
         let security_ledger_state = security_block_header
             .and_then(|header| cryptarchia.ledger.state(&header))
             .cloned();
@@ -96,12 +113,21 @@ impl<TxS, BxS, NetworkAdapterSettings, BlendAdapterSettings, TimeBackendSettings
             .and_then(|header_id| leader.notes(&header_id))
             .map(Vec::from);
 
-        Self::new(
-            Some(cryptarchia.tip()),
-            security_block_header,
-            security_ledger_state,
-            security_leader_notes,
-        )
+        Self {
+            leader_notes_updates: leader.notes_updates().clone(),
+            ..Self::new(
+                Some(cryptarchia.tip()),
+                security_block_header,
+                security_ledger_state,
+                security_leader_notes,
+            )
+        }
+    }
+
+    /// Takes the leader notes added and removed at runtime, which are not
+    /// part of the recovery strategy as they apply to all of them.
+    pub fn take_leader_notes_updates(&mut self) -> LeaderNotesUpdates {
+        std::mem::take(&mut self.leader_notes_updates)
     }
 
     const fn can_recover(&self) -> bool {
@@ -165,7 +191,12 @@ impl<TxS, BxS, NetworkAdapterSettings, BlendAdapterSettings, TimeBackendSettings
 
 #[cfg(test)]
 mod tests {
-    use std::fmt::{Debug, Formatter};
+    use std::{
+        fmt::{Debug, Formatter},
+        num::NonZero,
+    };
+
+    use cl::NullifierSecret;
 
     use super::*;
 
@@ -295,4 +326,40 @@ mod tests {
             ))
         );
     }
+
+    #[test]
+    fn test_recover_from_chain_root() {
+        let config = nomos_ledger::Config {
+            epoch_config: cryptarchia_engine::EpochConfig {
+                epoch_stake_distribution_stabilization: NonZero::new(3).unwrap(),
+                epoch_period_nonce_buffer: NonZero::new(3).unwrap(),
+                epoch_period_nonce_stabilization: NonZero::new(4).unwrap(),
+            },
+            consensus_config: cryptarchia_engine::Config {
+                security_param: NonZero::new(10).unwrap(),
+                active_slot_coeff: 1.0,
+            },
+        };
+        // a chain bootstrapped from a snapshot, with no security block yet
+        let snapshot_id = HeaderId::from([1; 32]);
+        let ledger_state = LedgerState::from_commitments(vec![], 0);
+        let cryptarchia = Cryptarchia::new(snapshot_id, ledger_state.clone(), config);
+        let leader = Leader::new(snapshot_id, vec![], NullifierSecret([1; 16]), config);
+
+        let mut state = CryptarchiaConsensusState::<(), (), (), (), ()>::from_cryptarchia(
+            &cryptarchia,
+            &leader,
+        );
+        assert_eq!(
+            state.recovery_strategy(),
+            CryptarchiaInitialisationStrategy::RecoveryFromSecurity(Box::new(
+                SecurityRecoveryStrategy {
+                    tip: snapshot_id,
+                    security_block_id: snapshot_id,
+                    security_ledger_state: ledger_state,
+                    security_leader_notes: Vec::new(),
+                }
+            ))
+        );
+    }
 }
@@ -43,6 +43,25 @@ where
         self.storage_relay.send(msg).await.unwrap();
         receiver.recv().await.unwrap()
     }
+
+    /// Sends a remove message to the storage service to delete a value by its
+    /// key
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the value to remove
+    ///
+    /// # Returns
+    ///
+    /// The removed value. If no value is found, returns None.
+    pub async fn remove_value<Value>(&self, key: Bytes) -> Option<Value>
+    where
+        Value: DeserializeOwned,
+    {
+        let (msg, receiver) = <StorageMsg<Storage>>::new_remove_message(key);
+        self.storage_relay.send(msg).await.unwrap();
+        receiver.recv().await.unwrap()
+    }
 }
 
 #[async_trait::async_trait]
@@ -72,4 +91,9 @@ where
         let key: [u8; 32] = (*key).into();
         self.get_value(Bytes::copy_from_slice(&key)).await
     }
+
+    async fn remove_block(&self, key: &HeaderId) -> Option<Self::Block> {
+        let key: [u8; 32] = (*key).into();
+        self.remove_value(Bytes::copy_from_slice(&key)).await
+    }
 }
@@ -22,4 +22,12 @@ pub trait StorageAdapter<RuntimeServiceId> {
     ///
     /// The block with the given header id. If no block is found, returns None.
     async fn get_block(&self, key: &HeaderId) -> Option<Self::Block>;
+
+    /// Sends a remove message to the storage service to delete a block by its
+    /// header id
+    ///
+    /// # Returns
+    ///
+    /// The removed block. If no block is found, returns None.
+    async fn remove_block(&self, key: &HeaderId) -> Option<Self::Block>;
 }
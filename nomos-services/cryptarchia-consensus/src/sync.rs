@@ -0,0 +1,551 @@
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet, VecDeque},
+    hash::Hash,
+    time::Duration,
+};
+
+use cryptarchia_engine::{Branch, Branches};
+use nomos_core::{block::Block, header::HeaderId, wire};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+use crate::{messages::ChainTip, network::NetworkAdapter};
+
+// Maximum number of blocks exchanged in a single chain sync response
+pub const MAX_SYNC_BATCH_SIZE: usize = 64;
+// Maximum serialized size of the blocks of a chain sync response, leaving room
+// for the rest of the response within the 16 MiB accepted by the network
+pub const MAX_SYNC_RESPONSE_SIZE: usize = 15 << 20;
+// Number of times peers are looked up before assuming there is nobody to sync with
+const PEER_DISCOVERY_ATTEMPTS: usize = 5;
+const PEER_DISCOVERY_INTERVAL: Duration = Duration::from_secs(1);
+// Number of most recent ids included one by one in a block locator, before
+// starting to skip exponentially
+const LOCATOR_DENSE_PREFIX: usize = 10;
+// Interval at which peers are asked again for their tips, so that a node which
+// fell behind catches up with the rest of the network
+pub const RESYNC_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Synchronization status of the local chain with the rest of the network.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum SyncStatus {
+    /// Blocks are being downloaded from peers, up to the highest tip they
+    /// advertised
+    Syncing {
+        target_height: u64,
+    },
+    Synced,
+}
+
+impl SyncStatus {
+    #[must_use]
+    pub const fn is_synced(self) -> bool {
+        matches!(self, Self::Synced)
+    }
+}
+
+/// Progress notifications sent by [`sync_with_peers`].
+pub enum SyncEvent<Tx, BlobCertificate>
+where
+    Tx: Clone + Eq + Hash,
+    BlobCertificate: Clone + Eq + Hash,
+{
+    /// Height of the highest tip advertised by peers
+    Target(u64),
+    /// Downloaded blocks, ordered from the oldest one
+    Blocks(Vec<Block<Tx, BlobCertificate>>),
+    Completed,
+}
+
+/// Builds a block locator from the ids of a chain, ordered from its tip.
+///
+/// The most recent ids are all included, then the distance between them
+/// doubles at each step. The last id of the chain is always included so that
+/// peers can always find a common ancestor.
+pub fn block_locator<Id: Copy>(chain: impl IntoIterator<Item = Id>) -> Vec<Id> {
+    let mut locator = Vec::new();
+    let mut last = None;
+    let mut next = 0;
+    let mut step = 1;
+    for (index, id) in chain.into_iter().enumerate() {
+        if index == next {
+            locator.push(id);
+            if locator.len() >= LOCATOR_DENSE_PREFIX {
+                step *= 2;
+            }
+            next += step;
+            last = None;
+        } else {
+            last = Some(id);
+        }
+    }
+    locator.extend(last);
+    locator
+}
+
+fn height<Id: Copy>(branch: &Branch<Id>) -> usize {
+    usize::try_from(branch.length()).expect("chain height must fit in usize")
+}
+
+/// Ids of the local chain indexed by their height, so that chain sync requests
+/// are answered without walking the chain back from its tip.
+#[derive(Debug)]
+pub struct ChainIndex<Id> {
+    ids: Vec<Id>,
+}
+
+impl<Id> ChainIndex<Id>
+where
+    Id: Eq + Hash + Copy,
+{
+    /// Creates the index of a chain made of its `root` block only.
+    pub fn new(root: Id) -> Self {
+        Self { ids: vec![root] }
+    }
+
+    fn contains(&self, branch: &Branch<Id>) -> bool {
+        self.ids.get(height(branch)) == Some(&branch.id())
+    }
+
+    /// Moves the index to the chain ending at `tip`, only walking it back
+    /// until the most recent block already indexed.
+    pub fn update(&mut self, branches: &Branches<Id>, tip: Id) {
+        let Some(tip_branch) = branches.get(&tip) else {
+            return;
+        };
+        let mut added = Vec::new();
+        let mut branch = tip_branch;
+        while !self.contains(branch) {
+            added.push(branch.id());
+            if branch.parent() == branch.id() {
+                break;
+            }
+            let Some(parent) = branches.get(&branch.parent()) else {
+                return;
+            };
+            branch = parent;
+        }
+        self.ids.truncate(height(tip_branch) + 1 - added.len());
+        self.ids.extend(added.into_iter().rev());
+    }
+}
+
+/// Returns up to `limit` ids of the chain ending at `tip`, starting right
+/// after the most recent block in `locator` belonging to it.
+///
+/// Ids are ordered from the oldest one. If no block in `locator` belongs to
+/// the chain, it is returned from the block following the genesis one.
+///
+/// The chain is only walked back from `tip` until it joins the chain in
+/// `index`, which is expected to be up to date with `branches`.
+pub fn blocks_after_locator<Id>(
+    branches: &Branches<Id>,
+    index: &ChainIndex<Id>,
+    locator: &[Id],
+    tip: Id,
+    limit: usize,
+) -> Vec<Id>
+where
+    Id: Eq + Hash + Copy,
+{
+    if limit == 0 {
+        return Vec::new();
+    }
+    let Some(tip_branch) = branches.get(&tip) else {
+        return Vec::new();
+    };
+    // blocks of the chain not in the index, from the tip
+    let mut fork = Vec::new();
+    let mut branch = tip_branch;
+    while !index.contains(branch) {
+        fork.push(branch.id());
+        let Some(parent) = (branch.parent() != branch.id())
+            .then(|| branches.get(&branch.parent()))
+            .flatten()
+        else {
+            return Vec::new();
+        };
+        branch = parent;
+    }
+    let fork_height = height(branch);
+    let tip_height = height(tip_branch);
+    let id_at = |height: usize| {
+        if height <= fork_height {
+            index.ids.get(height).copied()
+        } else {
+            fork.get(tip_height - height).copied()
+        }
+    };
+
+    let start = locator
+        .iter()
+        .filter_map(|id| {
+            let height = height(branches.get(id)?);
+            (height <= tip_height)
+                .then_some(height)
+                .filter(|height| id_at(*height) == Some(*id))
+        })
+        .max()
+        .unwrap_or(0)
+        + 1;
+    (start..=tip_height).take(limit).filter_map(id_at).collect()
+}
+
+/// Blocks of a chain sync response, limited to [`MAX_SYNC_RESPONSE_SIZE`]
+/// bytes once serialized.
+///
+/// The first block is always accepted, so that the requesting peer makes
+/// progress whatever the size of the blocks.
+pub struct ResponseBlocks<Block> {
+    blocks: Vec<Block>,
+    size: usize,
+}
+
+impl<Block: Serialize> ResponseBlocks<Block> {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            blocks: Vec::with_capacity(capacity),
+            size: 0,
+        }
+    }
+
+    /// Adds `block` to the response, returning `false` if it would exceed
+    /// the size limit.
+    pub fn push(&mut self, block: Block) -> bool {
+        let block_size = wire::serialized_size(&block)
+            .ok()
+            .and_then(|size| usize::try_from(size).ok())
+            .unwrap_or(usize::MAX);
+        let size = self.size.saturating_add(block_size);
+        if !self.blocks.is_empty() && size > MAX_SYNC_RESPONSE_SIZE {
+            return false;
+        }
+        self.size = size;
+        self.blocks.push(block);
+        true
+    }
+
+    pub fn into_blocks(self) -> Vec<Block> {
+        self.blocks
+    }
+}
+
+/// Ids of the blocks downloaded during a chain sync, in the order they were
+/// received.
+#[derive(Default)]
+struct DownloadedBlocks {
+    ids: Vec<HeaderId>,
+    known: HashSet<HeaderId>,
+}
+
+impl DownloadedBlocks {
+    /// Records the ids of a batch of blocks, returning whether any of them
+    /// was not downloaded before.
+    fn extend(&mut self, ids: impl IntoIterator<Item = HeaderId>) -> bool {
+        let mut progress = false;
+        for id in ids {
+            if self.known.insert(id) {
+                self.ids.push(id);
+                progress = true;
+            }
+        }
+        progress
+    }
+}
+
+/// Downloads from the connected peers the chains ending at their tips,
+/// starting from the blocks referenced by `locator`.
+///
+/// Peers may disagree on the tip of the chain, so every advertised chain is
+/// downloaded and the fork choice rule is left to decide which one to follow.
+pub async fn sync_with_peers<NetAdapter, RuntimeServiceId>(
+    network_adapter: NetAdapter,
+    locator: Vec<HeaderId>,
+    events: mpsc::Sender<SyncEvent<NetAdapter::Tx, NetAdapter::BlobCertificate>>,
+) where
+    NetAdapter: NetworkAdapter<RuntimeServiceId> + Sync,
+{
+    let mut candidates: Vec<_> = peer_tips(&network_adapter).await.into_values().collect();
+    candidates.sort_by_key(|(tip, _)| Reverse(tip.height));
+    if let Some((best_tip, _)) = candidates.first() {
+        tracing::info!(
+            "syncing with {} peers, best tip {} at height {}",
+            candidates
+                .iter()
+                .map(|(_, peers)| peers.len())
+                .sum::<usize>(),
+            best_tip.id,
+            best_tip.height
+        );
+        if events
+            .send(SyncEvent::Target(best_tip.height))
+            .await
+            .is_err()
+        {
+            return;
+        }
+    }
+
+    // ids downloaded so far, so that chains sharing a prefix with previous
+    // ones are not downloaded again
+    let mut downloaded = DownloadedBlocks::default();
+    for (tip, peers) in candidates {
+        let locator = block_locator(downloaded.ids.iter().rev().copied())
+            .into_iter()
+            .chain(locator.iter().copied())
+            .collect();
+        if !download_chain(
+            &network_adapter,
+            &peers,
+            tip,
+            locator,
+            &mut downloaded,
+            &events,
+        )
+        .await
+        {
+            return;
+        }
+    }
+
+    if events.send(SyncEvent::Completed).await.is_err() {
+        tracing::debug!("chain sync completed but consensus is not listening anymore");
+    }
+}
+
+/// Asks every connected peer for its tip, grouping peers by the tip they
+/// advertised.
+async fn peer_tips<NetAdapter, RuntimeServiceId>(
+    network_adapter: &NetAdapter,
+) -> HashMap<HeaderId, (ChainTip, Vec<NetAdapter::PeerId>)>
+where
+    NetAdapter: NetworkAdapter<RuntimeServiceId> + Sync,
+{
+    let mut tips: HashMap<_, (_, Vec<_>)> = HashMap::new();
+    for attempt in 1..=PEER_DISCOVERY_ATTEMPTS {
+        let peers = match network_adapter.connected_peers().await {
+            Ok(peers) => peers,
+            Err(e) => {
+                tracing::error!("failed to retrieve connected peers: {e}");
+                Vec::new()
+            }
+        };
+        for peer in peers {
+            match network_adapter.request_tip(peer.clone()).await {
+                Ok(tip) => tips.entry(tip.id).or_insert((tip, Vec::new())).1.push(peer),
+                Err(e) => tracing::debug!("failed to request tip from {peer}: {e}"),
+            }
+        }
+        if !tips.is_empty() {
+            break;
+        }
+        tracing::debug!("no peers to sync with (attempt {attempt})");
+        tokio::time::sleep(PEER_DISCOVERY_INTERVAL).await;
+    }
+    tips
+}
+
+/// Downloads the chain ending at `tip` in batches, falling back to the next
+/// peer advertising it if a request fails or the peer does not make progress.
+///
+/// A peer is not asked for more batches than needed to download the whole
+/// chain up to the height it advertised, batches carrying at least one block.
+///
+/// Returns `false` if consensus stopped listening for sync events.
+async fn download_chain<NetAdapter, RuntimeServiceId>(
+    network_adapter: &NetAdapter,
+    peers: &[NetAdapter::PeerId],
+    tip: ChainTip,
+    locator: Vec<HeaderId>,
+    downloaded: &mut DownloadedBlocks,
+    events: &mpsc::Sender<SyncEvent<NetAdapter::Tx, NetAdapter::BlobCertificate>>,
+) -> bool
+where
+    NetAdapter: NetworkAdapter<RuntimeServiceId> + Sync,
+{
+    let max_rounds = usize::try_from(tip.height)
+        .unwrap_or(usize::MAX)
+        .saturating_add(1);
+    for peer in peers {
+        let mut locator = locator.clone();
+        for _ in 0..max_rounds {
+            let blocks = match network_adapter
+                .request_blocks(peer.clone(), locator.clone(), tip.id, MAX_SYNC_BATCH_SIZE)
+                .await
+            {
+                Ok(blocks) => blocks,
+                Err(e) => {
+                    tracing::debug!("failed to request blocks from {peer}: {e}");
+                    break;
+                }
+            };
+            let Some(last) = blocks.last().map(|block| block.header().id()) else {
+                return true;
+            };
+            if !downloaded.extend(blocks.iter().map(|block| block.header().id())) {
+                tracing::debug!("{peer} sent blocks already downloaded, chain sync is stuck");
+                break;
+            }
+            if events.send(SyncEvent::Blocks(blocks)).await.is_err() {
+                return false;
+            }
+            if last == tip.id {
+                return true;
+            }
+            locator = vec![last];
+        }
+    }
+    tracing::warn!(
+        "could not download chain ending at {} from any peer",
+        tip.id
+    );
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZero;
+
+    use cryptarchia_engine::{Config, Cryptarchia};
+
+    use super::*;
+
+    fn chain(length: u8) -> Cryptarchia<u8> {
+        let config = Config {
+            security_param: NonZero::new(1).unwrap(),
+            active_slot_coeff: 1.0,
+        };
+        (1..=length).fold(Cryptarchia::new(0, config), |cryptarchia, id| {
+            cryptarchia
+                .receive_block(id, id - 1, u64::from(id).into())
+                .unwrap()
+        })
+    }
+
+    #[test]
+    fn test_block_locator() {
+        assert_eq!(block_locator(Vec::<u32>::new()), Vec::<u32>::new());
+        assert_eq!(block_locator([5, 4, 3]), vec![5, 4, 3]);
+        assert_eq!(
+            block_locator((0..100).rev()),
+            vec![99, 98, 97, 96, 95, 94, 93, 92, 91, 90, 88, 84, 76, 60, 28, 0]
+        );
+    }
+
+    fn index(cryptarchia: &Cryptarchia<u8>, tip: u8) -> ChainIndex<u8> {
+        let mut index = ChainIndex::new(0);
+        index.update(cryptarchia.branches(), tip);
+        index
+    }
+
+    #[test]
+    fn test_blocks_after_locator() {
+        let cryptarchia = chain(10);
+        let branches = cryptarchia.branches();
+        let index = index(&cryptarchia, 10);
+
+        assert_eq!(
+            blocks_after_locator(branches, &index, &[0], 10, 3),
+            vec![1, 2, 3]
+        );
+        assert_eq!(
+            blocks_after_locator(branches, &index, &[7, 2], 10, 5),
+            vec![8, 9, 10]
+        );
+        // no common block, start after genesis
+        assert_eq!(
+            blocks_after_locator(branches, &index, &[42], 3, 5),
+            vec![1, 2, 3]
+        );
+        // locator blocks beyond the requested tip are not on its chain
+        assert_eq!(
+            blocks_after_locator(branches, &index, &[9], 3, 5),
+            vec![1, 2, 3]
+        );
+        // tip already known
+        assert!(blocks_after_locator(branches, &index, &[10], 10, 5).is_empty());
+        // unknown tip
+        assert!(blocks_after_locator(branches, &index, &[0], 42, 5).is_empty());
+    }
+
+    #[test]
+    fn test_blocks_after_locator_on_fork() {
+        // fork of the chain after block 5
+        let cryptarchia = chain(10)
+            .receive_block(11, 5, 6.into())
+            .unwrap()
+            .receive_block(12, 11, 7.into())
+            .unwrap();
+        let branches = cryptarchia.branches();
+        let index = index(&cryptarchia, 10);
+
+        assert_eq!(
+            blocks_after_locator(branches, &index, &[3], 12, 10),
+            vec![4, 5, 11, 12]
+        );
+        assert_eq!(
+            blocks_after_locator(branches, &index, &[11, 9], 12, 10),
+            vec![12]
+        );
+        // blocks of the local chain after the fork are not on the chain of the
+        // requested tip
+        assert_eq!(
+            blocks_after_locator(branches, &index, &[6], 12, 2),
+            vec![1, 2]
+        );
+    }
+
+    #[test]
+    fn test_chain_index_update() {
+        let cryptarchia = chain(10)
+            .receive_block(11, 5, 6.into())
+            .unwrap()
+            .receive_block(12, 11, 7.into())
+            .unwrap();
+        let branches = cryptarchia.branches();
+        let mut index = index(&cryptarchia, 10);
+        assert_eq!(index.ids, (0..=10).collect::<Vec<_>>());
+
+        index.update(branches, 12);
+        assert_eq!(index.ids, vec![0, 1, 2, 3, 4, 5, 11, 12]);
+
+        index.update(branches, 7);
+        assert_eq!(index.ids, (0..=7).collect::<Vec<_>>());
+
+        // unknown tips leave the index untouched
+        index.update(branches, 42);
+        assert_eq!(index.ids, (0..=7).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_downloaded_blocks_progress() {
+        let mut downloaded = DownloadedBlocks::default();
+        let id = |byte| HeaderId::from([byte; 32]);
+
+        assert!(downloaded.extend([id(1), id(2)]));
+        assert!(!downloaded.extend([id(1), id(2)]));
+        assert!(downloaded.extend([id(2), id(3)]));
+        assert_eq!(downloaded.ids, vec![id(1), id(2), id(3)]);
+    }
+
+    #[test]
+    fn test_response_blocks_size_limit() {
+        // blocks of the maximum size accepted by consensus
+        let block = vec![0u8; 1 << 20];
+        let mut response = ResponseBlocks::with_capacity(MAX_SYNC_BATCH_SIZE);
+        let accepted = (0..MAX_SYNC_BATCH_SIZE)
+            .take_while(|_| response.push(block.clone()))
+            .count();
+        assert_eq!(accepted, 14);
+        let blocks = response.into_blocks();
+        assert!(wire::serialize(&blocks).unwrap().len() < 1 << 24);
+
+        // a block above the limit is still sent alone
+        let mut response = ResponseBlocks::with_capacity(1);
+        assert!(response.push(vec![0u8; MAX_SYNC_RESPONSE_SIZE + 1]));
+        assert!(!response.push(Vec::new()));
+        assert_eq!(response.into_blocks().len(), 1);
+    }
+}
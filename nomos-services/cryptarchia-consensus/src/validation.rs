@@ -0,0 +1,491 @@
+use std::{
+    collections::{BTreeSet, HashSet},
+    hash::Hash,
+};
+
+use cryptarchia_engine::Slot;
+use nomos_core::{
+    block::Block,
+    da::blob::info::DispersedBlobInfo,
+    header::{Header, HeaderId},
+    tx::Transaction,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use thiserror::Error;
+
+// 1 MiB
+const DEFAULT_MAX_BLOCK_SIZE: u32 = 1 << 20;
+const DEFAULT_MAX_SLOT_DRIFT: u64 = 2;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum BlockValidationError {
+    #[error("Content size {actual} does not match the size {header} in the header")]
+    ContentSizeMismatch { header: u32, actual: usize },
+    #[error("Content does not match the content id in the header")]
+    ContentIdMismatch,
+    #[error("Block size {size} exceeds the maximum of {max} bytes")]
+    BlockTooLarge { size: usize, max: u32 },
+    #[error("Block slot {block:?} is too far ahead of the current slot {current:?}")]
+    FutureSlot { block: Slot, current: Slot },
+    #[error("Transaction included more than once")]
+    DuplicateTransaction,
+    #[error("Blob included more than once")]
+    DuplicateBlob,
+    #[error("Blob was not sampled")]
+    BlobNotSampled,
+    #[error("Orphan proof {0} is included more than once")]
+    DuplicateOrphanProof(HeaderId),
+    #[error("Orphan proof {0} is the parent of the block")]
+    OrphanProofIsParent(HeaderId),
+    #[error("Orphan proof {orphan} from slot {orphan_slot:?} is not older than the block")]
+    OrphanProofNotOlder { orphan: HeaderId, orphan_slot: Slot },
+    #[error("Orphan proof {0} includes orphan proofs itself")]
+    NestedOrphanProofs(HeaderId),
+}
+
+/// Settings for the structural validation of blocks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockValidationSettings {
+    /// Maximum size of the content of a block, in bytes
+    pub max_block_size: u32,
+    /// Number of slots a block can be ahead of the local clock, to account for
+    /// clock drift between nodes
+    pub max_slot_drift: u64,
+}
+
+impl Default for BlockValidationSettings {
+    fn default() -> Self {
+        Self {
+            max_block_size: DEFAULT_MAX_BLOCK_SIZE,
+            max_slot_drift: DEFAULT_MAX_SLOT_DRIFT,
+        }
+    }
+}
+
+/// Validates the structure of blocks, before they are applied to the ledger
+/// and the chain.
+#[derive(Debug, Clone)]
+pub struct BlockValidator {
+    settings: BlockValidationSettings,
+    // the slot of the local clock
+    current_slot: Slot,
+}
+
+impl BlockValidator {
+    #[must_use]
+    pub const fn new(settings: BlockValidationSettings, current_slot: Slot) -> Self {
+        Self {
+            settings,
+            current_slot,
+        }
+    }
+
+    pub const fn update_slot(&mut self, slot: Slot) {
+        self.current_slot = slot;
+    }
+
+    pub fn validate<Tx, Blob>(
+        &self,
+        block: &Block<Tx, Blob>,
+        sampled_blobs: &BTreeSet<Blob::BlobId>,
+    ) -> Result<(), BlockValidationError>
+    where
+        Tx: Transaction + Clone + Eq + Hash + Serialize + DeserializeOwned,
+        Blob: DispersedBlobInfo + Clone + Eq + Hash + Serialize + DeserializeOwned,
+        Blob::BlobId: Ord,
+    {
+        let header = block.header();
+        self.validate_slot(header.slot())?;
+        self.validate_content(block)?;
+
+        let mut tx_hashes = HashSet::new();
+        if !block.transactions().all(|tx| tx_hashes.insert(tx.hash())) {
+            return Err(BlockValidationError::DuplicateTransaction);
+        }
+        let mut blob_ids = BTreeSet::new();
+        for blob in block.blobs() {
+            let blob_id = blob.blob_id();
+            if !sampled_blobs.contains(&blob_id) {
+                return Err(BlockValidationError::BlobNotSampled);
+            }
+            if !blob_ids.insert(blob_id) {
+                return Err(BlockValidationError::DuplicateBlob);
+            }
+        }
+
+        Self::validate_orphan_proofs(header)
+    }
+
+    fn validate_slot(&self, slot: Slot) -> Result<(), BlockValidationError> {
+        let current = self.current_slot;
+        if slot > current + self.settings.max_slot_drift {
+            return Err(BlockValidationError::FutureSlot {
+                block: slot,
+                current,
+            });
+        }
+        Ok(())
+    }
+
+    fn validate_content<Tx, Blob>(
+        &self,
+        block: &Block<Tx, Blob>,
+    ) -> Result<(), BlockValidationError>
+    where
+        Tx: Clone + Eq + Hash + Serialize + DeserializeOwned,
+        Blob: Clone + Eq + Hash + Serialize + DeserializeOwned,
+    {
+        let header = block.header();
+        let (content_id, size) = block.content_id_and_size();
+        let max = self.settings.max_block_size;
+        if u32::try_from(size).ok().is_none_or(|size| size > max) {
+            return Err(BlockValidationError::BlockTooLarge { size, max });
+        }
+        if usize::try_from(header.content_size()).ok() != Some(size) {
+            return Err(BlockValidationError::ContentSizeMismatch {
+                header: header.content_size(),
+                actual: size,
+            });
+        }
+        if header.content_id() != content_id {
+            return Err(BlockValidationError::ContentIdMismatch);
+        }
+        Ok(())
+    }
+
+    // Leader proofs of the orphans are checked by the ledger
+    fn validate_orphan_proofs(header: &Header) -> Result<(), BlockValidationError> {
+        let mut orphan_ids = HashSet::new();
+        for orphan in header.orphaned_proofs() {
+            let id = orphan.id();
+            if !orphan_ids.insert(id) {
+                return Err(BlockValidationError::DuplicateOrphanProof(id));
+            }
+            if id == header.parent() {
+                return Err(BlockValidationError::OrphanProofIsParent(id));
+            }
+            if orphan.slot() >= header.slot() {
+                return Err(BlockValidationError::OrphanProofNotOlder {
+                    orphan: id,
+                    orphan_slot: orphan.slot(),
+                });
+            }
+            if !orphan.orphaned_proofs().is_empty() {
+                return Err(BlockValidationError::NestedOrphanProofs(id));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::LazyLock;
+
+    use bytes::Bytes;
+    use kzgrs_backend::dispersal::{BlobInfo, Index, Metadata};
+    use nomos_core::{
+        block::builder::BlockBuilder,
+        da::blob::select::FillSize as FillSizeWithBlobs,
+        header::{Builder, ContentId},
+        proofs::leader_proof::Risc0LeaderProof,
+        staking::NMO_UNIT,
+        tx::{select::FillSize as FillSizeWithTx, TransactionHasher},
+        wire,
+    };
+    use nomos_proof_statements::leadership::{LeaderPrivate, LeaderPublic};
+    use rand::thread_rng;
+
+    use super::*;
+
+    // transactions are identified by `id` only, so that distinct transactions
+    // can share a hash
+    #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    struct TestTx {
+        id: u8,
+        payload: Vec<u8>,
+    }
+
+    impl Transaction for TestTx {
+        const HASHER: TransactionHasher<Self> = |tx| tx.id;
+        type Hash = u8;
+
+        fn as_bytes(&self) -> Bytes {
+            wire::serialize(self).unwrap().into()
+        }
+    }
+
+    type TestBlock = Block<TestTx, BlobInfo>;
+
+    // the validator does not check leader proofs, any valid one will do
+    fn leader_proof() -> Risc0LeaderProof {
+        static PROOF: LazyLock<Risc0LeaderProof> = LazyLock::new(|| {
+            let mut rng = thread_rng();
+            let note = cl::NoteWitness::basic(100, NMO_UNIT, &mut rng);
+            let nf_sk = cl::NullifierSecret::random(&mut rng);
+            let leaves = cl::merkle::padded_leaves::<256>(&[note
+                .commit(nf_sk.commit())
+                .as_bytes()
+                .to_vec()]);
+            let input = cl::InputWitness {
+                note,
+                nf_sk,
+                cm_path: cl::merkle::path(leaves, 0),
+            };
+            let mut public_inputs = LeaderPublic::new(
+                cl::merkle::root(leaves),
+                [0; 32],
+                0,
+                0.9,
+                100,
+                input.nullifier(),
+                input.evolve_output(b"NOMOS_POL").commit_note(),
+            );
+            while !public_inputs.check_winning(&input) {
+                public_inputs.slot += 1;
+            }
+            // requires RISC0_DEV_MODE=1 on machines that cannot build real proofs
+            Risc0LeaderProof::prove(
+                public_inputs,
+                &LeaderPrivate { input },
+                risc0_zkvm::default_prover().as_ref(),
+            )
+            .unwrap()
+        });
+        PROOF.clone()
+    }
+
+    fn header(parent: HeaderId, slot: u64) -> Builder {
+        Builder::new(parent, slot.into(), leader_proof())
+    }
+
+    fn block(header: Builder, txs: Vec<TestTx>, blobs: Vec<BlobInfo>) -> TestBlock {
+        BlockBuilder::new(
+            FillSizeWithTx::<{ usize::MAX }, TestTx>::new(),
+            FillSizeWithBlobs::<{ usize::MAX }, BlobInfo>::new(),
+            header,
+        )
+        .with_transactions(txs.into_iter())
+        .with_blobs_info(blobs.into_iter())
+        .build()
+        .unwrap()
+    }
+
+    // replaces the header of `block`, which the block builder does not allow
+    fn with_header(block: &TestBlock, header: Header) -> TestBlock {
+        let txs: Vec<_> = block.transactions().cloned().collect();
+        let blobs: Vec<_> = block.blobs().cloned().collect();
+        Block::from_bytes(&wire::serialize(&(header, txs, blobs)).unwrap())
+    }
+
+    fn tx(id: u8) -> TestTx {
+        TestTx {
+            id,
+            payload: vec![id; 32],
+        }
+    }
+
+    fn blob(id: u8, index: u8) -> BlobInfo {
+        BlobInfo::new(
+            [id; 32],
+            Metadata::new([0; 32], Index::from(u64::from(index))),
+            2,
+            1,
+        )
+    }
+
+    fn validator(current_slot: u64) -> BlockValidator {
+        BlockValidator::new(BlockValidationSettings::default(), current_slot.into())
+    }
+
+    fn validate(validator: &BlockValidator, block: &TestBlock) -> Result<(), BlockValidationError> {
+        let sampled_blobs = block.blobs().map(DispersedBlobInfo::blob_id).collect();
+        validator.validate(block, &sampled_blobs)
+    }
+
+    #[test]
+    fn test_valid_block() {
+        let block = block(
+            header([0; 32].into(), 1),
+            vec![tx(0), tx(1)],
+            vec![blob(0, 0), blob(1, 0)],
+        );
+        assert_eq!(validate(&validator(1), &block), Ok(()));
+    }
+
+    #[test]
+    fn test_block_size() {
+        let block = block(header([0; 32].into(), 1), vec![tx(0), tx(1)], vec![]);
+        let (_, size) = block.content_id_and_size();
+        let max = u32::try_from(size).unwrap();
+
+        let at_limit = BlockValidationSettings {
+            max_block_size: max,
+            ..Default::default()
+        };
+        assert_eq!(
+            validate(&BlockValidator::new(at_limit, 1.into()), &block),
+            Ok(())
+        );
+
+        let below_size = BlockValidationSettings {
+            max_block_size: max - 1,
+            ..Default::default()
+        };
+        assert_eq!(
+            validate(&BlockValidator::new(below_size, 1.into()), &block),
+            Err(BlockValidationError::BlockTooLarge { size, max: max - 1 })
+        );
+    }
+
+    #[test]
+    fn test_slot_drift() {
+        let mut validator = validator(10);
+        let max_drift = BlockValidationSettings::default().max_slot_drift;
+        let drifted = block(header([0; 32].into(), 10 + max_drift), vec![], vec![]);
+        let too_far = block(header([0; 32].into(), 11 + max_drift), vec![], vec![]);
+
+        assert_eq!(validate(&validator, &drifted), Ok(()));
+        assert_eq!(
+            validate(&validator, &too_far),
+            Err(BlockValidationError::FutureSlot {
+                block: (11 + max_drift).into(),
+                current: 10.into(),
+            })
+        );
+
+        // the block is no longer in the future once the clock catches up
+        validator.update_slot(11.into());
+        assert_eq!(validate(&validator, &too_far), Ok(()));
+    }
+
+    #[test]
+    fn test_content_mismatch() {
+        let block = block(header([0; 32].into(), 1), vec![tx(0)], vec![]);
+        let (content_id, size) = block.content_id_and_size();
+        let header_size = u32::try_from(size).unwrap();
+
+        let wrong_size = with_header(
+            &block,
+            Header::new(
+                [0; 32].into(),
+                header_size + 1,
+                content_id,
+                1.into(),
+                leader_proof(),
+            ),
+        );
+        assert_eq!(
+            validate(&validator(1), &wrong_size),
+            Err(BlockValidationError::ContentSizeMismatch {
+                header: header_size + 1,
+                actual: size,
+            })
+        );
+
+        let wrong_id = with_header(
+            &block,
+            Header::new(
+                [0; 32].into(),
+                header_size,
+                ContentId::from([1; 32]),
+                1.into(),
+                leader_proof(),
+            ),
+        );
+        assert_eq!(
+            validate(&validator(1), &wrong_id),
+            Err(BlockValidationError::ContentIdMismatch)
+        );
+    }
+
+    #[test]
+    fn test_duplicate_content() {
+        let mut same_hash = tx(0);
+        same_hash.payload = vec![1];
+        let duplicate_tx = block(header([0; 32].into(), 1), vec![tx(0), same_hash], vec![]);
+        assert_eq!(
+            validate(&validator(1), &duplicate_tx),
+            Err(BlockValidationError::DuplicateTransaction)
+        );
+
+        let duplicate_blob = block(
+            header([0; 32].into(), 1),
+            vec![],
+            vec![blob(0, 0), blob(0, 1)],
+        );
+        assert_eq!(
+            validate(&validator(1), &duplicate_blob),
+            Err(BlockValidationError::DuplicateBlob)
+        );
+    }
+
+    #[test]
+    fn test_blob_not_sampled() {
+        let block = block(
+            header([0; 32].into(), 1),
+            vec![],
+            vec![blob(0, 0), blob(1, 0)],
+        );
+        let sampled_blobs = BTreeSet::from([[0; 32]]);
+        assert_eq!(
+            validator(1).validate(&block, &sampled_blobs),
+            Err(BlockValidationError::BlobNotSampled)
+        );
+    }
+
+    #[test]
+    fn test_orphan_proofs() {
+        let parent = HeaderId::from([0; 32]);
+        let orphan = header(parent, 1).build(ContentId::from([0; 32]), 0);
+        let orphan_id = orphan.id();
+        let orphan_block = |parent, slot, orphans| {
+            block(
+                header(parent, slot).with_orphaned_proofs(orphans),
+                vec![],
+                vec![],
+            )
+        };
+
+        assert_eq!(
+            validate(
+                &validator(2),
+                &orphan_block(parent, 2, vec![orphan.clone()])
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            validate(
+                &validator(2),
+                &orphan_block(parent, 2, vec![orphan.clone(), orphan.clone()])
+            ),
+            Err(BlockValidationError::DuplicateOrphanProof(orphan_id))
+        );
+        assert_eq!(
+            validate(
+                &validator(2),
+                &orphan_block(orphan_id, 2, vec![orphan.clone()])
+            ),
+            Err(BlockValidationError::OrphanProofIsParent(orphan_id))
+        );
+        assert_eq!(
+            validate(
+                &validator(2),
+                &orphan_block(parent, 1, vec![orphan.clone()])
+            ),
+            Err(BlockValidationError::OrphanProofNotOlder {
+                orphan: orphan_id,
+                orphan_slot: 1.into(),
+            })
+        );
+
+        let nested = header(parent, 1)
+            .with_orphaned_proofs(vec![orphan])
+            .build(ContentId::from([0; 32]), 0);
+        let nested_id = nested.id();
+        assert_eq!(
+            validate(&validator(2), &orphan_block(parent, 2, vec![nested])),
+            Err(BlockValidationError::NestedOrphanProofs(nested_id))
+        );
+    }
+}
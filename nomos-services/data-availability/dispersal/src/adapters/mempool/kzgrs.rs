@@ -1,6 +1,6 @@
 use std::{fmt::Debug, hash::Hash, marker::PhantomData};
 
-use kzgrs_backend::dispersal::{self, BlobInfo};
+use kzgrs_backend::dispersal::BlobInfo;
 use nomos_core::{
     da::{blob::info::DispersedBlobInfo, BlobId},
     header::HeaderId,
@@ -114,7 +114,7 @@ where
         RuntimeServiceId,
     >;
     type BlobId = BlobId;
-    type Metadata = dispersal::Metadata;
+    type BlobInfo = BlobInfo;
 
     fn new(mempool_relay: OutboundRelay<<Self::MempoolService as ServiceData>::Message>) -> Self {
         Self {
@@ -125,15 +125,15 @@ where
         }
     }
 
-    async fn post_blob_id(
+    async fn post_blob_info(
         &self,
         blob_id: Self::BlobId,
-        metadata: Self::Metadata,
+        blob_info: Self::BlobInfo,
     ) -> Result<(), DaMempoolAdapterError> {
         let (reply_channel, receiver) = oneshot::channel();
         self.mempool_relay
             .send(MempoolMsg::Add {
-                payload: BlobInfo::new(blob_id, metadata),
+                payload: blob_info,
                 key: blob_id,
                 reply_channel,
             })
@@ -27,13 +27,13 @@ where
 pub trait DaMempoolAdapter {
     type MempoolService: ServiceData;
     type BlobId;
-    type Metadata;
+    type BlobInfo;
 
     fn new(outbound_relay: OutboundRelay<<Self::MempoolService as ServiceData>::Message>) -> Self;
 
-    async fn post_blob_id(
+    async fn post_blob_info(
         &self,
         blob_id: Self::BlobId,
-        metadata: Self::Metadata,
+        blob_info: Self::BlobInfo,
     ) -> Result<(), DaMempoolAdapterError>;
 }
@@ -2,7 +2,6 @@ use std::{sync::Arc, time::Duration};
 
 use futures::StreamExt;
 use kzgrs_backend::{
-    common::build_blob_id,
     dispersal, encoder,
     encoder::{DaEncoderParams, EncodedData},
 };
@@ -51,7 +50,12 @@ pub enum MempoolPublishStrategy {
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct EncoderSettings {
+    /// Number of columns of the dispersed blobs, one per subnetwork. Small
+    /// blobs are encoded with all of them as well, so their shares are not
+    /// concentrated on the first subnetworks.
     pub num_columns: usize,
+    /// Maximum size in bytes of the dispersed blobs.
+    pub max_blob_size: usize,
     pub with_cache: bool,
     pub global_params_path: String,
 }
@@ -93,10 +97,7 @@ where
     async fn disperse(&self, encoded_data: Self::EncodedData) -> Result<(), Self::Error> {
         let adapter = self.adapter.as_ref();
         let num_columns = encoded_data.column_commitments.len();
-        let blob_id = build_blob_id(
-            &encoded_data.aggregated_column_commitment,
-            &encoded_data.row_commitments,
-        );
+        let blob_id = encoded_data.blob_id();
 
         let responses_stream = adapter.dispersal_events_stream().await?;
         for (subnetwork_id, share) in encoded_data.into_iter().enumerate() {
@@ -128,7 +129,7 @@ impl<NetworkAdapter, MempoolAdapter> DispersalBackend
 where
     NetworkAdapter: DispersalNetworkAdapter + Send + Sync,
     NetworkAdapter::SubnetworkId: From<u16> + Send + Sync,
-    MempoolAdapter: DaMempoolAdapter<BlobId = BlobId, Metadata = dispersal::Metadata> + Send + Sync,
+    MempoolAdapter: DaMempoolAdapter<BlobId = BlobId, BlobInfo = dispersal::BlobInfo> + Send + Sync,
 {
     type Settings = DispersalKZGRSBackendSettings;
     type Encoder = encoder::DaEncoder;
@@ -137,6 +138,7 @@ where
     type MempoolAdapter = MempoolAdapter;
     type Metadata = dispersal::Metadata;
     type BlobId = BlobId;
+    type BlobInfo = dispersal::BlobInfo;
 
     fn init(
         settings: Self::Settings,
@@ -148,11 +150,14 @@ where
             &encoder_settings.global_params_path,
         )
         .expect("Global encoder params should be available");
-        let encoder = Self::Encoder::new(DaEncoderParams::new(
-            encoder_settings.num_columns,
-            encoder_settings.with_cache,
-            global_params,
-        ));
+        let encoder = Self::Encoder::new(
+            DaEncoderParams::new(
+                encoder_settings.num_columns,
+                encoder_settings.with_cache,
+                global_params,
+            )
+            .with_min_column_count(encoder_settings.num_columns),
+        );
         Self {
             settings,
             network_adapter: Arc::new(network_adapter),
@@ -165,15 +170,19 @@ where
         &self,
         data: Vec<u8>,
     ) -> Result<(Self::BlobId, <Self::Encoder as DaEncoder>::EncodedData), DynError> {
+        let max_blob_size = self.settings.encoder_settings.max_blob_size;
+        if data.len() > max_blob_size {
+            return Err(format!(
+                "Blob of {} bytes exceeds the maximum size of {max_blob_size} bytes",
+                data.len()
+            )
+            .into());
+        }
         let encoder = Arc::clone(&self.encoder);
         // this is a REALLY heavy task, so we should try not to block the thread here
         let heavy_task = tokio::task::spawn_blocking(move || encoder.encode(&data));
         let encoded_data = heavy_task.await??;
-        let blob_id = build_blob_id(
-            &encoded_data.aggregated_column_commitment,
-            &encoded_data.row_commitments,
-        );
-        Ok((blob_id, encoded_data))
+        Ok((encoded_data.blob_id(), encoded_data))
     }
 
     async fn disperse(
@@ -188,17 +197,32 @@ where
         .await
     }
 
+    fn blob_info(
+        encoded_data: &<Self::Encoder as DaEncoder>::EncodedData,
+        metadata: Self::Metadata,
+    ) -> Self::BlobInfo {
+        dispersal::BlobInfo::new(
+            encoded_data.blob_id(),
+            metadata,
+            encoded_data.domain_size,
+            encoded_data.data.len(),
+        )
+    }
+
     async fn publish_to_mempool(
         &self,
         blob_id: Self::BlobId,
-        metadata: Self::Metadata,
+        blob_info: Self::BlobInfo,
     ) -> Result<(), DynError> {
         self.mempool_adapter
-            .post_blob_id(blob_id, metadata)
+            .post_blob_info(blob_id, blob_info)
             .await
             .or_else(|err| match err {
                 DaMempoolAdapterError::Mempool(MempoolError::ExistingItem) => Ok(()),
-                DaMempoolAdapterError::Mempool(MempoolError::DynamicPoolError(err))
+                DaMempoolAdapterError::Mempool(err @ MempoolError::PoolFull) => Err(err.into()),
+                DaMempoolAdapterError::Mempool(
+                    MempoolError::DynamicPoolError(err) | MempoolError::InvalidItem(err),
+                )
                 | DaMempoolAdapterError::Other(err) => Err(err),
             })
     }
@@ -211,15 +235,16 @@ where
     ) -> Result<(), DynError> {
         let (blob_id, encoded_data) = self.encode(data).await?;
         info_with_id!(blob_id.as_ref(), "ProcessDispersal");
+        let blob_info = Self::blob_info(&encoded_data, metadata);
         self.disperse(encoded_data).await?;
         match self.settings.mempool_strategy {
             MempoolPublishStrategy::Immediately => {
-                self.publish_to_mempool(blob_id, metadata).await?;
+                self.publish_to_mempool(blob_id, blob_info).await?;
             }
             // MempoolPublishStrategy::Timeout { wait_duration } => {
             MempoolPublishStrategy::Timeout(Timeout { wait_duration }) => {
                 tokio::time::sleep(wait_duration).await;
-                self.publish_to_mempool(blob_id, metadata).await?;
+                self.publish_to_mempool(blob_id, blob_info).await?;
             }
             MempoolPublishStrategy::SampleSubnetworks(SampleSubnetworks {
                 sample_threshold,
@@ -229,8 +254,8 @@ where
                 let subnets = {
                     // ThreadRng is not Send, need to drop before await bound.
                     let mut rng = thread_rng();
-                    (0..self.settings.encoder_settings.num_columns as u16)
-                        .choose_multiple(&mut rng, sample_threshold)
+                    // only the columns of the blob can be sampled
+                    (0..blob_info.domain_size() as u16).choose_multiple(&mut rng, sample_threshold)
                 };
 
                 match tokio::time::timeout(
@@ -241,7 +266,7 @@ where
                 .await
                 {
                     Ok(Ok(())) => {
-                        self.publish_to_mempool(blob_id, metadata).await?;
+                        self.publish_to_mempool(blob_id, blob_info).await?;
                     }
                     Ok(Err(e)) => return Err(e),
                     Err(Elapsed { .. }) => {
@@ -18,6 +18,7 @@ pub trait DispersalBackend {
     type MempoolAdapter: DaMempoolAdapter;
     type Metadata: Debug + metadata::Metadata + Send;
     type BlobId: AsRef<[u8]> + Send;
+    type BlobInfo: Send;
 
     fn init(
         config: Self::Settings,
@@ -33,10 +34,16 @@ pub trait DispersalBackend {
         encoded_data: <Self::Encoder as DaEncoder>::EncodedData,
     ) -> Result<(), DynError>;
 
+    /// Info of the encoded blob published to the mempool.
+    fn blob_info(
+        encoded_data: &<Self::Encoder as DaEncoder>::EncodedData,
+        metadata: Self::Metadata,
+    ) -> Self::BlobInfo;
+
     async fn publish_to_mempool(
         &self,
         blob_id: Self::BlobId,
-        metadata: Self::Metadata,
+        blob_info: Self::BlobInfo,
     ) -> Result<(), DynError>;
 
     #[instrument(skip_all)]
@@ -47,10 +54,11 @@ pub trait DispersalBackend {
     ) -> Result<(), DynError> {
         let (blob_id, encoded_data) = self.encode(data).await?;
         info_with_id!(blob_id.as_ref(), "ProcessDispersal");
+        let blob_info = Self::blob_info(&encoded_data, metadata);
         self.disperse(encoded_data).await?;
         // let disperse and replication happen before pushing to mempool
         tokio::time::sleep(Duration::from_secs(1)).await;
-        self.publish_to_mempool(blob_id, metadata).await?;
+        self.publish_to_mempool(blob_id, blob_info).await?;
         Ok(())
     }
 }
@@ -1,9 +1,12 @@
-use cryptarchia_consensus::ConsensusMsg;
+use cryptarchia_consensus::{ConsensusMsg, FinalizedBlock};
 use futures::Stream;
 use nomos_core::block::Block;
 use overwatch::services::relay::OutboundRelay;
 use tokio::sync::oneshot;
-use tokio_stream::{wrappers::BroadcastStream, StreamExt};
+use tokio_stream::{
+    wrappers::{BroadcastStream, ReceiverStream},
+    StreamExt,
+};
 use tracing::debug;
 
 use crate::consensus::ConsensusAdapter;
@@ -55,4 +58,24 @@ where
             }),
         )
     }
+
+    async fn finalized_block_stream(
+        &self,
+    ) -> Box<dyn Stream<Item = FinalizedBlock<Block<Self::Tx, Self::Cert>>> + Unpin + Send> {
+        let (sender, receiver) = oneshot::channel();
+
+        self.consensus_relay
+            .send(ConsensusMsg::FinalizedBlockSubscribe {
+                from_height: None,
+                sender,
+            })
+            .await
+            .expect("Failed to send FinalizedBlockSubscribe message");
+
+        let finalized_blocks = receiver
+            .await
+            .expect("Failed to receive finalized blocks receiver");
+
+        Box::new(ReceiverStream::new(finalized_blocks))
+    }
 }
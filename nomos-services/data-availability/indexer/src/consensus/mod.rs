@@ -1,6 +1,6 @@
 pub mod adapters;
 
-use cryptarchia_consensus::ConsensusMsg;
+use cryptarchia_consensus::{ConsensusMsg, FinalizedBlock};
 use futures::Stream;
 use nomos_core::block::Block;
 use overwatch::services::relay::OutboundRelay;
@@ -16,4 +16,10 @@ pub trait ConsensusAdapter {
     async fn block_stream(
         &self,
     ) -> Box<dyn Stream<Item = Block<Self::Tx, Self::Cert>> + Unpin + Send>;
+
+    /// Stream of the blocks finalized from now on, without gaps. The stream
+    /// ends if consensus cannot deliver some block.
+    async fn finalized_block_stream(
+        &self,
+    ) -> Box<dyn Stream<Item = FinalizedBlock<Block<Self::Tx, Self::Cert>>> + Unpin + Send>;
 }
@@ -8,15 +8,19 @@ use std::{
 };
 
 use consensus::ConsensusAdapter;
-use cryptarchia_consensus::{network::NetworkAdapter, CryptarchiaConsensus};
+use cryptarchia_consensus::{network::NetworkAdapter, CryptarchiaConsensus, FinalizedBlock};
 use futures::StreamExt;
 use nomos_core::{
     block::Block,
-    da::blob::{info::DispersedBlobInfo, metadata::Metadata, BlobSelect},
+    da::{
+        blob::{info::DispersedBlobInfo, metadata::Metadata, BlobSelect},
+        BlobId,
+    },
     header::HeaderId,
     tx::{Transaction, TxSelect},
 };
 use nomos_da_sampling::backend::DaSamplingServiceBackend;
+use nomos_da_storage::retention::{BlockReference, RetentionPolicy};
 use nomos_mempool::{
     backend::{MemPool, RecoverableMempool},
     network::NetworkAdapter as MempoolAdapter,
@@ -114,6 +118,11 @@ pub enum DaMsg<Blob, Meta: Metadata> {
         range: Range<<Meta as Metadata>::Index>,
         reply_channel: Sender<Vec<(<Meta as Metadata>::Index, Vec<Blob>)>>,
     },
+    GetBlobIds {
+        app_id: <Meta as Metadata>::AppId,
+        range: Range<<Meta as Metadata>::Index>,
+        reply_channel: Sender<Result<Vec<(<Meta as Metadata>::Index, Option<BlobId>)>, DynError>>,
+    },
 }
 
 impl<Blob: 'static, Meta: Metadata + 'static> Debug for DaMsg<Blob, Meta> {
@@ -125,6 +134,9 @@ impl<Blob: 'static, Meta: Metadata + 'static> Debug for DaMsg<Blob, Meta> {
             Self::GetRange { .. } => {
                 write!(f, "DaMsg::GetRange")
             }
+            Self::GetBlobIds { .. } => {
+                write!(f, "DaMsg::GetBlobIds")
+            }
         }
     }
 }
@@ -329,6 +341,25 @@ where
         Ok(())
     }
 
+    #[instrument(skip_all)]
+    async fn handle_finalized_block(
+        storage_adapter: &DaStorage,
+        retention: Option<RetentionPolicy>,
+        FinalizedBlock { height, block, .. }: FinalizedBlock<Block<ClPool::Item, DaPool::Item>>,
+    ) -> Result<(), DynError> {
+        let tip = BlockReference {
+            height,
+            slot: block.header().slot().into(),
+        };
+        for info in block.blobs() {
+            storage_adapter.add_block_reference(info, tip).await?;
+        }
+        if let Some(policy) = retention {
+            storage_adapter.prune(policy, tip).await?;
+        }
+        Ok(())
+    }
+
     #[instrument(skip_all)]
     async fn handle_da_msg(
         storage_adapter: &DaStorage,
@@ -351,6 +382,17 @@ where
                     .send(results)
                     .map_err(|_| "Error sending range response".into())
             }
+            DaMsg::GetBlobIds {
+                app_id,
+                range,
+                reply_channel,
+            } => {
+                let blob_ids = storage_adapter.get_blob_ids(app_id, range).await;
+
+                reply_channel
+                    .send(blob_ids)
+                    .map_err(|_| "Error sending blob ids response".into())
+            }
         }
     }
 }
@@ -510,6 +552,8 @@ where
 
     async fn run(self) -> Result<(), DynError> {
         let Self { mut service_state } = self;
+        let IndexerSettings { retention, .. } =
+            service_state.settings_reader.get_updated_settings();
 
         let consensus_relay = service_state
             .overwatch_handle
@@ -525,6 +569,7 @@ where
 
         let consensus_adapter = Consensus::new(consensus_relay).await;
         let mut consensus_blocks = consensus_adapter.block_stream().await;
+        let mut finalized_blocks = consensus_adapter.finalized_block_stream().await;
         let storage_adapter = DaStorage::new(storage_relay).await;
 
         let mut lifecycle_stream = service_state.lifecycle_handle.message_stream();
@@ -535,6 +580,14 @@ where
                         tracing::debug!("Failed to add  a new received block: {e:?}");
                     }
                 }
+                finalized_block = finalized_blocks.next() => {
+                    let Some(finalized_block) = finalized_block else {
+                        return Err(DynError::from("Finalized blocks stream closed"));
+                    };
+                    if let Err(e) = Self::handle_finalized_block(&storage_adapter, retention, finalized_block).await {
+                        tracing::error!("Failed to handle finalized block: {e:?}");
+                    }
+                }
                 Some(msg) = service_state.inbound_relay.recv() => {
                     if let Err(e) = Self::handle_da_msg(&storage_adapter, msg).await {
                         tracing::debug!("Failed to handle da msg: {e:?}");
@@ -555,4 +608,7 @@ where
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IndexerSettings<S> {
     pub storage: S,
+    /// Blobs are kept forever if not set.
+    #[serde(default)]
+    pub retention: Option<RetentionPolicy>,
 }
@@ -1,4 +1,4 @@
-use std::{marker::PhantomData, ops::Range, path::PathBuf};
+use std::{collections::HashSet, marker::PhantomData, ops::Range, path::PathBuf};
 
 use bytes::Bytes;
 use futures::{stream::FuturesUnordered, try_join, Stream};
@@ -11,11 +11,19 @@ use nomos_core::da::{
     },
     BlobId,
 };
-use nomos_da_storage::rocksdb::{
-    key_bytes, DA_SHARED_COMMITMENTS_PREFIX, DA_SHARE_PREFIX, DA_VID_KEY_PREFIX,
+use nomos_da_storage::{
+    retention::{BlobRetention, BlockReference, RetentionPolicy},
+    rocksdb::{
+        create_share_idx, key_bytes, DA_BLOB_RETENTION_PENDING_PREFIX, DA_BLOB_RETENTION_PREFIX,
+        DA_BLOB_RETENTION_QUEUE_PREFIX, DA_BLOB_SHARES_INDEX_PREFIX, DA_SHARED_COMMITMENTS_PREFIX,
+        DA_SHARE_PREFIX, DA_VID_KEY_PREFIX,
+    },
 };
 use nomos_storage::{
-    backends::{rocksdb::RocksBackend, StorageSerde},
+    backends::{
+        rocksdb::{Error, RocksBackend, Transaction, DB},
+        BatchOperation, StorageBatch as _, StorageSerde,
+    },
     StorageMsg, StorageService,
 };
 use overwatch::{
@@ -60,22 +68,8 @@ where
     }
 
     async fn add_index(&self, info: &Self::Info) -> Result<(), DynError> {
-        // Check if Info in a block is something that the node've seen before.
-        let share_key = key_bytes(DA_SHARE_PREFIX, info.blob_id().as_ref());
-        let (reply_tx, reply_rx) = tokio::sync::oneshot::channel();
-        self.storage_relay
-            .send(StorageMsg::LoadPrefix {
-                prefix: share_key,
-                reply_channel: reply_tx,
-            })
-            .await
-            .expect("Failed to send load request to storage relay");
-
-        // If node haven't attested this info, return early.
-        if reply_rx.await?.is_empty() {
-            return Ok(());
-        }
-
+        // Blobs are indexed whether or not the node holds shares of them, so
+        // they can be retrieved from the network by index.
         let (app_id, idx) = info.metadata();
         let vid_key = key_bytes(
             DA_VID_KEY_PREFIX,
@@ -88,11 +82,35 @@ where
 
         self.storage_relay
             .send(StorageMsg::Store {
-                key: vid_key,
+                key: vid_key.clone(),
                 value,
             })
             .await
-            .map_err(|(e, _)| e.into())
+            .map_err(|(e, _)| e)?;
+
+        // Keep track of the key so it is removed together with the blob.
+        let vid_key = vid_key.to_vec();
+        self.execute(update_retention::<S>(info.blob_id(), move |retention| {
+            if !retention.vid_keys.contains(&vid_key) {
+                retention.vid_keys.push(vid_key);
+            }
+        }))
+        .await
+    }
+
+    async fn add_block_reference(
+        &self,
+        info: &Self::Info,
+        block: BlockReference,
+    ) -> Result<(), DynError> {
+        self.execute(update_retention::<S>(info.blob_id(), move |retention| {
+            retention.reference(block);
+        }))
+        .await
+    }
+
+    async fn prune(&self, policy: RetentionPolicy, tip: BlockReference) -> Result<(), DynError> {
+        self.execute(prune_transaction::<S>(policy, tip)).await
     }
 
     async fn get_range_stream(
@@ -177,9 +195,14 @@ where
                     .into_iter()
                     .filter_map(|bytes| S::deserialize::<DaLightShare>(bytes).ok());
 
-                let deserialized_shared_commitments =
+                // Commitments stored before blobs carried their dimensions can
+                // not be deserialized.
+                let Ok(deserialized_shared_commitments) =
                     S::deserialize::<DaSharesCommitments>(shared_commitments)
-                        .expect("Failed to deserialize shared commitments");
+                else {
+                    tracing::error!("Failed to deserialize shared commitments from storage");
+                    return (idx, Vec::new());
+                };
 
                 let da_shares: Vec<_> = deserialized_shares
                     .map(|share| {
@@ -198,9 +221,385 @@ where
 
         Box::new(futures)
     }
+
+    async fn get_blob_ids(
+        &self,
+        app_id: <Self::Info as Metadata>::AppId,
+        index_range: Range<<Self::Info as Metadata>::Index>,
+    ) -> Result<Vec<(<Self::Info as Metadata>::Index, Option<BlobId>)>, DynError> {
+        let mut blob_ids = Vec::new();
+        let mut current_index = index_range.start.clone();
+        while current_index <= index_range.end {
+            let (reply_tx, reply_rx) = tokio::sync::oneshot::channel();
+            self.storage_relay
+                .send(StorageMsg::Load {
+                    key: key_bytes(
+                        DA_VID_KEY_PREFIX,
+                        [app_id.as_ref(), current_index.as_ref()].concat(),
+                    ),
+                    reply_channel: reply_tx,
+                })
+                .await
+                .map_err(|(e, _)| e)?;
+
+            let blob_id = reply_rx
+                .await?
+                .map(|id| BlobId::try_from(id.as_ref()))
+                .transpose()?;
+            let next_index = current_index.clone().next();
+            blob_ids.push((current_index, blob_id));
+            current_index = next_index;
+        }
+
+        Ok(blob_ids)
+    }
+}
+
+impl<S, Meta> RocksAdapter<S, Meta>
+where
+    S: StorageSerde + Send + Sync + 'static,
+    Meta: DispersedBlobInfo<BlobId = BlobId> + Metadata + Send + Sync,
+{
+    /// Runs the transaction in the storage service, which executes it without
+    /// interleaving other storage requests.
+    async fn execute(&self, transaction: Transaction) -> Result<(), DynError> {
+        let (msg, receiver) = StorageMsg::<RocksBackend<S>>::new_transaction_message(transaction);
+        self.storage_relay.send(msg).await.map_err(|(e, _)| e)?;
+        RocksBackend::<S>::batch_result(receiver.into_inner().await?).map_err(Into::into)
+    }
+}
+
+fn retention_queue_key(reference: BlockReference, blob_id: &BlobId) -> Bytes {
+    key_bytes(
+        DA_BLOB_RETENTION_QUEUE_PREFIX,
+        [
+            reference.height.to_be_bytes().as_slice(),
+            blob_id.as_slice(),
+        ]
+        .concat(),
+    )
+}
+
+fn load_retention<S: StorageSerde>(
+    db: &DB,
+    blob_id: &BlobId,
+) -> Result<Option<BlobRetention<BlobId>>, Error> {
+    Ok(db
+        .get(key_bytes(DA_BLOB_RETENTION_PREFIX, blob_id))?
+        .and_then(|bytes| {
+            S::deserialize(Bytes::from(bytes))
+                .inspect_err(|e| tracing::error!("Failed to deserialize blob retention: {e:?}"))
+                .ok()
+        }))
+}
+
+/// Transaction applying `update` to the retention record of the blob, and
+/// moving the blob in the retention queue if its reference changes.
+fn update_retention<S>(
+    blob_id: BlobId,
+    update: impl FnOnce(&mut BlobRetention<BlobId>) + Send + Sync + 'static,
+) -> Transaction
+where
+    S: StorageSerde + Send + Sync + 'static,
+{
+    Transaction::new(move |db| {
+        let mut retention =
+            load_retention::<S>(db, &blob_id)?.unwrap_or_else(|| BlobRetention::new(blob_id));
+        let previous = retention.referenced_at;
+        update(&mut retention);
+
+        let mut operations = Vec::new();
+        match retention.referenced_at {
+            Some(reference) if previous != Some(reference) => {
+                if let Some(previous) = previous {
+                    operations.push(BatchOperation::Remove {
+                        key: retention_queue_key(previous, &blob_id),
+                    });
+                }
+                operations.extend([
+                    BatchOperation::Store {
+                        key: retention_queue_key(reference, &blob_id),
+                        value: S::serialize(reference),
+                    },
+                    BatchOperation::Remove {
+                        key: key_bytes(DA_BLOB_RETENTION_PENDING_PREFIX, blob_id),
+                    },
+                ]);
+            }
+            Some(_) => {}
+            // Blobs without a reference are queued by the next prune.
+            None => operations.push(BatchOperation::Store {
+                key: key_bytes(DA_BLOB_RETENTION_PENDING_PREFIX, blob_id),
+                value: Bytes::new(),
+            }),
+        }
+        operations.push(BatchOperation::Store {
+            key: key_bytes(DA_BLOB_RETENTION_PREFIX, blob_id),
+            value: S::serialize(retention),
+        });
+        RocksBackend::<S>::batch_transaction(operations).execute(db)
+    })
+}
+
+/// Transaction removing the blobs whose latest reference falls out of the
+/// window ending at `tip`.
+///
+/// Blobs not referenced by a block yet are queued at `tip` first, so they get
+/// a full window from now on. The queue is ordered by height, so only the
+/// expired entries and the first one kept are read.
+fn prune_transaction<S>(policy: RetentionPolicy, tip: BlockReference) -> Transaction
+where
+    S: StorageSerde + Send + Sync + 'static,
+{
+    Transaction::new(move |db| {
+        let mut operations = Vec::new();
+
+        let pending_prefix = DA_BLOB_RETENTION_PENDING_PREFIX.as_bytes();
+        for item in db.prefix_iterator(pending_prefix) {
+            let (key, _) = item?;
+            let Some(blob_id) = key.strip_prefix(pending_prefix) else {
+                break;
+            };
+            operations.push(BatchOperation::Remove {
+                key: Bytes::copy_from_slice(&key),
+            });
+            let Ok(blob_id) = BlobId::try_from(blob_id) else {
+                continue;
+            };
+            let mut retention =
+                load_retention::<S>(db, &blob_id)?.unwrap_or_else(|| BlobRetention::new(blob_id));
+            if retention.referenced_at.is_none() {
+                retention.referenced_at = Some(tip);
+                operations.extend([
+                    BatchOperation::Store {
+                        key: retention_queue_key(tip, &blob_id),
+                        value: S::serialize(tip),
+                    },
+                    BatchOperation::Store {
+                        key: key_bytes(DA_BLOB_RETENTION_PREFIX, blob_id),
+                        value: S::serialize(retention),
+                    },
+                ]);
+            }
+        }
+
+        let queue_prefix = DA_BLOB_RETENTION_QUEUE_PREFIX.as_bytes();
+        for item in db.prefix_iterator(queue_prefix) {
+            let (key, value) = item?;
+            let Some(entry) = key.strip_prefix(queue_prefix) else {
+                break;
+            };
+            let reference = S::deserialize::<BlockReference>(Bytes::copy_from_slice(&value)).ok();
+            if reference.is_some_and(|reference| !policy.is_expired(reference, tip)) {
+                break;
+            }
+            operations.push(BatchOperation::Remove {
+                key: Bytes::copy_from_slice(&key),
+            });
+            let Some(blob_id) = entry.get(8..).and_then(|id| BlobId::try_from(id).ok()) else {
+                continue;
+            };
+            match load_retention::<S>(db, &blob_id)? {
+                Some(retention) if retention.referenced_at == reference => {
+                    operations.extend(blob_removals::<S>(db, retention)?);
+                }
+                // Stale entry, the blob was queued again.
+                _ => {}
+            }
+        }
+
+        if operations.is_empty() {
+            return Ok(None);
+        }
+        RocksBackend::<S>::batch_transaction(operations).execute(db)
+    })
+}
+
+/// Removals of everything stored for the blob.
+fn blob_removals<S: StorageSerde>(
+    db: &DB,
+    retention: BlobRetention<BlobId>,
+) -> Result<Vec<BatchOperation>, Error> {
+    let blob_id = retention.blob_id;
+    let shares_index_key = key_bytes(DA_BLOB_SHARES_INDEX_PREFIX, blob_id);
+    let shares_index: HashSet<<DaShare as Share>::ShareIndex> = db
+        .get(&shares_index_key)?
+        .and_then(|bytes| S::deserialize(Bytes::from(bytes)).ok())
+        .unwrap_or_default();
+
+    let mut removals: Vec<_> = shares_index
+        .into_iter()
+        .map(|share_idx| key_bytes(DA_SHARE_PREFIX, create_share_idx(&blob_id, &share_idx)))
+        .chain([
+            shares_index_key,
+            key_bytes(DA_SHARED_COMMITMENTS_PREFIX, blob_id),
+            key_bytes(DA_BLOB_RETENTION_PREFIX, blob_id),
+            key_bytes(DA_BLOB_RETENTION_PENDING_PREFIX, blob_id),
+        ])
+        .collect();
+    // The index could point to a newer blob already.
+    for vid_key in retention.vid_keys {
+        if db.get(&vid_key)?.as_deref() == Some(blob_id.as_slice()) {
+            removals.push(Bytes::from(vid_key));
+        }
+    }
+
+    Ok(removals
+        .into_iter()
+        .map(|key| BatchOperation::Remove { key })
+        .collect())
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RocksAdapterSettings {
     pub blob_storage_directory: PathBuf,
 }
+
+#[cfg(test)]
+mod tests {
+    use nomos_core::wire;
+    use nomos_storage::backends::{rocksdb::RocksBackendSettings, StorageBackend as _};
+    use serde::de::DeserializeOwned;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    struct Wire;
+
+    impl StorageSerde for Wire {
+        type Error = wire::Error;
+
+        fn serialize<T: Serialize>(value: T) -> Bytes {
+            wire::serialize(&value).unwrap().into()
+        }
+
+        fn deserialize<T: DeserializeOwned>(buff: Bytes) -> Result<T, Self::Error> {
+            wire::deserialize(&buff)
+        }
+    }
+
+    const fn block(height: u64) -> BlockReference {
+        BlockReference {
+            height,
+            slot: height,
+        }
+    }
+
+    async fn store_blob(backend: &mut RocksBackend<Wire>, blob_id: BlobId) {
+        let share_idx = [0u8; 2];
+        let index: HashSet<[u8; 2]> = HashSet::from([share_idx]);
+        for (key, value) in [
+            (
+                key_bytes(DA_SHARE_PREFIX, create_share_idx(&blob_id, &share_idx)),
+                Bytes::from_static(b"share"),
+            ),
+            (
+                key_bytes(DA_BLOB_SHARES_INDEX_PREFIX, blob_id),
+                Wire::serialize(index),
+            ),
+            (
+                key_bytes(DA_SHARED_COMMITMENTS_PREFIX, blob_id),
+                Bytes::from_static(b"commitments"),
+            ),
+            (
+                key_bytes(DA_BLOB_RETENTION_PENDING_PREFIX, blob_id),
+                Bytes::new(),
+            ),
+        ] {
+            backend.store(key, value).await.unwrap();
+        }
+    }
+
+    async fn is_stored(backend: &mut RocksBackend<Wire>, blob_id: BlobId) -> bool {
+        let shares = backend
+            .load_prefix(&key_bytes(DA_SHARE_PREFIX, blob_id))
+            .await
+            .unwrap();
+        let commitments = backend
+            .load(&key_bytes(DA_SHARED_COMMITMENTS_PREFIX, blob_id))
+            .await
+            .unwrap();
+        assert_eq!(shares.is_empty(), commitments.is_none());
+        commitments.is_some()
+    }
+
+    async fn execute(backend: &mut RocksBackend<Wire>, transaction: Transaction) {
+        RocksBackend::<Wire>::batch_result(backend.execute(transaction).await.unwrap()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn prune_expired_blobs() {
+        let path = TempDir::new().unwrap();
+        let mut backend = RocksBackend::<Wire>::new(RocksBackendSettings {
+            db_path: path.path().to_path_buf(),
+            read_only: false,
+            column_family: None,
+        })
+        .unwrap();
+        let policy = RetentionPolicy::BlockDepth(10);
+
+        let (old, referenced_again, recent, unreferenced) = ([1; 32], [2; 32], [3; 32], [4; 32]);
+        for blob_id in [old, referenced_again, recent, unreferenced] {
+            store_blob(&mut backend, blob_id).await;
+        }
+        let vid_key = key_bytes(DA_VID_KEY_PREFIX, b"app");
+        backend
+            .store(vid_key.clone(), Bytes::copy_from_slice(&old))
+            .await
+            .unwrap();
+        let old_vid_key = vid_key.to_vec();
+        execute(
+            &mut backend,
+            update_retention::<Wire>(old, move |retention| {
+                retention.vid_keys.push(old_vid_key);
+            }),
+        )
+        .await;
+        for (blob_id, height) in [
+            (old, 1),
+            (referenced_again, 2),
+            (recent, 12),
+            (referenced_again, 15),
+        ] {
+            execute(
+                &mut backend,
+                update_retention::<Wire>(blob_id, move |retention| {
+                    retention.reference(block(height));
+                }),
+            )
+            .await;
+        }
+
+        execute(&mut backend, prune_transaction::<Wire>(policy, block(20))).await;
+        assert!(!is_stored(&mut backend, old).await);
+        assert!(backend.load(&vid_key).await.unwrap().is_none());
+        assert!(backend
+            .load(&key_bytes(DA_BLOB_RETENTION_PREFIX, old))
+            .await
+            .unwrap()
+            .is_none());
+        for blob_id in [referenced_again, recent, unreferenced] {
+            assert!(is_stored(&mut backend, blob_id).await);
+        }
+        assert!(backend
+            .load_prefix(DA_BLOB_RETENTION_PENDING_PREFIX.as_bytes())
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Blobs without a reference get a full window from the first prune.
+        execute(&mut backend, prune_transaction::<Wire>(policy, block(23))).await;
+        assert!(!is_stored(&mut backend, recent).await);
+        assert!(is_stored(&mut backend, referenced_again).await);
+        assert!(is_stored(&mut backend, unreferenced).await);
+
+        execute(&mut backend, prune_transaction::<Wire>(policy, block(30))).await;
+        assert!(!is_stored(&mut backend, referenced_again).await);
+        assert!(!is_stored(&mut backend, unreferenced).await);
+        assert!(backend
+            .load_prefix(DA_BLOB_RETENTION_QUEUE_PREFIX.as_bytes())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
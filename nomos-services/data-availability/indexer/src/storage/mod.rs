@@ -3,7 +3,11 @@ pub mod adapters;
 use std::ops::Range;
 
 use futures::Stream;
-use nomos_core::da::blob::{info::DispersedBlobInfo, metadata::Metadata};
+use nomos_core::da::{
+    blob::{info::DispersedBlobInfo, metadata::Metadata},
+    BlobId,
+};
+use nomos_da_storage::retention::{BlockReference, RetentionPolicy};
 use nomos_storage::{backends::StorageBackend, StorageService};
 use overwatch::{
     services::{relay::OutboundRelay, ServiceData},
@@ -25,9 +29,25 @@ pub trait DaStorageAdapter<RuntimeServiceId> {
     ) -> Self;
 
     async fn add_index(&self, vid: &Self::Info) -> Result<(), DynError>;
+    /// Records that the blob is referenced by a finalized block.
+    async fn add_block_reference(
+        &self,
+        vid: &Self::Info,
+        block: BlockReference,
+    ) -> Result<(), DynError>;
+    /// Removes shares, commitments and indexes of the blobs whose latest
+    /// reference falls out of the retention window ending at `tip`.
+    async fn prune(&self, policy: RetentionPolicy, tip: BlockReference) -> Result<(), DynError>;
     async fn get_range_stream(
         &self,
         app_id: <Self::Info as Metadata>::AppId,
         range: Range<<Self::Info as Metadata>::Index>,
     ) -> Box<dyn Stream<Item = (<Self::Info as Metadata>::Index, Vec<Self::Share>)> + Unpin + Send>;
+    /// Resolves the blob ids indexed in the range, `None` for the indexes no
+    /// blob is indexed at.
+    async fn get_blob_ids(
+        &self,
+        app_id: <Self::Info as Metadata>::AppId,
+        range: Range<<Self::Info as Metadata>::Index>,
+    ) -> Result<Vec<(<Self::Info as Metadata>::Index, Option<BlobId>)>, DynError>;
 }
@@ -0,0 +1,102 @@
+use std::{collections::HashMap, marker::PhantomData};
+
+use libp2p::{identity::ed25519, Multiaddr, PeerId};
+use nomos_da_network_service::{backends::NetworkBackend, DaNetworkMsg, NetworkService};
+use nomos_sdp::membership::{MembershipAdapter, MembershipRelay, SessionMembershipService};
+use nomos_sdp_core::{Locator, ProviderId, ServiceType};
+use overwatch::DynError;
+use subnetworks_assignations::MembershipCreator;
+use tokio::sync::oneshot;
+use tracing::warn;
+
+/// Service keeping the DA network membership in sync with the
+/// `DataAvailability` providers declared through SDP.
+pub type DaMembershipService<Backend, Sdp, RuntimeServiceId> =
+    SessionMembershipService<DaMembershipAdapter<Backend>, Sdp, RuntimeServiceId>;
+
+/// Applies the `DataAvailability` providers to the DA network membership.
+pub struct DaMembershipAdapter<Backend> {
+    _backend: PhantomData<fn() -> Backend>,
+}
+
+#[async_trait::async_trait]
+impl<Backend, RuntimeServiceId> MembershipAdapter<RuntimeServiceId> for DaMembershipAdapter<Backend>
+where
+    Backend: NetworkBackend<RuntimeServiceId> + Send + Sync + 'static,
+    Backend::Membership: MembershipCreator<Id = PeerId>,
+    RuntimeServiceId: Send + Sync + 'static,
+{
+    type Service = NetworkService<Backend, RuntimeServiceId>;
+
+    const SERVICE_TYPE: ServiceType = ServiceType::DataAvailability;
+
+    /// Replaces the DA network membership with one built from the given
+    /// providers, keeping the assignation parameters of the current one. The
+    /// membership is left untouched if none of the providers can be reached.
+    async fn update_membership(
+        da_network_relay: &MembershipRelay<Self, RuntimeServiceId>,
+        providers: &HashMap<ProviderId, Vec<Locator>>,
+    ) -> Result<(), DynError> {
+        let addressbook = providers_addressbook(providers);
+        if addressbook.is_empty() {
+            warn!("No active DA providers, keeping the current membership");
+            return Ok(());
+        }
+
+        let (reply_channel, membership_receiver) = oneshot::channel();
+        da_network_relay
+            .send(DaNetworkMsg::GetMembership { reply_channel })
+            .await
+            .map_err(|(e, _)| Box::new(e) as DynError)?;
+        let membership = membership_receiver.await?.init(addressbook);
+        da_network_relay
+            .send(DaNetworkMsg::UpdateMembership(membership))
+            .await
+            .map_err(|(e, _)| Box::new(e) as DynError)?;
+        Ok(())
+    }
+}
+
+/// Maps the providers to their DA network peer id and address.
+///
+/// Provider ids are the ed25519 public keys of the nodes, which the peer ids
+/// are derived from. Providers with an invalid key or without locators are
+/// skipped.
+fn providers_addressbook(
+    providers: &HashMap<ProviderId, Vec<Locator>>,
+) -> HashMap<PeerId, Multiaddr> {
+    providers
+        .iter()
+        .filter_map(|(provider_id, locators)| {
+            let Ok(public_key) = ed25519::PublicKey::try_from_bytes(&provider_id.0) else {
+                warn!("Invalid DA provider id {provider_id:?}");
+                return None;
+            };
+            let peer_id = PeerId::from_public_key(&public_key.into());
+            let locator = locators.first()?;
+            Some((peer_id, locator.addr().clone()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_providers_addressbook() {
+        let keypair = ed25519::Keypair::from(ed25519::SecretKey::try_from_bytes([1; 32]).unwrap());
+        let provider_id = ProviderId(keypair.public().to_bytes());
+        let address: Multiaddr = "/ip4/127.0.0.1/udp/8716/quic-v1".parse().unwrap();
+        let providers = HashMap::from([
+            (provider_id, vec![Locator::new(address.clone())]),
+            // provider without locators
+            (ProviderId([2; 32]), Vec::new()),
+        ]);
+
+        let addressbook = providers_addressbook(&providers);
+
+        let peer_id = PeerId::from_public_key(&keypair.public().into());
+        assert_eq!(addressbook, HashMap::from([(peer_id, address)]));
+    }
+}
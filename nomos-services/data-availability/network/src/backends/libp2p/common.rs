@@ -1,6 +1,7 @@
 use std::{fmt::Debug, time::Duration};
 
 use futures::StreamExt;
+use key_management_system::{retrieve_ed25519_key, NodeKMSRelay};
 use kzgrs_backend::common::{
     share::{DaLightShare, DaShare},
     ShareIndex,
@@ -19,7 +20,8 @@ use nomos_da_network_core::{
     },
     SubnetworkId,
 };
-use nomos_libp2p::{ed25519, secret_key_serde, Multiaddr};
+use nomos_libp2p::{ed25519, Multiaddr};
+use overwatch::DynError;
 use serde::{Deserialize, Serialize};
 use tokio::sync::{
     broadcast, mpsc,
@@ -31,9 +33,8 @@ pub(crate) const BROADCAST_CHANNEL_SIZE: usize = 128;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DaNetworkBackendSettings<Membership> {
-    // Identification Secp256k1 private key in Hex format (`0x123...abc`). Default random.
-    #[serde(with = "secret_key_serde", default = "ed25519::SecretKey::generate")]
-    pub node_key: ed25519::SecretKey,
+    /// Id of the ed25519 node key in the key management service
+    pub node_key_id: String,
     /// Membership of DA network `PoV` set
     pub membership: Membership,
     pub listening_address: Multiaddr,
@@ -179,3 +180,14 @@ pub(crate) async fn handle_balancer_command<Stats: Debug>(
         error!("Error stats request: {cmd:?}");
     }
 }
+
+pub(crate) async fn retrieve_node_keypair(
+    kms: &NodeKMSRelay,
+    node_key_id: String,
+) -> Result<libp2p::identity::Keypair, DynError> {
+    let mut node_key = retrieve_ed25519_key(kms, node_key_id).await?;
+    let node_key = ed25519::SecretKey::try_from_bytes(node_key.as_mut_slice())?;
+    Ok(libp2p::identity::Keypair::from(ed25519::Keypair::from(
+        node_key,
+    )))
+}
@@ -1,10 +1,11 @@
-use std::{fmt::Debug, marker::PhantomData, pin::Pin, sync::Arc};
+use std::{fmt::Debug, pin::Pin, sync::Arc};
 
 use futures::{
     future::Aborted,
     stream::{AbortHandle, Abortable},
     Stream, StreamExt,
 };
+use key_management_system::NodeKMSRelay;
 use kzgrs_backend::common::share::DaShare;
 use libp2p::PeerId;
 use log::error;
@@ -15,9 +16,8 @@ use nomos_da_network_core::{
     swarm::{executor::ExecutorSwarm, BalancerStats, MonitorStats},
     SubnetworkId,
 };
-use nomos_libp2p::ed25519;
 use nomos_tracing::info_with_id;
-use overwatch::{overwatch::handle::OverwatchHandle, services::state::NoState};
+use overwatch::{overwatch::handle::OverwatchHandle, services::state::NoState, DynError};
 use serde::{Deserialize, Serialize};
 use subnetworks_assignations::MembershipHandler;
 use tokio::{
@@ -30,8 +30,8 @@ use tracing::instrument;
 use crate::backends::{
     libp2p::common::{
         handle_balancer_command, handle_monitor_command, handle_sample_request,
-        handle_validator_events_stream, DaNetworkBackendSettings, SamplingEvent,
-        BROADCAST_CHANNEL_SIZE,
+        handle_validator_events_stream, retrieve_node_keypair, DaNetworkBackendSettings,
+        SamplingEvent, BROADCAST_CHANNEL_SIZE,
     },
     NetworkBackend,
 };
@@ -94,7 +94,8 @@ where
     dispersal_shares_sender: UnboundedSender<(Membership::NetworkId, DaShare)>,
     balancer_command_sender: UnboundedSender<ConnectionBalancerCommand<BalancerStats>>,
     monitor_command_sender: UnboundedSender<ConnectionMonitorCommand<MonitorStats>>,
-    _membership: PhantomData<Membership>,
+    membership_updates_sender: UnboundedSender<Arc<Membership>>,
+    membership: Membership,
 }
 
 #[async_trait::async_trait]
@@ -114,11 +115,15 @@ where
     type Message = ExecutorDaNetworkMessage<BalancerStats, MonitorStats>;
     type EventKind = DaNetworkEventKind;
     type NetworkEvent = DaNetworkEvent;
+    type Membership = Membership;
 
-    fn new(config: Self::Settings, overwatch_handle: OverwatchHandle<RuntimeServiceId>) -> Self {
-        let keypair = libp2p::identity::Keypair::from(ed25519::Keypair::from(
-            config.validator_settings.node_key.clone(),
-        ));
+    async fn new(
+        config: Self::Settings,
+        overwatch_handle: OverwatchHandle<RuntimeServiceId>,
+        kms: NodeKMSRelay,
+    ) -> Result<Self, DynError> {
+        let keypair =
+            retrieve_node_keypair(&kms, config.validator_settings.node_key_id.clone()).await?;
         let (mut executor_swarm, executor_events_stream) = ExecutorSwarm::new(
             keypair,
             Arc::new(config.validator_settings.membership.clone()),
@@ -141,6 +146,7 @@ where
         let dispersal_shares_sender = executor_swarm.dispersal_shares_channel();
         let balancer_command_sender = executor_swarm.balancer_command_channel();
         let monitor_command_sender = executor_swarm.monitor_command_channel();
+        let membership_updates_sender = executor_swarm.membership_update_channel();
 
         let (task_abort_handle, abort_registration) = AbortHandle::new_pair();
         let task = (
@@ -183,7 +189,7 @@ where
             )),
         );
 
-        Self {
+        Ok(Self {
             task,
             verifier_replies_task,
             executor_replies_task,
@@ -194,8 +200,9 @@ where
             dispersal_shares_sender,
             balancer_command_sender,
             monitor_command_sender,
-            _membership: PhantomData,
-        }
+            membership_updates_sender,
+            membership: config.validator_settings.membership,
+        })
     }
 
     fn shutdown(&mut self) {
@@ -210,6 +217,21 @@ where
         executor_handle.abort();
     }
 
+    fn membership(&self) -> Self::Membership {
+        self.membership.clone()
+    }
+
+    fn update_membership(&mut self, membership: Self::Membership) {
+        if let Err(e) = self
+            .membership_updates_sender
+            .send(Arc::new(membership.clone()))
+        {
+            error!("Error sending membership update to the DA swarm: {e:?}");
+            return;
+        }
+        self.membership = membership;
+    }
+
     #[instrument(skip_all)]
     async fn process(&self, msg: Self::Message) {
         match msg {
@@ -1,9 +1,10 @@
-use std::{fmt::Debug, marker::PhantomData, pin::Pin, sync::Arc};
+use std::{fmt::Debug, pin::Pin, sync::Arc};
 
 use futures::{
     future::{AbortHandle, Abortable, Aborted},
     Stream, StreamExt,
 };
+use key_management_system::NodeKMSRelay;
 use kzgrs_backend::common::share::DaShare;
 use libp2p::PeerId;
 use nomos_core::da::BlobId;
@@ -12,9 +13,8 @@ use nomos_da_network_core::{
     swarm::{validator::ValidatorSwarm, BalancerStats, MonitorStats},
     SubnetworkId,
 };
-use nomos_libp2p::ed25519;
 use nomos_tracing::info_with_id;
-use overwatch::{overwatch::handle::OverwatchHandle, services::state::NoState};
+use overwatch::{overwatch::handle::OverwatchHandle, services::state::NoState, DynError};
 use serde::Serialize;
 use subnetworks_assignations::MembershipHandler;
 use tokio::{
@@ -22,13 +22,13 @@ use tokio::{
     task::JoinHandle,
 };
 use tokio_stream::wrappers::BroadcastStream;
-use tracing::instrument;
+use tracing::{error, instrument};
 
 use crate::backends::{
     libp2p::common::{
         handle_balancer_command, handle_monitor_command, handle_sample_request,
-        handle_validator_events_stream, DaNetworkBackendSettings, SamplingEvent,
-        BROADCAST_CHANNEL_SIZE,
+        handle_validator_events_stream, retrieve_node_keypair, DaNetworkBackendSettings,
+        SamplingEvent, BROADCAST_CHANNEL_SIZE,
     },
     NetworkBackend,
 };
@@ -76,7 +76,8 @@ pub struct DaNetworkValidatorBackend<Membership> {
     monitor_command_sender: UnboundedSender<ConnectionMonitorCommand<MonitorStats>>,
     sampling_broadcast_receiver: broadcast::Receiver<SamplingEvent>,
     verifying_broadcast_receiver: broadcast::Receiver<DaShare>,
-    _membership: PhantomData<Membership>,
+    membership_updates_sender: UnboundedSender<Arc<Membership>>,
+    membership: Membership,
 }
 
 #[async_trait::async_trait]
@@ -96,10 +97,14 @@ where
     type Message = DaNetworkMessage<BalancerStats, MonitorStats>;
     type EventKind = DaNetworkEventKind;
     type NetworkEvent = DaNetworkEvent;
+    type Membership = Membership;
 
-    fn new(config: Self::Settings, overwatch_handle: OverwatchHandle<RuntimeServiceId>) -> Self {
-        let keypair =
-            libp2p::identity::Keypair::from(ed25519::Keypair::from(config.node_key.clone()));
+    async fn new(
+        config: Self::Settings,
+        overwatch_handle: OverwatchHandle<RuntimeServiceId>,
+        kms: NodeKMSRelay,
+    ) -> Result<Self, DynError> {
+        let keypair = retrieve_node_keypair(&kms, config.node_key_id.clone()).await?;
         let (mut validator_swarm, validator_events_stream) = ValidatorSwarm::new(
             keypair,
             Arc::new(config.membership.clone()),
@@ -121,6 +126,7 @@ where
         let sampling_request_channel = validator_swarm.sample_request_channel();
         let balancer_command_sender = validator_swarm.balancer_command_channel();
         let monitor_command_sender = validator_swarm.monitor_command_channel();
+        let membership_updates_sender = validator_swarm.membership_update_channel();
 
         let (task_abort_handle, abort_registration) = AbortHandle::new_pair();
         let task = (
@@ -146,7 +152,7 @@ where
             )),
         );
 
-        Self {
+        Ok(Self {
             task,
             replies_task,
             sampling_request_channel,
@@ -154,8 +160,9 @@ where
             monitor_command_sender,
             sampling_broadcast_receiver,
             verifying_broadcast_receiver,
-            _membership: PhantomData,
-        }
+            membership_updates_sender,
+            membership: config.membership,
+        })
     }
 
     fn shutdown(&mut self) {
@@ -168,6 +175,21 @@ where
         replies_handle.abort();
     }
 
+    fn membership(&self) -> Self::Membership {
+        self.membership.clone()
+    }
+
+    fn update_membership(&mut self, membership: Self::Membership) {
+        if let Err(e) = self
+            .membership_updates_sender
+            .send(Arc::new(membership.clone()))
+        {
+            error!("Error sending membership update to the DA swarm: {e:?}");
+            return;
+        }
+        self.membership = membership;
+    }
+
     #[instrument(skip_all)]
     async fn process(&self, msg: Self::Message) {
         match msg {
@@ -1,8 +1,9 @@
 use std::pin::Pin;
 
 use futures::{Stream, StreamExt};
+use key_management_system::NodeKMSRelay;
 use kzgrs_backend::common::{build_blob_id, share::DaShare};
-use overwatch::{overwatch::handle::OverwatchHandle, services::state::NoState};
+use overwatch::{overwatch::handle::OverwatchHandle, services::state::NoState, DynError};
 use serde::{Deserialize, Serialize};
 use tokio::sync::{
     broadcast::{self},
@@ -70,27 +71,40 @@ impl<RuntimeServiceId> NetworkBackend<RuntimeServiceId> for MockExecutorBackend
     type Message = Command;
     type EventKind = EventKind;
     type NetworkEvent = Event;
+    type Membership = ();
 
-    fn new(config: Self::Settings, _: OverwatchHandle<RuntimeServiceId>) -> Self {
+    async fn new(
+        config: Self::Settings,
+        _: OverwatchHandle<RuntimeServiceId>,
+        _: NodeKMSRelay,
+    ) -> Result<Self, DynError> {
         let (commands_tx, _) = tokio::sync::mpsc::channel(BUFFER_SIZE);
         let (events_tx, _) = tokio::sync::broadcast::channel(BUFFER_SIZE);
-        Self {
+        Ok(Self {
             _config: config,
             _commands_tx: commands_tx,
             events_tx,
-        }
+        })
     }
 
     fn shutdown(&mut self) {}
 
+    fn membership(&self) -> Self::Membership {}
+
+    fn update_membership(&mut self, _membership: Self::Membership) {}
+
     async fn process(&self, msg: Self::Message) {
         match msg {
             Command::Disperse {
                 share,
                 subnetwork_id,
             } => {
-                let blob_id =
-                    build_blob_id(&share.aggregated_column_commitment, &share.rows_commitments);
+                let blob_id = build_blob_id(
+                    &share.aggregated_column_commitment,
+                    &share.rows_commitments,
+                    share.domain_size,
+                    share.data_size,
+                );
 
                 let success_message = DisperseMessage::DispersalSuccess {
                     blob_id,
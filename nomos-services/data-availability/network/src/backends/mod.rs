@@ -4,7 +4,8 @@ pub mod mock;
 use std::pin::Pin;
 
 use futures::Stream;
-use overwatch::{overwatch::handle::OverwatchHandle, services::state::ServiceState};
+use key_management_system::NodeKMSRelay;
+use overwatch::{overwatch::handle::OverwatchHandle, services::state::ServiceState, DynError};
 
 use super::Debug;
 
@@ -15,9 +16,19 @@ pub trait NetworkBackend<RuntimeServiceId> {
     type Message: Debug + Send + Sync + 'static;
     type EventKind: Debug + Send + Sync + 'static;
     type NetworkEvent: Debug + Send + Sync + 'static;
+    type Membership: Clone + Send + Sync + 'static;
 
-    fn new(config: Self::Settings, overwatch_handle: OverwatchHandle<RuntimeServiceId>) -> Self;
+    /// Creates the backend, which retrieves the keys it needs through `kms`.
+    async fn new(
+        config: Self::Settings,
+        overwatch_handle: OverwatchHandle<RuntimeServiceId>,
+        kms: NodeKMSRelay,
+    ) -> Result<Self, DynError>
+    where
+        Self: Sized;
     fn shutdown(&mut self);
+    fn membership(&self) -> Self::Membership;
+    fn update_membership(&mut self, membership: Self::Membership);
     async fn process(&self, msg: Self::Message);
     async fn subscribe(
         &mut self,
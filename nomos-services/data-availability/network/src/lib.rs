@@ -2,12 +2,14 @@ pub mod backends;
 
 use std::{
     fmt::{self, Debug, Display},
+    marker::PhantomData,
     pin::Pin,
 };
 
 use async_trait::async_trait;
 use backends::NetworkBackend;
 use futures::{Stream, StreamExt};
+use key_management_system::NodeKMSService;
 use overwatch::{
     services::{
         state::{NoOperator, ServiceState},
@@ -25,6 +27,13 @@ pub enum DaNetworkMsg<B: NetworkBackend<RuntimeServiceId>, RuntimeServiceId> {
         kind: B::EventKind,
         sender: oneshot::Sender<Pin<Box<dyn Stream<Item = B::NetworkEvent> + Send>>>,
     },
+    /// Current membership of the DA network
+    GetMembership {
+        reply_channel: oneshot::Sender<B::Membership>,
+    },
+    /// Replace the membership of the DA network, e.g. when the set of DA
+    /// providers changes at a session boundary
+    UpdateMembership(B::Membership),
 }
 
 impl<B: NetworkBackend<RuntimeServiceId>, RuntimeServiceId> Debug
@@ -36,6 +45,8 @@ impl<B: NetworkBackend<RuntimeServiceId>, RuntimeServiceId> Debug
             Self::Subscribe { kind, .. } => {
                 write!(fmt, "DaNetworkMsg::Subscribe{{ kind: {kind:?}}}")
             }
+            Self::GetMembership { .. } => write!(fmt, "DaNetworkMsg::GetMembership"),
+            Self::UpdateMembership(_) => write!(fmt, "DaNetworkMsg::UpdateMembership"),
         }
     }
 }
@@ -54,8 +65,8 @@ impl<B: NetworkBackend<RuntimeServiceId>, RuntimeServiceId> Debug
 }
 
 pub struct NetworkService<B: NetworkBackend<RuntimeServiceId> + Send + 'static, RuntimeServiceId> {
-    backend: B,
     service_state: OpaqueServiceStateHandle<Self, RuntimeServiceId>,
+    _backend: PhantomData<B>,
 }
 
 pub struct NetworkState<B: NetworkBackend<RuntimeServiceId>, RuntimeServiceId> {
@@ -76,32 +87,48 @@ impl<B, RuntimeServiceId> ServiceCore<RuntimeServiceId> for NetworkService<B, Ru
 where
     B: NetworkBackend<RuntimeServiceId> + Send + 'static,
     B::State: Send + Sync,
-    RuntimeServiceId: AsServiceId<Self> + Clone + Display + Send,
+    RuntimeServiceId: AsServiceId<Self>
+        + AsServiceId<NodeKMSService<RuntimeServiceId>>
+        + Clone
+        + Debug
+        + Display
+        + Send
+        + Sync
+        + 'static,
 {
     fn init(
         service_state: OpaqueServiceStateHandle<Self, RuntimeServiceId>,
         _init_state: Self::State,
     ) -> Result<Self, overwatch::DynError> {
         Ok(Self {
-            backend: <B as NetworkBackend<RuntimeServiceId>>::new(
-                service_state.settings_reader.get_updated_settings().backend,
-                service_state.overwatch_handle.clone(),
-            ),
             service_state,
+            _backend: PhantomData,
         })
     }
 
-    async fn run(mut self) -> Result<(), overwatch::DynError> {
+    async fn run(self) -> Result<(), overwatch::DynError> {
         let Self {
             service_state:
                 OpaqueServiceStateHandle::<Self, RuntimeServiceId> {
                     mut inbound_relay,
                     lifecycle_handle,
+                    settings_reader,
+                    overwatch_handle,
                     ..
                 },
-            mut backend,
+            ..
         } = self;
 
+        let kms = overwatch_handle
+            .relay::<NodeKMSService<RuntimeServiceId>>()
+            .await?;
+        let mut backend = <B as NetworkBackend<RuntimeServiceId>>::new(
+            settings_reader.get_updated_settings().backend,
+            overwatch_handle.clone(),
+            kms,
+        )
+        .await?;
+
         let mut lifecycle_stream = lifecycle_handle.message_stream();
         loop {
             tokio::select! {
@@ -145,6 +172,14 @@ where
                         "client hung up before a subscription handle could be established"
                     );
                 }),
+            DaNetworkMsg::GetMembership { reply_channel } => reply_channel
+                .send(backend.membership())
+                .unwrap_or_else(|_| {
+                    tracing::warn!("client hung up before the membership could be sent");
+                }),
+            DaNetworkMsg::UpdateMembership(membership) => {
+                backend.update_membership(membership);
+            }
         }
     }
 }
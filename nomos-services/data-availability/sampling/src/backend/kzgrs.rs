@@ -23,6 +23,8 @@ use crate::{backend::SamplingState, DaSamplingServiceBackend};
 #[derive(Clone)]
 pub struct SamplingContext {
     subnets: HashSet<SubnetworkId>,
+    /// Number of subnets to sample, picked once the commitments are known.
+    required_samples: usize,
     started: Instant,
     commitment: Option<Arc<DaSharesCommitments>>,
 }
@@ -94,7 +96,7 @@ impl<R: Rng + Sync + Send> DaSamplingServiceBackend<R> for KzgrsSamplingBackend<
             ctx.subnets.insert(column_idx as SubnetworkId);
 
             // sampling of this blob_id terminated successfully
-            if ctx.subnets.len() == self.settings.num_samples as usize {
+            if ctx.subnets.len() == ctx.required_samples {
                 self.validated_blobs.insert(blob_id);
                 tracing::info!(
                     "blob_id {} has been successfully sampled",
@@ -129,16 +131,14 @@ impl<R: Rng + Sync + Send> DaSamplingServiceBackend<R> for KzgrsSamplingBackend<
             return SamplingState::Terminated;
         }
 
-        let subnets: Vec<SubnetworkId> = (0..self.settings.num_subnets as SubnetworkId)
-            .choose_multiple(&mut self.rng, self.settings.num_samples.into());
-
         let ctx: SamplingContext = SamplingContext {
             subnets: HashSet::new(),
+            required_samples: 0,
             started: Instant::now(),
             commitment: None,
         };
         self.pending_sampling_blobs.insert(blob_id, ctx);
-        SamplingState::Init(subnets)
+        SamplingState::WaitingCommitments
     }
 
     fn prune(&mut self) {
@@ -150,10 +150,26 @@ impl<R: Rng + Sync + Send> DaSamplingServiceBackend<R> for KzgrsSamplingBackend<
         ctx.commitment.clone()
     }
 
-    fn add_commitments(&mut self, blob_id: &Self::BlobId, commitments: Self::SharesCommitments) {
-        if let Some(ctx) = self.pending_sampling_blobs.get_mut(blob_id) {
-            ctx.commitment = Some(Arc::new(commitments));
+    fn add_commitments(
+        &mut self,
+        blob_id: &Self::BlobId,
+        commitments: Self::SharesCommitments,
+    ) -> SamplingState {
+        let Some(ctx) = self.pending_sampling_blobs.get_mut(blob_id) else {
+            return SamplingState::Terminated;
+        };
+        if ctx.commitment.is_some() {
+            return SamplingState::Tracking;
         }
+        // blobs smaller than the network are not spread to every subnet
+        let columns = commitments
+            .domain_size
+            .min(self.settings.num_subnets.into()) as SubnetworkId;
+        let subnets: Vec<SubnetworkId> =
+            (0..columns).choose_multiple(&mut self.rng, self.settings.num_samples.into());
+        ctx.required_samples = subnets.len();
+        ctx.commitment = Some(Arc::new(commitments));
+        SamplingState::Init(subnets)
     }
 }
 
@@ -165,7 +181,10 @@ mod test {
     };
 
     use kzgrs::{Commitment, Proof};
-    use kzgrs_backend::common::{share::DaShare, Column};
+    use kzgrs_backend::common::{
+        share::{DaShare, DaSharesCommitments},
+        Column,
+    };
     use nomos_core::da::BlobId;
     use rand::{prelude::*, rngs::StdRng};
 
@@ -185,6 +204,22 @@ mod test {
         KzgrsSamplingBackend::new(settings, rng)
     }
 
+    fn commitments(domain_size: usize) -> DaSharesCommitments {
+        DaSharesCommitments {
+            domain_size,
+            ..Default::default()
+        }
+    }
+
+    async fn start_sampling(
+        sampler: &mut KzgrsSamplingBackend<StdRng>,
+        blob_id: BlobId,
+        domain_size: usize,
+    ) -> SamplingState {
+        sampler.init_sampling(blob_id).await;
+        sampler.add_commitments(&blob_id, commitments(domain_size))
+    }
+
     #[tokio::test]
     async fn test_init_sampling_subnet_range() {
         let number_of_subnets = 42;
@@ -192,7 +227,11 @@ mod test {
         let mut backend = create_sampler(num_samples, number_of_subnets);
 
         let blob_id = BlobId::default();
-        let state = backend.init_sampling(blob_id).await;
+        assert!(matches!(
+            backend.init_sampling(blob_id).await,
+            SamplingState::WaitingCommitments
+        ));
+        let state = backend.add_commitments(&blob_id, commitments(64));
 
         if let SamplingState::Init(subnets) = state {
             let unique_subnet_ids: HashSet<_> = subnets.iter().copied().collect();
@@ -218,6 +257,28 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn test_sampling_within_blob_domain() {
+        let mut sampler = create_sampler(8, 16);
+        let blob_id = BlobId::default();
+
+        let SamplingState::Init(subnets) = start_sampling(&mut sampler, blob_id, 4).await else {
+            panic!("unexpected return value")
+        };
+        assert_eq!(subnets.len(), 4);
+        assert!(subnets.iter().all(|subnet| *subnet < 4));
+        assert!(matches!(
+            sampler.add_commitments(&blob_id, commitments(4)),
+            SamplingState::Tracking
+        ));
+
+        // the blob is validated once its columns are sampled
+        for subnet in subnets {
+            sampler.handle_sampling_success(blob_id, subnet).await;
+        }
+        assert!(sampler.validated_blobs.contains(&blob_id));
+    }
+
     #[tokio::test]
     #[expect(clippy::too_many_lines, reason = "TODO: Test function")]
     async fn test_sampler() {
@@ -238,6 +299,8 @@ mod test {
             aggregated_column_proof: Proof::default(),
             rows_commitments: vec![],
             rows_proofs: vec![],
+            domain_size: 64,
+            data_size: 0,
         };
         let share2 = share.clone();
         let mut share3 = share2.clone();
@@ -248,7 +311,7 @@ mod test {
         assert!(sampler.get_validated_blobs().await.is_empty());
 
         // start sampling for b1
-        let SamplingState::Init(subnets_to_sample) = sampler.init_sampling(b1).await else {
+        let SamplingState::Init(subnets_to_sample) = start_sampling(sampler, b1, 64).await else {
             panic!("unexpected return value")
         };
         assert!(subnets_to_sample.len() == subnet_num);
@@ -256,7 +319,7 @@ mod test {
         assert!(sampler.pending_sampling_blobs.len() == 1);
 
         // start sampling for b2
-        let SamplingState::Init(subnets_to_sample2) = sampler.init_sampling(b2).await else {
+        let SamplingState::Init(subnets_to_sample2) = start_sampling(sampler, b2, 64).await else {
             panic!("unexpected return value")
         };
         assert!(subnets_to_sample2.len() == subnet_num);
@@ -270,8 +333,8 @@ mod test {
         assert!(sampler.validated_blobs.is_empty());
 
         // because they're reset, we need to restart sampling for the test
-        _ = sampler.init_sampling(b1).await;
-        _ = sampler.init_sampling(b2).await;
+        _ = start_sampling(sampler, b1, 64).await;
+        _ = start_sampling(sampler, b2, 64).await;
 
         // handle ficticious error for b2
         // b2 should be gone, b1 still around
@@ -375,6 +438,7 @@ mod test {
         // first set will go through as in time
         let ctx1 = SamplingContext {
             subnets: HashSet::new(),
+            required_samples: 42,
             started: Instant::now(),
             commitment: None,
         };
@@ -384,6 +448,7 @@ mod test {
         // second set: will fail for expired
         let ctx11 = SamplingContext {
             subnets: HashSet::new(),
+            required_samples: 42,
             started: Instant::now().checked_sub(Duration::from_secs(1)).unwrap(),
             commitment: None,
         };
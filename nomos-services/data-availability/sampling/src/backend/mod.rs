@@ -28,6 +28,12 @@ pub trait DaSamplingServiceBackend<R: Rng> {
     async fn init_sampling(&mut self, blob_id: Self::BlobId) -> SamplingState;
     fn prune_interval(&self) -> Interval;
     fn prune(&mut self);
-    fn add_commitments(&mut self, blob_id: &Self::BlobId, commitments: Self::SharesCommitments);
+    /// Adds the commitments of a blob waiting for them, the subnets to sample
+    /// are picked among the blob columns.
+    fn add_commitments(
+        &mut self,
+        blob_id: &Self::BlobId,
+        commitments: Self::SharesCommitments,
+    ) -> SamplingState;
     fn get_commitments(&self, blob_id: &Self::BlobId) -> Option<Arc<Self::SharesCommitments>>;
 }
@@ -1,6 +1,7 @@
 pub mod api;
 pub mod backend;
 pub mod network;
+pub mod retrieval;
 pub mod storage;
 
 use std::{
@@ -14,7 +15,7 @@ use backend::{DaSamplingServiceBackend, SamplingState};
 use kzgrs_backend::common::share::{DaLightShare, DaShare, DaSharesCommitments};
 use network::NetworkAdapter;
 use nomos_core::da::{blob::Share, BlobId, DaVerifier};
-use nomos_da_network_core::protocols::sampling::behaviour::SamplingError;
+use nomos_da_network_core::{protocols::sampling::behaviour::SamplingError, SubnetworkId};
 use nomos_da_network_service::{backends::libp2p::common::SamplingEvent, NetworkService};
 use nomos_da_verifier::{
     backend::VerifierBackend as VerifierBackendTrait, DaVerifierMsg, DaVerifierService,
@@ -30,6 +31,7 @@ use overwatch::{
     DynError, OpaqueServiceStateHandle,
 };
 use rand::{Rng, SeedableRng};
+use retrieval::{BlobRetrievals, CompletedRetrieval};
 use serde::{Deserialize, Serialize};
 use services_utils::overwatch::lifecycle;
 use storage::DaStorageAdapter;
@@ -61,6 +63,12 @@ pub enum DaSamplingServiceMsg<BlobId> {
     MarkInBlock {
         blobs_id: Vec<BlobId>,
     },
+    /// Retrieve the original data of a blob by sampling enough of its columns
+    /// from the network to reconstruct it.
+    RetrieveBlob {
+        blob_id: BlobId,
+        reply_channel: oneshot::Sender<Result<Vec<u8>, DynError>>,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -186,21 +194,30 @@ where
         storage_adapter: &SamplingStorage,
         api_adapter: &ApiAdapter,
         sampler: &mut SamplingBackend,
+        retrievals: &mut BlobRetrievals,
+        verifier_relay: &VerifierRelay<VerifierBackend>,
     ) {
         match msg {
             DaSamplingServiceMsg::TriggerSampling { blob_id } => {
-                if let SamplingState::Init(sampling_subnets) = sampler.init_sampling(blob_id).await
-                {
+                if matches!(
+                    sampler.init_sampling(blob_id).await,
+                    SamplingState::WaitingCommitments
+                ) {
                     info_with_id!(blob_id, "InitSampling");
-                    if let Some(commitments) =
+                    // the subnets to sample depend on the blob domain, known
+                    // from its commitments
+                    let Some(commitments) =
                         Self::request_commitments(storage_adapter, api_adapter, blob_id).await
-                    {
-                        sampler.add_commitments(&blob_id, commitments);
-                    } else {
+                    else {
                         error_with_id!(blob_id, "Error getting commitments");
                         sampler.handle_sampling_error(blob_id).await;
                         return;
-                    }
+                    };
+                    let SamplingState::Init(sampling_subnets) =
+                        sampler.add_commitments(&blob_id, commitments)
+                    else {
+                        return;
+                    };
 
                     if let Err(e) = network_adapter
                         .start_sampling(blob_id, &sampling_subnets)
@@ -221,13 +238,122 @@ where
             DaSamplingServiceMsg::MarkInBlock { blobs_id } => {
                 sampler.mark_completed(&blobs_id).await;
             }
+            DaSamplingServiceMsg::RetrieveBlob {
+                blob_id,
+                reply_channel,
+            } => {
+                Self::handle_retrieve_blob(
+                    blob_id,
+                    reply_channel,
+                    network_adapter,
+                    storage_adapter,
+                    api_adapter,
+                    retrievals,
+                    verifier_relay,
+                )
+                .await;
+            }
+        }
+    }
+
+    async fn handle_retrieve_blob(
+        blob_id: BlobId,
+        reply_channel: oneshot::Sender<Result<Vec<u8>, DynError>>,
+        network_adapter: &mut SamplingNetwork,
+        storage_adapter: &SamplingStorage,
+        api_adapter: &ApiAdapter,
+        retrievals: &mut BlobRetrievals,
+        verifier_relay: &VerifierRelay<VerifierBackend>,
+    ) {
+        let Err(reply_channel) = retrievals.subscribe(&blob_id, reply_channel) else {
+            return;
+        };
+        info_with_id!(blob_id, "InitRetrieval");
+        let Some(commitments) =
+            Self::request_commitments(storage_adapter, api_adapter, blob_id).await
+        else {
+            error_with_id!(blob_id, "Error getting commitments");
+            if reply_channel
+                .send(Err("Blob commitments not found".into()))
+                .is_err()
+            {
+                error!("Error replying retrieved blob");
+            }
+            return;
+        };
+        // The domain drives the subnetworks sampled, it must be within the
+        // limits shares are verified against.
+        let commitments = Arc::new(commitments);
+        let verification = Self::verify_commitments(verifier_relay, Arc::clone(&commitments)).await;
+        let domain_size = SubnetworkId::try_from(commitments.domain_size);
+        let (Ok(()), Ok(domain_size)) = (verification, domain_size) else {
+            error_with_id!(blob_id, "Invalid commitments");
+            if reply_channel
+                .send(Err("Blob commitments exceed the verifier limits".into()))
+                .is_err()
+            {
+                error!("Error replying retrieved blob");
+            }
+            return;
+        };
+        retrievals.start(blob_id, commitments, reply_channel);
+
+        // Shares in storage were verified when received, only the missing
+        // ones are sampled from the network.
+        let local_shares = storage_adapter
+            .get_light_shares(blob_id)
+            .await
+            .unwrap_or_else(|error| {
+                error!("Failed to get shares from storage adapter: {error}");
+                Vec::new()
+            });
+        let mut missing_subnets: BTreeSet<SubnetworkId> = (0..domain_size).collect();
+        for share in local_shares {
+            missing_subnets.remove(&share.share_idx);
+            if let Some(retrieval) = retrievals.add_share(&blob_id, share) {
+                Self::reconstruct_blob(retrieval);
+                return;
+            }
+        }
+
+        let missing_subnets: Vec<_> = missing_subnets.into_iter().collect();
+        retrievals.sampling(&blob_id, &missing_subnets);
+        if let Err(e) = network_adapter
+            .start_sampling(blob_id, &missing_subnets)
+            .await
+        {
+            error_with_id!(blob_id, "Error retrieving BlobId: {blob_id:?}: {e}");
+            retrievals.fail(&blob_id, &format!("Failed to sample the blob: {e}"));
+        }
+    }
+
+    /// Samples again a subnetwork which failed to provide a valid share of a
+    /// blob being retrieved, failing the retrieval if the blob can not be
+    /// reconstructed anymore.
+    async fn retry_retrieval_sample(
+        network_adapter: &mut SamplingNetwork,
+        retrievals: &mut BlobRetrievals,
+        blob_id: BlobId,
+        subnetwork_id: SubnetworkId,
+    ) {
+        if !retrievals.sample_failed(&blob_id, subnetwork_id) {
+            return;
+        }
+        if let Err(e) = network_adapter
+            .start_sampling(blob_id, &[subnetwork_id])
+            .await
+        {
+            error_with_id!(blob_id, "Error retrieving BlobId: {blob_id:?}: {e}");
+            retrievals.fail(&blob_id, &format!("Failed to sample the blob: {e}"));
         }
     }
 
     #[instrument(skip_all)]
     async fn handle_sampling_message(
         event: SamplingEvent,
+        network_adapter: &mut SamplingNetwork,
         sampler: &mut SamplingBackend,
+        retrievals: &mut BlobRetrievals,
         storage_adapter: &SamplingStorage,
         verifier_relay: &VerifierRelay<VerifierBackend>,
     ) {
@@ -237,6 +363,31 @@ where
                 light_share,
             } => {
                 info_with_id!(blob_id, "SamplingSuccess");
+                if let Some(commitments) = retrievals.commitments(&blob_id) {
+                    if Self::verify_blob(verifier_relay, commitments, light_share.clone())
+                        .await
+                        .is_ok()
+                    {
+                        if let Some(retrieval) =
+                            retrievals.add_share(&blob_id, (*light_share).clone())
+                        {
+                            Self::reconstruct_blob(retrieval);
+                        }
+                    } else {
+                        error_with_id!(blob_id, "RetrievalError");
+                        Self::retry_retrieval_sample(
+                            network_adapter,
+                            retrievals,
+                            blob_id,
+                            light_share.share_idx,
+                        )
+                        .await;
+                    }
+                    // Shares retrieved for blobs which are not being sampled are done.
+                    if sampler.get_commitments(&blob_id).is_none() {
+                        return;
+                    }
+                }
                 let Some(commitments) = sampler.get_commitments(&blob_id) else {
                     error_with_id!(blob_id, "Error getting commitments for blob");
                     sampler.handle_sampling_error(blob_id).await;
@@ -256,10 +407,19 @@ where
                 return;
             }
             SamplingEvent::SamplingError { error } => {
-                if let Some(blob_id) = error.blob_id() {
+                if let Some(blob_id) = error.blob_id().copied() {
                     error_with_id!(blob_id, "SamplingError");
+                    if let Some(subnetwork_id) = error.subnetwork_id() {
+                        Self::retry_retrieval_sample(
+                            network_adapter,
+                            retrievals,
+                            blob_id,
+                            subnetwork_id,
+                        )
+                        .await;
+                    }
                     if let SamplingError::BlobNotFound { .. } = error {
-                        sampler.handle_sampling_error(*blob_id).await;
+                        sampler.handle_sampling_error(blob_id).await;
                         return;
                     }
                 }
@@ -288,6 +448,11 @@ where
         }
     }
 
+    fn reconstruct_blob(retrieval: CompletedRetrieval) {
+        // Reconstruction is CPU heavy, keep it out of the service loop.
+        tokio::task::spawn_blocking(move || retrieval.reply());
+    }
+
     async fn request_commitments(
         storage_adapter: &SamplingStorage,
         api_request: &ApiAdapter,
@@ -304,7 +469,31 @@ where
             error!("Error sending request to API backend: {e}");
         }
 
-        reply_channel.await.ok().flatten()
+        // Commitments from peers are only trusted if they match the blob id
+        let commitments = reply_channel.await.ok().flatten()?;
+        if commitments.blob_id() != blob_id {
+            error_with_id!(blob_id, "Commitments do not match the blob id");
+            return None;
+        }
+        Some(commitments)
+    }
+
+    async fn verify_commitments(
+        verifier_relay: &OutboundRelay<VerifierMessage>,
+        commitments: Arc<DaSharesCommitments>,
+    ) -> Result<(), DynError> {
+        let (reply_sender, reply_channel) = oneshot::channel();
+        verifier_relay
+            .send(DaVerifierMsg::VerifyCommitments {
+                commitments,
+                reply_channel: reply_sender,
+            })
+            .await
+            .expect("Failed to send verify commitments message to verifier relay");
+
+        reply_channel
+            .await
+            .expect("Failed to receive reply commitments message from verifier relay")
     }
 
     async fn verify_blob(
@@ -461,15 +650,16 @@ where
         let rng = SamplingRng::from_entropy();
         let mut sampler = SamplingBackend::new(sampling_settings, rng);
         let mut next_prune_tick = sampler.prune_interval();
+        let mut retrievals = BlobRetrievals::default();
 
         let mut lifecycle_stream = service_state.lifecycle_handle.message_stream();
         loop {
             tokio::select! {
                 Some(service_message) = service_state.inbound_relay.recv() => {
-                    Self::handle_service_message(service_message, &mut network_adapter,  &storage_adapter, &api_adapter, &mut sampler).await;
+                    Self::handle_service_message(service_message, &mut network_adapter,  &storage_adapter, &api_adapter, &mut sampler, &mut retrievals, &verifier_relay).await;
                 }
                 Some(sampling_message) = sampling_message_stream.next() => {
-                    Self::handle_sampling_message(sampling_message, &mut sampler, &storage_adapter, &verifier_relay).await;
+                    Self::handle_sampling_message(sampling_message, &mut network_adapter, &mut sampler, &mut retrievals, &storage_adapter, &verifier_relay).await;
                 }
                 Some(msg) = lifecycle_stream.next() => {
                     if lifecycle::should_stop_service::<Self, RuntimeServiceId>(&msg) {
@@ -479,6 +669,7 @@ where
                 // cleanup not on time samples
                 _ = next_prune_tick.tick() => {
                     sampler.prune();
+                    retrievals.prune();
                 }
 
             }
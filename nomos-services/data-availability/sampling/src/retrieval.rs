@@ -0,0 +1,297 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    sync::Arc,
+};
+
+use kzgrs_backend::{
+    common::{
+        share::{DaLightShare, DaSharesCommitments},
+        ShareIndex,
+    },
+    reconstruction::reconstruct_from_columns,
+};
+use nomos_core::da::BlobId;
+use overwatch::DynError;
+use tokio::sync::oneshot;
+use tracing::error;
+
+pub type RetrievalReply = oneshot::Sender<Result<Vec<u8>, DynError>>;
+
+/// Number of times a subnetwork is sampled for a blob being retrieved before
+/// giving up on it.
+const MAX_SUBNETWORK_ATTEMPTS: usize = 3;
+
+struct BlobRetrieval {
+    commitments: Arc<DaSharesCommitments>,
+    shares: BTreeMap<ShareIndex, DaLightShare>,
+    /// Number of times each subnetwork was sampled.
+    attempts: HashMap<ShareIndex, usize>,
+    /// Subnetworks given up on after too many failed attempts.
+    exhausted: BTreeSet<ShareIndex>,
+    reply_channels: Vec<RetrievalReply>,
+}
+
+/// Blobs being retrieved from the network.
+///
+/// Verified shares are gathered per blob until half of the columns are
+/// available, which is enough to reconstruct the original data. Subnetworks
+/// failing to provide a share are sampled again, and the retrieval fails once
+/// too few of them are left to reconstruct the blob.
+#[derive(Default)]
+pub struct BlobRetrievals {
+    retrievals: HashMap<BlobId, BlobRetrieval>,
+}
+
+impl BlobRetrievals {
+    #[must_use]
+    pub fn contains(&self, blob_id: &BlobId) -> bool {
+        self.retrievals.contains_key(blob_id)
+    }
+
+    #[must_use]
+    pub fn commitments(&self, blob_id: &BlobId) -> Option<Arc<DaSharesCommitments>> {
+        self.retrievals
+            .get(blob_id)
+            .map(|retrieval| Arc::clone(&retrieval.commitments))
+    }
+
+    /// Waits for an ongoing retrieval of the blob, the reply channel is given
+    /// back if the blob is not being retrieved.
+    pub fn subscribe(
+        &mut self,
+        blob_id: &BlobId,
+        reply_channel: RetrievalReply,
+    ) -> Result<(), RetrievalReply> {
+        match self.retrievals.get_mut(blob_id) {
+            Some(retrieval) => {
+                retrieval.reply_channels.push(reply_channel);
+                Ok(())
+            }
+            None => Err(reply_channel),
+        }
+    }
+
+    pub fn start(
+        &mut self,
+        blob_id: BlobId,
+        commitments: Arc<DaSharesCommitments>,
+        reply_channel: RetrievalReply,
+    ) {
+        self.retrievals.insert(
+            blob_id,
+            BlobRetrieval {
+                commitments,
+                shares: BTreeMap::new(),
+                attempts: HashMap::new(),
+                exhausted: BTreeSet::new(),
+                reply_channels: vec![reply_channel],
+            },
+        );
+    }
+
+    /// Records that the subnetworks are being sampled for the blob.
+    pub fn sampling(&mut self, blob_id: &BlobId, subnetworks: &[ShareIndex]) {
+        if let Some(retrieval) = self.retrievals.get_mut(blob_id) {
+            for subnetwork_id in subnetworks {
+                *retrieval.attempts.entry(*subnetwork_id).or_default() += 1;
+            }
+        }
+    }
+
+    /// Records that sampling the subnetwork failed to provide a valid share of
+    /// the blob. Returns whether the subnetwork should be sampled again, when
+    /// too many subnetworks failed for the blob to be reconstructed the
+    /// retrieval fails instead.
+    pub fn sample_failed(&mut self, blob_id: &BlobId, subnetwork_id: ShareIndex) -> bool {
+        let Some(retrieval) = self.retrievals.get_mut(blob_id) else {
+            return false;
+        };
+        if retrieval.shares.contains_key(&subnetwork_id)
+            || retrieval.exhausted.contains(&subnetwork_id)
+        {
+            return false;
+        }
+        let attempts = retrieval.attempts.entry(subnetwork_id).or_default();
+        if *attempts < MAX_SUBNETWORK_ATTEMPTS {
+            *attempts += 1;
+            return true;
+        }
+        retrieval.exhausted.insert(subnetwork_id);
+        let domain_size = retrieval.commitments.domain_size;
+        if domain_size - retrieval.exhausted.len() < domain_size / 2 {
+            self.fail(
+                blob_id,
+                "Not enough subnetworks provided shares to reconstruct the blob",
+            );
+        }
+        false
+    }
+
+    /// Removes the retrieval, replying the error to everyone waiting for it.
+    pub fn fail(&mut self, blob_id: &BlobId, error: &str) {
+        let Some(retrieval) = self.retrievals.remove(blob_id) else {
+            return;
+        };
+        for reply_channel in retrieval.reply_channels {
+            if reply_channel.send(Err(error.into())).is_err() {
+                error!("Error replying retrieved blob");
+            }
+        }
+    }
+
+    /// Adds a verified share of the blob. Once half of the blob columns are
+    /// gathered the retrieval is removed and returned, so the data can be
+    /// reconstructed.
+    pub fn add_share(
+        &mut self,
+        blob_id: &BlobId,
+        share: DaLightShare,
+    ) -> Option<CompletedRetrieval> {
+        let retrieval = self.retrievals.get_mut(blob_id)?;
+        retrieval.shares.insert(share.share_idx, share);
+        if retrieval.shares.len() < retrieval.commitments.domain_size / 2 {
+            return None;
+        }
+        self.retrievals
+            .remove(blob_id)
+            .map(|retrieval| CompletedRetrieval {
+                shares: retrieval.shares.into_values().collect(),
+                reply_channels: retrieval.reply_channels,
+                commitments: retrieval.commitments,
+            })
+    }
+
+    /// Drops the retrievals nobody is waiting for anymore.
+    pub fn prune(&mut self) {
+        self.retrievals.retain(|_, retrieval| {
+            retrieval
+                .reply_channels
+                .retain(|reply_channel| !reply_channel.is_closed());
+            !retrieval.reply_channels.is_empty()
+        });
+    }
+}
+
+pub struct CompletedRetrieval {
+    shares: Vec<DaLightShare>,
+    reply_channels: Vec<RetrievalReply>,
+    commitments: Arc<DaSharesCommitments>,
+}
+
+impl CompletedRetrieval {
+    /// Reconstructs the blob data and sends it to everyone waiting for it.
+    pub fn reply(self) {
+        let data = reconstruct_from_columns(
+            self.shares
+                .iter()
+                .map(|share| (share.share_idx, &share.column)),
+            &self.commitments,
+        );
+        for reply_channel in self.reply_channels {
+            let reply = data.as_ref().cloned().map_err(|e| e.to_string().into());
+            if reply_channel.send(reply).is_err() {
+                error!("Error replying retrieved blob");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use kzgrs_backend::{
+        common::share::DaShare,
+        encoder::{DaEncoder, DaEncoderParams},
+    };
+    use nomos_core::da::{blob::Share as _, DaEncoder as _};
+    use rand::{thread_rng, RngCore as _};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_retrieve_from_half_of_the_shares() {
+        // the last row is not full
+        let mut data = vec![0; 31 * 7];
+        thread_rng().fill_bytes(&mut data);
+        let encoded_data = DaEncoder::new(DaEncoderParams::default_with(4))
+            .encode(&data)
+            .unwrap();
+        let shares: Vec<DaLightShare> = encoded_data
+            .iter()
+            .map(|share: DaShare| share.into_share_and_commitments().0)
+            .collect();
+        let commitments = Arc::new(DaSharesCommitments {
+            aggregated_column_commitment: encoded_data.aggregated_column_commitment,
+            rows_commitments: encoded_data.row_commitments.clone(),
+            domain_size: encoded_data.domain_size,
+            data_size: data.len(),
+        });
+        let blob_id = [0; 32];
+
+        let mut retrievals = BlobRetrievals::default();
+        let (reply_sender, first_reply_receiver) = oneshot::channel();
+        let reply_sender = retrievals.subscribe(&blob_id, reply_sender).unwrap_err();
+        retrievals.start(blob_id, commitments, reply_sender);
+        let (reply_sender, second_reply_receiver) = oneshot::channel();
+        assert!(retrievals.subscribe(&blob_id, reply_sender).is_ok());
+
+        // extended columns only, duplicated shares do not count
+        assert!(retrievals.add_share(&blob_id, shares[3].clone()).is_none());
+        assert!(retrievals.add_share(&blob_id, shares[3].clone()).is_none());
+        let completed = retrievals.add_share(&blob_id, shares[2].clone()).unwrap();
+        assert!(!retrievals.contains(&blob_id));
+
+        completed.reply();
+        assert_eq!(first_reply_receiver.await.unwrap().unwrap(), data);
+        assert_eq!(second_reply_receiver.await.unwrap().unwrap(), data);
+    }
+
+    #[tokio::test]
+    async fn test_fail_when_not_enough_subnetworks_left() {
+        let commitments = Arc::new(DaSharesCommitments {
+            domain_size: 4,
+            ..Default::default()
+        });
+        let blob_id = [0; 32];
+        let mut retrievals = BlobRetrievals::default();
+        let (reply_sender, reply_receiver) = oneshot::channel();
+        retrievals.start(blob_id, commitments, reply_sender);
+        retrievals.sampling(&blob_id, &[0, 1, 2, 3]);
+
+        // each subnetwork is retried until it runs out of attempts
+        for _ in 1..MAX_SUBNETWORK_ATTEMPTS {
+            assert!(retrievals.sample_failed(&blob_id, 0));
+        }
+        assert!(!retrievals.sample_failed(&blob_id, 0));
+        assert!(!retrievals.sample_failed(&blob_id, 0));
+        for _ in 1..MAX_SUBNETWORK_ATTEMPTS {
+            assert!(retrievals.sample_failed(&blob_id, 1));
+        }
+        assert!(!retrievals.sample_failed(&blob_id, 1));
+        // two subnetworks are still enough to reconstruct the blob
+        assert!(retrievals.contains(&blob_id));
+
+        for _ in 1..MAX_SUBNETWORK_ATTEMPTS {
+            assert!(retrievals.sample_failed(&blob_id, 2));
+        }
+        assert!(!retrievals.sample_failed(&blob_id, 2));
+        assert!(!retrievals.contains(&blob_id));
+        assert!(reply_receiver.await.unwrap().is_err());
+    }
+
+    #[test]
+    fn test_prune_abandoned_retrievals() {
+        let mut retrievals = BlobRetrievals::default();
+        let (reply_sender, reply_receiver) = oneshot::channel();
+        retrievals.start([0; 32], Arc::default(), reply_sender);
+        let (reply_sender, _) = oneshot::channel();
+        retrievals.start([1; 32], Arc::default(), reply_sender);
+
+        retrievals.prune();
+        assert!(retrievals.contains(&[0; 32]));
+        assert!(!retrievals.contains(&[1; 32]));
+
+        drop(reply_receiver);
+        retrievals.prune();
+        assert!(!retrievals.contains(&[0; 32]));
+    }
+}
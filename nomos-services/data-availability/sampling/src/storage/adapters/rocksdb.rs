@@ -63,11 +63,13 @@ where
             .await
             .expect("Failed to send load request to storage relay");
 
-        let shared_commitments = sc_reply_rx.await?;
-        let shared_commitments = shared_commitments
-            .map(|sc| S::deserialize(sc).expect("Failed to deserialize shared commitments"));
-
-        Ok(shared_commitments)
+        // Commitments stored before blobs carried their dimensions can not be
+        // deserialized, those are reported as errors instead of panicking.
+        sc_reply_rx
+            .await?
+            .map(|sc| S::deserialize(sc))
+            .transpose()
+            .map_err(|error| format!("Failed to deserialize shared commitments: {error}").into())
     }
 
     async fn get_light_share(
@@ -91,6 +93,29 @@ where
 
         Ok(share)
     }
+
+    async fn get_light_shares(
+        &self,
+        blob_id: <Self::Share as Share>::BlobId,
+    ) -> Result<Vec<<Self::Share as Share>::LightShare>, DynError> {
+        let shares_prefix_key = key_bytes(DA_SHARE_PREFIX, blob_id);
+        let (shares_reply_tx, shares_reply_rx) = tokio::sync::oneshot::channel();
+        self.storage_relay
+            .send(StorageMsg::LoadPrefix {
+                prefix: shares_prefix_key,
+                reply_channel: shares_reply_tx,
+            })
+            .await
+            .expect("Failed to send load request to storage relay");
+
+        let shares = shares_reply_rx.await?;
+        let shares = shares
+            .into_iter()
+            .map(|share| S::deserialize(share).expect("Failed to deserialize blob"))
+            .collect();
+
+        Ok(shares)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
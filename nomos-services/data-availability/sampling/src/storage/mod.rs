@@ -29,4 +29,9 @@ pub trait DaStorageAdapter<RuntimeServiceId> {
         blob_id: <Self::Share as Share>::BlobId,
         share_idx: ShareIndex,
     ) -> Result<Option<<Self::Share as Share>::LightShare>, DynError>;
+
+    async fn get_light_shares(
+        &self,
+        blob_id: <Self::Share as Share>::BlobId,
+    ) -> Result<Vec<<Self::Share as Share>::LightShare>, DynError>;
 }
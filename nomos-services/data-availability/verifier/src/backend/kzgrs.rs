@@ -1,7 +1,8 @@
 use core::fmt;
 
 use kzgrs_backend::{
-    common::share::DaShare, global::global_parameters_from_file,
+    common::share::{DaShare, DaSharesCommitments},
+    global::global_parameters_from_file,
     verifier::DaVerifier as NomosKzgrsVerifier,
 };
 use nomos_core::da::{blob::Share, DaVerifier};
@@ -12,12 +13,29 @@ use super::VerifierBackend;
 #[derive(Debug)]
 pub enum KzgrsDaVerifierError {
     VerificationError,
+    BlobTooLarge {
+        domain_size: usize,
+        data_size: usize,
+    },
+    DomainTooSmall {
+        domain_size: usize,
+    },
 }
 
 impl fmt::Display for KzgrsDaVerifierError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             Self::VerificationError => write!(f, "Verification failed"),
+            Self::BlobTooLarge {
+                domain_size,
+                data_size,
+            } => write!(
+                f,
+                "Blob of {data_size} bytes with domain size {domain_size} exceeds the maximum"
+            ),
+            Self::DomainTooSmall { domain_size } => {
+                write!(f, "Blob domain size {domain_size} is below the minimum")
+            }
         }
     }
 }
@@ -26,7 +44,9 @@ impl std::error::Error for KzgrsDaVerifierError {}
 
 pub struct KzgrsDaVerifier {
     verifier: NomosKzgrsVerifier,
-    domain_size: usize,
+    min_domain_size: usize,
+    max_domain_size: usize,
+    max_blob_size: usize,
 }
 
 impl VerifierBackend for KzgrsDaVerifier {
@@ -39,9 +59,40 @@ impl VerifierBackend for KzgrsDaVerifier {
         let verifier = NomosKzgrsVerifier::new(global_params);
         Self {
             verifier,
-            domain_size: settings.domain_size,
+            min_domain_size: settings.min_domain_size,
+            max_domain_size: settings.max_domain_size,
+            max_blob_size: settings.max_blob_size,
         }
     }
+
+    fn verify_commitments(
+        &self,
+        commitments: &DaSharesCommitments,
+    ) -> Result<(), KzgrsDaVerifierError> {
+        self.check_size(commitments)
+    }
+}
+
+impl KzgrsDaVerifier {
+    const fn check_size(
+        &self,
+        commitments: &DaSharesCommitments,
+    ) -> Result<(), KzgrsDaVerifierError> {
+        if commitments.domain_size < self.min_domain_size {
+            return Err(KzgrsDaVerifierError::DomainTooSmall {
+                domain_size: commitments.domain_size,
+            });
+        }
+        if commitments.domain_size > self.max_domain_size
+            || commitments.data_size > self.max_blob_size
+        {
+            return Err(KzgrsDaVerifierError::BlobTooLarge {
+                domain_size: commitments.domain_size,
+                data_size: commitments.data_size,
+            });
+        }
+        Ok(())
+    }
 }
 
 impl DaVerifier for KzgrsDaVerifier {
@@ -53,17 +104,51 @@ impl DaVerifier for KzgrsDaVerifier {
         commitments: &<Self::DaShare as Share>::SharesCommitments,
         light_share: &<Self::DaShare as Share>::LightShare,
     ) -> Result<(), Self::Error> {
-        // TODO: Prepare the domain depending the size, if fixed, so fixed domain, if
-        // not it needs to come with some metadata.
+        self.check_size(commitments)?;
         self.verifier
-            .verify(commitments, light_share, self.domain_size)
+            .verify(commitments, light_share)
             .then_some(())
             .ok_or(KzgrsDaVerifierError::VerificationError)
     }
+
+    fn verify_batch(
+        &self,
+        shares: &[(
+            &<Self::DaShare as Share>::SharesCommitments,
+            &<Self::DaShare as Share>::LightShare,
+        )],
+    ) -> Vec<Result<(), Self::Error>> {
+        let mut results: Vec<_> = shares
+            .iter()
+            .map(|(commitments, _)| self.check_size(commitments))
+            .collect();
+        let (positions, batch): (Vec<_>, Vec<_>) = shares
+            .iter()
+            .zip(&results)
+            .enumerate()
+            .filter(|(_, (_, result))| result.is_ok())
+            .map(|(position, (share, _))| (position, *share))
+            .unzip();
+        for (position, verified) in positions
+            .into_iter()
+            .zip(self.verifier.verify_batch(&batch))
+        {
+            if !verified {
+                results[position] = Err(KzgrsDaVerifierError::VerificationError);
+            }
+        }
+        results
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KzgrsDaVerifierSettings {
     pub global_params_path: String,
-    pub domain_size: usize,
+    /// Minimum number of columns of the blobs, so their shares are spread
+    /// over enough subnetworks to be sampled.
+    pub min_domain_size: usize,
+    /// Maximum number of columns of the blobs.
+    pub max_domain_size: usize,
+    /// Maximum size in bytes of the blobs.
+    pub max_blob_size: usize,
 }
@@ -1,8 +1,16 @@
 pub mod kzgrs;
 
+use nomos_core::da::blob::Share;
 pub use nomos_core::da::DaVerifier;
 
 pub trait VerifierBackend: DaVerifier {
     type Settings;
     fn new(settings: Self::Settings) -> Self;
+
+    /// Checks the blob described by `commitments` is within the limits of the
+    /// verifier, without verifying any share.
+    fn verify_commitments(
+        &self,
+        commitments: &<Self::DaShare as Share>::SharesCommitments,
+    ) -> Result<(), Self::Error>;
 }
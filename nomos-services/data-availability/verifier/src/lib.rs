@@ -3,12 +3,14 @@ pub mod network;
 pub mod storage;
 
 use std::{
+    collections::VecDeque,
     error::Error,
     fmt::{Debug, Display, Formatter},
     sync::Arc,
 };
 
 use backend::VerifierBackend;
+use futures::future::join_all;
 use network::NetworkAdapter;
 use nomos_core::da::blob::Share;
 use nomos_da_network_service::NetworkService;
@@ -24,10 +26,20 @@ use overwatch::{
 use serde::{Deserialize, Serialize};
 use services_utils::overwatch::lifecycle;
 use storage::DaStorageAdapter;
-use tokio::sync::oneshot::Sender;
+use tokio::{sync::oneshot::Sender, task::JoinSet};
 use tokio_stream::StreamExt;
 use tracing::{error, instrument};
 
+/// Maximum number of shares received from the network that are verified
+/// together in a single batch.
+const MAX_SHARES_BATCH: usize = 64;
+/// Maximum number of batches and verification requests handled at the same
+/// time, further shares are not pulled from the network and verification
+/// requests are queued until one finishes.
+const MAX_CONCURRENT_TASKS: usize = 8;
+
+type AddShareReply = Sender<Option<()>>;
+
 pub enum DaVerifierMsg<Commitments, LightShare, Share, Answer> {
     AddShare {
         share: Share,
@@ -38,6 +50,12 @@ pub enum DaVerifierMsg<Commitments, LightShare, Share, Answer> {
         light_share: Box<LightShare>,
         reply_channel: Sender<Result<(), DynError>>,
     },
+    /// Check the blob described by `commitments` is within the verifier
+    /// limits, e.g. before sampling its shares.
+    VerifyCommitments {
+        commitments: Arc<Commitments>,
+        reply_channel: Sender<Result<(), DynError>>,
+    },
 }
 
 impl<C: 'static, L: 'static, B: 'static, A: 'static> Debug for DaVerifierMsg<C, L, B, A> {
@@ -49,6 +67,9 @@ impl<C: 'static, L: 'static, B: 'static, A: 'static> Debug for DaVerifierMsg<C,
             Self::VerifyShare { .. } => {
                 write!(f, "DaVerifierMsg::VerifyShare")
             }
+            Self::VerifyCommitments { .. } => {
+                write!(f, "DaVerifierMsg::VerifyCommitments")
+            }
         }
     }
 }
@@ -63,42 +84,163 @@ where
     S: DaStorageAdapter<RuntimeServiceId>,
 {
     service_state: OpaqueServiceStateHandle<Self, RuntimeServiceId>,
-    verifier: Backend,
+    verifier: Arc<Backend>,
 }
 
 impl<Backend, N, S, RuntimeServiceId> DaVerifierService<Backend, N, S, RuntimeServiceId>
 where
     Backend: VerifierBackend + Send + Sync + 'static,
     Backend::DaShare: Debug + Send,
-    Backend::Error: Error + Send + Sync,
+    Backend::Error: Error + Send + Sync + 'static,
     Backend::Settings: Clone,
-    <Backend::DaShare as Share>::BlobId: AsRef<[u8]>,
+    <Backend::DaShare as Share>::BlobId: AsRef<[u8]> + Debug + Send + 'static,
+    <Backend::DaShare as Share>::ShareIndex: Send + 'static,
+    <Backend::DaShare as Share>::LightShare: Send + Sync + 'static,
+    <Backend::DaShare as Share>::SharesCommitments: Send + Sync + 'static,
     N: NetworkAdapter<RuntimeServiceId, Share = Backend::DaShare> + Send + 'static,
     N::Settings: Clone,
     S: DaStorageAdapter<RuntimeServiceId, Share = Backend::DaShare> + Send + Sync + 'static,
 {
+    /// Handles a batch of shares, returning the result for each of them in
+    /// the same order.
+    ///
+    /// Storage lookups and writes run concurrently, and all the new shares are
+    /// verified together in a blocking task.
     #[instrument(skip_all)]
-    async fn handle_new_share(
-        verifier: &Backend,
+    async fn handle_new_shares(
+        verifier: &Arc<Backend>,
         storage_adapter: &S,
-        share: Backend::DaShare,
-    ) -> Result<(), DynError> {
-        if storage_adapter
-            .get_share(share.blob_id(), share.share_idx())
-            .await?
-            .is_some()
+        shares: Vec<Backend::DaShare>,
+    ) -> Vec<Result<(), DynError>> {
+        let stored = join_all(
+            shares
+                .iter()
+                .map(|share| storage_adapter.get_share(share.blob_id(), share.share_idx())),
+        )
+        .await;
+
+        let mut results = Vec::with_capacity(shares.len());
+        let mut positions = Vec::new();
+        let mut new_shares = Vec::new();
+        for (position, (share, stored)) in shares.into_iter().zip(stored).enumerate() {
+            match stored {
+                Err(err) => results.push(Err(err)),
+                Ok(Some(_)) => {
+                    info_with_id!(share.blob_id().as_ref(), "VerifierShareExists");
+                    results.push(Ok(()));
+                }
+                Ok(None) => {
+                    info_with_id!(share.blob_id().as_ref(), "VerifierAddShare");
+                    let (blob_id, share_idx) = (share.blob_id(), share.share_idx());
+                    let (light_share, commitments) = share.into_share_and_commitments();
+                    results.push(Ok(()));
+                    positions.push(position);
+                    new_shares.push((blob_id, share_idx, commitments, light_share));
+                }
+            }
+        }
+        if new_shares.is_empty() {
+            return results;
+        }
+
+        let verifier = Arc::clone(verifier);
+        let verification = tokio::task::spawn_blocking(move || {
+            let verified = verifier.verify_batch(
+                &new_shares
+                    .iter()
+                    .map(|(_, _, commitments, light_share)| (commitments, light_share))
+                    .collect::<Vec<_>>(),
+            );
+            (new_shares, verified)
+        })
+        .await;
+        let (new_shares, verified) = match verification {
+            Ok(verification) => verification,
+            Err(err) => {
+                for position in positions {
+                    results[position] = Err(format!("Verification task failed: {err}").into());
+                }
+                return results;
+            }
+        };
+
+        let mut stored_positions = Vec::new();
+        let mut store_futures = Vec::new();
+        for ((position, (blob_id, share_idx, commitments, light_share)), verified) in
+            positions.into_iter().zip(new_shares).zip(verified)
         {
-            info_with_id!(share.blob_id().as_ref(), "VerifierShareExists");
-        } else {
-            info_with_id!(share.blob_id().as_ref(), "VerifierAddShare");
-            let (blob_id, share_idx) = (share.blob_id(), share.share_idx());
-            let (light_share, commitments) = share.into_share_and_commitments();
-            verifier.verify(&commitments, &light_share)?;
-            storage_adapter
-                .add_share(blob_id, share_idx, commitments, light_share)
-                .await?;
+            match verified {
+                Ok(()) => {
+                    stored_positions.push(position);
+                    store_futures.push(storage_adapter.add_share(
+                        blob_id,
+                        share_idx,
+                        commitments,
+                        light_share,
+                    ));
+                }
+                Err(err) => results[position] = Err(err.into()),
+            }
+        }
+        for (position, stored) in stored_positions
+            .into_iter()
+            .zip(join_all(store_futures).await)
+        {
+            results[position] = stored;
+        }
+        results
+    }
+
+    /// Handles a batch of shares, replying to the ones added through
+    /// [`DaVerifierMsg::AddShare`].
+    async fn handle_batch(
+        verifier: Arc<Backend>,
+        storage_adapter: Arc<S>,
+        batch: Vec<(Backend::DaShare, Option<AddShareReply>)>,
+    ) {
+        let (shares, reply_channels): (Vec<_>, Vec<_>) = batch.into_iter().unzip();
+        let blob_ids: Vec<_> = shares.iter().map(Share::blob_id).collect();
+        let results = Self::handle_new_shares(&verifier, &storage_adapter, shares).await;
+        for ((blob_id, reply_channel), result) in
+            blob_ids.into_iter().zip(reply_channels).zip(results)
+        {
+            let attestation = match result {
+                Ok(()) => Some(()),
+                Err(err) => {
+                    error!("Error handling blob {blob_id:?} due to {err:?}");
+                    None
+                }
+            };
+            if let Some(reply_channel) = reply_channel {
+                if let Err(err) = reply_channel.send(attestation) {
+                    error!("Error replying attestation {err:?}");
+                }
+            }
+        }
+    }
+
+    async fn handle_verify_share(
+        verifier: Arc<Backend>,
+        commitments: Arc<<Backend::DaShare as Share>::SharesCommitments>,
+        light_share: Box<<Backend::DaShare as Share>::LightShare>,
+        reply_channel: Sender<Result<(), DynError>>,
+    ) {
+        let verification =
+            tokio::task::spawn_blocking(move || verifier.verify(&commitments, &light_share)).await;
+        let result = match verification {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(err)) => {
+                error!("Error verifying blob due to {err:?}");
+                Err(err.into())
+            }
+            Err(err) => {
+                error!("Error verifying blob due to {err:?}");
+                Err(err.into())
+            }
+        };
+        if let Err(err) = reply_channel.send(result) {
+            error!("Error replying verification {err:?}");
         }
-        Ok(())
     }
 }
 
@@ -132,6 +274,7 @@ where
     Backend::DaShare: Debug + Send + Sync + 'static,
     Backend::Error: Error + Send + Sync + 'static,
     <Backend::DaShare as Share>::BlobId: AsRef<[u8]> + Debug + Send + Sync + 'static,
+    <Backend::DaShare as Share>::ShareIndex: Send + 'static,
     <Backend::DaShare as Share>::LightShare: Debug + Send + Sync + 'static,
     <Backend::DaShare as Share>::SharesCommitments: Debug + Send + Sync + 'static,
     N: NetworkAdapter<RuntimeServiceId, Share = Backend::DaShare> + Send + Sync + 'static,
@@ -156,7 +299,7 @@ where
         } = service_state.settings_reader.get_updated_settings();
         Ok(Self {
             service_state,
-            verifier: Backend::new(verifier_settings),
+            verifier: Arc::new(Backend::new(verifier_settings)),
         })
     }
 
@@ -181,57 +324,51 @@ where
             .relay::<NetworkService<_, _>>()
             .await?;
         let network_adapter = N::new(network_adapter_settings, network_relay).await;
-        let mut share_stream = network_adapter.share_stream().await;
+        let mut share_stream = futures::StreamExt::ready_chunks(
+            network_adapter.share_stream().await,
+            MAX_SHARES_BATCH,
+        );
 
         let storage_relay = service_state
             .overwatch_handle
             .relay::<StorageService<_, _>>()
             .await?;
-        let storage_adapter = S::new(storage_relay).await;
+        let storage_adapter = Arc::new(S::new(storage_relay).await);
 
+        // Shares added through messages wait here to be verified along with
+        // the next batch.
+        let mut pending_shares: Vec<(Backend::DaShare, Option<AddShareReply>)> = Vec::new();
+        // Verification requests wait here for a free task slot, no more
+        // messages are received while a full batch of them is waiting.
+        let mut pending_verifications = VecDeque::new();
+        let mut tasks = JoinSet::new();
         let mut lifecycle_stream = service_state.lifecycle_handle.message_stream();
         loop {
             tokio::select! {
-                Some(share) = share_stream.next() => {
-                    let blob_id = share.blob_id();
-                    if let Err(err) =  Self::handle_new_share(&verifier,&storage_adapter, share).await {
-                        error!("Error handling blob {blob_id:?} due to {err:?}");
-                    }
+                Some(shares) = share_stream.next(), if tasks.len() < MAX_CONCURRENT_TASKS => {
+                    let mut batch: Vec<_> = shares.into_iter().map(|share| (share, None)).collect();
+                    batch.append(&mut pending_shares);
+                    tasks.spawn(Self::handle_batch(Arc::clone(&verifier), Arc::clone(&storage_adapter), batch));
                 }
-                Some(msg) = service_state.inbound_relay.recv() => {
+                Some(msg) = service_state.inbound_relay.recv(), if pending_shares.len() < MAX_SHARES_BATCH && pending_verifications.len() < MAX_SHARES_BATCH => {
                     match msg {
                         DaVerifierMsg::AddShare { share, reply_channel } => {
-                            let blob_id = share.blob_id();
-                            match Self::handle_new_share(&verifier, &storage_adapter, share).await {
-                                Ok(attestation) => {
-                                    if let Err(err) = reply_channel.send(Some(attestation)) {
-                                        error!("Error replying attestation {err:?}");
-                                    }
-                                },
-                                Err(err) => {
-                                    error!("Error handling blob {blob_id:?} due to {err:?}");
-                                    if let Err(err) = reply_channel.send(None) {
-                                        error!("Error replying attestation {err:?}");
-                                    }
-                                },
-                            };
+                            pending_shares.push((share, Some(reply_channel)));
                         },
                         DaVerifierMsg::VerifyShare {commitments,  light_share, reply_channel } => {
-                            match verifier.verify(&commitments, &light_share) {
-                                Ok(()) => {
-                                    if let Err(err) = reply_channel.send(Ok(())) {
-                                        error!("Error replying verification {err:?}");
-                                    }
-                                },
-                                Err(err) => {
-                                    error!("Error verifying blob due to {err:?}");
-                                    if let Err(err) = reply_channel.send(Err(err.into())) {
-                                        error!("Error replying verification {err:?}");
-                                    }
-                                },
+                            pending_verifications.push_back((commitments, light_share, reply_channel));
+                        },
+                        DaVerifierMsg::VerifyCommitments { commitments, reply_channel } => {
+                            let result = verifier.verify_commitments(&commitments).map_err(Into::into);
+                            if let Err(err) = reply_channel.send(result) {
+                                error!("Error replying commitments verification {err:?}");
                             }
                         },
-
+                    }
+                }
+                Some(task) = tasks.join_next() => {
+                    if let Err(err) = task {
+                        error!("Error joining verifier task {err:?}");
                     }
                 }
                 Some(msg) = lifecycle_stream.next() => {
@@ -240,6 +377,26 @@ where
                     }
                 }
             }
+            if !pending_shares.is_empty() && tasks.len() < MAX_CONCURRENT_TASKS {
+                tasks.spawn(Self::handle_batch(
+                    Arc::clone(&verifier),
+                    Arc::clone(&storage_adapter),
+                    std::mem::take(&mut pending_shares),
+                ));
+            }
+            while tasks.len() < MAX_CONCURRENT_TASKS {
+                let Some((commitments, light_share, reply_channel)) =
+                    pending_verifications.pop_front()
+                else {
+                    break;
+                };
+                tasks.spawn(Self::handle_verify_share(
+                    Arc::clone(&verifier),
+                    commitments,
+                    light_share,
+                    reply_channel,
+                ));
+            }
         }
 
         Ok(())
@@ -1,13 +1,16 @@
 use std::{collections::HashSet, fmt::Debug, hash::Hash, marker::PhantomData, path::PathBuf};
 
-use futures::try_join;
+use bytes::Bytes;
 use nomos_core::da::blob::Share;
 use nomos_da_storage::rocksdb::{
-    create_share_idx, key_bytes, DA_BLOB_SHARES_INDEX_PREFIX, DA_SHARED_COMMITMENTS_PREFIX,
-    DA_SHARE_PREFIX,
+    create_share_idx, key_bytes, DA_BLOB_RETENTION_PENDING_PREFIX, DA_BLOB_SHARES_INDEX_PREFIX,
+    DA_SHARED_COMMITMENTS_PREFIX, DA_SHARE_PREFIX,
 };
 use nomos_storage::{
-    backends::{rocksdb::RocksBackend, StorageSerde},
+    backends::{
+        rocksdb::{RocksBackend, Transaction},
+        BatchOperation, StorageBatch as _, StorageSerde,
+    },
     StorageMsg, StorageService,
 };
 use overwatch::{
@@ -15,7 +18,6 @@ use overwatch::{
     DynError,
 };
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use tokio::sync::oneshot;
 
 use crate::storage::DaStorageAdapter;
 
@@ -31,7 +33,7 @@ where
 impl<B, S, RuntimeServiceId> DaStorageAdapter<RuntimeServiceId> for RocksAdapter<B, S>
 where
     B: Share + Clone + Send + Sync + 'static,
-    B::BlobId: AsRef<[u8]> + Send + Sync + 'static,
+    B::BlobId: AsRef<[u8]> + Serialize + Send + Sync + 'static,
     B::ShareIndex: AsRef<[u8]> + Eq + Hash + Serialize + DeserializeOwned + Send + Sync + 'static,
     B::LightShare: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
     B::SharesCommitments: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
@@ -64,30 +66,48 @@ where
         let shared_commitments_key = key_bytes(DA_SHARED_COMMITMENTS_PREFIX, &blob_id);
         let index_key = key_bytes(DA_BLOB_SHARES_INDEX_PREFIX, &blob_id);
 
-        let mut shares_index = self
-            .try_load_shares_index(&blob_id)
-            .await?
-            .unwrap_or_default();
-        shares_index.insert(share_idx);
+        let retention_key = key_bytes(DA_BLOB_RETENTION_PENDING_PREFIX, &blob_id);
+        let light_share = S::serialize(light_share);
+        let shared_commitments = S::serialize(shared_commitments);
 
-        try_join!(
-            self.storage_relay.send(StorageMsg::Store {
-                key: share_key,
-                value: S::serialize(light_share),
-            }),
-            self.storage_relay.send(StorageMsg::Store {
-                key: shared_commitments_key,
-                value: S::serialize(shared_commitments),
-            }),
-            self.storage_relay.send(StorageMsg::Store {
-                key: index_key,
-                value: S::serialize(shares_index),
-            }),
-        )
-        .map_err(|(e, _)| DynError::from(e))?;
+        // Shares of a blob are stored concurrently, the index is updated in a
+        // single transaction so none of them is lost.
+        let transaction = Transaction::new(move |db| {
+            let mut shares_index: HashSet<B::ShareIndex> = db
+                .get(&index_key)?
+                .map(|bytes| {
+                    S::deserialize(Bytes::from(bytes)).expect("Failed to deserialize shares")
+                })
+                .unwrap_or_default();
+            shares_index.insert(share_idx);
 
-        Ok(())
+            RocksBackend::<S>::batch_transaction(vec![
+                BatchOperation::Store {
+                    key: share_key,
+                    value: light_share,
+                },
+                BatchOperation::Store {
+                    key: shared_commitments_key,
+                    value: shared_commitments,
+                },
+                BatchOperation::Store {
+                    key: index_key,
+                    value: S::serialize(shares_index),
+                },
+                // The indexer picks the blob up from here, so its shares get
+                // pruned even if it never lands in a block.
+                BatchOperation::Store {
+                    key: retention_key,
+                    value: Bytes::new(),
+                },
+            ])
+            .execute(db)
+        });
+        let (msg, receiver) = StorageMsg::<RocksBackend<S>>::new_transaction_message(transaction);
+        self.storage_relay.send(msg).await.map_err(|(e, _)| e)?;
+        RocksBackend::<S>::batch_result(receiver.into_inner().await?).map_err(Into::into)
     }
+
     async fn get_share(
         &self,
         blob_id: <Self::Share as Share>::BlobId,
@@ -120,42 +140,6 @@ where
     }
 }
 
-impl<B, S> RocksAdapter<B, S>
-where
-    B: Share + Clone + Send + Sync + 'static,
-    B::BlobId: AsRef<[u8]> + Send + Sync + 'static,
-    B::ShareIndex: AsRef<[u8]> + DeserializeOwned + Eq + Hash + Send + Sync + 'static,
-    B::LightShare: Clone + DeserializeOwned + Send + Serialize + Sync + 'static,
-    B::SharesCommitments: Clone + DeserializeOwned + Send + Serialize + Sync + 'static,
-    S: Send + StorageSerde + Sync + 'static,
-{
-    async fn try_load_shares_index(
-        &self,
-        blob_id: &<B as Share>::BlobId,
-    ) -> Result<Option<HashSet<B::ShareIndex>>, DynError> {
-        // Load the blob index. This should be most of the time only read from memory
-        // because all shares are added around the same time. So we probably
-        // don't need to keep additional state in the adapter to avoid reads per
-        // each share
-        let index_key = key_bytes(DA_BLOB_SHARES_INDEX_PREFIX, blob_id);
-        let (reply_tx, reply_rx) = oneshot::channel();
-        self.storage_relay
-            .send(StorageMsg::Load {
-                key: index_key,
-                reply_channel: reply_tx,
-            })
-            .await
-            .map_err(|(e, _)| e)?;
-
-        let shares = reply_rx.await?.map_or_else(
-            || None,
-            |bytes| Some(S::deserialize(bytes).expect("Failed to deserialize shares")),
-        );
-
-        Ok(shares)
-    }
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RocksAdapterSettings {
     pub blob_storage_directory: PathBuf,
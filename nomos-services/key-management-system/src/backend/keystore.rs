@@ -0,0 +1,553 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::Write as _,
+    path::{Path, PathBuf},
+};
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use bytes::Bytes;
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, Payload},
+    ChaCha20Poly1305,
+};
+use overwatch::DynError;
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use super::KMSBackend;
+use crate::{
+    keys::{Key, SupportedKeyTypes},
+    secure_key::SecuredKey,
+    KMSOperator,
+};
+
+const SALT_SIZE: usize = 16;
+const NONCE_SIZE: usize = 12;
+/// Value encrypted along with the keys to check the password, even when the
+/// keystore has no keys.
+const CHECK_VALUE: &[u8] = b"nomos keystore";
+
+/// Backend keeping its keys in a file, encrypted with a key derived from a
+/// password.
+///
+/// The encryption key is derived with Argon2 from the password and a random
+/// salt, both stored in the file along with the Argon2 parameters. Every key is
+/// encrypted with ChaCha20-Poly1305 under its own nonce, and its id is
+/// authenticated along with it. The file is rewritten on every change to the
+/// keys.
+pub struct KeystoreKMSBackend {
+    path: PathBuf,
+    cipher: ChaCha20Poly1305,
+    file: KeystoreFile,
+    keys: HashMap<String, Key>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KeystoreKMSBackendSettings {
+    /// Keystore file, created if it doesn't exist.
+    pub path: PathBuf,
+    /// Where to read the password of the keystore from, so that it is not
+    /// kept in the settings.
+    pub password: PasswordSource,
+}
+
+/// Source of the password of a keystore.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum PasswordSource {
+    /// File containing the password. A trailing newline is ignored.
+    File(PathBuf),
+    /// Environment variable containing the password.
+    Env(String),
+}
+
+impl PasswordSource {
+    /// Reads the password, which is zeroized once dropped.
+    pub fn read(&self) -> Result<Zeroizing<String>, Error> {
+        match self {
+            Self::File(path) => {
+                let mut password = Zeroizing::new(fs::read_to_string(path)?);
+                let len = password.trim_end_matches(['\r', '\n']).len();
+                password.truncate(len);
+                Ok(password)
+            }
+            Self::Env(var) => std::env::var(var)
+                .map(Zeroizing::new)
+                .map_err(|_| Error::PasswordNotSet(var.clone())),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct KeystoreFile {
+    kdf: KdfParams,
+    salt: [u8; SALT_SIZE],
+    /// [`CHECK_VALUE`] encrypted with the derived key.
+    check: EncryptedKey,
+    keys: HashMap<String, EncryptedKey>,
+}
+
+/// Argon2 parameters the encryption key is derived with, so that keystores
+/// can still be opened if the defaults change.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct KdfParams {
+    algorithm: String,
+    version: u32,
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        let params = Params::default();
+        Self {
+            algorithm: Algorithm::default().as_str().to_owned(),
+            version: Version::default().into(),
+            m_cost: params.m_cost(),
+            t_cost: params.t_cost(),
+            p_cost: params.p_cost(),
+        }
+    }
+}
+
+impl KdfParams {
+    fn argon2(&self) -> Result<Argon2<'static>, Error> {
+        let algorithm = self.algorithm.parse().map_err(Error::KeyDerivation)?;
+        let version = Version::try_from(self.version).map_err(Error::KeyDerivation)?;
+        let params = Params::new(self.m_cost, self.t_cost, self.p_cost, None)
+            .map_err(Error::KeyDerivation)?;
+        Ok(Argon2::new(algorithm, version, params))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct EncryptedKey {
+    nonce: [u8; NONCE_SIZE],
+    ciphertext: Vec<u8>,
+}
+
+impl KeystoreKMSBackend {
+    /// Opens the keystore at `path`, creating an empty one if the file doesn't
+    /// exist.
+    pub fn open(path: PathBuf, password: &str) -> Result<Self, Error> {
+        let (file, cipher) = if path.exists() {
+            let file: KeystoreFile = serde_json::from_slice(&fs::read(&path)?)?;
+            let cipher = derive_cipher(password, &file.kdf, &file.salt)?;
+            let check = decrypt(&cipher, &[], &file.check).map_err(|_| Error::WrongPassword)?;
+            if check.as_slice() != CHECK_VALUE {
+                return Err(Error::WrongPassword);
+            }
+            (file, cipher)
+        } else {
+            let kdf = KdfParams::default();
+            let mut salt = [0; SALT_SIZE];
+            OsRng.fill_bytes(&mut salt);
+            let cipher = derive_cipher(password, &kdf, &salt)?;
+            let check = encrypt(&cipher, &[], CHECK_VALUE)?;
+            let file = KeystoreFile {
+                kdf,
+                salt,
+                check,
+                keys: HashMap::new(),
+            };
+            (file, cipher)
+        };
+        let keys = file
+            .keys
+            .iter()
+            .map(|(key_id, encrypted_key)| {
+                decrypt_key(&cipher, key_id, encrypted_key).map(|key| (key_id.clone(), key))
+            })
+            .collect::<Result<_, _>>()?;
+        let keystore = Self {
+            path,
+            cipher,
+            file,
+            keys,
+        };
+        if !keystore.path.exists() {
+            keystore.persist()?;
+        }
+        Ok(keystore)
+    }
+
+    /// Generates a new key of the given type and stores it under `key_id`.
+    pub fn generate(&mut self, key_id: String, key_type: SupportedKeyTypes) -> Result<(), Error> {
+        self.import(key_id, Key::generate(key_type, &mut OsRng))
+    }
+
+    /// Stores an existing key under `key_id`.
+    pub fn import(&mut self, key_id: String, key: Key) -> Result<(), Error> {
+        if self.keys.contains_key(&key_id) {
+            return Err(Error::KeyAlreadyExists(key_id));
+        }
+        let encrypted_key = encrypt_key(&self.cipher, &key_id, &key)?;
+        self.file.keys.insert(key_id.clone(), encrypted_key);
+        self.keys.insert(key_id.clone(), key);
+        self.persist().inspect_err(|_| {
+            self.file.keys.remove(&key_id);
+            self.keys.remove(&key_id);
+        })
+    }
+
+    /// Returns a copy of the key stored under `key_id`, decrypted again from
+    /// the keystore file.
+    pub fn export(&self, key_id: &str) -> Result<Key, Error> {
+        let encrypted_key = self
+            .file
+            .keys
+            .get(key_id)
+            .ok_or_else(|| Error::KeyNotRegistered(key_id.to_owned()))?;
+        decrypt_key(&self.cipher, key_id, encrypted_key)
+    }
+
+    /// Ids and types of the stored keys, sorted by id.
+    #[must_use]
+    pub fn list(&self) -> Vec<(String, SupportedKeyTypes)> {
+        let mut keys: Vec<_> = self
+            .keys
+            .iter()
+            .map(|(key_id, key)| (key_id.clone(), key.key_type()))
+            .collect();
+        keys.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        keys
+    }
+
+    /// Removes the key stored under `key_id` from the keystore.
+    pub fn delete(&mut self, key_id: &str) -> Result<(), Error> {
+        let Some(encrypted_key) = self.file.keys.remove(key_id) else {
+            return Err(Error::KeyNotRegistered(key_id.to_owned()));
+        };
+        let key = self.keys.remove(key_id);
+        self.persist().inspect_err(|_| {
+            self.file.keys.insert(key_id.to_owned(), encrypted_key);
+            if let Some(key) = key {
+                self.keys.insert(key_id.to_owned(), key);
+            }
+        })
+    }
+
+    fn get(&self, key_id: &str) -> Result<&Key, Error> {
+        self.keys
+            .get(key_id)
+            .ok_or_else(|| Error::KeyNotRegistered(key_id.to_owned()))
+    }
+
+    /// Writes the keystore to a temporary file first, so that the previous one
+    /// is kept if writing fails. The file is only readable by its owner.
+    fn persist(&self) -> Result<(), Error> {
+        let tmp_path = tmp_path(&self.path);
+        // a leftover from an interrupted write could have other permissions
+        match fs::remove_file(&tmp_path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+            _ => {}
+        }
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut tmp_file = options.open(&tmp_path)?;
+        tmp_file.write_all(&serde_json::to_vec(&self.file)?)?;
+        // the content must be on disk before it replaces the previous keystore
+        tmp_file.sync_all()?;
+        fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl KMSBackend for KeystoreKMSBackend {
+    type SupportedKeyTypes = SupportedKeyTypes;
+    type KeyId = String;
+    type Settings = KeystoreKMSBackendSettings;
+
+    fn new(settings: Self::Settings) -> Result<Self, DynError> {
+        let KeystoreKMSBackendSettings { path, password } = settings;
+        Ok(Self::open(path, &password.read()?)?)
+    }
+
+    /// Checks that the key stored under `key_id` has the requested type, or
+    /// generates a new one if there is none.
+    fn register(
+        &mut self,
+        key_id: Self::KeyId,
+        key_type: Self::SupportedKeyTypes,
+    ) -> Result<Self::KeyId, DynError> {
+        match self.keys.get(&key_id) {
+            Some(key) if key.key_type() != key_type => {
+                Err(Error::KeyTypeMismatch(key.key_type(), key_type).into())
+            }
+            Some(_) => Ok(key_id),
+            None => {
+                self.generate(key_id.clone(), key_type)?;
+                Ok(key_id)
+            }
+        }
+    }
+
+    fn public_key(&self, key_id: Self::KeyId) -> Result<Bytes, DynError> {
+        Ok(self.get(&key_id)?.as_pk())
+    }
+
+    fn sign(&self, key_id: Self::KeyId, data: Bytes) -> Result<Bytes, DynError> {
+        self.get(&key_id)?.sign(data)
+    }
+
+    async fn execute(&mut self, key_id: Self::KeyId, mut op: KMSOperator) -> Result<(), DynError> {
+        op(self.get(&key_id)?).await
+    }
+}
+
+fn derive_cipher(
+    password: &str,
+    kdf: &KdfParams,
+    salt: &[u8; SALT_SIZE],
+) -> Result<ChaCha20Poly1305, Error> {
+    let mut key = Zeroizing::new([0; 32]);
+    kdf.argon2()?
+        .hash_password_into(password.as_bytes(), salt, key.as_mut())
+        .map_err(Error::KeyDerivation)?;
+    Ok(ChaCha20Poly1305::new(key.as_ref().into()))
+}
+
+fn encrypt(cipher: &ChaCha20Poly1305, aad: &[u8], msg: &[u8]) -> Result<EncryptedKey, Error> {
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, Payload { msg, aad })
+        .map_err(|_| Error::Encryption)?;
+    Ok(EncryptedKey {
+        nonce: nonce.into(),
+        ciphertext,
+    })
+}
+
+fn decrypt(
+    cipher: &ChaCha20Poly1305,
+    aad: &[u8],
+    EncryptedKey { nonce, ciphertext }: &EncryptedKey,
+) -> Result<Zeroizing<Vec<u8>>, chacha20poly1305::Error> {
+    cipher
+        .decrypt(
+            nonce.into(),
+            Payload {
+                msg: ciphertext,
+                aad,
+            },
+        )
+        .map(Zeroizing::new)
+}
+
+fn encrypt_key(cipher: &ChaCha20Poly1305, key_id: &str, key: &Key) -> Result<EncryptedKey, Error> {
+    let plaintext = Zeroizing::new(serde_json::to_vec(key)?);
+    encrypt(cipher, key_id.as_bytes(), &plaintext)
+}
+
+fn decrypt_key(
+    cipher: &ChaCha20Poly1305,
+    key_id: &str,
+    encrypted_key: &EncryptedKey,
+) -> Result<Key, Error> {
+    let plaintext = decrypt(cipher, key_id.as_bytes(), encrypted_key)
+        .map_err(|_| Error::Decryption(key_id.to_owned()))?;
+    Ok(serde_json::from_slice(&plaintext)?)
+}
+
+fn tmp_path(path: &Path) -> PathBuf {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    tmp_path.into()
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Key({0}) was not registered")]
+    KeyNotRegistered(String),
+    #[error("Key({0}) already exists")]
+    KeyAlreadyExists(String),
+    #[error("KeyType mismatch: {0:?} != {1:?}")]
+    KeyTypeMismatch(SupportedKeyTypes, SupportedKeyTypes),
+    #[error("Key({0}) could not be decrypted")]
+    Decryption(String),
+    #[error("Wrong keystore password")]
+    WrongPassword,
+    #[error("Key could not be encrypted")]
+    Encryption,
+    #[error("Password environment variable {0} is not set")]
+    PasswordNotSet(String),
+    #[error("Encryption key could not be derived: {0}")]
+    KeyDerivation(argon2::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Serialization(#[from] serde_json::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    const PASSWORD: &str = "password";
+
+    fn keystore_path(dir: &TempDir) -> PathBuf {
+        dir.path().join("keystore.json")
+    }
+
+    #[test]
+    fn keys_are_kept_across_reopens() {
+        let dir = TempDir::new().unwrap();
+        let mut keystore = KeystoreKMSBackend::open(keystore_path(&dir), PASSWORD).unwrap();
+        keystore
+            .generate("network".to_owned(), SupportedKeyTypes::Ed25519)
+            .unwrap();
+        keystore
+            .generate("blend".to_owned(), SupportedKeyTypes::Ed25519)
+            .unwrap();
+        keystore
+            .generate("leader".to_owned(), SupportedKeyTypes::NullifierSecret)
+            .unwrap();
+        let public_keys: Vec<_> = ["blend", "leader", "network"]
+            .into_iter()
+            .map(|key_id| keystore.public_key(key_id.to_owned()).unwrap())
+            .collect();
+
+        let keystore = KeystoreKMSBackend::open(keystore_path(&dir), PASSWORD).unwrap();
+        assert_eq!(
+            keystore.list(),
+            vec![
+                ("blend".to_owned(), SupportedKeyTypes::Ed25519),
+                ("leader".to_owned(), SupportedKeyTypes::NullifierSecret),
+                ("network".to_owned(), SupportedKeyTypes::Ed25519),
+            ]
+        );
+        for (key_id, public_key) in ["blend", "leader", "network"].into_iter().zip(public_keys) {
+            assert_eq!(keystore.public_key(key_id.to_owned()).unwrap(), public_key);
+        }
+    }
+
+    #[test]
+    fn keys_are_encrypted_at_rest() {
+        let dir = TempDir::new().unwrap();
+        let mut keystore = KeystoreKMSBackend::open(keystore_path(&dir), PASSWORD).unwrap();
+        let key = ed25519_dalek::SigningKey::generate(&mut OsRng);
+        keystore
+            .import(
+                "network".to_owned(),
+                Key::Ed25519(crate::keys::ed25519::Ed25519Key::new(key.clone())),
+            )
+            .unwrap();
+
+        let file = fs::read_to_string(keystore_path(&dir)).unwrap();
+        assert!(!file.contains(&serde_json::to_string(&key).unwrap()));
+        assert!(matches!(
+            KeystoreKMSBackend::open(keystore_path(&dir), "wrong password"),
+            Err(Error::WrongPassword)
+        ));
+    }
+
+    #[test]
+    fn empty_keystore_checks_password() {
+        let dir = TempDir::new().unwrap();
+        KeystoreKMSBackend::open(keystore_path(&dir), PASSWORD).unwrap();
+
+        let file: KeystoreFile =
+            serde_json::from_slice(&fs::read(keystore_path(&dir)).unwrap()).unwrap();
+        assert_eq!(file.kdf, KdfParams::default());
+        assert!(file.keys.is_empty());
+        assert!(matches!(
+            KeystoreKMSBackend::open(keystore_path(&dir), "wrong password"),
+            Err(Error::WrongPassword)
+        ));
+        assert!(KeystoreKMSBackend::open(keystore_path(&dir), PASSWORD).is_ok());
+    }
+
+    #[test]
+    fn import_export_and_delete() {
+        let dir = TempDir::new().unwrap();
+        let mut keystore = KeystoreKMSBackend::open(keystore_path(&dir), PASSWORD).unwrap();
+        keystore
+            .generate("blend".to_owned(), SupportedKeyTypes::Ed25519)
+            .unwrap();
+        assert!(matches!(
+            keystore.import("blend".to_owned(), keystore.export("blend").unwrap()),
+            Err(Error::KeyAlreadyExists(_))
+        ));
+
+        let key = keystore.export("blend").unwrap();
+        keystore.import("blend/copy".to_owned(), key).unwrap();
+        assert_eq!(
+            keystore.public_key("blend/copy".to_owned()).unwrap(),
+            keystore.public_key("blend".to_owned()).unwrap()
+        );
+
+        keystore.delete("blend").unwrap();
+        assert!(matches!(
+            keystore.delete("blend"),
+            Err(Error::KeyNotRegistered(_))
+        ));
+        let keystore = KeystoreKMSBackend::open(keystore_path(&dir), PASSWORD).unwrap();
+        assert_eq!(
+            keystore.list(),
+            vec![("blend/copy".to_owned(), SupportedKeyTypes::Ed25519)]
+        );
+    }
+
+    #[test]
+    fn register_generates_missing_keys() {
+        let dir = TempDir::new().unwrap();
+        let password_path = dir.path().join("password");
+        fs::write(&password_path, format!("{PASSWORD}\n")).unwrap();
+        let mut keystore = KeystoreKMSBackend::new(KeystoreKMSBackendSettings {
+            path: keystore_path(&dir),
+            password: PasswordSource::File(password_path),
+        })
+        .unwrap();
+
+        let key_id = keystore
+            .register("network".to_owned(), SupportedKeyTypes::Ed25519)
+            .unwrap();
+        let public_key = keystore.public_key(key_id.clone()).unwrap();
+        assert_eq!(
+            keystore
+                .register(key_id.clone(), SupportedKeyTypes::Ed25519)
+                .unwrap(),
+            key_id
+        );
+        assert_eq!(keystore.public_key(key_id.clone()).unwrap(), public_key);
+        assert!(keystore
+            .register(key_id.clone(), SupportedKeyTypes::NullifierSecret)
+            .is_err());
+
+        let data = Bytes::from("data");
+        let signature = keystore.sign(key_id, data.clone()).unwrap();
+        let verifying_key = ed25519_dalek::VerifyingKey::try_from(public_key.as_ref()).unwrap();
+        let signature = ed25519_dalek::Signature::from_slice(&signature).unwrap();
+        assert!(verifying_key.verify_strict(&data, &signature).is_ok());
+    }
+
+    #[test]
+    fn password_is_read_from_env() {
+        let var = "KEYSTORE_TEST_PASSWORD";
+        let source = PasswordSource::Env(var.to_owned());
+        assert!(matches!(source.read(), Err(Error::PasswordNotSet(_))));
+        std::env::set_var(var, PASSWORD);
+        assert_eq!(source.read().unwrap().as_str(), PASSWORD);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn keystore_is_only_readable_by_its_owner() {
+        use std::os::unix::fs::PermissionsExt as _;
+
+        let dir = TempDir::new().unwrap();
+        KeystoreKMSBackend::open(keystore_path(&dir), PASSWORD).unwrap();
+        let mode = fs::metadata(keystore_path(&dir))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
@@ -1,3 +1,7 @@
+#[cfg(feature = "keystore")]
+pub mod keystore;
+#[cfg(all(feature = "keystore", feature = "preload"))]
+pub mod node;
 #[cfg(feature = "preload")]
 pub mod preload;
 
@@ -12,7 +16,9 @@ pub trait KMSBackend {
     type KeyId;
     type Settings;
 
-    fn new(settings: Self::Settings) -> Self;
+    fn new(settings: Self::Settings) -> Result<Self, DynError>
+    where
+        Self: Sized;
     fn register(
         &mut self,
         key_id: Self::KeyId,
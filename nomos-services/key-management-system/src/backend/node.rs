@@ -0,0 +1,76 @@
+use bytes::Bytes;
+use overwatch::DynError;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    keystore::{KeystoreKMSBackend, KeystoreKMSBackendSettings},
+    preload::{PreloadKMSBackend, PreloadKMSBackendSettings},
+    KMSBackend,
+};
+use crate::{keys::SupportedKeyTypes, KMSOperator};
+
+/// Backend of the key management service of the nodes, chosen in the
+/// settings.
+pub enum NodeKMSBackend {
+    Preload(PreloadKMSBackend),
+    Keystore(KeystoreKMSBackend),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum NodeKMSBackendSettings {
+    /// Keys are given in the settings.
+    Preload(PreloadKMSBackendSettings),
+    /// Keys are kept encrypted in a keystore file, and generated on their
+    /// first use.
+    Keystore(KeystoreKMSBackendSettings),
+}
+
+#[async_trait::async_trait]
+impl KMSBackend for NodeKMSBackend {
+    type SupportedKeyTypes = SupportedKeyTypes;
+    type KeyId = String;
+    type Settings = NodeKMSBackendSettings;
+
+    fn new(settings: Self::Settings) -> Result<Self, DynError> {
+        match settings {
+            NodeKMSBackendSettings::Preload(settings) => {
+                PreloadKMSBackend::new(settings).map(Self::Preload)
+            }
+            NodeKMSBackendSettings::Keystore(settings) => {
+                KeystoreKMSBackend::new(settings).map(Self::Keystore)
+            }
+        }
+    }
+
+    fn register(
+        &mut self,
+        key_id: Self::KeyId,
+        key_type: Self::SupportedKeyTypes,
+    ) -> Result<Self::KeyId, DynError> {
+        match self {
+            Self::Preload(backend) => backend.register(key_id, key_type),
+            Self::Keystore(backend) => backend.register(key_id, key_type),
+        }
+    }
+
+    fn public_key(&self, key_id: Self::KeyId) -> Result<Bytes, DynError> {
+        match self {
+            Self::Preload(backend) => backend.public_key(key_id),
+            Self::Keystore(backend) => backend.public_key(key_id),
+        }
+    }
+
+    fn sign(&self, key_id: Self::KeyId, data: Bytes) -> Result<Bytes, DynError> {
+        match self {
+            Self::Preload(backend) => backend.sign(key_id, data),
+            Self::Keystore(backend) => backend.sign(key_id, data),
+        }
+    }
+
+    async fn execute(&mut self, key_id: Self::KeyId, op: KMSOperator) -> Result<(), DynError> {
+        match self {
+            Self::Preload(backend) => backend.execute(key_id, op).await,
+            Self::Keystore(backend) => backend.execute(key_id, op).await,
+        }
+    }
+}
@@ -1,24 +1,30 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 use bytes::Bytes;
 use overwatch::DynError;
 use serde::{Deserialize, Serialize};
-use zeroize::ZeroizeOnDrop;
 
 use super::KMSBackend;
-use crate::{keys::ed25519::Ed25519Key, secure_key::SecuredKey, KMSOperator};
+use crate::{
+    keys::{Key, SupportedKeyTypes},
+    secure_key::SecuredKey,
+    KMSOperator,
+};
 
 pub struct PreloadKMSBackend {
-    keys: HashMap<String, Key>,
+    keys: HashMap<String, Arc<Key>>,
 }
 
 /// This settings contain all [`Key`]s to be loaded into the
 /// [`PreloadKMSBackend`]. This implements [`serde::Serialize`] for users to
 /// populate the settings from bytes. The [`Key`] also implements
 /// [`zeroize::ZeroizeOnDrop`] for security.
-#[derive(Serialize, Deserialize)]
+///
+/// Keys are shared with the backend rather than copied, so that cloning the
+/// settings doesn't leave copies of the secrets around.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct PreloadKMSBackendSettings {
-    keys: HashMap<String, Key>,
+    pub keys: HashMap<String, Arc<Key>>,
 }
 
 #[async_trait::async_trait]
@@ -27,10 +33,10 @@ impl KMSBackend for PreloadKMSBackend {
     type KeyId = String;
     type Settings = PreloadKMSBackendSettings;
 
-    fn new(settings: Self::Settings) -> Self {
-        Self {
+    fn new(settings: Self::Settings) -> Result<Self, DynError> {
+        Ok(Self {
             keys: settings.keys,
-        }
+        })
     }
 
     /// This function just checks if the key_id was preloaded successfully.
@@ -69,46 +75,13 @@ impl KMSBackend for PreloadKMSBackend {
     async fn execute(&mut self, key_id: Self::KeyId, mut op: KMSOperator) -> Result<(), DynError> {
         op(self
             .keys
-            .get_mut(&key_id)
-            .ok_or(Error::KeyNotRegistered(key_id))?)
+            .get(&key_id)
+            .ok_or(Error::KeyNotRegistered(key_id))?
+            .as_ref())
         .await
     }
 }
 
-// This enum won't be used outside of this module
-// because [`PreloadKMSBackend`] doesn't support generating new keys.
-#[derive(Debug, PartialEq, Eq)]
-pub enum SupportedKeyTypes {
-    Ed25519,
-}
-
-#[derive(Serialize, Deserialize, ZeroizeOnDrop)]
-enum Key {
-    Ed25519(Ed25519Key),
-}
-
-impl SecuredKey for Key {
-    fn sign(&self, data: Bytes) -> Result<Bytes, DynError> {
-        match self {
-            Self::Ed25519(key) => key.sign(data),
-        }
-    }
-
-    fn as_pk(&self) -> Bytes {
-        match self {
-            Self::Ed25519(key) => key.as_pk(),
-        }
-    }
-}
-
-impl Key {
-    const fn key_type(&self) -> SupportedKeyTypes {
-        match self {
-            Self::Ed25519(_) => SupportedKeyTypes::Ed25519,
-        }
-    }
-}
-
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error("Key({0}) was not registered")]
@@ -119,11 +92,12 @@ pub enum Error {
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::{collections::HashMap, sync::Arc};
 
     use rand::rngs::OsRng;
 
     use super::*;
+    use crate::keys::ed25519::Ed25519Key;
 
     #[tokio::test]
     async fn preload_backend() {
@@ -133,9 +107,10 @@ mod tests {
         let mut backend = PreloadKMSBackend::new(PreloadKMSBackendSettings {
             keys: HashMap::from_iter(vec![(
                 key_id.clone(),
-                Key::Ed25519(Ed25519Key(key.clone())),
+                Arc::new(Key::Ed25519(Ed25519Key(key.clone()))),
             )]),
-        });
+        })
+        .unwrap();
 
         // Check if the key was preloaded successfully with the same key type.
         assert_eq!(
@@ -159,17 +134,33 @@ mod tests {
         backend
             .execute(
                 key_id.clone(),
-                Box::new(move |_: &mut dyn SecuredKey| Box::pin(async move { Ok(()) })),
+                Box::new(move |_: &dyn SecuredKey| Box::pin(async move { Ok(()) })),
             )
             .await
             .unwrap();
     }
 
+    #[test]
+    fn settings_debug_hides_secret_keys() {
+        let key = ed25519_dalek::SigningKey::generate(&mut OsRng);
+        let settings = PreloadKMSBackendSettings {
+            keys: HashMap::from([(
+                "blend/1".to_owned(),
+                Arc::new(Key::Ed25519(Ed25519Key::new(key.clone()))),
+            )]),
+        };
+
+        let debug = format!("{settings:?}");
+        assert!(!debug.contains(&format!("{:?}", key.to_bytes())));
+        assert!(debug.contains(&format!("{:?}", key.verifying_key())));
+    }
+
     #[tokio::test]
     async fn key_not_registered() {
         let mut backend = PreloadKMSBackend::new(PreloadKMSBackendSettings {
             keys: HashMap::new(),
-        });
+        })
+        .unwrap();
 
         let key_id = "blend/not_registered".to_owned();
         assert!(backend
@@ -180,7 +171,7 @@ mod tests {
         assert!(backend
             .execute(
                 key_id,
-                Box::new(move |_: &mut dyn SecuredKey| Box::pin(async move { Ok(()) })),
+                Box::new(move |_: &dyn SecuredKey| Box::pin(async move { Ok(()) })),
             )
             .await
             .is_err());
@@ -1,14 +1,43 @@
+use std::fmt::{Debug, Formatter};
+
 use bytes::Bytes;
 use ed25519_dalek::ed25519::signature::Signer;
 use overwatch::DynError;
+use rand::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
-use zeroize::ZeroizeOnDrop;
+use zeroize::{ZeroizeOnDrop, Zeroizing};
 
 use crate::secure_key::SecuredKey;
 
 #[derive(Serialize, Deserialize, ZeroizeOnDrop)]
 pub struct Ed25519Key(pub(crate) ed25519_dalek::SigningKey);
 
+impl Ed25519Key {
+    #[must_use]
+    pub const fn new(signing_key: ed25519_dalek::SigningKey) -> Self {
+        Self(signing_key)
+    }
+
+    /// Key from its raw secret bytes.
+    #[must_use]
+    pub fn from_bytes(secret: &[u8; 32]) -> Self {
+        Self(ed25519_dalek::SigningKey::from_bytes(secret))
+    }
+
+    pub fn generate<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        Self(ed25519_dalek::SigningKey::generate(rng))
+    }
+}
+
+// Only the public key is printed, so that settings holding keys can be logged.
+impl Debug for Ed25519Key {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Ed25519Key")
+            .field(&self.0.verifying_key())
+            .finish()
+    }
+}
+
 impl SecuredKey for Ed25519Key {
     fn sign(&self, data: Bytes) -> Result<Bytes, DynError> {
         Ok(Bytes::copy_from_slice(&self.0.sign(&data).to_bytes()))
@@ -17,4 +46,8 @@ impl SecuredKey for Ed25519Key {
     fn as_pk(&self) -> Bytes {
         Bytes::copy_from_slice(self.0.verifying_key().as_bytes())
     }
+
+    fn secret_bytes(&self) -> Zeroizing<Vec<u8>> {
+        Zeroizing::new(self.0.as_bytes().to_vec())
+    }
 }
@@ -1 +1,76 @@
 pub mod ed25519;
+pub mod nullifier;
+
+use bytes::Bytes;
+use overwatch::DynError;
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use zeroize::{ZeroizeOnDrop, Zeroizing};
+
+use crate::{
+    keys::{ed25519::Ed25519Key, nullifier::NullifierSecretKey},
+    secure_key::SecuredKey,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SupportedKeyTypes {
+    /// Signing keys, like the node key which the libp2p identity and the
+    /// blend encryption key are derived from.
+    Ed25519,
+    /// Nullifier secret of the CL notes.
+    NullifierSecret,
+}
+
+#[derive(Debug, Serialize, Deserialize, ZeroizeOnDrop)]
+pub enum Key {
+    Ed25519(Ed25519Key),
+    NullifierSecret(NullifierSecretKey),
+}
+
+impl Key {
+    pub fn generate<R: RngCore + CryptoRng>(key_type: SupportedKeyTypes, rng: &mut R) -> Self {
+        match key_type {
+            SupportedKeyTypes::Ed25519 => Self::Ed25519(Ed25519Key::generate(rng)),
+            SupportedKeyTypes::NullifierSecret => {
+                Self::NullifierSecret(NullifierSecretKey::generate(rng))
+            }
+        }
+    }
+
+    #[must_use]
+    pub const fn key_type(&self) -> SupportedKeyTypes {
+        match self {
+            Self::Ed25519(_) => SupportedKeyTypes::Ed25519,
+            Self::NullifierSecret(_) => SupportedKeyTypes::NullifierSecret,
+        }
+    }
+}
+
+impl SecuredKey for Key {
+    fn sign(&self, data: Bytes) -> Result<Bytes, DynError> {
+        match self {
+            Self::Ed25519(key) => key.sign(data),
+            Self::NullifierSecret(key) => key.sign(data),
+        }
+    }
+
+    fn as_pk(&self) -> Bytes {
+        match self {
+            Self::Ed25519(key) => key.as_pk(),
+            Self::NullifierSecret(key) => key.as_pk(),
+        }
+    }
+
+    fn secret_bytes(&self) -> Zeroizing<Vec<u8>> {
+        match self {
+            Self::Ed25519(key) => key.secret_bytes(),
+            Self::NullifierSecret(key) => key.secret_bytes(),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("{0:?} keys cannot sign")]
+    SigningNotSupported(SupportedKeyTypes),
+}
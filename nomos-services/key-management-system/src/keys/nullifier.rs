@@ -0,0 +1,60 @@
+use std::fmt::{Debug, Formatter};
+
+use bytes::Bytes;
+use cl::NullifierSecret;
+use overwatch::DynError;
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use crate::{
+    keys::{Error, SupportedKeyTypes},
+    secure_key::SecuredKey,
+};
+
+/// Nullifier secret of the CL notes. Its public key is the nullifier
+/// commitment notes are created with.
+#[derive(Serialize, Deserialize)]
+pub struct NullifierSecretKey(pub(crate) NullifierSecret);
+
+impl NullifierSecretKey {
+    #[must_use]
+    pub const fn new(secret: NullifierSecret) -> Self {
+        Self(secret)
+    }
+
+    pub fn generate<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        Self(NullifierSecret::random(rng))
+    }
+}
+
+// `NullifierSecret` is a plain byte array, so its bytes are cleared here.
+impl Drop for NullifierSecretKey {
+    fn drop(&mut self) {
+        self.0 .0.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for NullifierSecretKey {}
+
+impl Debug for NullifierSecretKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("NullifierSecretKey")
+            .field(&self.0.commit())
+            .finish()
+    }
+}
+
+impl SecuredKey for NullifierSecretKey {
+    fn sign(&self, _data: Bytes) -> Result<Bytes, DynError> {
+        Err(Error::SigningNotSupported(SupportedKeyTypes::NullifierSecret).into())
+    }
+
+    fn as_pk(&self) -> Bytes {
+        Bytes::copy_from_slice(self.0.commit().as_bytes())
+    }
+
+    fn secret_bytes(&self) -> Zeroizing<Vec<u8>> {
+        Zeroizing::new(self.0 .0.to_vec())
+    }
+}
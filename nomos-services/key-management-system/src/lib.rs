@@ -5,29 +5,39 @@ use std::{
 };
 
 use bytes::Bytes;
+use cl::NullifierSecret;
 use futures::StreamExt;
 use log::error;
 use overwatch::{
     services::{
+        relay::OutboundRelay,
         state::{NoOperator, NoState},
         AsServiceId, ServiceCore, ServiceData,
     },
     DynError, OpaqueServiceStateHandle,
 };
+use serde::{Deserialize, Serialize};
 use services_utils::overwatch::lifecycle;
 use tokio::sync::oneshot;
+use zeroize::Zeroizing;
 
 use crate::{backend::KMSBackend, secure_key::SecuredKey};
 
-mod backend;
-mod keys;
-mod secure_key;
+pub mod backend;
+pub mod keys;
+pub mod secure_key;
+
+#[cfg(all(feature = "keystore", feature = "preload"))]
+pub use backend::node::{NodeKMSBackend, NodeKMSBackendSettings};
+
+#[cfg(all(feature = "keystore", feature = "preload"))]
+pub type NodeKMSService<RuntimeServiceId> = KMSService<NodeKMSBackend, RuntimeServiceId>;
+#[cfg(all(feature = "keystore", feature = "preload"))]
+pub type NodeKMSRelay = OutboundRelay<KMSMessage<NodeKMSBackend>>;
 
 // TODO: Use [`AsyncFnMut`](https://doc.rust-lang.org/stable/std/ops/trait.AsyncFnMut.html#tymethod.async_call_mut) once it is stabilized.
 pub type KMSOperator = Box<
-    dyn FnMut(
-            &mut dyn SecuredKey,
-        ) -> Pin<Box<dyn Future<Output = Result<(), DynError>> + Send + Sync>>
+    dyn FnMut(&dyn SecuredKey) -> Pin<Box<dyn Future<Output = Result<(), DynError>> + Send + Sync>>
         + Send
         + Sync,
 >;
@@ -85,9 +95,9 @@ where
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct KMSServiceSettings<BackendSettings> {
-    backend_settings: BackendSettings,
+    pub backend_settings: BackendSettings,
 }
 
 pub struct KMSService<Backend, RuntimeServiceId>
@@ -130,7 +140,7 @@ where
     ) -> Result<Self, DynError> {
         let KMSServiceSettings { backend_settings } =
             service_state.settings_reader.get_updated_settings();
-        let backend = Backend::new(backend_settings);
+        let backend = Backend::new(backend_settings)?;
         Ok(Self {
             backend,
             service_state,
@@ -171,43 +181,183 @@ where
                 key_id,
                 key_type,
                 reply_channel,
-            } => {
-                let Ok(key_id) = backend.register(key_id, key_type) else {
-                    panic!("A key could not be registered");
-                };
-                if let Err(_key_id) = reply_channel.send(key_id) {
-                    error!("Could not reply key_id for register request");
+            } => match backend.register(key_id, key_type) {
+                Ok(key_id) => {
+                    if let Err(_key_id) = reply_channel.send(key_id) {
+                        error!("Could not reply key_id for register request");
+                    }
                 }
-            }
+                // dropping the reply channel tells the requester
+                Err(e) => error!("A key could not be registered: {e}"),
+            },
             KMSMessage::PublicKey {
                 key_id,
                 reply_channel,
-            } => {
-                let Ok(pk_bytes) = backend.public_key(key_id) else {
-                    panic!("Requested public key for nonexistent KeyId");
-                };
-                if let Err(_pk_bytes) = reply_channel.send(pk_bytes) {
-                    error!("Could not reply public key to request channel");
+            } => match backend.public_key(key_id) {
+                Ok(pk_bytes) => {
+                    if let Err(_pk_bytes) = reply_channel.send(pk_bytes) {
+                        error!("Could not reply public key to request channel");
+                    }
                 }
-            }
+                Err(e) => error!("Requested public key for nonexistent KeyId: {e}"),
+            },
             KMSMessage::Sign {
                 key_id,
                 data,
                 reply_channel,
-            } => {
-                let Ok(signature) = backend.sign(key_id, data) else {
-                    panic!("Could not sign ")
-                };
-                if let Err(_signature) = reply_channel.send(signature) {
-                    error!("Could not reply public key to request channel");
+            } => match backend.sign(key_id, data) {
+                Ok(signature) => {
+                    if let Err(_signature) = reply_channel.send(signature) {
+                        error!("Could not reply signature to request channel");
+                    }
                 }
-            }
+                Err(e) => error!("Could not sign: {e}"),
+            },
             KMSMessage::Execute { key_id, operator } => {
-                backend
-                    .execute(key_id, operator)
-                    .await
-                    .expect("Could not execute operator");
+                if let Err(e) = backend.execute(key_id, operator).await {
+                    error!("Could not execute operator: {e}");
+                }
             }
         }
     }
 }
+
+/// Registers `key_id` with type `key_type`, returning the id the key is
+/// known by in the backend.
+async fn register<Backend>(
+    relay: &OutboundRelay<KMSMessage<Backend>>,
+    key_id: Backend::KeyId,
+    key_type: Backend::SupportedKeyTypes,
+) -> Result<Backend::KeyId, DynError>
+where
+    Backend: KMSBackend,
+{
+    let (reply_channel, receiver) = oneshot::channel();
+    relay
+        .send(KMSMessage::Register {
+            key_id,
+            key_type,
+            reply_channel,
+        })
+        .await
+        .map_err(|(e, _)| e)?;
+    Ok(receiver.await?)
+}
+
+/// Retrieves the raw secret key registered under `key_id` with type
+/// `key_type`.
+async fn retrieve_secret_key<Backend>(
+    relay: &OutboundRelay<KMSMessage<Backend>>,
+    key_id: Backend::KeyId,
+    key_type: Backend::SupportedKeyTypes,
+) -> Result<Zeroizing<Vec<u8>>, DynError>
+where
+    Backend: KMSBackend,
+{
+    let key_id = register(relay, key_id, key_type).await?;
+
+    let (reply_channel, receiver) = oneshot::channel();
+    let mut reply_channel = Some(reply_channel);
+    let operator: KMSOperator = Box::new(move |key: &dyn SecuredKey| {
+        if let Some(reply_channel) = reply_channel.take() {
+            if reply_channel.send(key.secret_bytes()).is_err() {
+                error!("Could not reply secret key to request channel");
+            }
+        }
+        Box::pin(async { Ok(()) })
+    });
+    relay
+        .send(KMSMessage::Execute { key_id, operator })
+        .await
+        .map_err(|(e, _)| e)?;
+    Ok(receiver.await?)
+}
+
+/// Retrieves the ed25519 node key registered under `key_id`, to build the
+/// libp2p identity of a swarm.
+///
+/// Secret keys only leave the key management service where they can't be
+/// used through it: libp2p builds the identity of the node from the raw key,
+/// and the leader proof takes the nullifier secret as a private input, see
+/// [`retrieve_nullifier_secret`]. Other keys are used through [`sign`] and
+/// [`public_key`].
+pub async fn retrieve_ed25519_key<Backend>(
+    relay: &OutboundRelay<KMSMessage<Backend>>,
+    key_id: Backend::KeyId,
+) -> Result<Zeroizing<[u8; ed25519_dalek::SECRET_KEY_LENGTH]>, DynError>
+where
+    Backend: KMSBackend<SupportedKeyTypes = keys::SupportedKeyTypes>,
+{
+    let secret_key = retrieve_secret_key(relay, key_id, keys::SupportedKeyTypes::Ed25519).await?;
+    let mut ed25519_key = Zeroizing::new([0; ed25519_dalek::SECRET_KEY_LENGTH]);
+    if secret_key.len() != ed25519_key.len() {
+        return Err("Secret key is not an ed25519 key".into());
+    }
+    ed25519_key.copy_from_slice(&secret_key);
+    Ok(ed25519_key)
+}
+
+/// Retrieves the nullifier secret registered under `key_id`, which leader
+/// proofs are built with.
+pub async fn retrieve_nullifier_secret<Backend>(
+    relay: &OutboundRelay<KMSMessage<Backend>>,
+    key_id: Backend::KeyId,
+) -> Result<NullifierSecret, DynError>
+where
+    Backend: KMSBackend<SupportedKeyTypes = keys::SupportedKeyTypes>,
+{
+    let secret_key =
+        retrieve_secret_key(relay, key_id, keys::SupportedKeyTypes::NullifierSecret).await?;
+    Ok(NullifierSecret(
+        secret_key
+            .as_slice()
+            .try_into()
+            .map_err(|_| "Secret key is not a nullifier secret")?,
+    ))
+}
+
+/// Public key of the key registered under `key_id` with type `key_type`.
+pub async fn public_key<Backend>(
+    relay: &OutboundRelay<KMSMessage<Backend>>,
+    key_id: Backend::KeyId,
+    key_type: Backend::SupportedKeyTypes,
+) -> Result<Bytes, DynError>
+where
+    Backend: KMSBackend,
+{
+    let key_id = register(relay, key_id, key_type).await?;
+
+    let (reply_channel, receiver) = oneshot::channel();
+    relay
+        .send(KMSMessage::PublicKey {
+            key_id,
+            reply_channel,
+        })
+        .await
+        .map_err(|(e, _)| e)?;
+    Ok(receiver.await?)
+}
+
+/// Signs `data` with the key registered under `key_id` with type `key_type`.
+pub async fn sign<Backend>(
+    relay: &OutboundRelay<KMSMessage<Backend>>,
+    key_id: Backend::KeyId,
+    key_type: Backend::SupportedKeyTypes,
+    data: Bytes,
+) -> Result<Bytes, DynError>
+where
+    Backend: KMSBackend,
+{
+    let key_id = register(relay, key_id, key_type).await?;
+
+    let (reply_channel, receiver) = oneshot::channel();
+    relay
+        .send(KMSMessage::Sign {
+            key_id,
+            data,
+            reply_channel,
+        })
+        .await
+        .map_err(|(e, _)| e)?;
+    Ok(receiver.await?)
+}
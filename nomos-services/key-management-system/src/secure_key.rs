@@ -1,7 +1,12 @@
 use bytes::Bytes;
 use overwatch::DynError;
+use zeroize::{ZeroizeOnDrop, Zeroizing};
 
-pub trait SecuredKey {
+/// Key material held by a backend. The key is zeroized when dropped.
+pub trait SecuredKey: ZeroizeOnDrop {
     fn sign(&self, data: Bytes) -> Result<Bytes, DynError>;
     fn as_pk(&self) -> Bytes;
+    /// Raw secret key, only handed out for building libp2p identities and
+    /// leader proofs, see [`crate::retrieve_ed25519_key`].
+    fn secret_bytes(&self) -> Zeroizing<Vec<u8>>;
 }
@@ -0,0 +1,409 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    hash::Hash,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::rejected::RejectedItems;
+
+/// Maximum number of items of finalized blocks remembered once their blocks
+/// are forgotten, older ones are forgotten first.
+const MAX_FINALIZED_ITEMS: usize = 4096;
+
+#[derive(Clone, Debug)]
+struct Block<BlockId, Key, Item> {
+    parent: BlockId,
+    // items are missing if they were not in the pool when the block was marked
+    items: Vec<(Key, Option<Item>)>,
+}
+
+/// Items included in blocks, tracked per block so that they can be returned to
+/// the pool when their blocks are no longer on the chain of the tip.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(
+    from = "SerializedInBlockItems<BlockId, Key, Item>",
+    into = "SerializedInBlockItems<BlockId, Key, Item>",
+    bound(
+        serialize = "BlockId: Ord + Copy + Serialize, Key: Hash + Eq + Clone + Serialize, Item: Clone + Serialize",
+        deserialize = "BlockId: Ord + Copy + Deserialize<'de>, Key: Hash + Eq + Clone + Deserialize<'de>, Item: Clone + Deserialize<'de>"
+    )
+)]
+pub struct InBlockItems<BlockId, Key, Item> {
+    blocks: BTreeMap<BlockId, Block<BlockId, Key, Item>>,
+    tip: Option<BlockId>,
+    // the tip and its known ancestors
+    chain: BTreeSet<BlockId>,
+    // items included in the chain, with the first block that included them
+    by_id: HashMap<Key, BlockId>,
+    // items included in recently forgotten ancestors of the finalized block,
+    // so that they are not admitted to the pool again when gossiped again
+    finalized: RejectedItems<Key>,
+}
+
+impl<BlockId, Key, Item> Default for InBlockItems<BlockId, Key, Item> {
+    fn default() -> Self {
+        Self {
+            blocks: BTreeMap::new(),
+            tip: None,
+            chain: BTreeSet::new(),
+            by_id: HashMap::new(),
+            finalized: RejectedItems::with_capacity(MAX_FINALIZED_ITEMS),
+        }
+    }
+}
+
+impl<BlockId, Key, Item> InBlockItems<BlockId, Key, Item>
+where
+    BlockId: Ord + Copy,
+    Key: Hash + Eq + Clone,
+    Item: Clone,
+{
+    /// Number of known blocks.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// Records the items included in `block`, child of `parent`.
+    ///
+    /// Items only count as included once `block` is on the chain of the tip.
+    pub fn insert(
+        &mut self,
+        block: BlockId,
+        parent: BlockId,
+        items: impl IntoIterator<Item = (Key, Option<Item>)>,
+    ) {
+        let entry = self.blocks.entry(block).or_insert_with(|| Block {
+            parent,
+            items: Vec::new(),
+        });
+        let start = entry.items.len();
+        entry.items.extend(items);
+        if self.chain.contains(&block) {
+            for (key, _) in &entry.items[start..] {
+                self.by_id.entry(key.clone()).or_insert(block);
+            }
+        }
+    }
+
+    /// Block of the chain of the tip which included `key`, if any.
+    #[must_use]
+    pub fn block_of(&self, key: &Key) -> Option<BlockId> {
+        self.by_id.get(key).copied()
+    }
+
+    /// Whether `key` is included in the chain of the tip, or in one of the
+    /// most recently forgotten finalized blocks.
+    #[must_use]
+    pub fn contains(&self, key: &Key) -> bool {
+        self.by_id.contains_key(key) || self.finalized.contains(key)
+    }
+
+    /// Items included in `block`, whether it is on the chain or not.
+    pub fn block_items(&self, block: &BlockId) -> Option<impl Iterator<Item = &Item>> {
+        self.blocks
+            .get(block)
+            .map(|block| block.items.iter().filter_map(|(_, item)| item.as_ref()))
+    }
+
+    // Blocks leaving and joining the chain if the tip moved to `tip`, each
+    // from the newest to the oldest one
+    fn reorg(&self, tip: BlockId) -> (Vec<BlockId>, Vec<BlockId>) {
+        let mut joining = Vec::new();
+        let mut fork_point = tip;
+        while let Some(block) = self.blocks.get(&fork_point) {
+            if self.chain.contains(&fork_point) {
+                break;
+            }
+            joining.push(fork_point);
+            fork_point = block.parent;
+        }
+
+        let mut leaving = Vec::new();
+        let mut block = self.tip;
+        while let Some(id) = block.filter(|id| *id != fork_point && self.chain.contains(id)) {
+            leaving.push(id);
+            block = self.blocks.get(&id).map(|block| block.parent);
+        }
+        (leaving, joining)
+    }
+
+    /// Moves the tip of the chain to `tip`.
+    ///
+    /// `take_pending` is called with the items included in blocks joining the
+    /// chain, to remove them from the pool. Returns the items included in the
+    /// blocks leaving the chain, which are not included in the chain anymore.
+    pub fn update_tip(
+        &mut self,
+        tip: BlockId,
+        mut take_pending: impl FnMut(&Key) -> Option<Item>,
+    ) -> Vec<(Key, Item)> {
+        let (leaving, joining) = self.reorg(tip);
+        for id in &leaving {
+            self.chain.remove(id);
+        }
+        self.by_id.retain(|_, block| self.chain.contains(block));
+
+        for id in joining.into_iter().rev() {
+            self.chain.insert(id);
+            let block = self.blocks.get_mut(&id).expect("joining blocks are known");
+            for (key, item) in &mut block.items {
+                if let Some(pending) = take_pending(key) {
+                    item.get_or_insert(pending);
+                }
+                self.by_id.entry(key.clone()).or_insert(id);
+            }
+        }
+        self.tip = Some(tip);
+
+        let mut returned = HashSet::new();
+        leaving
+            .iter()
+            .rev()
+            .flat_map(|id| &self.blocks[id].items)
+            .filter_map(|(key, item)| Some((key, item.as_ref()?)))
+            .filter(|(key, _)| !self.by_id.contains_key(*key) && returned.insert(*key))
+            .map(|(key, item)| (key.clone(), item.clone()))
+            .collect()
+    }
+
+    /// Forgets the blocks which are not `finalized` or one of its descendants,
+    /// as they cannot join or leave the chain anymore.
+    ///
+    /// Items included in the ancestors of `finalized` cannot leave the chain
+    /// anymore. Only a bounded number of the most recent ones is remembered
+    /// once their blocks are forgotten.
+    pub fn prune(&mut self, finalized: BlockId) {
+        let mut descendants = BTreeSet::from([finalized]);
+        let mut forgotten = BTreeSet::new();
+        for &block in self.blocks.keys() {
+            let mut path = Vec::new();
+            let mut id = block;
+            let descends = loop {
+                if descendants.contains(&id) {
+                    break true;
+                }
+                if forgotten.contains(&id) {
+                    break false;
+                }
+                let Some(block) = self.blocks.get(&id) else {
+                    break false;
+                };
+                path.push(id);
+                id = block.parent;
+            };
+            if descends {
+                descendants.extend(path);
+            } else {
+                forgotten.extend(path);
+            }
+        }
+
+        for id in &forgotten {
+            let block = self.blocks.remove(id).expect("forgotten blocks are known");
+            if !self.chain.remove(id) {
+                continue;
+            }
+            for (key, _) in block.items {
+                if self.by_id.get(&key) == Some(id) {
+                    self.by_id.remove(&key);
+                    self.finalized.insert(key);
+                }
+            }
+        }
+    }
+
+    /// Differences between the chain of `ancestor` and the chain of the tip.
+    ///
+    /// Returns the items included in the chain of the tip but not in the chain
+    /// of `ancestor`, and the keys of the items included in the chain of
+    /// `ancestor` but not in the chain of the tip. Unknown blocks are assumed
+    /// to be on the chain of the tip.
+    pub fn diff(&self, ancestor: BlockId) -> (Vec<&Item>, HashSet<&Key>) {
+        if !self.blocks.contains_key(&ancestor) {
+            return (Vec::new(), HashSet::new());
+        }
+        let (leaving, joining) = self.reorg(ancestor);
+        let included: HashSet<&Key> = joining
+            .iter()
+            .flat_map(|id| &self.blocks[id].items)
+            .map(|(key, _)| key)
+            .collect();
+
+        let mut returned = HashSet::new();
+        let items = leaving
+            .iter()
+            .rev()
+            .flat_map(|id| &self.blocks[id].items)
+            .filter_map(|(key, item)| Some((key, item.as_ref()?)))
+            .filter(|(key, _)| !included.contains(key) && returned.insert(*key))
+            .map(|(_, item)| item)
+            .collect();
+        (items, included)
+    }
+}
+
+// (id, parent, items)
+type SerializedBlock<BlockId, Key, Item> = (BlockId, BlockId, Vec<(Key, Option<Item>)>);
+
+// Only blocks and the tip are serialized, the chain is rebuilt from them.
+// Items of forgotten finalized blocks are not part of the recovery state.
+#[derive(Serialize, Deserialize)]
+struct SerializedInBlockItems<BlockId, Key, Item> {
+    blocks: Vec<SerializedBlock<BlockId, Key, Item>>,
+    tip: Option<BlockId>,
+}
+
+impl<BlockId, Key, Item> From<SerializedInBlockItems<BlockId, Key, Item>>
+    for InBlockItems<BlockId, Key, Item>
+where
+    BlockId: Ord + Copy,
+    Key: Hash + Eq + Clone,
+    Item: Clone,
+{
+    fn from(
+        SerializedInBlockItems { blocks, tip }: SerializedInBlockItems<BlockId, Key, Item>,
+    ) -> Self {
+        let mut in_block_items = Self {
+            blocks: blocks
+                .into_iter()
+                .map(|(id, parent, items)| (id, Block { parent, items }))
+                .collect(),
+            ..Self::default()
+        };
+        if let Some(tip) = tip {
+            in_block_items.update_tip(tip, |_| None);
+        }
+        in_block_items
+    }
+}
+
+impl<BlockId, Key, Item> From<InBlockItems<BlockId, Key, Item>>
+    for SerializedInBlockItems<BlockId, Key, Item>
+{
+    fn from(InBlockItems { blocks, tip, .. }: InBlockItems<BlockId, Key, Item>) -> Self {
+        Self {
+            blocks: blocks
+                .into_iter()
+                .map(|(id, Block { parent, items })| (id, parent, items))
+                .collect(),
+            tip,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nomos_core::wire;
+
+    use super::*;
+
+    type Items = InBlockItems<u8, u8, u16>;
+
+    fn items(keys: &[u8]) -> Vec<(u8, Option<u16>)> {
+        keys.iter()
+            .map(|key| (*key, Some(u16::from(*key))))
+            .collect()
+    }
+
+    // 0 - 1 - 2 - 3
+    //      \
+    //       4 - 5
+    fn fork() -> Items {
+        let mut in_block_items = Items::default();
+        in_block_items.insert(1, 0, items(&[1]));
+        in_block_items.insert(2, 1, items(&[2]));
+        in_block_items.insert(3, 2, items(&[3]));
+        in_block_items.insert(4, 1, items(&[2, 4]));
+        in_block_items.insert(5, 4, items(&[5]));
+        in_block_items
+    }
+
+    #[test]
+    fn test_mark_in_block() {
+        let mut in_block_items = fork();
+        // items are only included once their block is on the chain
+        assert!(!in_block_items.contains(&1));
+        let mut taken = Vec::new();
+        let returned = in_block_items.update_tip(3, |key| {
+            taken.push(*key);
+            None
+        });
+        assert!(returned.is_empty());
+        assert_eq!(taken, vec![1, 2, 3]);
+        assert_eq!(in_block_items.block_of(&2), Some(2));
+        assert!(!in_block_items.contains(&4));
+
+        // blocks marked on the chain include their items right away
+        in_block_items.insert(6, 3, items(&[6]));
+        assert!(!in_block_items.contains(&6));
+        in_block_items.update_tip(6, |_| None);
+        in_block_items.insert(6, 3, items(&[7]));
+        assert_eq!(in_block_items.block_of(&7), Some(6));
+    }
+
+    #[test]
+    fn test_reorg() {
+        let mut in_block_items = fork();
+        in_block_items.update_tip(3, |_| None);
+
+        let (returned, included) = in_block_items.diff(5);
+        assert_eq!(returned, vec![&3]);
+        assert_eq!(included, HashSet::from([&2, &4, &5]));
+
+        let mut returned = in_block_items.update_tip(5, |_| None);
+        returned.sort_unstable();
+        assert_eq!(returned, vec![(3, 3)]);
+        assert_eq!(in_block_items.block_of(&2), Some(4));
+        assert!(!in_block_items.contains(&3));
+    }
+
+    #[test]
+    fn test_prune() {
+        let mut in_block_items = fork();
+        in_block_items.update_tip(3, |_| None);
+        in_block_items.prune(2);
+
+        // the fork and the ancestors of the finalized block are forgotten
+        assert_eq!(in_block_items.len(), 2);
+        assert!(in_block_items.block_items(&1).is_none());
+        assert!(in_block_items.block_items(&4).is_none());
+        // items of the finalized chain are still included, not those of the fork
+        assert!(in_block_items.contains(&1));
+        assert_eq!(in_block_items.block_of(&1), None);
+        assert_eq!(in_block_items.block_of(&2), Some(2));
+        assert!(!in_block_items.contains(&5));
+        assert_eq!(in_block_items.diff(5), (Vec::new(), HashSet::new()));
+
+        // items included in the forgotten ancestors never return to the pool
+        in_block_items.insert(6, 2, items(&[6]));
+        let returned = in_block_items.update_tip(6, |_| None);
+        assert_eq!(returned, vec![(3, 3)]);
+        assert!(in_block_items.contains(&1));
+    }
+
+    #[test]
+    fn test_recover() {
+        let mut in_block_items = fork();
+        in_block_items.update_tip(3, |_| None);
+        in_block_items.prune(2);
+
+        let mut recovered: Items =
+            wire::deserialize(&wire::serialize(&in_block_items).unwrap()).unwrap();
+        assert_eq!(recovered.len(), in_block_items.len());
+        for key in 1..=5 {
+            assert_eq!(recovered.block_of(&key), in_block_items.block_of(&key));
+        }
+        // the chain of the tip is rebuilt
+        recovered.insert(6, 2, items(&[6]));
+        let returned = recovered.update_tip(6, |_| None);
+        assert_eq!(returned, vec![(3, 3)]);
+        assert_eq!(recovered.block_of(&2), Some(2));
+    }
+}
@@ -1,5 +1,4 @@
 use std::{
-    collections::BTreeMap,
     convert::Infallible,
     fmt::Debug,
     hash::Hash,
@@ -12,7 +11,9 @@ use overwatch::services::state::ServiceState;
 
 use super::Status;
 use crate::{
-    backend::{MemPool, MempoolError, RecoverableMempool},
+    backend::{
+        in_block::InBlockItems, rejected::RejectedItems, MemPool, MempoolError, RecoverableMempool,
+    },
     tx::settings::TxMempoolSettings,
 };
 
@@ -31,10 +32,13 @@ pub struct MockPool<BlockId, Item, Key> {
         )
     )]
     pending_items: LinkedHashMap<Key, Item>,
-    #[serde(bound(deserialize = "BlockId: Ord + Deserialize<'de>"))]
-    in_block_items: BTreeMap<BlockId, Vec<Item>>,
-    #[serde(bound(deserialize = "Key: Ord + Deserialize<'de>"))]
-    in_block_items_by_id: BTreeMap<Key, BlockId>,
+    #[serde(bound(
+        serialize = "BlockId: Ord + Copy + Serialize, Key: Hash + Eq + Clone + Serialize, Item: Clone + Serialize",
+        deserialize = "BlockId: Ord + Copy + Deserialize<'de>, Key: Hash + Eq + Clone + Deserialize<'de>, Item: Clone + Deserialize<'de>"
+    ))]
+    in_block_items: InBlockItems<BlockId, Key, Item>,
+    #[serde(skip)]
+    rejected_items: RejectedItems<Key>,
     last_item_timestamp: u64,
 }
 
@@ -45,7 +49,7 @@ impl<BlockId, Item, Key> MockPool<BlockId, Item, Key> {
     }
 
     #[must_use]
-    pub const fn in_block_items(&self) -> &BTreeMap<BlockId, Vec<Item>> {
+    pub const fn in_block_items(&self) -> &InBlockItems<BlockId, Key, Item> {
         &self.in_block_items
     }
 
@@ -65,7 +69,7 @@ where
         f.debug_struct("MockPool")
             .field("pending_items", &self.pending_items)
             .field("in_block_items", &self.in_block_items)
-            .field("in_block_items_by_id", &self.in_block_items_by_id)
+            .field("rejected_items", &self.rejected_items)
             .field("last_item_timestamp", &self.last_item_timestamp)
             .finish()
     }
@@ -78,8 +82,8 @@ where
     fn default() -> Self {
         Self {
             pending_items: LinkedHashMap::new(),
-            in_block_items: BTreeMap::new(),
-            in_block_items_by_id: BTreeMap::new(),
+            in_block_items: InBlockItems::default(),
+            rejected_items: RejectedItems::default(),
             last_item_timestamp: 0,
         }
     }
@@ -95,7 +99,7 @@ where
         Self {
             pending_items: self.pending_items.clone(),
             in_block_items: self.in_block_items.clone(),
-            in_block_items_by_id: self.in_block_items_by_id.clone(),
+            rejected_items: self.rejected_items.clone(),
             last_item_timestamp: self.last_item_timestamp,
         }
     }
@@ -131,9 +135,11 @@ where
         key: Self::Key,
         item: I,
     ) -> Result<(), MempoolError> {
-        if self.pending_items.contains_key(&key) || self.in_block_items_by_id.contains_key(&key) {
+        if self.pending_items.contains_key(&key) || self.in_block_items.contains(&key) {
             return Err(MempoolError::ExistingItem);
         }
+        // rejected items may become valid again, e.g. after a reorg
+        self.rejected_items.remove(&key);
         self.pending_items.insert(key, item.into());
         self.last_item_timestamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -145,32 +151,61 @@ where
         Ok(())
     }
 
-    fn view(&self, _ancestor_hint: BlockId) -> Box<dyn Iterator<Item = Self::Item> + Send> {
+    fn view(&self, ancestor_hint: BlockId) -> Box<dyn Iterator<Item = Self::Item> + Send> {
+        let (returned, included) = self.in_block_items.diff(ancestor_hint);
         #[expect(
             clippy::needless_collect,
             reason = "We need to have an owned version of the iterator to bypass adding a lifetime bound to the return iterator type"
         )]
-        let pending_items: Vec<Item> = self.pending_items.values().cloned().collect();
+        let pending_items: Vec<Item> = self
+            .pending_items
+            .iter()
+            .filter(|(key, _)| !included.contains(key))
+            .map(|(_, item)| item)
+            .chain(returned)
+            .cloned()
+            .collect();
         Box::new(pending_items.into_iter())
     }
 
-    fn mark_in_block(&mut self, keys: &[Self::Key], block: BlockId) {
-        let mut items_in_block = Vec::with_capacity(keys.len());
+    fn mark_in_block(&mut self, keys: &[Self::Key], block: BlockId, parent: BlockId) {
+        let items: Vec<_> = keys
+            .iter()
+            .map(|key| (key.clone(), self.pending_items.get(key).cloned()))
+            .collect();
+        self.in_block_items.insert(block, parent, items);
+        // the block may already be on the chain of the tip
         for key in keys {
-            if let Some(item) = self.pending_items.remove(key) {
-                items_in_block.push(item);
+            if self.in_block_items.contains(key) {
+                self.pending_items.remove(key);
             }
         }
-        let block_entry = self.in_block_items.entry(block).or_default();
-        self.in_block_items_by_id
-            .extend(keys.iter().cloned().map(|key| (key, block)));
-        block_entry.append(&mut items_in_block);
+    }
+
+    fn update_tip(&mut self, tip: BlockId) {
+        let returned = self
+            .in_block_items
+            .update_tip(tip, |key| self.pending_items.remove(key));
+        for (key, item) in returned {
+            if !self.pending_items.contains_key(&key) {
+                self.pending_items.insert(key, item);
+            }
+        }
+    }
+
+    fn mark_finalized(&mut self, block: BlockId) {
+        self.in_block_items.prune(block);
     }
 
     #[cfg(test)]
     fn block_items(&self, block: BlockId) -> Option<Box<dyn Iterator<Item = Self::Item> + Send>> {
-        self.in_block_items.get(&block).map(|items| {
-            Box::new(items.clone().into_iter()) as Box<dyn Iterator<Item = Self::Item> + Send>
+        self.in_block_items.block_items(&block).map(|items| {
+            #[expect(
+                clippy::needless_collect,
+                reason = "We need to have an owned version of the iterator to bypass adding a lifetime bound to the return iterator type"
+            )]
+            let items: Vec<_> = items.cloned().collect();
+            Box::new(items.into_iter()) as Box<dyn Iterator<Item = Self::Item> + Send>
         })
     }
 
@@ -180,6 +215,13 @@ where
         }
     }
 
+    fn reject(&mut self, keys: &[Self::Key]) {
+        for key in keys {
+            self.pending_items.remove(key);
+            self.rejected_items.insert(key.clone());
+        }
+    }
+
     fn pending_item_count(&self) -> usize {
         self.pending_items.len()
     }
@@ -194,8 +236,10 @@ where
             .map(|key| {
                 if self.pending_items.contains_key(key) {
                     Status::Pending
-                } else if let Some(block) = self.in_block_items_by_id.get(key) {
-                    Status::InBlock { block: *block }
+                } else if let Some(block) = self.in_block_items.block_of(key) {
+                    Status::InBlock { block }
+                } else if self.rejected_items.contains(key) {
+                    Status::Rejected
                 } else {
                     Status::Unknown
                 }
@@ -1,5 +1,8 @@
+pub mod in_block;
 #[cfg(feature = "mock")]
 pub mod mockpool;
+pub mod priority;
+pub mod rejected;
 
 use serde::{Deserialize, Serialize};
 
@@ -7,6 +10,10 @@ use serde::{Deserialize, Serialize};
 pub enum MempoolError {
     #[error("Item already in mempool")]
     ExistingItem,
+    #[error("Mempool is full")]
+    PoolFull,
+    #[error("Invalid item: {0}")]
+    InvalidItem(overwatch::DynError),
     #[error(transparent)]
     DynamicPoolError(#[from] overwatch::DynError),
 }
@@ -30,14 +37,24 @@ pub trait MemPool {
 
     /// Return a view over items contained in the mempool.
     /// Implementations should provide *at least* all the items which have not
-    /// been marked as in a block.
+    /// been included in the chain of the tip.
     /// The hint on the ancestor *can* be used by the implementation to display
     /// additional items that were not included up to that point if
     /// available.
     fn view(&self, ancestor_hint: Self::BlockId) -> Box<dyn Iterator<Item = Self::Item> + Send>;
 
-    /// Record that a set of items were included in a block
-    fn mark_in_block(&mut self, items: &[Self::Key], block: Self::BlockId);
+    /// Record that a set of items were included in a block, child of `parent`.
+    /// Items are no longer pending once the block is on the chain of the tip.
+    fn mark_in_block(&mut self, items: &[Self::Key], block: Self::BlockId, parent: Self::BlockId);
+
+    /// Record that the tip of the chain moved to `tip`.
+    /// Items included in blocks that are not on the chain of `tip` anymore
+    /// return to pending.
+    fn update_tip(&mut self, tip: Self::BlockId);
+
+    /// Record that `block` was finalized. Blocks which are not descendants of
+    /// it can be forgotten, along with the items included in its ancestors.
+    fn mark_finalized(&mut self, block: Self::BlockId);
 
     /// Returns all of the transactions for the block
     #[cfg(test)]
@@ -50,6 +67,11 @@ pub trait MemPool {
     /// and can be discarded.
     fn prune(&mut self, items: &[Self::Key]);
 
+    /// Discard a set of items because they are not valid, e.g. because they
+    /// conflict with the current tip of the chain.
+    /// Their status is reported as `Rejected` afterwards.
+    fn reject(&mut self, items: &[Self::Key]);
+
     fn pending_item_count(&self) -> usize;
     fn last_item_timestamp(&self) -> u64;
 
@@ -0,0 +1,487 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt::Debug,
+    hash::Hash,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::Status;
+use crate::backend::{
+    in_block::InBlockItems, rejected::RejectedItems, MemPool, MempoolError, RecoverableMempool,
+};
+
+/// Settings for the [`PriorityPool`].
+#[derive(Clone, Debug)]
+pub struct PriorityPoolSettings<Item> {
+    /// Maximum number of pending items, the ones with the lowest weight are
+    /// evicted to make room for new ones.
+    pub max_pending_items: usize,
+    /// Pending items are discarded after this time.
+    pub item_ttl: Duration,
+    /// Weight of an item, items with a higher weight are preferred.
+    pub weight: fn(&Item) -> u64,
+}
+
+/// Weight preferring smaller items, for items without fees.
+#[must_use]
+pub fn size_weight(size: usize) -> u64 {
+    u64::MAX / u64::try_from(size.max(1)).unwrap_or(u64::MAX)
+}
+
+#[derive(Clone, Debug)]
+struct PendingItem<Item> {
+    item: Item,
+    weight: u64,
+    // insertion order, breaks ties between items with the same weight
+    seq: u64,
+    timestamp: u64,
+}
+
+/// A mempool that keeps a bounded number of pending items, ordered by weight.
+///
+/// When the pool is full, a new item evicts the pending item with the lowest
+/// weight if it has a higher one. Pending items expire after a configurable
+/// time.
+pub struct PriorityPool<BlockId, Item, Key> {
+    settings: PriorityPoolSettings<Item>,
+    pending_items: HashMap<Key, PendingItem<Item>>,
+    // pending keys, from the highest to the lowest weight, older items first
+    by_priority: BTreeSet<(Reverse<u64>, u64, Key)>,
+    // pending keys by insertion order, which is also the expiration order
+    by_age: BTreeMap<u64, Key>,
+    next_seq: u64,
+    in_block_items: InBlockItems<BlockId, Key, Item>,
+    rejected_items: RejectedItems<Key>,
+    last_item_timestamp: u64,
+}
+
+impl<BlockId, Item, Key> PriorityPool<BlockId, Item, Key>
+where
+    Key: Hash + Eq + Ord + Clone,
+{
+    fn insert_pending(&mut self, key: Key, item: Item, timestamp: u64) {
+        let weight = (self.settings.weight)(&item);
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.by_priority.insert((Reverse(weight), seq, key.clone()));
+        self.by_age.insert(seq, key.clone());
+        self.pending_items.insert(
+            key,
+            PendingItem {
+                item,
+                weight,
+                seq,
+                timestamp,
+            },
+        );
+    }
+
+    fn remove_pending(&mut self, key: &Key) -> Option<Item> {
+        let PendingItem {
+            item, weight, seq, ..
+        } = self.pending_items.remove(key)?;
+        self.by_priority
+            .remove(&(Reverse(weight), seq, key.clone()));
+        self.by_age.remove(&seq);
+        Some(item)
+    }
+
+    fn is_expired(&self, timestamp: u64, now: u64) -> bool {
+        u128::from(now.saturating_sub(timestamp)) >= self.settings.item_ttl.as_millis()
+    }
+
+    fn remove_expired(&mut self, now: u64) {
+        while let Some((_, key)) = self.by_age.first_key_value() {
+            let timestamp = self.pending_items[key].timestamp;
+            if !self.is_expired(timestamp, now) {
+                break;
+            }
+            let key = key.clone();
+            self.remove_pending(&key);
+        }
+    }
+
+    // Makes room for an item of the given weight, returns whether it fits
+    fn make_room(&mut self, weight: u64) -> bool {
+        if self.pending_items.len() < self.settings.max_pending_items {
+            return true;
+        }
+        match self.by_priority.last() {
+            Some((Reverse(lowest), _, key)) if *lowest < weight => {
+                let key = key.clone();
+                self.remove_pending(&key);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis()
+        .try_into()
+        .expect("Failed to convert timestamp to u64.")
+}
+
+impl<BlockId, Item, Key> MemPool for PriorityPool<BlockId, Item, Key>
+where
+    Key: Hash + Eq + Ord + Clone + Send,
+    Item: Clone + Send + 'static,
+    BlockId: Ord + Copy,
+{
+    type Settings = PriorityPoolSettings<Item>;
+    type Item = Item;
+    type Key = Key;
+    type BlockId = BlockId;
+
+    fn new(settings: Self::Settings) -> Self {
+        Self {
+            settings,
+            pending_items: HashMap::new(),
+            by_priority: BTreeSet::new(),
+            by_age: BTreeMap::new(),
+            next_seq: 0,
+            in_block_items: InBlockItems::default(),
+            rejected_items: RejectedItems::default(),
+            last_item_timestamp: 0,
+        }
+    }
+
+    fn add_item<I: Into<Self::Item>>(
+        &mut self,
+        key: Self::Key,
+        item: I,
+    ) -> Result<(), MempoolError> {
+        if self.pending_items.contains_key(&key) || self.in_block_items.contains(&key) {
+            return Err(MempoolError::ExistingItem);
+        }
+        let now = now_millis();
+        self.remove_expired(now);
+
+        let item = item.into();
+        if !self.make_room((self.settings.weight)(&item)) {
+            return Err(MempoolError::PoolFull);
+        }
+        // rejected items may become valid again, e.g. after a reorg
+        self.rejected_items.remove(&key);
+        self.insert_pending(key, item, now);
+        self.last_item_timestamp = now;
+        Ok(())
+    }
+
+    fn view(&self, ancestor_hint: BlockId) -> Box<dyn Iterator<Item = Self::Item> + Send> {
+        let now = now_millis();
+        let (returned, included) = self.in_block_items.diff(ancestor_hint);
+        let mut items: Vec<(u64, Item)> = self
+            .by_priority
+            .iter()
+            .filter(|(_, _, key)| !included.contains(key))
+            .map(|(_, _, key)| &self.pending_items[key])
+            .filter(|pending| !self.is_expired(pending.timestamp, now))
+            .map(|pending| (pending.weight, pending.item.clone()))
+            .collect();
+        if !returned.is_empty() {
+            items.extend(
+                returned
+                    .into_iter()
+                    .map(|item| ((self.settings.weight)(item), item.clone())),
+            );
+            items.sort_by_key(|(weight, _)| Reverse(*weight));
+        }
+        Box::new(items.into_iter().map(|(_, item)| item))
+    }
+
+    fn mark_in_block(&mut self, keys: &[Self::Key], block: BlockId, parent: BlockId) {
+        let items: Vec<_> = keys
+            .iter()
+            .map(|key| {
+                let item = self
+                    .pending_items
+                    .get(key)
+                    .map(|pending| pending.item.clone());
+                (key.clone(), item)
+            })
+            .collect();
+        self.in_block_items.insert(block, parent, items);
+        // the block may already be on the chain of the tip
+        for key in keys {
+            if self.in_block_items.contains(key) {
+                self.remove_pending(key);
+            }
+        }
+    }
+
+    fn update_tip(&mut self, tip: BlockId) {
+        let now = now_millis();
+        self.remove_expired(now);
+        let mut in_block_items = std::mem::take(&mut self.in_block_items);
+        let returned = in_block_items.update_tip(tip, |key| self.remove_pending(key));
+        self.in_block_items = in_block_items;
+
+        for (key, item) in returned {
+            if !self.pending_items.contains_key(&key)
+                && self.make_room((self.settings.weight)(&item))
+            {
+                self.insert_pending(key, item, now);
+            }
+        }
+    }
+
+    fn mark_finalized(&mut self, block: BlockId) {
+        self.in_block_items.prune(block);
+    }
+
+    #[cfg(test)]
+    fn block_items(&self, block: BlockId) -> Option<Box<dyn Iterator<Item = Self::Item> + Send>> {
+        self.in_block_items.block_items(&block).map(|items| {
+            #[expect(
+                clippy::needless_collect,
+                reason = "We need to have an owned version of the iterator to bypass adding a lifetime bound to the return iterator type"
+            )]
+            let items: Vec<_> = items.cloned().collect();
+            Box::new(items.into_iter()) as Box<dyn Iterator<Item = Self::Item> + Send>
+        })
+    }
+
+    fn prune(&mut self, keys: &[Self::Key]) {
+        for key in keys {
+            self.remove_pending(key);
+        }
+    }
+
+    fn reject(&mut self, keys: &[Self::Key]) {
+        for key in keys {
+            self.remove_pending(key);
+            self.rejected_items.insert(key.clone());
+        }
+    }
+
+    fn pending_item_count(&self) -> usize {
+        let now = now_millis();
+        let expired = self
+            .by_age
+            .values()
+            .take_while(|key| self.is_expired(self.pending_items[*key].timestamp, now))
+            .count();
+        self.pending_items.len() - expired
+    }
+
+    fn last_item_timestamp(&self) -> u64 {
+        self.last_item_timestamp
+    }
+
+    fn status(&self, items: &[Self::Key]) -> Vec<Status<BlockId>> {
+        items
+            .iter()
+            .map(|key| {
+                if self.pending_items.contains_key(key) {
+                    Status::Pending
+                } else if let Some(block) = self.in_block_items.block_of(key) {
+                    Status::InBlock { block }
+                } else if self.rejected_items.contains(key) {
+                    Status::Rejected
+                } else {
+                    Status::Unknown
+                }
+            })
+            .collect()
+    }
+}
+
+/// Recovery state of a [`PriorityPool`].
+///
+/// Weights are not saved, they are computed again from the settings of the
+/// recovered pool.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PriorityPoolState<BlockId, Item, Key> {
+    // (key, item, timestamp), by insertion order
+    pending_items: Vec<(Key, Item, u64)>,
+    #[serde(bound(
+        serialize = "BlockId: Ord + Copy + Serialize, Key: Hash + Eq + Clone + Serialize, Item: Clone + Serialize",
+        deserialize = "BlockId: Ord + Copy + Deserialize<'de>, Key: Hash + Eq + Clone + Deserialize<'de>, Item: Clone + Deserialize<'de>"
+    ))]
+    in_block_items: InBlockItems<BlockId, Key, Item>,
+    last_item_timestamp: u64,
+}
+
+impl<BlockId, Item, Key> RecoverableMempool for PriorityPool<BlockId, Item, Key>
+where
+    Key: Hash + Eq + Ord + Clone + Send,
+    Item: Clone + Send + 'static,
+    BlockId: Ord + Copy,
+{
+    type RecoveryState = PriorityPoolState<BlockId, Item, Key>;
+
+    fn recover(settings: Self::Settings, state: Self::RecoveryState) -> Self {
+        let mut pool = <Self as MemPool>::new(settings);
+        for (key, item, timestamp) in state.pending_items {
+            pool.insert_pending(key, item, timestamp);
+        }
+        pool.remove_expired(now_millis());
+        // the cap may have been lowered since the state was saved
+        while pool.pending_items.len() > pool.settings.max_pending_items {
+            let Some((_, _, key)) = pool.by_priority.last() else {
+                break;
+            };
+            let key = key.clone();
+            pool.remove_pending(&key);
+        }
+        pool.in_block_items = state.in_block_items;
+        pool.last_item_timestamp = state.last_item_timestamp;
+        pool
+    }
+
+    fn save(&self) -> Self::RecoveryState {
+        PriorityPoolState {
+            pending_items: self
+                .by_age
+                .values()
+                .map(|key| {
+                    let pending = &self.pending_items[key];
+                    (key.clone(), pending.item.clone(), pending.timestamp)
+                })
+                .collect(),
+            in_block_items: self.in_block_items.clone(),
+            last_item_timestamp: self.last_item_timestamp,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Pool = PriorityPool<u8, u64, u64>;
+
+    fn settings(max_pending_items: usize, item_ttl: Duration) -> PriorityPoolSettings<u64> {
+        PriorityPoolSettings {
+            max_pending_items,
+            item_ttl,
+            // items are their own weight
+            weight: |item| *item,
+        }
+    }
+
+    fn pool(max_pending_items: usize) -> Pool {
+        <Pool as MemPool>::new(settings(max_pending_items, Duration::from_secs(10)))
+    }
+
+    #[test]
+    fn test_view_by_priority() {
+        let mut pool = pool(10);
+        for (key, weight) in [(1, 5u64), (2, 10), (3, 1), (4, 10)] {
+            pool.add_item(key, weight).unwrap();
+        }
+        // same weights are ordered by arrival
+        assert_eq!(pool.view(0).collect::<Vec<_>>(), vec![10, 10, 5, 1]);
+        assert!(matches!(
+            pool.add_item(1, 5u64),
+            Err(MempoolError::ExistingItem)
+        ));
+
+        pool.mark_in_block(&[2], 1, 0);
+        pool.update_tip(1);
+        assert_eq!(pool.view(1).collect::<Vec<_>>(), vec![10, 5, 1]);
+        assert_eq!(
+            pool.status(&[2, 3]),
+            vec![Status::InBlock { block: 1 }, Status::Pending]
+        );
+    }
+
+    #[test]
+    fn test_evict_lowest_weight_when_full() {
+        let mut pool = pool(2);
+        pool.add_item(1, 5u64).unwrap();
+        pool.add_item(2, 3u64).unwrap();
+
+        // not better than the items in the pool
+        assert!(matches!(
+            pool.add_item(3, 3u64),
+            Err(MempoolError::PoolFull)
+        ));
+        pool.add_item(4, 4u64).unwrap();
+        assert_eq!(pool.view(0).collect::<Vec<_>>(), vec![5, 4]);
+        assert_eq!(pool.status(&[2]), vec![Status::Unknown]);
+        assert_eq!(pool.pending_item_count(), 2);
+    }
+
+    #[test]
+    fn test_items_expire() {
+        let mut pool = <Pool as MemPool>::new(settings(2, Duration::ZERO));
+        pool.add_item(1, 5u64).unwrap();
+        assert_eq!(pool.view(0).count(), 0);
+        assert_eq!(pool.pending_item_count(), 0);
+
+        // expired items do not take space
+        pool.add_item(2, 1u64).unwrap();
+        pool.add_item(3, 1u64).unwrap();
+        assert_eq!(pool.pending_items.len(), 1);
+    }
+
+    #[test]
+    fn test_recover() {
+        let mut pool = pool(10);
+        for (key, weight) in [(1, 5u64), (2, 10), (3, 1)] {
+            pool.add_item(key, weight).unwrap();
+        }
+        pool.mark_in_block(&[3], 7, 0);
+        pool.update_tip(7);
+        pool.reject(&[1]);
+
+        let recovered = Pool::recover(settings(10, Duration::from_secs(10)), pool.save());
+        assert_eq!(recovered.view(7).collect::<Vec<_>>(), vec![10]);
+        // rejected items are not recovered
+        assert_eq!(
+            recovered.status(&[1, 2, 3]),
+            vec![
+                Status::Unknown,
+                Status::Pending,
+                Status::InBlock { block: 7 }
+            ]
+        );
+        assert_eq!(recovered.last_item_timestamp(), pool.last_item_timestamp());
+
+        // a smaller cap evicts the lowest weights
+        pool.add_item(4, 2u64).unwrap();
+        let recovered = Pool::recover(settings(1, Duration::from_secs(10)), pool.save());
+        assert_eq!(recovered.view(7).collect::<Vec<_>>(), vec![10]);
+    }
+
+    #[test]
+    fn test_reorg_returns_items_to_pending() {
+        let mut pool = pool(10);
+        for (key, weight) in [(1, 5u64), (2, 10), (3, 1)] {
+            pool.add_item(key, weight).unwrap();
+        }
+        // blocks 1 <- 2 and 3 are all descendants of block 0
+        pool.mark_in_block(&[1], 1, 0);
+        pool.mark_in_block(&[2], 2, 1);
+        pool.update_tip(2);
+        assert_eq!(pool.view(2).collect::<Vec<_>>(), vec![1]);
+        // items of block 2 are not included in the chain of block 1
+        assert_eq!(pool.view(1).collect::<Vec<_>>(), vec![10, 1]);
+
+        pool.mark_in_block(&[3], 3, 0);
+        // block 3 is not on the chain of the tip yet
+        assert_eq!(pool.status(&[3]), vec![Status::Pending]);
+        assert_eq!(pool.view(3).collect::<Vec<_>>(), vec![10, 5]);
+
+        pool.update_tip(3);
+        assert_eq!(pool.view(3).collect::<Vec<_>>(), vec![10, 5]);
+        assert_eq!(pool.pending_item_count(), 2);
+        assert_eq!(
+            pool.status(&[1, 2, 3]),
+            vec![
+                Status::Pending,
+                Status::Pending,
+                Status::InBlock { block: 3 }
+            ]
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    hash::Hash,
+};
+
+/// Maximum number of rejected items remembered, older ones are forgotten.
+const MAX_REJECTED_ITEMS: usize = 4096;
+
+/// Keys of the most recently rejected items, so that their status can be
+/// reported.
+///
+/// Anyone can make the pool reject items, so only a bounded number of them is
+/// remembered and they are not part of the recovery state.
+#[derive(Clone, Debug)]
+pub struct RejectedItems<Key> {
+    keys: HashSet<Key>,
+    // keys by rejection order, the oldest ones are forgotten first
+    order: VecDeque<Key>,
+    capacity: usize,
+}
+
+impl<Key> Default for RejectedItems<Key> {
+    fn default() -> Self {
+        Self::with_capacity(MAX_REJECTED_ITEMS)
+    }
+}
+
+impl<Key> RejectedItems<Key> {
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            keys: HashSet::new(),
+            order: VecDeque::new(),
+            capacity,
+        }
+    }
+}
+
+impl<Key: Hash + Eq + Clone> RejectedItems<Key> {
+    pub fn insert(&mut self, key: Key) {
+        if !self.keys.insert(key.clone()) {
+            return;
+        }
+        self.order.push_back(key);
+        while self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.keys.remove(&oldest);
+            }
+        }
+    }
+
+    pub fn remove(&mut self, key: &Key) {
+        if self.keys.remove(key) {
+            self.order.retain(|rejected| rejected != key);
+        }
+    }
+
+    #[must_use]
+    pub fn contains(&self, key: &Key) -> bool {
+        self.keys.contains(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forgets_oldest_items() {
+        let mut rejected = RejectedItems::with_capacity(2);
+        rejected.insert(1);
+        rejected.insert(2);
+        rejected.insert(1);
+        rejected.insert(3);
+        assert!(!rejected.contains(&1));
+        assert!(rejected.contains(&2));
+        assert!(rejected.contains(&3));
+
+        rejected.remove(&2);
+        rejected.insert(4);
+        assert!(rejected.contains(&3));
+        assert!(rejected.contains(&4));
+    }
+}
@@ -427,8 +427,14 @@ where
                     .send(self.pool.view(ancestor_hint))
                     .unwrap_or_else(|_| tracing::debug!("could not send back pool view"));
             }
-            MempoolMsg::MarkInBlock { ids, block } => {
-                self.pool.mark_in_block(&ids, block);
+            MempoolMsg::MarkInBlock { ids, block, parent } => {
+                self.pool.mark_in_block(&ids, block, parent);
+            }
+            MempoolMsg::MarkFinalized { block } => {
+                self.pool.mark_finalized(block);
+                self.service_state_handle
+                    .state_updater
+                    .update(self.pool.save().into());
             }
             #[cfg(test)]
             MempoolMsg::BlockItems {
@@ -459,6 +465,13 @@ where
                     .send(self.pool.status(&items))
                     .unwrap_or_else(|_| tracing::debug!("could not send back mempool status"));
             }
+            // blob certificates do not depend on the ledger state
+            MempoolMsg::UpdateTip { block, .. } => {
+                self.pool.update_tip(block);
+                self.service_state_handle
+                    .state_updater
+                    .update(self.pool.save().into());
+            }
         }
     }
 }
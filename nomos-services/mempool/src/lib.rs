@@ -8,6 +8,7 @@ use std::fmt::{Debug, Error, Formatter};
 
 use backend::{MempoolError, Status};
 pub use da::{service::DaMempoolService, settings::DaMempoolSettings};
+use nomos_ledger::LedgerState;
 use tokio::sync::oneshot::Sender;
 pub use tx::{service::TxMempoolService, settings::TxMempoolSettings};
 
@@ -32,6 +33,12 @@ pub enum MempoolMsg<BlockId, Payload, Item, Key> {
     MarkInBlock {
         ids: Vec<Key>,
         block: BlockId,
+        parent: BlockId,
+    },
+    /// Signal that `block` was finalized, so that the blocks which cannot be
+    /// part of the chain anymore are forgotten.
+    MarkFinalized {
+        block: BlockId,
     },
     Metrics {
         reply_channel: Sender<MempoolMetrics>,
@@ -40,6 +47,14 @@ pub enum MempoolMsg<BlockId, Payload, Item, Key> {
         items: Vec<Key>,
         reply_channel: Sender<Vec<Status<BlockId>>>,
     },
+    /// Signal that the tip of the chain moved to `block`, whose ledger state,
+    /// with the transactions of the chain applied, items are verified against
+    /// from now on. Items are rejected until the first tip is known.
+    /// Items included in blocks that are not on its chain return to pending
+    UpdateTip {
+        block: BlockId,
+        state: LedgerState,
+    },
 }
 
 impl<BlockId, Payload, Item, Key> Debug for MempoolMsg<BlockId, Payload, Item, Key>
@@ -56,18 +71,24 @@ where
             }
             Self::Add { payload, .. } => write!(f, "MempoolMsg::Add{{payload: {payload:?}}}"),
             Self::Prune { ids } => write!(f, "MempoolMsg::Prune{{ids: {ids:?}}}"),
-            Self::MarkInBlock { ids, block } => {
+            Self::MarkInBlock { ids, block, parent } => {
                 write!(
                     f,
-                    "MempoolMsg::MarkInBlock{{ids: {ids:?}, block: {block:?}}}"
+                    "MempoolMsg::MarkInBlock{{ids: {ids:?}, block: {block:?}, parent: {parent:?}}}"
                 )
             }
+            Self::MarkFinalized { block } => {
+                write!(f, "MempoolMsg::MarkFinalized{{block: {block:?}}}")
+            }
             #[cfg(test)]
             Self::BlockItems { block, .. } => {
                 write!(f, "MempoolMsg::BlockItem{{block: {block:?}}}")
             }
             Self::Metrics { .. } => write!(f, "MempoolMsg::Metrics"),
             Self::Status { items, .. } => write!(f, "MempoolMsg::Status{{items: {items:?}}}"),
+            Self::UpdateTip { block, .. } => {
+                write!(f, "MempoolMsg::UpdateTip{{block: {block:?}}}")
+            }
         }
     }
 }
@@ -6,10 +6,12 @@ pub mod openapi {
 
 use std::{
     fmt::{Debug, Display},
+    hash::Hash,
     marker::PhantomData,
 };
 
 use futures::StreamExt;
+use nomos_core::tx::Transaction;
 use nomos_network::{NetworkMsg, NetworkService};
 use overwatch::{
     services::{
@@ -23,9 +25,10 @@ use services_utils::overwatch::{
 };
 
 use crate::{
-    backend::{MemPool, RecoverableMempool},
+    backend::{MemPool, MempoolError, RecoverableMempool, Status},
     network::NetworkAdapter as NetworkAdapterTrait,
     tx::{settings::TxMempoolSettings, state::TxMempoolState},
+    verify::{LedgerVerifiable, LedgerVerifier},
     MempoolMetrics, MempoolMsg,
 };
 
@@ -59,6 +62,7 @@ where
     RecoveryBackend: RecoveryBackendTrait,
 {
     pool: Pool,
+    verifier: LedgerVerifier<Pool::Key>,
     service_state_handle: OpaqueServiceStateHandle<Self, RuntimeServiceId>,
     _phantom: PhantomData<(NetworkAdapter, RecoveryBackend)>,
 }
@@ -72,12 +76,13 @@ where
     NetworkAdapter::Settings: Clone,
     RecoveryBackend: RecoveryBackendTrait,
 {
-    pub const fn new(
+    pub fn new(
         pool: Pool,
         service_state_handle: OpaqueServiceStateHandle<Self, RuntimeServiceId>,
     ) -> Self {
         Self {
             pool,
+            verifier: LedgerVerifier::default(),
             service_state_handle,
             _phantom: PhantomData,
         }
@@ -105,8 +110,8 @@ impl<Pool, NetworkAdapter, RecoveryBackend, RuntimeServiceId> ServiceCore<Runtim
 where
     Pool: RecoverableMempool + Send,
     Pool::RecoveryState: Debug + Send + Sync,
-    Pool::Key: Send,
-    Pool::Item: Clone + Send + 'static,
+    Pool::Key: Clone + Eq + Hash + Send,
+    Pool::Item: LedgerVerifiable + Transaction<Hash = Pool::Key> + Clone + Send + 'static,
     Pool::BlockId: Send,
     Pool::Settings: Clone + Sync + Send,
     NetworkAdapter:
@@ -172,7 +177,7 @@ where
                     self.handle_mempool_message(relay_msg, network_service_relay.clone());
                 }
                 Some((key, item )) = network_items.next() => {
-                    self.pool.add_item(key, item).unwrap_or_else(|e| {
+                    self.add_item(key, item).unwrap_or_else(|e| {
                         tracing::debug!("could not add item to the pool due to: {e}");
                     });
                     tracing::info!(counter.tx_mempool_pending_items = self.pool.pending_item_count());
@@ -193,7 +198,8 @@ impl<Pool, NetworkAdapter, RecoveryBackend, RuntimeServiceId>
     GenericTxMempoolService<Pool, NetworkAdapter, RecoveryBackend, RuntimeServiceId>
 where
     Pool: RecoverableMempool,
-    Pool::Item: Clone + Send + 'static,
+    Pool::Key: Clone + Eq + Hash,
+    Pool::Item: LedgerVerifiable + Transaction<Hash = Pool::Key> + Clone + Send + 'static,
     Pool::Settings: Clone,
     NetworkAdapter: NetworkAdapterTrait<RuntimeServiceId, Payload = Pool::Item> + Send,
     NetworkAdapter::Settings: Clone + Send + 'static,
@@ -215,7 +221,7 @@ where
                 key,
                 reply_channel,
             } => {
-                match self.pool.add_item(key, item.clone()) {
+                match self.add_item(key, item.clone()) {
                     Ok(_id) => {
                         // Broadcast the item to the network
                         let settings = self
@@ -252,8 +258,14 @@ where
                     .send(self.pool.view(ancestor_hint))
                     .unwrap_or_else(|_| tracing::debug!("could not send back pool view"));
             }
-            MempoolMsg::MarkInBlock { ids, block } => {
-                self.pool.mark_in_block(&ids, block);
+            MempoolMsg::MarkInBlock { ids, block, parent } => {
+                self.pool.mark_in_block(&ids, block, parent);
+            }
+            MempoolMsg::MarkFinalized { block } => {
+                self.pool.mark_finalized(block);
+                self.service_state_handle
+                    .state_updater
+                    .update(self.pool.save().into());
             }
             #[cfg(test)]
             MempoolMsg::BlockItems {
@@ -284,6 +296,43 @@ where
                     .send(self.pool.status(&items))
                     .unwrap_or_else(|_| tracing::debug!("could not send back mempool status"));
             }
+            MempoolMsg::UpdateTip { block, state } => {
+                self.pool.update_tip(block);
+                let pending = self.pool.view(block).map(|item| (item.hash(), item));
+                let rejected = self.verifier.update_tip(state, pending);
+                if !rejected.is_empty() {
+                    tracing::debug!(
+                        "evicting {} items conflicting with the new tip",
+                        rejected.len()
+                    );
+                    self.pool.reject(&rejected);
+                }
+                self.service_state_handle
+                    .state_updater
+                    .update(self.pool.save().into());
+            }
+        }
+    }
+
+    /// Adds an item to the pool if it is valid on top of the current tip and
+    /// the items already in the pool.
+    fn add_item(&mut self, key: Pool::Key, item: Pool::Item) -> Result<(), MempoolError> {
+        // items already in the pool would conflict with themselves, so they
+        // have to be detected before verification
+        if let Some(Status::Pending | Status::InBlock { .. }) =
+            self.pool.status(std::slice::from_ref(&key)).first()
+        {
+            return Err(MempoolError::ExistingItem);
         }
+        let state = match self.verifier.verify(&key, &item) {
+            Ok(state) => state,
+            Err(e) => {
+                self.pool.reject(&[key]);
+                return Err(MempoolError::InvalidItem(Box::new(e)));
+            }
+        };
+        self.pool.add_item(key.clone(), item)?;
+        self.verifier.accept(key, state);
+        Ok(())
     }
 }
@@ -1,13 +1,329 @@
-#[async_trait::async_trait]
-pub trait MempoolVerificationProvider {
-    type Payload;
-    type Parameters;
-    type Settings: Clone;
-
-    // TODO: Payload verification parameters most likely will come from another
-    // Overwatch service. Once it's decided, update the `new` method to require
-    // service relay as parameter.
-    fn new(settings: Self::Settings) -> Self;
-
-    async fn get_parameters(&self, payload: &Self::Payload) -> Self::Parameters;
+use std::{collections::HashSet, hash::Hash};
+
+use nomos_core::{
+    header::HeaderId,
+    proofs::stake_proof::Risc0StakeProof,
+    staking::NMO_UNIT,
+    tx::{SdpMessage, Tx, TxKind},
+    wire,
+};
+use nomos_ledger::{
+    bundle_proof::BundleProof,
+    stake_proof::{verify_stake_proof, StakeProofError},
+    LedgerError, LedgerState,
+};
+
+/// Items whose validity depends on the ledger state, e.g. transactions
+/// spending notes.
+pub trait LedgerVerifiable {
+    /// Verifies the proofs of the item, which do not depend on the ledger
+    /// state.
+    fn verify_proofs<Id>(&self) -> Result<(), LedgerError<Id>>;
+
+    /// Applies the item on top of `state`, assuming its proofs are valid,
+    /// failing if it conflicts with it.
+    fn try_apply_verified<Id>(&self, state: LedgerState) -> Result<LedgerState, LedgerError<Id>>;
+
+    /// Applies the item on top of `state`, failing if it is not valid or
+    /// conflicts with it.
+    fn try_apply<Id>(&self, state: LedgerState) -> Result<LedgerState, LedgerError<Id>> {
+        self.verify_proofs()?;
+        self.try_apply_verified(state)
+    }
+}
+
+fn stake_proof(proof_of_funds: &[u8]) -> Result<Risc0StakeProof, StakeProofError> {
+    wire::deserialize(proof_of_funds).map_err(|_| StakeProofError::InvalidProof)
+}
+
+impl LedgerVerifiable for Tx {
+    fn verify_proofs<Id>(&self) -> Result<(), LedgerError<Id>> {
+        match self.kind() {
+            TxKind::Bundle(bundle) => bundle
+                .verify()
+                .then_some(())
+                .ok_or(LedgerError::InvalidBundleProof),
+            TxKind::Sdp(SdpMessage::Declare(declaration)) => {
+                let proof = stake_proof(&declaration.proof_of_funds)?;
+                // the minimum stake is enforced by the SDP ledger once finalized
+                let min_value = proof.public_inputs().min_value;
+                verify_stake_proof(&proof, declaration.provider_id.0, NMO_UNIT, min_value)?;
+                Ok(())
+            }
+            TxKind::Sdp(SdpMessage::Reward(_) | SdpMessage::Withdraw(_)) => Ok(()),
+        }
+    }
+
+    fn try_apply_verified<Id>(&self, state: LedgerState) -> Result<LedgerState, LedgerError<Id>> {
+        match self.kind() {
+            TxKind::Bundle(bundle) => state.try_apply_verified_bundle(bundle),
+            TxKind::Sdp(SdpMessage::Declare(declaration)) => {
+                state.verify_stake_note(&stake_proof(&declaration.proof_of_funds)?)?;
+                Ok(state)
+            }
+            // rewards and withdrawals only depend on the SDP ledger, which
+            // validates them once finalized
+            TxKind::Sdp(SdpMessage::Reward(_) | SdpMessage::Withdraw(_)) => Ok(state),
+        }
+    }
+}
+
+#[cfg(feature = "mock")]
+impl<M> LedgerVerifiable for nomos_core::tx::mock::MockTransaction<M> {
+    fn verify_proofs<Id>(&self) -> Result<(), LedgerError<Id>> {
+        Ok(())
+    }
+
+    fn try_apply_verified<Id>(&self, state: LedgerState) -> Result<LedgerState, LedgerError<Id>> {
+        Ok(state)
+    }
+}
+
+/// Errors verifying an item against the ledger state.
+#[derive(thiserror::Error, Debug)]
+pub enum VerifierError {
+    #[error("The tip of the chain is not known yet")]
+    UnknownTip,
+    #[error(transparent)]
+    Ledger(#[from] LedgerError<HeaderId>),
+}
+
+/// Verifies items against the ledger state at the tip of the chain, with the
+/// transactions of its blocks applied, updated with the items already
+/// admitted in the pool, so that conflicting items are not admitted.
+///
+/// Items are rejected until the tip of the chain is known.
+///
+/// The proofs of the admitted items do not depend on the tip, they are only
+/// verified once and remembered by the key of the item while it is pending.
+#[derive(Clone, Debug)]
+pub struct LedgerVerifier<Key> {
+    state: Option<LedgerState>,
+    verified_proofs: HashSet<Key>,
+}
+
+impl<Key> Default for LedgerVerifier<Key> {
+    fn default() -> Self {
+        Self {
+            state: None,
+            verified_proofs: HashSet::new(),
+        }
+    }
+}
+
+impl<Key: Eq + Hash> LedgerVerifier<Key> {
+    /// Verifies the item `key`, returning the ledger state with its effects
+    /// applied.
+    ///
+    /// The state is not updated until the item is [`accepted`](Self::accept),
+    /// so that items which are verified but not admitted in the pool do not
+    /// leave any trace.
+    pub fn verify<Item>(&self, key: &Key, item: &Item) -> Result<LedgerState, VerifierError>
+    where
+        Item: LedgerVerifiable,
+    {
+        let state = self.state.clone().ok_or(VerifierError::UnknownTip)?;
+        if self.verified_proofs.contains(key) {
+            Ok(item.try_apply_verified(state)?)
+        } else {
+            Ok(item.try_apply(state)?)
+        }
+    }
+
+    /// Keeps track of the effects of the item `key` admitted in the pool, as
+    /// returned by [`verify`](Self::verify).
+    pub fn accept(&mut self, key: Key, state: LedgerState) {
+        self.verified_proofs.insert(key);
+        self.state = Some(state);
+    }
+
+    /// Moves to a new tip of the chain, verifying again the `pending` items
+    /// against its ledger state, in order. Only the parts depending on the
+    /// ledger state are verified again.
+    ///
+    /// `state` is expected to have the transactions of the blocks up to the
+    /// tip already applied, so that items included in them are not pending
+    /// anymore.
+    ///
+    /// Returns the keys of the items that are not valid anymore.
+    pub fn update_tip<Item>(
+        &mut self,
+        state: LedgerState,
+        pending: impl IntoIterator<Item = (Key, Item)>,
+    ) -> Vec<Key>
+    where
+        Item: LedgerVerifiable,
+    {
+        self.state = Some(state);
+        // items which are not pending anymore are forgotten
+        let mut verified_proofs = HashSet::new();
+        let rejected = pending
+            .into_iter()
+            .filter_map(|(key, item)| match self.verify(&key, &item) {
+                Ok(state) => {
+                    self.state = Some(state);
+                    verified_proofs.insert(key);
+                    None
+                }
+                Err(e) => {
+                    tracing::debug!("pending item is not valid anymore: {e}");
+                    Some(key)
+                }
+            })
+            .collect();
+        self.verified_proofs = verified_proofs;
+        rejected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use nomos_sdp_core::{
+        DeclarationId, DeclarationMessage, ProviderId, ServiceType, WithdrawMessage,
+    };
+
+    use super::*;
+
+    // spends a single nullifier, with a proof that is valid or not, counting
+    // how many times the proof was verified
+    #[derive(Clone)]
+    struct DummyTx(cl::Bundle, bool, Rc<Cell<usize>>);
+
+    impl BundleProof for DummyTx {
+        fn verify(&self) -> bool {
+            self.2.set(self.2.get() + 1);
+            self.1
+        }
+
+        fn bundle(&self) -> &cl::Bundle {
+            &self.0
+        }
+
+        fn cm_roots(&self) -> &[[u8; 32]] {
+            &[]
+        }
+    }
+
+    impl LedgerVerifiable for DummyTx {
+        fn verify_proofs<Id>(&self) -> Result<(), LedgerError<Id>> {
+            self.verify()
+                .then_some(())
+                .ok_or(LedgerError::InvalidBundleProof)
+        }
+
+        fn try_apply_verified<Id>(
+            &self,
+            state: LedgerState,
+        ) -> Result<LedgerState, LedgerError<Id>> {
+            state.try_apply_verified_bundle(self)
+        }
+    }
+
+    fn tx(nullifier: u8, valid: bool) -> DummyTx {
+        let ptx = cl::PartialTx {
+            inputs: vec![cl::Input {
+                nullifier: cl::Nullifier::new(
+                    cl::NullifierSecret([nullifier; 16]),
+                    cl::NoteCommitment([0; 32]),
+                ),
+                covenant: cl::Covenant([0; 32]),
+            }],
+            outputs: vec![],
+            balance: cl::BalanceWitness::zero([0; 16]).commit(),
+        };
+        DummyTx(cl::Bundle::new(vec![ptx]), valid, Rc::new(Cell::new(0)))
+    }
+
+    #[test]
+    fn test_verify_against_tip() {
+        let mut verifier = LedgerVerifier::default();
+        // nothing is admitted until the tip is known
+        assert!(matches!(
+            verifier.verify(&0, &tx(0, true)),
+            Err(VerifierError::UnknownTip)
+        ));
+
+        let state = LedgerState::from_commitments([], 0);
+        assert!(verifier.update_tip::<DummyTx>(state, []).is_empty());
+        let state = verifier.verify(&1, &tx(1, true)).unwrap();
+        // items are only tracked once accepted
+        assert!(verifier.verify(&1, &tx(1, true)).is_ok());
+        verifier.accept(1, state);
+        // conflicts with the pending item
+        assert!(matches!(
+            verifier.verify(&2, &tx(1, true)),
+            Err(VerifierError::Ledger(LedgerError::DoubleSpend(_)))
+        ));
+        assert!(matches!(
+            verifier.verify(&2, &tx(2, false)),
+            Err(VerifierError::Ledger(LedgerError::InvalidBundleProof))
+        ));
+        assert!(verifier.verify(&2, &tx(2, true)).is_ok());
+    }
+
+    #[test]
+    fn test_verify_sdp_messages() {
+        let state = LedgerState::from_commitments([], 0);
+        let declaration = Tx::from(TxKind::Sdp(SdpMessage::Declare(DeclarationMessage {
+            service_type: ServiceType::DataAvailability,
+            locators: Vec::new(),
+            proof_of_funds: vec![0; 4],
+            provider_id: ProviderId([0; 32]),
+        })));
+        assert!(matches!(
+            declaration.try_apply::<HeaderId>(state.clone()),
+            Err(LedgerError::InvalidStake(StakeProofError::InvalidProof))
+        ));
+
+        let withdrawal = Tx::from(TxKind::Sdp(SdpMessage::Withdraw(WithdrawMessage {
+            declaration_id: DeclarationId([0; 32]),
+            service_type: ServiceType::DataAvailability,
+            provider_id: ProviderId([0; 32]),
+            nonce: [0; 16],
+            metadata: None,
+        })));
+        assert!(withdrawal.try_apply::<HeaderId>(state).is_ok());
+    }
+
+    #[test]
+    fn test_update_tip_evicts_conflicting_items() {
+        let mut verifier = LedgerVerifier::default();
+        let spent = LedgerState::from_commitments([], 0)
+            .try_apply_bundle::<_, HeaderId>(&tx(1, true))
+            .unwrap();
+
+        let rejected = verifier.update_tip(
+            spent,
+            [(1, tx(1, true)), (2, tx(2, true)), (3, tx(2, true))],
+        );
+        assert_eq!(rejected, vec![1, 3]);
+    }
+
+    #[test]
+    fn test_update_tip_verifies_proofs_once() {
+        let mut verifier = LedgerVerifier::default();
+        verifier.update_tip::<DummyTx>(LedgerState::from_commitments([], 0), []);
+        let pending = tx(1, true);
+        let verifications = Rc::clone(&pending.2);
+        let state = verifier.verify(&1, &pending).unwrap();
+        verifier.accept(1, state);
+        assert_eq!(verifications.get(), 1);
+
+        let state = LedgerState::from_commitments([], 0);
+        assert!(verifier
+            .update_tip(state.clone(), [(1, pending.clone())])
+            .is_empty());
+        assert!(verifier
+            .update_tip(state, [(1, pending.clone())])
+            .is_empty());
+        assert_eq!(verifications.get(), 1);
+
+        // proofs of items which are not pending anymore are forgotten
+        let state = LedgerState::from_commitments([], 0);
+        assert!(verifier.update_tip::<DummyTx>(state, []).is_empty());
+        assert!(verifier.verify(&1, &pending).is_ok());
+        assert_eq!(verifications.get(), 2);
+    }
 }
@@ -4,10 +4,15 @@ use std::{
     sync::Arc,
 };
 
+use key_management_system::{
+    backend::preload::PreloadKMSBackendSettings, KMSServiceSettings, NodeKMSBackendSettings,
+    NodeKMSService,
+};
 use nomos_core::{
     header::HeaderId,
     tx::mock::{MockTransaction, MockTxId},
 };
+use nomos_ledger::LedgerState;
 use nomos_mempool::{
     backend::mockpool::MockPool,
     network::adapters::mock::{MockAdapter, MOCK_TX_CONTENT_TOPIC},
@@ -41,6 +46,7 @@ type MockMempoolService = GenericTxMempoolService<
 #[derive_services]
 struct MockPoolNode {
     logging: Tracing<RuntimeServiceId>,
+    kms: NodeKMSService<RuntimeServiceId>,
     network: NetworkService<Mock, RuntimeServiceId>,
     mockpool: MockMempoolService,
 }
@@ -80,6 +86,11 @@ fn test_mockmempool() {
 
         let app = OverwatchRunner::<MockPoolNode>::run(
             MockPoolNodeServiceSettings {
+                kms: KMSServiceSettings {
+                    backend_settings: NodeKMSBackendSettings::Preload(
+                        PreloadKMSBackendSettings::default(),
+                    ),
+                },
                 network: NetworkConfig {
                     backend: MockConfig {
                         predefined_messages,
@@ -112,6 +123,23 @@ fn test_mockmempool() {
                 .await
                 .unwrap();
 
+            // items are only admitted once the tip of the chain is known
+            mempool_outbound
+                .send(MempoolMsg::UpdateTip {
+                    block: [0; 32].into(),
+                    state: LedgerState::from_commitments([], 0),
+                })
+                .await
+                .unwrap();
+            // relay messages are handled in order, so the tip is known once
+            // the metrics are back
+            let (mtx, mrx) = tokio::sync::oneshot::channel();
+            mempool_outbound
+                .send(MempoolMsg::Metrics { reply_channel: mtx })
+                .await
+                .unwrap();
+            mrx.await.unwrap();
+
             // subscribe to the mock content topic
             network_outbound
                 .send(NetworkMsg::Process(MockBackendMessage::RelaySubscribe {
@@ -1,6 +1,9 @@
 use std::collections::HashMap;
 
-use nomos_libp2p::{libp2p::kad::PeerInfo, Multiaddr, PeerId};
+use nomos_libp2p::{
+    libp2p::{kad::PeerInfo, request_response::InboundRequestId},
+    Multiaddr, PeerId,
+};
 use serde::{Deserialize, Serialize};
 use tokio::sync::oneshot;
 
@@ -40,12 +43,41 @@ pub enum NetworkCommand {
     Info { reply: oneshot::Sender<Libp2pInfo> },
 }
 
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ChainSyncCommand {
+    /// Send a request to a peer, or to a random connected peer if none is
+    /// specified
+    Request {
+        peer_id: Option<PeerId>,
+        request: Box<[u8]>,
+        reply: oneshot::Sender<Result<Box<[u8]>, ChainSyncError>>,
+    },
+    /// Answer a request previously notified as an
+    /// [`Event::ChainSyncRequest`](super::Event::ChainSyncRequest)
+    Respond {
+        request_id: InboundRequestId,
+        response: Box<[u8]>,
+    },
+    /// List the peers chain sync requests can currently be sent to
+    ConnectedPeers { reply: oneshot::Sender<Vec<PeerId>> },
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ChainSyncError {
+    #[error("no connected peers to send the request to")]
+    NoPeers,
+    #[error("request to peer {peer_id} failed: {reason}")]
+    RequestFailed { peer_id: PeerId, reason: String },
+}
+
 #[derive(Debug)]
 #[non_exhaustive]
 pub enum Command {
     PubSub(PubSubCommand),
     Discovery(DiscoveryCommand),
     Network(NetworkCommand),
+    ChainSync(ChainSyncCommand),
 }
 
 #[derive(Debug)]
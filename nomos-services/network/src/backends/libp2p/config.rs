@@ -5,6 +5,8 @@ use serde::{Deserialize, Serialize};
 pub struct Libp2pConfig {
     #[serde(flatten)]
     pub inner: SwarmConfig,
+    /// Id of the ed25519 node key in the key management service
+    pub node_key_id: String,
     // Initial peers to connect to
     #[serde(default)]
     pub initial_peers: Vec<Multiaddr>,
@@ -2,31 +2,51 @@ mod command;
 mod config;
 pub(crate) mod swarm;
 
-pub use nomos_libp2p::libp2p::gossipsub::{Message, TopicHash};
-use overwatch::{overwatch::handle::OverwatchHandle, services::state::NoState};
+use key_management_system::{retrieve_ed25519_key, NodeKMSRelay};
+use nomos_libp2p::ed25519;
+pub use nomos_libp2p::{
+    libp2p::{
+        gossipsub::{Message, TopicHash},
+        request_response::InboundRequestId,
+    },
+    PeerId,
+};
+use overwatch::{overwatch::handle::OverwatchHandle, services::state::NoState, DynError};
 use tokio::sync::{broadcast, mpsc};
 
 use self::swarm::SwarmHandler;
 pub use self::{
-    command::{Command, Dial, DiscoveryCommand, Libp2pInfo, NetworkCommand, PubSubCommand, Topic},
+    command::{
+        ChainSyncCommand, ChainSyncError, Command, Dial, DiscoveryCommand, Libp2pInfo,
+        NetworkCommand, PubSubCommand, Topic,
+    },
     config::Libp2pConfig,
 };
 use super::NetworkBackend;
 
 pub struct Libp2p {
     events_tx: broadcast::Sender<Event>,
+    chain_sync_events_tx: broadcast::Sender<Event>,
     commands_tx: mpsc::Sender<Command>,
 }
 
 #[derive(Debug)]
 pub enum EventKind {
     Message,
+    ChainSyncRequest,
 }
 
 /// Events emitted from [`NomosLibp2p`], which users can subscribe
 #[derive(Debug, Clone)]
 pub enum Event {
     Message(Message),
+    /// A chain sync request received from a peer, to be answered with
+    /// [`ChainSyncCommand::Respond`]
+    ChainSyncRequest {
+        peer_id: PeerId,
+        request_id: InboundRequestId,
+        request: Box<[u8]>,
+    },
 }
 
 const BUFFER_SIZE: usize = 64;
@@ -39,22 +59,35 @@ impl<RuntimeServiceId> NetworkBackend<RuntimeServiceId> for Libp2p {
     type EventKind = EventKind;
     type NetworkEvent = Event;
 
-    fn new(config: Self::Settings, overwatch_handle: OverwatchHandle<RuntimeServiceId>) -> Self {
+    async fn new(
+        config: Self::Settings,
+        overwatch_handle: OverwatchHandle<RuntimeServiceId>,
+        kms: NodeKMSRelay,
+    ) -> Result<Self, DynError> {
+        let mut node_key = retrieve_ed25519_key(&kms, config.node_key_id.clone()).await?;
+        let node_key = ed25519::SecretKey::try_from_bytes(node_key.as_mut_slice())?;
         let (commands_tx, commands_rx) = tokio::sync::mpsc::channel(BUFFER_SIZE);
         let (events_tx, _) = tokio::sync::broadcast::channel(BUFFER_SIZE);
+        let (chain_sync_events_tx, _) = tokio::sync::broadcast::channel(BUFFER_SIZE);
         let initial_peers = config.initial_peers.clone();
 
-        let mut swarm_handler =
-            SwarmHandler::new(config, commands_tx.clone(), commands_rx, events_tx.clone());
-
+        let mut swarm_handler = SwarmHandler::new(
+            config,
+            node_key,
+            commands_tx.clone(),
+            commands_rx,
+            events_tx.clone(),
+            chain_sync_events_tx.clone(),
+        );
         overwatch_handle.runtime().spawn(async move {
             swarm_handler.run(initial_peers).await;
         });
 
-        Self {
+        Ok(Self {
             events_tx,
+            chain_sync_events_tx,
             commands_tx,
-        }
+        })
     }
 
     async fn process(&self, msg: Self::Message) {
@@ -72,6 +105,10 @@ impl<RuntimeServiceId> NetworkBackend<RuntimeServiceId> for Libp2p {
                 tracing::debug!("processed subscription to incoming messages");
                 self.events_tx.subscribe()
             }
+            EventKind::ChainSyncRequest => {
+                tracing::debug!("processed subscription to incoming chain sync requests");
+                self.chain_sync_events_tx.subscribe()
+            }
         }
     }
 }
@@ -1,19 +1,24 @@
 use std::{collections::HashMap, time::Duration};
 
 use nomos_libp2p::{
-    gossipsub,
+    ed25519, gossipsub,
     libp2p::{
         identify,
         kad::{self, PeerInfo, ProgressStep, QueryId},
+        request_response::{self, InboundRequestId, OutboundRequestId, ResponseChannel},
         swarm::ConnectionId,
     },
     BehaviourEvent, Multiaddr, PeerId, Protocol, Swarm, SwarmEvent,
 };
+use rand::seq::IteratorRandom;
 use tokio::sync::{broadcast, mpsc, oneshot};
 use tokio_stream::StreamExt;
 
 use super::{
-    command::{Command, Dial, DiscoveryCommand, NetworkCommand, PubSubCommand, Topic},
+    command::{
+        ChainSyncCommand, ChainSyncError, Command, Dial, DiscoveryCommand, NetworkCommand,
+        PubSubCommand, Topic,
+    },
     Event, Libp2pConfig,
 };
 use crate::backends::libp2p::Libp2pInfo;
@@ -24,14 +29,21 @@ struct PendingQueryData {
     accumulated_results: Vec<PeerInfo>,
 }
 
+type ChainSyncReply = oneshot::Sender<Result<Box<[u8]>, ChainSyncError>>;
+
 pub struct SwarmHandler {
     pub swarm: Swarm,
     pub pending_dials: HashMap<ConnectionId, Dial>,
     pub commands_tx: mpsc::Sender<Command>,
     pub commands_rx: mpsc::Receiver<Command>,
     pub events_tx: broadcast::Sender<Event>,
+    pub chain_sync_events_tx: broadcast::Sender<Event>,
 
     pending_queries: HashMap<QueryId, PendingQueryData>,
+    // outbound chain sync requests waiting for a response
+    pending_chain_sync_requests: HashMap<OutboundRequestId, ChainSyncReply>,
+    // inbound chain sync requests waiting for the response from the local node
+    pending_chain_sync_responses: HashMap<InboundRequestId, ResponseChannel<Vec<u8>>>,
 }
 
 macro_rules! log_error {
@@ -50,11 +62,13 @@ const MAX_RETRY: usize = 3;
 impl SwarmHandler {
     pub fn new(
         config: Libp2pConfig,
+        node_key: ed25519::SecretKey,
         commands_tx: mpsc::Sender<Command>,
         commands_rx: mpsc::Receiver<Command>,
         events_tx: broadcast::Sender<Event>,
+        chain_sync_events_tx: broadcast::Sender<Event>,
     ) -> Self {
-        let swarm = Swarm::build(config.inner).unwrap();
+        let swarm = Swarm::build(config.inner, node_key).unwrap();
 
         // Keep the dialing history since swarm.connect doesn't return the result
         // synchronously
@@ -66,7 +80,10 @@ impl SwarmHandler {
             commands_tx,
             commands_rx,
             events_tx,
+            chain_sync_events_tx,
             pending_queries: HashMap::new(),
+            pending_chain_sync_requests: HashMap::new(),
+            pending_chain_sync_responses: HashMap::new(),
         }
     }
 
@@ -137,6 +154,10 @@ impl SwarmHandler {
                 self.handle_kademlia_event(event);
             }
 
+            SwarmEvent::Behaviour(BehaviourEvent::ChainSync(event)) => {
+                self.handle_chain_sync_event(event);
+            }
+
             SwarmEvent::ConnectionEstablished {
                 peer_id,
                 connection_id,
@@ -178,6 +199,7 @@ impl SwarmHandler {
             Command::Network(network_cmd) => self.handle_network_command(network_cmd),
             Command::PubSub(pubsub_cmd) => self.handle_pubsub_command(pubsub_cmd),
             Command::Discovery(discovery_cmd) => self.handle_discovery_command(discovery_cmd),
+            Command::ChainSync(chain_sync_cmd) => self.handle_chain_sync_command(chain_sync_cmd),
         }
     }
 
@@ -259,6 +281,113 @@ impl SwarmHandler {
         }
     }
 
+    fn handle_chain_sync_command(&mut self, command: ChainSyncCommand) {
+        match command {
+            ChainSyncCommand::Request {
+                peer_id,
+                request,
+                reply,
+            } => {
+                let peer_id = peer_id.or_else(|| {
+                    self.swarm
+                        .swarm()
+                        .connected_peers()
+                        .copied()
+                        .choose(&mut rand::thread_rng())
+                });
+                let Some(peer_id) = peer_id else {
+                    log_error!(reply.send(Err(ChainSyncError::NoPeers)));
+                    return;
+                };
+                tracing::debug!("sending chain sync request to peer {peer_id}");
+                let request_id = self
+                    .swarm
+                    .send_chain_sync_request(&peer_id, request.into_vec());
+                self.pending_chain_sync_requests.insert(request_id, reply);
+            }
+            ChainSyncCommand::Respond {
+                request_id,
+                response,
+            } => {
+                let Some(channel) = self.pending_chain_sync_responses.remove(&request_id) else {
+                    tracing::warn!("no pending chain sync request with id {request_id}");
+                    return;
+                };
+                if self
+                    .swarm
+                    .send_chain_sync_response(channel, response.into_vec())
+                    .is_err()
+                {
+                    tracing::debug!(
+                        "peer hung up before receiving chain sync response {request_id}"
+                    );
+                }
+            }
+            ChainSyncCommand::ConnectedPeers { reply } => {
+                let peers = self.swarm.swarm().connected_peers().copied().collect();
+                log_error!(reply.send(peers));
+            }
+        }
+    }
+
+    fn handle_chain_sync_event(&mut self, event: request_response::Event<Vec<u8>, Vec<u8>>) {
+        match event {
+            request_response::Event::Message { peer, message, .. } => match message {
+                request_response::Message::Request {
+                    request_id,
+                    request,
+                    channel,
+                } => {
+                    let event = Event::ChainSyncRequest {
+                        peer_id: peer,
+                        request_id,
+                        request: request.into_boxed_slice(),
+                    };
+                    // if nobody is listening the channel is dropped, which notifies the
+                    // failure to the requesting peer
+                    if self.chain_sync_events_tx.send(event).is_ok() {
+                        self.pending_chain_sync_responses
+                            .insert(request_id, channel);
+                    } else {
+                        tracing::debug!("no subscribers for chain sync request from {peer}");
+                    }
+                }
+                request_response::Message::Response {
+                    request_id,
+                    response,
+                } => {
+                    if let Some(reply) = self.pending_chain_sync_requests.remove(&request_id) {
+                        log_error!(reply.send(Ok(response.into_boxed_slice())));
+                    }
+                }
+            },
+            request_response::Event::OutboundFailure {
+                peer,
+                request_id,
+                error,
+                ..
+            } => {
+                tracing::debug!("chain sync request {request_id} to {peer} failed: {error}");
+                if let Some(reply) = self.pending_chain_sync_requests.remove(&request_id) {
+                    log_error!(reply.send(Err(ChainSyncError::RequestFailed {
+                        peer_id: peer,
+                        reason: error.to_string(),
+                    })));
+                }
+            }
+            request_response::Event::InboundFailure {
+                peer,
+                request_id,
+                error,
+                ..
+            } => {
+                tracing::debug!("chain sync request {request_id} from {peer} failed: {error}");
+                self.pending_chain_sync_responses.remove(&request_id);
+            }
+            request_response::Event::ResponseSent { .. } => {}
+        }
+    }
+
     async fn schedule_connect(dial: Dial, commands_tx: mpsc::Sender<Command>) {
         commands_tx
             .send(Command::Network(NetworkCommand::Connect(dial)))
@@ -485,7 +614,6 @@ mod tests {
         nomos_libp2p::SwarmConfig {
             host: Ipv4Addr::new(127, 0, 0, 1),
             port,
-            node_key: nomos_libp2p::ed25519::SecretKey::generate(),
             gossipsub_config: gossipsub::Config::default(),
             kademlia_config: Some(nomos_libp2p::KademliaSettings::default()),
             identify_config: Some(nomos_libp2p::IdentifySettings::default()),
@@ -496,6 +624,7 @@ mod tests {
     fn create_libp2p_config(initial_peers: Vec<Multiaddr>, port: u16) -> Libp2pConfig {
         Libp2pConfig {
             inner: create_swarm_config(port),
+            node_key_id: "network".to_owned(),
             initial_peers,
         }
     }
@@ -514,8 +643,16 @@ mod tests {
         txs.push(tx1.clone());
 
         let (events_tx1, _) = broadcast::channel(10);
+        let (chain_sync_events_tx1, _) = broadcast::channel(10);
         let config = create_libp2p_config(vec![], 8000);
-        let mut bootstrap_node = SwarmHandler::new(config, tx1.clone(), rx1, events_tx1.clone());
+        let mut bootstrap_node = SwarmHandler::new(
+            config,
+            ed25519::SecretKey::generate(),
+            tx1.clone(),
+            rx1,
+            events_tx1.clone(),
+            chain_sync_events_tx1,
+        );
 
         let bootstrap_node_peer_id = *bootstrap_node.swarm.swarm().local_peer_id();
 
@@ -557,10 +694,18 @@ mod tests {
             let (tx, rx) = mpsc::channel(10);
             txs.push(tx.clone());
             let (events_tx, _) = broadcast::channel(10);
+            let (chain_sync_events_tx, _) = broadcast::channel(10);
 
             // Each node connects to the bootstrap node
             let config = create_libp2p_config(vec![bootstrap_addr.clone()], 8000 + i as u16);
-            let mut handler = SwarmHandler::new(config, tx.clone(), rx, events_tx);
+            let mut handler = SwarmHandler::new(
+                config,
+                ed25519::SecretKey::generate(),
+                tx.clone(),
+                rx,
+                events_tx,
+                chain_sync_events_tx,
+            );
 
             let peer_id = *handler.swarm.swarm().local_peer_id();
             tracing::info!("Starting node {} with peer ID: {}", i, peer_id);
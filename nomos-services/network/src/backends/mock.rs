@@ -6,6 +6,7 @@ use std::{
 };
 
 use futures::{channel::oneshot, future::BoxFuture};
+use key_management_system::NodeKMSRelay;
 use overwatch::services::state::NoState;
 use rand::{
     distributions::{Distribution, WeightedIndex},
@@ -170,6 +171,24 @@ pub enum NetworkEvent {
 }
 
 impl Mock {
+    #[must_use]
+    pub fn from_config(config: MockConfig) -> Self {
+        let message_event = broadcast::channel(BROADCAST_CHANNEL_BUF).0;
+
+        Self {
+            subscribed_topics: Arc::new(Mutex::new(HashSet::new())),
+            messages: Arc::new(Mutex::new(
+                config
+                    .predefined_messages
+                    .iter()
+                    .map(|p| (p.content_topic.content_topic_name.to_string(), Vec::new()))
+                    .collect(),
+            )),
+            message_event,
+            config,
+        }
+    }
+
     /// Run producer message handler
     pub async fn run_producer_handler(&self) -> Result<(), overwatch::DynError> {
         match &self.config.weights {
@@ -238,21 +257,12 @@ impl<RuntimeServiceId> NetworkBackend<RuntimeServiceId> for Mock {
     type EventKind = EventKind;
     type NetworkEvent = NetworkEvent;
 
-    fn new(config: Self::Settings, _: OverwatchHandle<RuntimeServiceId>) -> Self {
-        let message_event = broadcast::channel(BROADCAST_CHANNEL_BUF).0;
-
-        Self {
-            subscribed_topics: Arc::new(Mutex::new(HashSet::new())),
-            messages: Arc::new(Mutex::new(
-                config
-                    .predefined_messages
-                    .iter()
-                    .map(|p| (p.content_topic.content_topic_name.to_string(), Vec::new()))
-                    .collect(),
-            )),
-            message_event,
-            config,
-        }
+    async fn new(
+        config: Self::Settings,
+        _: OverwatchHandle<RuntimeServiceId>,
+        _: NodeKMSRelay,
+    ) -> Result<Self, overwatch::DynError> {
+        Ok(Self::from_config(config))
     }
 
     async fn process(&self, msg: Self::Message) {
@@ -311,8 +321,6 @@ impl<RuntimeServiceId> NetworkBackend<RuntimeServiceId> for Mock {
 
 #[cfg(test)]
 mod tests {
-    use tokio::sync::mpsc;
-
     use super::*;
 
     static FOO_BROADCAST_MESSAGES: &[&str] = &["foo1", "foo2"];
@@ -350,10 +358,7 @@ mod tests {
             weights: None,
         };
 
-        let mock = Arc::new(Mock::new(
-            config,
-            OverwatchHandle::<()>::new(tokio::runtime::Handle::current(), mpsc::channel(1).0),
-        ));
+        let mock = Arc::new(Mock::from_config(config));
         // run producer
         let task = Arc::clone(&mock);
         tokio::spawn(async move {
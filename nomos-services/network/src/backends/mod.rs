@@ -1,4 +1,5 @@
-use overwatch::{overwatch::handle::OverwatchHandle, services::state::ServiceState};
+use key_management_system::NodeKMSRelay;
+use overwatch::{overwatch::handle::OverwatchHandle, services::state::ServiceState, DynError};
 use tokio::sync::broadcast::Receiver;
 
 use super::Debug;
@@ -17,7 +18,14 @@ pub trait NetworkBackend<RuntimeServiceId> {
     type EventKind: Debug + Send + Sync + 'static;
     type NetworkEvent: Debug + Send + Sync + 'static;
 
-    fn new(config: Self::Settings, overwatch_handle: OverwatchHandle<RuntimeServiceId>) -> Self;
+    /// Creates the backend, which retrieves the keys it needs through `kms`.
+    async fn new(
+        config: Self::Settings,
+        overwatch_handle: OverwatchHandle<RuntimeServiceId>,
+        kms: NodeKMSRelay,
+    ) -> Result<Self, DynError>
+    where
+        Self: Sized;
     async fn process(&self, msg: Self::Message);
     async fn subscribe(&mut self, event: Self::EventKind) -> Receiver<Self::NetworkEvent>;
 }
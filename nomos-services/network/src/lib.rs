@@ -1,9 +1,13 @@
 pub mod backends;
-use std::fmt::{self, Debug, Display};
+use std::{
+    fmt::{self, Debug, Display},
+    marker::PhantomData,
+};
 
 use async_trait::async_trait;
 use backends::NetworkBackend;
 use futures::StreamExt;
+use key_management_system::NodeKMSService;
 use overwatch::{
     services::{
         state::{NoOperator, ServiceState},
@@ -51,8 +55,8 @@ impl<B: NetworkBackend<RuntimeServiceId>, RuntimeServiceId> Debug
 }
 
 pub struct NetworkService<B: NetworkBackend<RuntimeServiceId> + 'static, RuntimeServiceId> {
-    backend: B,
     service_state: OpaqueServiceStateHandle<Self, RuntimeServiceId>,
+    _backend: PhantomData<B>,
 }
 
 pub struct NetworkState<B: NetworkBackend<RuntimeServiceId>, RuntimeServiceId> {
@@ -73,31 +77,48 @@ impl<B, RuntimeServiceId> ServiceCore<RuntimeServiceId> for NetworkService<B, Ru
 where
     B: NetworkBackend<RuntimeServiceId> + Send + 'static,
     B::State: Send + Sync,
-    RuntimeServiceId: AsServiceId<Self> + Clone + Display + Send,
+    RuntimeServiceId: AsServiceId<Self>
+        + AsServiceId<NodeKMSService<RuntimeServiceId>>
+        + Clone
+        + Debug
+        + Display
+        + Send
+        + Sync
+        + 'static,
 {
     fn init(
         service_state: OpaqueServiceStateHandle<Self, RuntimeServiceId>,
         _init_state: Self::State,
     ) -> Result<Self, overwatch::DynError> {
         Ok(Self {
-            backend: <B as NetworkBackend<RuntimeServiceId>>::new(
-                service_state.settings_reader.get_updated_settings().backend,
-                service_state.overwatch_handle.clone(),
-            ),
             service_state,
+            _backend: PhantomData,
         })
     }
 
-    async fn run(mut self) -> Result<(), overwatch::DynError> {
+    async fn run(self) -> Result<(), overwatch::DynError> {
         let Self {
             service_state:
                 OpaqueServiceStateHandle::<Self, RuntimeServiceId> {
                     mut inbound_relay,
                     lifecycle_handle,
+                    settings_reader,
+                    overwatch_handle,
                     ..
                 },
-            mut backend,
+            ..
         } = self;
+        // The backend is built here, as its keys are retrieved from the key
+        // management service
+        let kms = overwatch_handle
+            .relay::<NodeKMSService<RuntimeServiceId>>()
+            .await?;
+        let mut backend = <B as NetworkBackend<RuntimeServiceId>>::new(
+            settings_reader.get_updated_settings().backend,
+            overwatch_handle.clone(),
+            kms,
+        )
+        .await?;
         let mut lifecycle_stream = lifecycle_handle.message_stream();
         loop {
             tokio::select! {
@@ -0,0 +1,85 @@
+use std::{hash::Hash, marker::PhantomData};
+
+use cryptarchia_consensus::{ConsensusMsg, FinalizedBlock};
+use futures::{Stream, StreamExt};
+use nomos_core::block::Block;
+use nomos_sdp_core::BlockNumber;
+use overwatch::services::{relay::OutboundRelay, ServiceData};
+use tokio::sync::oneshot;
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::consensus::{FinalizedSdpMessages, SdpConsensusAdapter, SdpTransaction};
+
+pub struct CryptarchiaConsensusAdapter<Consensus, Tx, BlobCertificate>
+where
+    Consensus: ServiceData<Message = ConsensusMsg<Block<Tx, BlobCertificate>>>,
+    Tx: Clone + Eq + Hash,
+    BlobCertificate: Clone + Eq + Hash,
+{
+    consensus_relay: OutboundRelay<ConsensusMsg<Block<Tx, BlobCertificate>>>,
+    // only the service type is needed to get the relay, the adapter does not
+    // own a service
+    _consensus: PhantomData<fn() -> Consensus>,
+}
+
+#[async_trait::async_trait]
+impl<Consensus, Tx, BlobCertificate> SdpConsensusAdapter
+    for CryptarchiaConsensusAdapter<Consensus, Tx, BlobCertificate>
+where
+    Consensus: ServiceData<Message = ConsensusMsg<Block<Tx, BlobCertificate>>>,
+    Tx: SdpTransaction + Clone + Eq + Hash + Send + Sync + 'static,
+    Tx::Metadata: Clone + Send + 'static,
+    Tx::Proof: Clone + Send + 'static,
+    BlobCertificate: Clone + Eq + Hash + Send + Sync + 'static,
+{
+    type ConsensusService = Consensus;
+    type Metadata = Tx::Metadata;
+    type Proof = Tx::Proof;
+
+    fn new(
+        consensus_relay: OutboundRelay<<Self::ConsensusService as ServiceData>::Message>,
+    ) -> Self {
+        Self {
+            consensus_relay,
+            _consensus: PhantomData,
+        }
+    }
+
+    async fn finalized_messages_stream(
+        &self,
+        from_block: BlockNumber,
+    ) -> Box<dyn Stream<Item = FinalizedSdpMessages<Self::Metadata, Self::Proof>> + Unpin + Send>
+    {
+        let (sender, receiver) = oneshot::channel();
+
+        self.consensus_relay
+            .send(ConsensusMsg::FinalizedBlockSubscribe {
+                from_height: Some(from_block),
+                sender,
+            })
+            .await
+            .expect("Failed to send FinalizedBlockSubscribe message");
+
+        let finalized_blocks = receiver
+            .await
+            .expect("Failed to receive finalized blocks receiver");
+
+        Box::new(ReceiverStream::new(finalized_blocks).map(
+            |FinalizedBlock {
+                 height,
+                 block,
+                 ledger_state,
+             }| {
+                FinalizedSdpMessages {
+                    block_number: height,
+                    messages: block
+                        .transactions()
+                        .filter_map(SdpTransaction::sdp_message)
+                        .cloned()
+                        .collect(),
+                    ledger_state,
+                }
+            },
+        ))
+    }
+}
@@ -0,0 +1,58 @@
+pub mod adapters;
+
+use futures::Stream;
+use nomos_core::tx::{Tx, TxKind};
+use nomos_ledger::LedgerState;
+use nomos_sdp_core::{BlockNumber, SdpMessage};
+use overwatch::services::{relay::OutboundRelay, ServiceData};
+
+/// Transactions which may carry an SDP message.
+pub trait SdpTransaction {
+    type Metadata;
+    type Proof;
+
+    fn sdp_message(&self) -> Option<&SdpMessage<Self::Metadata, Self::Proof>>;
+}
+
+impl SdpTransaction for Tx {
+    type Metadata = Vec<u8>;
+    type Proof = Vec<u8>;
+
+    fn sdp_message(&self) -> Option<&SdpMessage<Self::Metadata, Self::Proof>> {
+        match self.kind() {
+            TxKind::Sdp(message) => Some(message),
+            TxKind::Bundle(_) => None,
+        }
+    }
+}
+
+/// SDP messages included in a finalized block, in block order.
+#[derive(Debug, Clone)]
+pub struct FinalizedSdpMessages<Metadata, Proof> {
+    pub block_number: BlockNumber,
+    pub messages: Vec<SdpMessage<Metadata, Proof>>,
+    /// Ledger state after the block, used to verify the stake of the
+    /// declarations
+    pub ledger_state: LedgerState,
+}
+
+#[async_trait::async_trait]
+pub trait SdpConsensusAdapter {
+    type ConsensusService: ServiceData;
+    type Metadata;
+    type Proof;
+
+    fn new(
+        consensus_relay: OutboundRelay<<Self::ConsensusService as ServiceData>::Message>,
+    ) -> Self;
+
+    /// Stream of the SDP messages of each block finalized by consensus,
+    /// starting from block `from_block`, from the oldest to the newest block.
+    ///
+    /// No block is skipped, the stream ends if consensus cannot deliver some
+    /// block.
+    async fn finalized_messages_stream(
+        &self,
+        from_block: BlockNumber,
+    ) -> Box<dyn Stream<Item = FinalizedSdpMessages<Self::Metadata, Self::Proof>> + Unpin + Send>;
+}
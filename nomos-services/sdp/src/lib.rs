@@ -0,0 +1,359 @@
+pub mod consensus;
+pub mod membership;
+pub mod repositories;
+pub mod rewards;
+mod sessions;
+pub mod stakes;
+
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Display},
+    num::NonZeroU64,
+};
+
+use bytes::Bytes;
+use consensus::{FinalizedSdpMessages, SdpConsensusAdapter};
+use futures::StreamExt;
+use nomos_sdp_core::{
+    ledger::SdpLedger, BlockNumber, Locator, MinStake, ProviderId, ServiceParameters, ServiceType,
+    StakeThreshold,
+};
+use nomos_storage::{
+    backends::{StorageBackend, StorageBatch, StorageSerde},
+    StorageMsg, StorageService,
+};
+use overwatch::{
+    services::{
+        relay::OutboundRelay,
+        state::{NoOperator, NoState},
+        AsServiceId, ServiceCore, ServiceData,
+    },
+    DynError, OpaqueServiceStateHandle,
+};
+use repositories::{StorageDeclarationsRepository, StorageServicesRepository, SDP_LAST_BLOCK_KEY};
+use rewards::LoggingRewardsSender;
+use serde::{Deserialize, Serialize};
+use services_utils::overwatch::lifecycle;
+use sessions::SessionProviders;
+use stakes::LedgerStakesVerifier;
+use tokio::sync::{broadcast, oneshot};
+use tracing::error;
+
+/// Address of the contract rewarding the providers of a service.
+pub type ContractAddress = [u8; 32];
+
+const SESSION_UPDATES_CHANNEL_SIZE: usize = 16;
+
+type Ledger<Backend, Metadata, Proof> = SdpLedger<
+    StorageDeclarationsRepository<Backend>,
+    LoggingRewardsSender<ContractAddress, Metadata>,
+    StorageServicesRepository<Backend, ContractAddress>,
+    LedgerStakesVerifier<Proof>,
+    Proof,
+    Metadata,
+    ContractAddress,
+>;
+
+#[derive(Debug)]
+pub enum SdpMsg {
+    /// Providers of a service active at the last finalized block, with their
+    /// locators
+    GetActiveProviders {
+        service_type: ServiceType,
+        reply_channel: oneshot::Sender<Result<HashMap<ProviderId, Vec<Locator>>, DynError>>,
+    },
+    /// Providers of a service active at the start of the current session,
+    /// the ones of the last [`SessionUpdate`], with their locators
+    GetSessionProviders {
+        service_type: ServiceType,
+        reply_channel: oneshot::Sender<HashMap<ProviderId, Vec<Locator>>>,
+    },
+    /// Subscribe to the providers of each service active at the start of
+    /// every session
+    SubscribeSessionUpdates {
+        result_sender: oneshot::Sender<broadcast::Receiver<SessionUpdate>>,
+    },
+}
+
+/// Providers of a service active at the first block of a session.
+#[derive(Clone, Debug)]
+pub struct SessionUpdate {
+    pub session_number: u64,
+    pub service_type: ServiceType,
+    pub providers: HashMap<ProviderId, Vec<Locator>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SdpSettings {
+    pub service_params: HashMap<ServiceType, ServiceParameters<ContractAddress>>,
+    /// Number of blocks after which the active providers of the services are
+    /// recomputed
+    pub session_length: NonZeroU64,
+    /// Minimum value of the note backing a declaration
+    pub min_stake: StakeThreshold,
+}
+
+/// Service applying the SDP messages of finalized blocks to the SDP ledger.
+///
+/// Finalized blocks are never reverted, so the updates of a block are applied
+/// once and for all and the ledger never rolls back on reorgs. A block which
+/// can't be persisted stops the service, and is applied again on restart.
+pub struct SdpService<Backend, Consensus, RuntimeServiceId>
+where
+    Backend: StorageBackend + Send + Sync + 'static,
+    Consensus: SdpConsensusAdapter,
+{
+    service_state: OpaqueServiceStateHandle<Self, RuntimeServiceId>,
+}
+
+impl<Backend, Consensus, RuntimeServiceId> ServiceData
+    for SdpService<Backend, Consensus, RuntimeServiceId>
+where
+    Backend: StorageBackend + Send + Sync + 'static,
+    Consensus: SdpConsensusAdapter,
+{
+    type Settings = SdpSettings;
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = SdpMsg;
+}
+
+#[async_trait::async_trait]
+impl<Backend, Consensus, RuntimeServiceId> ServiceCore<RuntimeServiceId>
+    for SdpService<Backend, Consensus, RuntimeServiceId>
+where
+    Backend: StorageBatch + Send + Sync + 'static,
+    <Backend::SerdeOperator as StorageSerde>::Error: Send + 'static,
+    Consensus: SdpConsensusAdapter + Send + Sync,
+    Consensus::Metadata: Send + Sync + 'static,
+    Consensus::Proof: AsRef<[u8]> + Send + Sync + 'static,
+    <Consensus::ConsensusService as ServiceData>::Message: 'static,
+    RuntimeServiceId: AsServiceId<Self>
+        + AsServiceId<StorageService<Backend, RuntimeServiceId>>
+        + AsServiceId<Consensus::ConsensusService>
+        + Debug
+        + Display
+        + Send
+        + Sync
+        + 'static,
+{
+    fn init(
+        service_state: OpaqueServiceStateHandle<Self, RuntimeServiceId>,
+        _initial_state: Self::State,
+    ) -> Result<Self, DynError> {
+        Ok(Self { service_state })
+    }
+
+    async fn run(self) -> Result<(), DynError> {
+        let Self { mut service_state } = self;
+        let SdpSettings {
+            service_params,
+            session_length,
+            min_stake,
+        } = service_state.settings_reader.get_updated_settings();
+        let service_types: Vec<ServiceType> = service_params.keys().copied().collect();
+        let (session_updates_sender, _) = broadcast::channel(SESSION_UPDATES_CHANNEL_SIZE);
+
+        let storage_relay = service_state
+            .overwatch_handle
+            .relay::<StorageService<Backend, RuntimeServiceId>>()
+            .await?;
+        let consensus_relay = service_state
+            .overwatch_handle
+            .relay::<Consensus::ConsensusService>()
+            .await?;
+        let consensus_adapter = Consensus::new(consensus_relay);
+
+        let services_repo = StorageServicesRepository::new(storage_relay.clone());
+        services_repo
+            .init_parameters(service_params)
+            .await
+            .map_err(|e| DynError::from(e.to_string()))?;
+        let mut ledger: Ledger<Backend, Consensus::Metadata, Consensus::Proof> = SdpLedger::new(
+            StorageDeclarationsRepository::new(storage_relay.clone()),
+            LoggingRewardsSender::default(),
+            services_repo,
+            LedgerStakesVerifier::new(MinStake {
+                threshold: min_stake,
+                timestamp: 0,
+            }),
+        );
+        let mut last_block = Self::load_last_block(&storage_relay).await?;
+        let mut session = SessionProviders::restore(
+            &ledger,
+            &storage_relay,
+            last_block,
+            session_length,
+            &service_types,
+        )
+        .await?;
+        // resume right after the last applied block, so that none is missed
+        let mut finalized_messages = consensus_adapter
+            .finalized_messages_stream(next_block(last_block))
+            .await;
+
+        let mut lifecycle_stream = service_state.lifecycle_handle.message_stream();
+        loop {
+            tokio::select! {
+                finalized = finalized_messages.next() => {
+                    let Some(FinalizedSdpMessages { block_number, messages, ledger_state }) = finalized else {
+                        return Err(DynError::from("Finalized blocks stream closed"));
+                    };
+                    // the SDP ledger would diverge from the one of other nodes
+                    if block_number != next_block(last_block) {
+                        return Err(DynError::from(format!(
+                            "Expected finalized block {}, got {block_number}",
+                            next_block(last_block)
+                        )));
+                    }
+                    ledger.stake_verifier_mut().update_ledger_state(ledger_state);
+                    // on failure the block is applied again on restart, as the last block
+                    // persisted is the previous one
+                    Self::process_block(&mut ledger, block_number, messages).await?;
+                    last_block = Some(block_number);
+                    if block_number % session_length == 0 {
+                        // on failure the providers are evaluated again on restart, as the
+                        // boundary is the last block applied
+                        session = SessionProviders::compute(
+                            &ledger,
+                            block_number,
+                            block_number / session_length,
+                            &service_types,
+                        )
+                        .await?;
+                        session.store(&storage_relay).await?;
+                        Self::notify_session_update(&session, &session_updates_sender);
+                    }
+                }
+                Some(msg) = service_state.inbound_relay.recv() => {
+                    Self::handle_sdp_message(
+                        &ledger,
+                        last_block,
+                        &session,
+                        &session_updates_sender,
+                        msg,
+                    )
+                    .await;
+                }
+                Some(msg) = lifecycle_stream.next() => {
+                    if lifecycle::should_stop_service::<Self, RuntimeServiceId>(&msg) {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Number of the block following `last_block`, blocks being numbered from 1
+/// as genesis has no SDP messages.
+fn next_block(last_block: Option<BlockNumber>) -> BlockNumber {
+    last_block.map_or(1, |last_block| last_block + 1)
+}
+
+impl<Backend, Consensus, RuntimeServiceId> SdpService<Backend, Consensus, RuntimeServiceId>
+where
+    Backend: StorageBatch + Send + Sync + 'static,
+    <Backend::SerdeOperator as StorageSerde>::Error: Send + 'static,
+    Consensus: SdpConsensusAdapter,
+    Consensus::Metadata: Send + Sync + 'static,
+    Consensus::Proof: AsRef<[u8]> + Send + Sync + 'static,
+{
+    async fn load_last_block(
+        storage_relay: &OutboundRelay<StorageMsg<Backend>>,
+    ) -> Result<Option<BlockNumber>, DynError> {
+        let (msg, receiver) = StorageMsg::<Backend>::new_load_message(Bytes::from_static(
+            SDP_LAST_BLOCK_KEY.as_bytes(),
+        ));
+        storage_relay
+            .send(msg)
+            .await
+            .map_err(|(e, _)| Box::new(e) as DynError)?;
+        Ok(receiver.recv().await?)
+    }
+
+    /// Applies the SDP messages of a finalized block. Invalid messages are
+    /// skipped, they do not invalidate the rest of the block.
+    ///
+    /// Fails if the updates of the block can't be persisted, in which case
+    /// none of them is applied.
+    async fn process_block(
+        ledger: &mut Ledger<Backend, Consensus::Metadata, Consensus::Proof>,
+        block_number: BlockNumber,
+        messages: Vec<nomos_sdp_core::SdpMessage<Consensus::Metadata, Consensus::Proof>>,
+    ) -> Result<(), DynError> {
+        for message in messages {
+            if let Err(e) = ledger.process_sdp_message(block_number, message).await {
+                error!("Invalid SDP message in block {block_number}: {e}");
+            }
+        }
+        ledger.mark_in_block(block_number).await.map_err(|e| {
+            DynError::from(format!(
+                "Could not apply SDP updates of block {block_number}: {e}"
+            ))
+        })
+    }
+
+    /// Broadcasts the providers of every service active at the first block
+    /// of a session. Nothing is sent if there are no subscribers.
+    fn notify_session_update(
+        session: &SessionProviders,
+        session_updates_sender: &broadcast::Sender<SessionUpdate>,
+    ) {
+        if session_updates_sender.receiver_count() == 0 {
+            return;
+        }
+        for (service_type, providers) in &session.providers {
+            let update = SessionUpdate {
+                session_number: session.session_number,
+                service_type: *service_type,
+                providers: providers.clone(),
+            };
+            if let Err(e) = session_updates_sender.send(update) {
+                error!("Could not send session update: {e}");
+            }
+        }
+    }
+
+    async fn handle_sdp_message(
+        ledger: &Ledger<Backend, Consensus::Metadata, Consensus::Proof>,
+        last_block: Option<BlockNumber>,
+        session: &SessionProviders,
+        session_updates_sender: &broadcast::Sender<SessionUpdate>,
+        msg: SdpMsg,
+    ) {
+        match msg {
+            SdpMsg::GetActiveProviders {
+                service_type,
+                reply_channel,
+            } => {
+                let providers = ledger
+                    .active_providers(last_block.unwrap_or_default(), service_type)
+                    .await
+                    .map_err(|e| DynError::from(e.to_string()));
+                if reply_channel.send(providers).is_err() {
+                    error!("Could not reply to active providers request");
+                }
+            }
+            SdpMsg::GetSessionProviders {
+                service_type,
+                reply_channel,
+            } => {
+                if reply_channel
+                    .send(session.service_providers(service_type))
+                    .is_err()
+                {
+                    error!("Could not reply to session providers request");
+                }
+            }
+            SdpMsg::SubscribeSessionUpdates { result_sender } => {
+                if result_sender
+                    .send(session_updates_sender.subscribe())
+                    .is_err()
+                {
+                    error!("Could not reply to session updates subscription");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Display},
+    marker::PhantomData,
+};
+
+use futures::StreamExt;
+use nomos_sdp_core::{Locator, ProviderId, ServiceType};
+use overwatch::{
+    services::{
+        relay::OutboundRelay,
+        state::{NoOperator, NoState},
+        AsServiceId, ServiceCore, ServiceData,
+    },
+    DynError, OpaqueServiceStateHandle,
+};
+use services_utils::overwatch::lifecycle;
+use tokio::sync::oneshot;
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+use tracing::{info, warn};
+
+use crate::{SdpMsg, SessionUpdate};
+
+/// Relay to the service whose membership is driven by `Adapter`.
+pub type MembershipRelay<Adapter, RuntimeServiceId> = OutboundRelay<
+    <<Adapter as MembershipAdapter<RuntimeServiceId>>::Service as ServiceData>::Message,
+>;
+
+/// Applies the providers of an SDP service type to the service relying on
+/// them, e.g. a network whose peers are the providers.
+///
+/// Provider ids are the ed25519 public keys identifying the providers, for
+/// every service type.
+#[async_trait::async_trait]
+pub trait MembershipAdapter<RuntimeServiceId> {
+    /// Service whose membership is kept in sync with the providers.
+    type Service: ServiceData;
+
+    /// Service type whose providers make up the membership.
+    const SERVICE_TYPE: ServiceType;
+
+    /// Replaces the membership of the service with the given providers.
+    async fn update_membership(
+        relay: &MembershipRelay<Self, RuntimeServiceId>,
+        providers: &HashMap<ProviderId, Vec<Locator>>,
+    ) -> Result<(), DynError>;
+}
+
+/// Service keeping the membership of another service in sync with the
+/// providers declared through SDP.
+///
+/// The membership is recomputed at every session boundary from the active
+/// providers and their locators, so providers can join and leave without
+/// restarting the service relying on them.
+pub struct SessionMembershipService<Adapter, Sdp, RuntimeServiceId> {
+    service_state: OpaqueServiceStateHandle<Self, RuntimeServiceId>,
+    _phantom: PhantomData<fn() -> (Adapter, Sdp)>,
+}
+
+impl<Adapter, Sdp, RuntimeServiceId> ServiceData
+    for SessionMembershipService<Adapter, Sdp, RuntimeServiceId>
+{
+    type Settings = ();
+    type State = NoState<Self::Settings>;
+    type StateOperator = NoOperator<Self::State>;
+    type Message = ();
+}
+
+#[async_trait::async_trait]
+impl<Adapter, Sdp, RuntimeServiceId> ServiceCore<RuntimeServiceId>
+    for SessionMembershipService<Adapter, Sdp, RuntimeServiceId>
+where
+    Adapter: MembershipAdapter<RuntimeServiceId> + 'static,
+    <Adapter::Service as ServiceData>::Message: Send,
+    Sdp: ServiceData<Message = SdpMsg> + Send + Sync,
+    RuntimeServiceId: AsServiceId<Self>
+        + AsServiceId<Adapter::Service>
+        + AsServiceId<Sdp>
+        + Debug
+        + Display
+        + Send
+        + Sync
+        + 'static,
+{
+    fn init(
+        service_state: OpaqueServiceStateHandle<Self, RuntimeServiceId>,
+        _initial_state: Self::State,
+    ) -> Result<Self, DynError> {
+        Ok(Self {
+            service_state,
+            _phantom: PhantomData,
+        })
+    }
+
+    async fn run(self) -> Result<(), DynError> {
+        let Self { service_state, .. } = self;
+
+        let relay = service_state
+            .overwatch_handle
+            .relay::<Adapter::Service>()
+            .await?;
+        let sdp_relay = service_state.overwatch_handle.relay::<Sdp>().await?;
+
+        let (result_sender, result_receiver) = oneshot::channel();
+        sdp_relay
+            .send(SdpMsg::SubscribeSessionUpdates { result_sender })
+            .await
+            .map_err(|(e, _)| Box::new(e) as DynError)?;
+        let mut session_updates = BroadcastStream::new(result_receiver.await?);
+
+        // providers of the session the service started in
+        Adapter::update_membership(
+            &relay,
+            &session_providers(&sdp_relay, Adapter::SERVICE_TYPE).await?,
+        )
+        .await?;
+
+        let mut lifecycle_stream = service_state.lifecycle_handle.message_stream();
+        loop {
+            tokio::select! {
+                update = session_updates.next() => {
+                    let Some(update) = update else {
+                        return Err(DynError::from("SDP session updates stream closed"));
+                    };
+                    match update {
+                        Ok(SessionUpdate {
+                            session_number,
+                            service_type,
+                            providers,
+                        }) if service_type == Adapter::SERVICE_TYPE => {
+                            info!("Updating {service_type:?} membership for session {session_number}");
+                            Adapter::update_membership(&relay, &providers).await?;
+                        }
+                        Ok(_) => {}
+                        // only the providers of the last session matter
+                        Err(BroadcastStreamRecvError::Lagged(missed)) => {
+                            warn!("Missed {missed} SDP session updates, fetching the session providers");
+                            Adapter::update_membership(
+                                &relay,
+                                &session_providers(&sdp_relay, Adapter::SERVICE_TYPE).await?,
+                            )
+                            .await?;
+                        }
+                    }
+                }
+                Some(msg) = lifecycle_stream.next() => {
+                    if lifecycle::should_stop_service::<Self, RuntimeServiceId>(&msg) {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Providers of `service_type` active at the start of the current session,
+/// the same ones as in the last session update.
+async fn session_providers(
+    sdp_relay: &OutboundRelay<SdpMsg>,
+    service_type: ServiceType,
+) -> Result<HashMap<ProviderId, Vec<Locator>>, DynError> {
+    let (reply_channel, providers_receiver) = oneshot::channel();
+    sdp_relay
+        .send(SdpMsg::GetSessionProviders {
+            service_type,
+            reply_channel,
+        })
+        .await
+        .map_err(|(e, _)| Box::new(e) as DynError)?;
+    Ok(providers_receiver.await?)
+}
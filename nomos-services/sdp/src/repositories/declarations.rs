@@ -0,0 +1,377 @@
+use std::collections::{hash_map::Entry, HashMap};
+
+use bytes::Bytes;
+use nomos_sdp_core::{
+    ledger::{BlockUpdate, DeclarationsRepository, DeclarationsRepositoryError},
+    BlockNumber, Declaration, DeclarationId, DeclarationUpdate, Nonce, ProviderId, ProviderInfo,
+    ServiceType, StakeId,
+};
+use nomos_storage::{
+    backends::{BatchOperation, StorageBackend, StorageBatch, StorageSerde},
+    StorageMsg,
+};
+use overwatch::services::relay::OutboundRelay;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::repositories::{
+    key_bytes, SDP_DECLARATION_PREFIX, SDP_LAST_BLOCK_KEY, SDP_NONCE_PREFIX, SDP_PROVIDER_PREFIX,
+    SDP_STAKE_PREFIX,
+};
+
+/// Declarations, provider information, used nonces and locked stakes persisted
+/// through the storage service.
+///
+/// The updates of a block are written in a single storage transaction, along
+/// with the number of the block.
+pub struct StorageDeclarationsRepository<Backend>
+where
+    Backend: StorageBackend,
+{
+    storage_relay: OutboundRelay<StorageMsg<Backend>>,
+}
+
+impl<Backend> StorageDeclarationsRepository<Backend>
+where
+    Backend: StorageBatch + Send + Sync + 'static,
+    <Backend::SerdeOperator as StorageSerde>::Error: Send + 'static,
+{
+    #[must_use]
+    pub const fn new(storage_relay: OutboundRelay<StorageMsg<Backend>>) -> Self {
+        Self { storage_relay }
+    }
+
+    async fn send(&self, msg: StorageMsg<Backend>) -> Result<(), DeclarationsRepositoryError> {
+        self.storage_relay
+            .send(msg)
+            .await
+            .map_err(|(e, _)| DeclarationsRepositoryError::Other(Box::new(e)))
+    }
+
+    async fn load<Value>(&self, key: Bytes) -> Result<Option<Value>, DeclarationsRepositoryError>
+    where
+        Value: DeserializeOwned,
+    {
+        let (msg, receiver) = StorageMsg::<Backend>::new_load_message(key);
+        self.send(msg).await?;
+        receiver
+            .recv()
+            .await
+            .map_err(|e| DeclarationsRepositoryError::Other(Box::new(e)))
+    }
+}
+
+#[async_trait::async_trait]
+impl<Backend> DeclarationsRepository for StorageDeclarationsRepository<Backend>
+where
+    Backend: StorageBatch + Send + Sync + 'static,
+    <Backend::SerdeOperator as StorageSerde>::Error: Send + 'static,
+{
+    async fn get_provider_info(
+        &self,
+        provider_id: ProviderId,
+    ) -> Result<ProviderInfo, DeclarationsRepositoryError> {
+        self.load(key_bytes(SDP_PROVIDER_PREFIX, provider_id.0))
+            .await?
+            .ok_or(DeclarationsRepositoryError::ProviderNotFound(provider_id))
+    }
+
+    async fn get_declaration(
+        &self,
+        declaration_id: DeclarationId,
+    ) -> Result<Declaration, DeclarationsRepositoryError> {
+        self.load(key_bytes(SDP_DECLARATION_PREFIX, declaration_id.0))
+            .await?
+            .ok_or(DeclarationsRepositoryError::DeclarationNotFound(
+                declaration_id,
+            ))
+    }
+
+    async fn apply_block_update(
+        &self,
+        block_number: BlockNumber,
+        block_update: BlockUpdate,
+    ) -> Result<(), DeclarationsRepositoryError> {
+        let BlockUpdate {
+            providers,
+            declarations: declaration_updates,
+            nonces,
+            stakes,
+        } = block_update;
+
+        // Several providers of the block can be added to the same declaration.
+        let mut declarations = HashMap::new();
+        for DeclarationUpdate {
+            declaration_id,
+            provider_id,
+            service_type,
+            locators,
+        } in declaration_updates
+        {
+            let declaration = match declarations.entry(declaration_id) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    let declaration = match self.get_declaration(declaration_id).await {
+                        Ok(declaration) => declaration,
+                        Err(DeclarationsRepositoryError::DeclarationNotFound(_)) => Declaration {
+                            declaration_id,
+                            locators,
+                            services: HashMap::new(),
+                        },
+                        Err(e) => return Err(e),
+                    };
+                    entry.insert(declaration)
+                }
+            };
+            declaration.insert_service_provider(provider_id, service_type);
+        }
+
+        let mut operations = Vec::new();
+        for provider_info in providers {
+            operations.push(store_operation::<Backend, _>(
+                key_bytes(SDP_PROVIDER_PREFIX, provider_info.provider_id.0),
+                provider_info,
+            ));
+        }
+        for (declaration_id, declaration) in declarations {
+            operations.push(store_operation::<Backend, _>(
+                key_bytes(SDP_DECLARATION_PREFIX, declaration_id.0),
+                declaration,
+            ));
+        }
+        for (provider_id, nonce) in nonces {
+            operations.push(store_operation::<Backend, _>(
+                nonce_key(provider_id, nonce),
+                (),
+            ));
+        }
+        for stake_id in stakes {
+            operations.push(store_operation::<Backend, _>(
+                key_bytes(SDP_STAKE_PREFIX, stake_id.0),
+                (),
+            ));
+        }
+        operations.push(store_operation::<Backend, _>(
+            Bytes::from_static(SDP_LAST_BLOCK_KEY.as_bytes()),
+            block_number,
+        ));
+
+        let (msg, receiver) =
+            StorageMsg::<Backend>::new_transaction_message(Backend::batch_transaction(operations));
+        self.send(msg).await?;
+        let result = receiver
+            .into_inner()
+            .await
+            .map_err(|e| DeclarationsRepositoryError::Other(Box::new(e)))?;
+        Backend::batch_result(result).map_err(|e| DeclarationsRepositoryError::Other(Box::new(e)))
+    }
+
+    async fn check_nonce(
+        &self,
+        provider_id: ProviderId,
+        nonce: Nonce,
+    ) -> Result<(), DeclarationsRepositoryError> {
+        if self
+            .load::<()>(nonce_key(provider_id, nonce))
+            .await?
+            .is_some()
+        {
+            return Err(DeclarationsRepositoryError::DuplicateNonce);
+        }
+        Ok(())
+    }
+
+    async fn check_stake(&self, stake_id: StakeId) -> Result<(), DeclarationsRepositoryError> {
+        if self
+            .load::<()>(key_bytes(SDP_STAKE_PREFIX, stake_id.0))
+            .await?
+            .is_some()
+        {
+            return Err(DeclarationsRepositoryError::DuplicateStake);
+        }
+        Ok(())
+    }
+
+    async fn get_declarations(
+        &self,
+        service_type: ServiceType,
+    ) -> Result<Vec<Declaration>, DeclarationsRepositoryError> {
+        let (reply_channel, receiver) = tokio::sync::oneshot::channel();
+        self.send(StorageMsg::LoadPrefix {
+            prefix: Bytes::from_static(SDP_DECLARATION_PREFIX.as_bytes()),
+            reply_channel,
+        })
+        .await?;
+        let values = receiver
+            .await
+            .map_err(|e| DeclarationsRepositoryError::Other(Box::new(e)))?;
+
+        let mut declarations = Vec::new();
+        for value in values {
+            let declaration: Declaration = Backend::SerdeOperator::deserialize(value)
+                .map_err(|e| DeclarationsRepositoryError::Other(Box::new(e)))?;
+            if declaration.services.contains_key(&service_type) {
+                declarations.push(declaration);
+            }
+        }
+        Ok(declarations)
+    }
+}
+
+fn store_operation<Backend, Value>(key: Bytes, value: Value) -> BatchOperation
+where
+    Backend: StorageBackend,
+    Value: Serialize,
+{
+    BatchOperation::Store {
+        key,
+        value: Backend::SerdeOperator::serialize(value),
+    }
+}
+
+fn nonce_key(provider_id: ProviderId, nonce: Nonce) -> Bytes {
+    key_bytes(
+        SDP_NONCE_PREFIX,
+        [provider_id.0.as_slice(), &nonce].concat(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::repositories::testing::{storage_relay, TestBackend};
+
+    fn repository() -> StorageDeclarationsRepository<TestBackend> {
+        StorageDeclarationsRepository::new(storage_relay())
+    }
+
+    fn declaration_update(
+        declaration_id: DeclarationId,
+        provider_id: ProviderId,
+        service_type: ServiceType,
+    ) -> DeclarationUpdate {
+        DeclarationUpdate {
+            declaration_id,
+            provider_id,
+            service_type,
+            locators: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_apply_block_update() {
+        let repository = repository();
+        let (did, pid1, pid2) = (
+            DeclarationId([0; 32]),
+            ProviderId([1; 32]),
+            ProviderId([2; 32]),
+        );
+        let (nonce, stake) = ([3; 16], StakeId([4; 32]));
+
+        repository
+            .apply_block_update(
+                5,
+                BlockUpdate {
+                    providers: vec![
+                        ProviderInfo::new(5, pid1, did),
+                        ProviderInfo::new(5, pid2, did),
+                    ],
+                    declarations: vec![
+                        declaration_update(did, pid1, ServiceType::DataAvailability),
+                        declaration_update(did, pid2, ServiceType::BlendNetwork),
+                    ],
+                    nonces: vec![(pid1, nonce)],
+                    stakes: vec![stake],
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            repository.get_provider_info(pid1).await.unwrap(),
+            ProviderInfo::new(5, pid1, did)
+        );
+        let declaration = repository.get_declaration(did).await.unwrap();
+        assert!(declaration.has_service_provider(ServiceType::DataAvailability, pid1));
+        assert!(declaration.has_service_provider(ServiceType::BlendNetwork, pid2));
+        assert!(matches!(
+            repository.check_nonce(pid1, nonce).await,
+            Err(DeclarationsRepositoryError::DuplicateNonce)
+        ));
+        assert!(repository.check_nonce(pid2, nonce).await.is_ok());
+        assert!(matches!(
+            repository.check_stake(stake).await,
+            Err(DeclarationsRepositoryError::DuplicateStake)
+        ));
+        assert_eq!(
+            repository
+                .load::<BlockNumber>(Bytes::from_static(SDP_LAST_BLOCK_KEY.as_bytes()))
+                .await
+                .unwrap(),
+            Some(5)
+        );
+        assert_eq!(
+            repository
+                .get_declarations(ServiceType::DataAvailability)
+                .await
+                .unwrap(),
+            vec![declaration]
+        );
+        assert!(repository
+            .get_declarations(ServiceType::ExecutorNetwork)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_apply_block_update_to_existing_declaration() {
+        let repository = repository();
+        let (did, pid1, pid2) = (
+            DeclarationId([0; 32]),
+            ProviderId([1; 32]),
+            ProviderId([2; 32]),
+        );
+
+        for (block_number, provider_id, service_type) in [
+            (1, pid1, ServiceType::DataAvailability),
+            (2, pid2, ServiceType::DataAvailability),
+        ] {
+            repository
+                .apply_block_update(
+                    block_number,
+                    BlockUpdate {
+                        providers: vec![ProviderInfo::new(block_number, provider_id, did)],
+                        declarations: vec![declaration_update(did, provider_id, service_type)],
+                        ..BlockUpdate::default()
+                    },
+                )
+                .await
+                .unwrap();
+        }
+
+        let declaration = repository.get_declaration(did).await.unwrap();
+        assert_eq!(
+            declaration.services.get(&ServiceType::DataAvailability),
+            Some(&HashSet::from([pid1, pid2]))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_not_found() {
+        let repository = repository();
+        let (did, pid) = (DeclarationId([0; 32]), ProviderId([1; 32]));
+
+        assert!(matches!(
+            repository.get_provider_info(pid).await,
+            Err(DeclarationsRepositoryError::ProviderNotFound(id)) if id == pid
+        ));
+        assert!(matches!(
+            repository.get_declaration(did).await,
+            Err(DeclarationsRepositoryError::DeclarationNotFound(id)) if id == did
+        ));
+        assert!(repository.check_nonce(pid, [0; 16]).await.is_ok());
+        assert!(repository.check_stake(StakeId([0; 32])).await.is_ok());
+    }
+}
@@ -0,0 +1,104 @@
+pub mod declarations;
+pub mod services;
+
+use bytes::{Bytes, BytesMut};
+
+pub use declarations::StorageDeclarationsRepository;
+pub use services::StorageServicesRepository;
+
+/// `sdp/provider/<provider id>`: [`nomos_sdp_core::ProviderInfo`]
+pub const SDP_PROVIDER_PREFIX: &str = "sdp/provider/";
+/// `sdp/declaration/<declaration id>`: [`nomos_sdp_core::Declaration`]
+pub const SDP_DECLARATION_PREFIX: &str = "sdp/declaration/";
+/// `sdp/nonce/<provider id><nonce>`: nonces used by the providers
+pub const SDP_NONCE_PREFIX: &str = "sdp/nonce/";
+/// `sdp/stake/<stake id>`: stakes backing a declaration
+pub const SDP_STAKE_PREFIX: &str = "sdp/stake/";
+/// `sdp/service/<service type>`: [`nomos_sdp_core::ServiceParameters`]
+pub const SDP_SERVICE_PREFIX: &str = "sdp/service/";
+/// `sdp/last_block`: number of the last finalized block applied to the SDP
+/// ledger
+pub const SDP_LAST_BLOCK_KEY: &str = "sdp/last_block";
+/// `sdp/session_providers`: providers of the current session, see
+/// [`crate::SdpMsg::GetSessionProviders`]
+pub const SDP_SESSION_PROVIDERS_KEY: &str = "sdp/session_providers";
+
+pub fn key_bytes(prefix: &str, id: impl AsRef<[u8]>) -> Bytes {
+    let mut buffer = BytesMut::new();
+
+    buffer.extend_from_slice(prefix.as_bytes());
+    buffer.extend_from_slice(id.as_ref());
+
+    buffer.freeze()
+}
+
+#[cfg(test)]
+pub mod testing {
+    use bytes::Bytes;
+    use nomos_core::wire;
+    use nomos_storage::{
+        backends::{mock::MockStorage, StorageBackend as _, StorageSerde},
+        StorageMsg,
+    };
+    use overwatch::services::relay::{relay, OutboundRelay};
+    use serde::{de::DeserializeOwned, Serialize};
+
+    pub struct Wire;
+
+    impl StorageSerde for Wire {
+        type Error = wire::Error;
+
+        fn serialize<T: Serialize>(value: T) -> Bytes {
+            wire::serialize(&value).unwrap().into()
+        }
+
+        fn deserialize<T: DeserializeOwned>(buff: Bytes) -> Result<T, Self::Error> {
+            wire::deserialize(&buff)
+        }
+    }
+
+    pub type TestBackend = MockStorage<Wire>;
+
+    /// Relay to an in memory backend, served the way the storage service
+    /// does.
+    pub fn storage_relay() -> OutboundRelay<StorageMsg<TestBackend>> {
+        let (mut inbound_relay, outbound_relay) = relay::<StorageMsg<TestBackend>>(16);
+        let mut backend = TestBackend::new(()).unwrap();
+        tokio::spawn(async move {
+            while let Some(msg) = inbound_relay.recv().await {
+                match msg {
+                    StorageMsg::Load { key, reply_channel } => {
+                        reply_channel
+                            .send(backend.load(&key).await.unwrap())
+                            .unwrap();
+                    }
+                    StorageMsg::LoadPrefix {
+                        prefix,
+                        reply_channel,
+                    } => {
+                        reply_channel
+                            .send(backend.load_prefix(&prefix).await.unwrap())
+                            .unwrap();
+                    }
+                    StorageMsg::Store { key, value } => {
+                        backend.store(key, value).await.unwrap();
+                    }
+                    StorageMsg::Remove { key, reply_channel } => {
+                        reply_channel
+                            .send(backend.remove(&key).await.unwrap())
+                            .unwrap();
+                    }
+                    StorageMsg::Execute {
+                        transaction,
+                        reply_channel,
+                    } => {
+                        reply_channel
+                            .send(backend.execute(transaction).await.unwrap())
+                            .unwrap();
+                    }
+                }
+            }
+        });
+        outbound_relay
+    }
+}
@@ -0,0 +1,153 @@
+use std::{collections::HashMap, marker::PhantomData};
+
+use bytes::Bytes;
+use nomos_sdp_core::{
+    ledger::{ServicesRepository, ServicesRepositoryError},
+    ServiceParameters, ServiceType,
+};
+use nomos_storage::{
+    backends::{StorageBackend, StorageSerde},
+    StorageMsg,
+};
+use overwatch::services::relay::OutboundRelay;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::repositories::{key_bytes, SDP_SERVICE_PREFIX};
+
+/// Service parameters persisted through the storage service.
+pub struct StorageServicesRepository<Backend, ContractAddress>
+where
+    Backend: StorageBackend,
+{
+    storage_relay: OutboundRelay<StorageMsg<Backend>>,
+    _contract_address: PhantomData<fn() -> ContractAddress>,
+}
+
+impl<Backend, ContractAddress> StorageServicesRepository<Backend, ContractAddress>
+where
+    Backend: StorageBackend + Send + Sync + 'static,
+    ContractAddress: Serialize + DeserializeOwned + Send + Sync,
+{
+    #[must_use]
+    pub const fn new(storage_relay: OutboundRelay<StorageMsg<Backend>>) -> Self {
+        Self {
+            storage_relay,
+            _contract_address: PhantomData,
+        }
+    }
+
+    /// Stores the parameters of the services which have none yet. Parameters
+    /// already persisted are kept.
+    pub async fn init_parameters(
+        &self,
+        service_params: HashMap<ServiceType, ServiceParameters<ContractAddress>>,
+    ) -> Result<(), ServicesRepositoryError> {
+        for (service_type, params) in service_params {
+            match self.get_parameters(service_type).await {
+                Ok(_) => {}
+                Err(ServicesRepositoryError::NotFound(_)) => {
+                    self.send(StorageMsg::<Backend>::new_store_message(
+                        service_key(service_type),
+                        params,
+                    ))
+                    .await?;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    async fn send(&self, msg: StorageMsg<Backend>) -> Result<(), ServicesRepositoryError> {
+        self.storage_relay
+            .send(msg)
+            .await
+            .map_err(|(e, _)| ServicesRepositoryError::Other(Box::new(e)))
+    }
+}
+
+#[async_trait::async_trait]
+impl<Backend, ContractAddress> ServicesRepository
+    for StorageServicesRepository<Backend, ContractAddress>
+where
+    Backend: StorageBackend + Send + Sync + 'static,
+    ContractAddress: Serialize + DeserializeOwned + Send + Sync,
+{
+    type ContractAddress = ContractAddress;
+
+    async fn get_parameters(
+        &self,
+        service_type: ServiceType,
+    ) -> Result<ServiceParameters<Self::ContractAddress>, ServicesRepositoryError> {
+        let (msg, receiver) = StorageMsg::<Backend>::new_load_message(service_key(service_type));
+        self.send(msg).await?;
+        receiver
+            .recv()
+            .await
+            .map_err(|e| ServicesRepositoryError::Other(Box::new(e)))?
+            .ok_or(ServicesRepositoryError::NotFound(service_type))
+    }
+}
+
+fn service_key(service_type: ServiceType) -> Bytes {
+    let service = match service_type {
+        ServiceType::BlendNetwork => "blend_network",
+        ServiceType::DataAvailability => "data_availability",
+        ServiceType::ExecutorNetwork => "executor_network",
+        ServiceType::GenericRestaking => "generic_restaking",
+    };
+    key_bytes(SDP_SERVICE_PREFIX, service)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::testing::{storage_relay, TestBackend};
+
+    const fn parameters(lock_period: u64) -> ServiceParameters<[u8; 32]> {
+        ServiceParameters {
+            lock_period,
+            inactivity_period: 20,
+            retention_period: 30,
+            reward_contract: [0; 32],
+            timestamp: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_init_parameters() {
+        let repository = StorageServicesRepository::<TestBackend, [u8; 32]>::new(storage_relay());
+
+        repository
+            .init_parameters(HashMap::from([(
+                ServiceType::DataAvailability,
+                parameters(10),
+            )]))
+            .await
+            .unwrap();
+        // parameters already persisted are kept
+        repository
+            .init_parameters(HashMap::from([
+                (ServiceType::DataAvailability, parameters(20)),
+                (ServiceType::BlendNetwork, parameters(30)),
+            ]))
+            .await
+            .unwrap();
+
+        for (service_type, lock_period) in [
+            (ServiceType::DataAvailability, 10),
+            (ServiceType::BlendNetwork, 30),
+        ] {
+            let params = repository.get_parameters(service_type).await.unwrap();
+            assert_eq!(params.lock_period, lock_period);
+        }
+        assert!(matches!(
+            repository
+                .get_parameters(ServiceType::ExecutorNetwork)
+                .await,
+            Err(ServicesRepositoryError::NotFound(
+                ServiceType::ExecutorNetwork
+            ))
+        ));
+    }
+}
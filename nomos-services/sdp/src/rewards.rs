@@ -0,0 +1,44 @@
+use std::{fmt::Debug, marker::PhantomData};
+
+use nomos_sdp_core::{
+    ledger::{RewardsRequestSender, RewardsSenderError},
+    RewardMessage,
+};
+
+/// Rewards sender which only records the requests in the logs, until reward
+/// contracts are available.
+pub struct LoggingRewardsSender<ContractAddress, Metadata> {
+    _phantom: PhantomData<(ContractAddress, Metadata)>,
+}
+
+impl<ContractAddress, Metadata> Default for LoggingRewardsSender<ContractAddress, Metadata> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<ContractAddress, Metadata> RewardsRequestSender
+    for LoggingRewardsSender<ContractAddress, Metadata>
+where
+    ContractAddress: Debug + Send + Sync + 'static,
+    Metadata: Send + Sync + 'static,
+{
+    type ContractAddress = ContractAddress;
+    type Metadata = Metadata;
+
+    async fn request_reward(
+        &self,
+        reward_contract: Self::ContractAddress,
+        reward_message: RewardMessage<Self::Metadata>,
+    ) -> Result<(), RewardsSenderError<Self::ContractAddress>> {
+        tracing::info!(
+            "reward requested for provider {:?} of {:?} to contract {reward_contract:?}",
+            reward_message.provider_id,
+            reward_message.service_type,
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,276 @@
+use std::{collections::HashMap, num::NonZeroU64};
+
+use bytes::Bytes;
+use nomos_sdp_core::{BlockNumber, Locator, ProviderId, ServiceType};
+use nomos_storage::{
+    backends::{StorageBatch, StorageSerde},
+    StorageMsg,
+};
+use overwatch::{services::relay::OutboundRelay, DynError};
+use serde::{Deserialize, Serialize};
+
+use crate::{repositories::SDP_SESSION_PROVIDERS_KEY, Ledger};
+
+/// Providers of every service active at the first block of a session, with
+/// their locators.
+///
+/// Providers are only evaluated at session boundaries, so every node sees the
+/// same membership for the whole session, whenever it asks for it.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionProviders {
+    pub session_number: u64,
+    pub providers: HashMap<ServiceType, HashMap<ProviderId, Vec<Locator>>>,
+}
+
+impl SessionProviders {
+    /// Providers of the session starting at `block_number`.
+    pub async fn compute<Backend, Metadata, Proof>(
+        ledger: &Ledger<Backend, Metadata, Proof>,
+        block_number: BlockNumber,
+        session_number: u64,
+        service_types: &[ServiceType],
+    ) -> Result<Self, DynError>
+    where
+        Backend: StorageBatch + Send + Sync + 'static,
+        <Backend::SerdeOperator as StorageSerde>::Error: Send + 'static,
+        Metadata: Send + Sync + 'static,
+        Proof: Sync,
+    {
+        let mut providers = HashMap::new();
+        for service_type in service_types {
+            let service_providers = ledger
+                .active_providers(block_number, *service_type)
+                .await
+                .map_err(|e| {
+                    DynError::from(format!(
+                        "Could not get active providers of session {session_number}: {e}"
+                    ))
+                })?;
+            providers.insert(*service_type, service_providers);
+        }
+        Ok(Self {
+            session_number,
+            providers,
+        })
+    }
+
+    /// Providers of the session `last_block` belongs to.
+    ///
+    /// The providers persisted at the session boundary are used if they are
+    /// the ones of that session. Otherwise they are evaluated at the first
+    /// block of the session, which is exact as long as no later block was
+    /// applied, that is if the node stopped right after the boundary.
+    pub async fn restore<Backend, Metadata, Proof>(
+        ledger: &Ledger<Backend, Metadata, Proof>,
+        storage_relay: &OutboundRelay<StorageMsg<Backend>>,
+        last_block: Option<BlockNumber>,
+        session_length: NonZeroU64,
+        service_types: &[ServiceType],
+    ) -> Result<Self, DynError>
+    where
+        Backend: StorageBatch + Send + Sync + 'static,
+        <Backend::SerdeOperator as StorageSerde>::Error: Send + 'static,
+        Metadata: Send + Sync + 'static,
+        Proof: Sync,
+    {
+        let last_block = last_block.unwrap_or_default();
+        let session_number = last_block / session_length;
+        if let Some(session) = Self::load(storage_relay).await? {
+            if session.session_number == session_number {
+                return Ok(session);
+            }
+        }
+        Self::compute(
+            ledger,
+            last_block - last_block % session_length,
+            session_number,
+            service_types,
+        )
+        .await
+    }
+
+    /// Persists the providers, so that they survive restarts in the middle
+    /// of the session.
+    pub async fn store<Backend>(
+        &self,
+        storage_relay: &OutboundRelay<StorageMsg<Backend>>,
+    ) -> Result<(), DynError>
+    where
+        Backend: StorageBatch + Send + Sync + 'static,
+    {
+        storage_relay
+            .send(StorageMsg::<Backend>::new_store_message(
+                Bytes::from_static(SDP_SESSION_PROVIDERS_KEY.as_bytes()),
+                self,
+            ))
+            .await
+            .map_err(|(e, _)| Box::new(e) as DynError)
+    }
+
+    async fn load<Backend>(
+        storage_relay: &OutboundRelay<StorageMsg<Backend>>,
+    ) -> Result<Option<Self>, DynError>
+    where
+        Backend: StorageBatch + Send + Sync + 'static,
+    {
+        let (msg, receiver) = StorageMsg::<Backend>::new_load_message(Bytes::from_static(
+            SDP_SESSION_PROVIDERS_KEY.as_bytes(),
+        ));
+        storage_relay
+            .send(msg)
+            .await
+            .map_err(|(e, _)| Box::new(e) as DynError)?;
+        Ok(receiver.recv().await?)
+    }
+
+    /// Providers of `service_type`, none if the service is unknown.
+    #[must_use]
+    pub fn service_providers(
+        &self,
+        service_type: ServiceType,
+    ) -> HashMap<ProviderId, Vec<Locator>> {
+        self.providers
+            .get(&service_type)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nomos_sdp_core::{
+        ledger::{BlockUpdate, DeclarationsRepository as _, SdpLedger},
+        DeclarationId, DeclarationUpdate, MinStake, ProviderInfo, ServiceParameters,
+    };
+
+    use super::*;
+    use crate::{
+        repositories::{
+            testing::{storage_relay, TestBackend},
+            StorageDeclarationsRepository, StorageServicesRepository,
+        },
+        rewards::LoggingRewardsSender,
+        stakes::LedgerStakesVerifier,
+        ContractAddress,
+    };
+
+    const SESSION_LENGTH: NonZeroU64 = NonZeroU64::new(10).unwrap();
+    const SERVICE_TYPES: [ServiceType; 1] = [ServiceType::BlendNetwork];
+
+    type TestLedger = Ledger<TestBackend, [u8; 32], Vec<u8>>;
+
+    async fn ledger(storage_relay: &OutboundRelay<StorageMsg<TestBackend>>) -> TestLedger {
+        let services_repo =
+            StorageServicesRepository::<_, ContractAddress>::new(storage_relay.clone());
+        services_repo
+            .init_parameters(HashMap::from([(
+                ServiceType::BlendNetwork,
+                ServiceParameters {
+                    lock_period: 10,
+                    inactivity_period: 20,
+                    retention_period: 30,
+                    reward_contract: [0; 32],
+                    timestamp: 0,
+                },
+            )]))
+            .await
+            .unwrap();
+        SdpLedger::new(
+            StorageDeclarationsRepository::new(storage_relay.clone()),
+            LoggingRewardsSender::default(),
+            services_repo,
+            LedgerStakesVerifier::new(MinStake {
+                threshold: 1,
+                timestamp: 0,
+            }),
+        )
+    }
+
+    fn declare(block_number: BlockNumber, provider_id: ProviderId) -> BlockUpdate {
+        let declaration_id = DeclarationId(provider_id.0);
+        BlockUpdate {
+            providers: vec![ProviderInfo::new(block_number, provider_id, declaration_id)],
+            declarations: vec![DeclarationUpdate {
+                declaration_id,
+                provider_id,
+                service_type: ServiceType::BlendNetwork,
+                locators: Vec::new(),
+            }],
+            nonces: Vec::new(),
+            stakes: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_restore_in_the_middle_of_a_session() {
+        let storage_relay = storage_relay();
+        let ledger = ledger(&storage_relay).await;
+        let declarations = StorageDeclarationsRepository::new(storage_relay.clone());
+        let (pid1, pid2) = (ProviderId([1; 32]), ProviderId([2; 32]));
+
+        declarations
+            .apply_block_update(5, declare(5, pid1))
+            .await
+            .unwrap();
+        // the node restarting right after the boundary evaluates the providers
+        // of the session the way the boundary update did
+        let boundary_session = SessionProviders::compute(&ledger, 10, 1, &SERVICE_TYPES)
+            .await
+            .unwrap();
+        assert_eq!(
+            SessionProviders::restore(
+                &ledger,
+                &storage_relay,
+                Some(10),
+                SESSION_LENGTH,
+                &SERVICE_TYPES
+            )
+            .await
+            .unwrap(),
+            boundary_session
+        );
+        boundary_session.store(&storage_relay).await.unwrap();
+
+        // providers joining and leaving in the middle of the session only
+        // count from the next one
+        let mut update = declare(12, pid2);
+        update.providers.push(ProviderInfo {
+            withdrawn: Some(12),
+            ..ProviderInfo::new(5, pid1, DeclarationId(pid1.0))
+        });
+        declarations.apply_block_update(12, update).await.unwrap();
+
+        let session = SessionProviders::restore(
+            &ledger,
+            &storage_relay,
+            Some(15),
+            SESSION_LENGTH,
+            &SERVICE_TYPES,
+        )
+        .await
+        .unwrap();
+        assert_eq!(session, boundary_session);
+        assert_eq!(
+            session.service_providers(ServiceType::BlendNetwork),
+            HashMap::from([(pid1, Vec::new())])
+        );
+        assert!(session
+            .service_providers(ServiceType::DataAvailability)
+            .is_empty());
+
+        // the next session is evaluated again
+        let next_session = SessionProviders::restore(
+            &ledger,
+            &storage_relay,
+            Some(20),
+            SESSION_LENGTH,
+            &SERVICE_TYPES,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            next_session.service_providers(ServiceType::BlendNetwork),
+            HashMap::from([(pid2, Vec::new())])
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use std::marker::PhantomData;
+
+use nomos_core::{proofs::stake_proof::Risc0StakeProof, staking::NMO_UNIT, wire};
+use nomos_ledger::{stake_proof::StakeProof, LedgerState};
+use nomos_sdp_core::{
+    ledger::{StakesVerifier, StakesVerifierError},
+    MinStake, ProviderId, StakeId,
+};
+use serde::de::DeserializeOwned;
+
+/// Stakes verifier checking the proof of funds of declarations against the
+/// ledger state of the block including them.
+///
+/// Proofs of funds are serialized [`StakeProof`]s, bound to the provider id
+/// and proving ownership of an unspent note of at least the minimum stake.
+/// The nullifier of the note identifies the stake, so a note backs a single
+/// declaration.
+pub struct LedgerStakesVerifier<Proof, Stake = Risc0StakeProof> {
+    ledger_state: Option<LedgerState>,
+    min_stake: MinStake,
+    _proof: PhantomData<(Proof, Stake)>,
+}
+
+impl<Proof, Stake> LedgerStakesVerifier<Proof, Stake> {
+    #[must_use]
+    pub const fn new(min_stake: MinStake) -> Self {
+        Self {
+            ledger_state: None,
+            min_stake,
+            _proof: PhantomData,
+        }
+    }
+
+    /// Sets the ledger state the following proofs are verified against.
+    /// Proofs are rejected until the first ledger state is known.
+    pub fn update_ledger_state(&mut self, ledger_state: LedgerState) {
+        self.ledger_state = Some(ledger_state);
+    }
+}
+
+#[async_trait::async_trait]
+impl<Proof, Stake> StakesVerifier for LedgerStakesVerifier<Proof, Stake>
+where
+    Proof: AsRef<[u8]> + Send + Sync + 'static,
+    Stake: StakeProof + DeserializeOwned + Send + Sync + 'static,
+{
+    type Proof = Proof;
+
+    async fn verify(
+        &self,
+        provider_id: ProviderId,
+        proof: Self::Proof,
+    ) -> Result<StakeId, StakesVerifierError> {
+        let Some(ledger_state) = &self.ledger_state else {
+            return Err(StakesVerifierError::Other(
+                "Unknown ledger state".to_owned().into(),
+            ));
+        };
+        let proof: Stake =
+            wire::deserialize(proof.as_ref()).map_err(|_| StakesVerifierError::InvalidProof)?;
+        ledger_state
+            .verify_stake(&proof, provider_id.0, NMO_UNIT, self.min_stake.threshold)
+            .map_err(|_| StakesVerifierError::InvalidProof)?;
+        Ok(StakeId(*proof.nullifier().as_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cl::{InputWitness, NoteWitness, NullifierSecret};
+    use nomos_ledger::stake_proof::WitnessStakeProof;
+    use rand::thread_rng;
+
+    use super::*;
+
+    const NF_SK: NullifierSecret = NullifierSecret([1; 16]);
+
+    fn stake_proof(
+        ledger_state: &LedgerState,
+        note: NoteWitness,
+        provider_id: ProviderId,
+    ) -> Vec<u8> {
+        let index = ledger_state
+            .spend_commitments()
+            .index_of(&note.commit(NF_SK.commit()))
+            .unwrap();
+        let cm_path = ledger_state.spend_commitments().witness(index).unwrap();
+        wire::serialize(&WitnessStakeProof {
+            input: InputWitness::new(note, NF_SK, cm_path),
+            message: provider_id.0,
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_verify_stake_proof() {
+        let provider_id = ProviderId([0; 32]);
+        let note = NoteWitness::basic(10, NMO_UNIT, &mut thread_rng());
+        let ledger_state = LedgerState::from_commitments([note.commit(NF_SK.commit())], 10);
+        let proof = stake_proof(&ledger_state, note, provider_id);
+
+        let mut verifier = LedgerStakesVerifier::<_, WitnessStakeProof>::new(MinStake {
+            threshold: 10,
+            timestamp: 0,
+        });
+        assert!(matches!(
+            verifier.verify(provider_id, proof.clone()).await,
+            Err(StakesVerifierError::Other(_))
+        ));
+
+        verifier.update_ledger_state(ledger_state);
+        let nullifier = cl::Nullifier::new(NF_SK, note.commit(NF_SK.commit()));
+        assert_eq!(
+            verifier.verify(provider_id, proof.clone()).await.unwrap(),
+            StakeId(*nullifier.as_bytes())
+        );
+        assert!(matches!(
+            verifier.verify(ProviderId([1; 32]), proof.clone()).await,
+            Err(StakesVerifierError::InvalidProof)
+        ));
+        assert!(matches!(
+            verifier.verify(provider_id, vec![0; 4]).await,
+            Err(StakesVerifierError::InvalidProof)
+        ));
+
+        let verifier = LedgerStakesVerifier {
+            min_stake: MinStake {
+                threshold: 11,
+                timestamp: 0,
+            },
+            ..verifier
+        };
+        assert!(matches!(
+            verifier.verify(provider_id, proof).await,
+            Err(StakesVerifierError::InvalidProof)
+        ));
+    }
+}
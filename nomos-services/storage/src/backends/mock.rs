@@ -4,7 +4,7 @@ use async_trait::async_trait;
 use bytes::Bytes;
 use thiserror::Error;
 
-use super::{StorageBackend, StorageSerde, StorageTransaction};
+use super::{BatchOperation, StorageBackend, StorageBatch, StorageSerde, StorageTransaction};
 
 #[derive(Debug, Error)]
 #[error("Errors in MockStorage should not happen")]
@@ -52,8 +52,18 @@ impl<SerdeOp: StorageSerde + Send + Sync + 'static> StorageBackend for MockStora
         Ok(self.inner.get(key).cloned())
     }
 
-    async fn load_prefix(&mut self, _key: &[u8]) -> Result<Vec<Bytes>, Self::Error> {
-        unimplemented!()
+    async fn load_prefix(&mut self, prefix: &[u8]) -> Result<Vec<Bytes>, Self::Error> {
+        let mut entries: Vec<_> = self
+            .inner
+            .iter()
+            .filter(|(key, _)| key.starts_with(prefix))
+            .collect();
+        // Keys are sorted like in the persistent backends.
+        entries.sort_unstable_by_key(|(key, _)| *key);
+        Ok(entries
+            .into_iter()
+            .map(|(_, value)| value.clone())
+            .collect())
     }
 
     async fn remove(&mut self, key: &[u8]) -> Result<Option<Bytes>, Self::Error> {
@@ -65,3 +75,26 @@ impl<SerdeOp: StorageSerde + Send + Sync + 'static> StorageBackend for MockStora
         Ok(())
     }
 }
+
+impl<SerdeOp: StorageSerde + Send + Sync + 'static> StorageBatch for MockStorage<SerdeOp> {
+    fn batch_transaction(operations: Vec<BatchOperation>) -> Self::Transaction {
+        Box::new(move |inner| {
+            for operation in operations.clone() {
+                match operation {
+                    BatchOperation::Store { key, value } => {
+                        inner.insert(key, value);
+                    }
+                    BatchOperation::Remove { key } => {
+                        inner.remove(&key);
+                    }
+                }
+            }
+        })
+    }
+
+    fn batch_result(
+        (): <Self::Transaction as StorageTransaction>::Result,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
@@ -28,6 +28,25 @@ pub trait StorageTransaction: Send + Sync {
     type Transaction: Send + Sync;
 }
 
+/// A write applied as part of a batch transaction
+#[derive(Clone, Debug)]
+pub enum BatchOperation {
+    Store { key: Bytes, value: Bytes },
+    Remove { key: Bytes },
+}
+
+/// Backends able to apply a batch of writes atomically through
+/// [`StorageBackend::execute`]
+pub trait StorageBatch: StorageBackend {
+    /// Build a transaction applying all the operations, or none of them
+    fn batch_transaction(operations: Vec<BatchOperation>) -> Self::Transaction;
+    /// Check the result of a transaction built by
+    /// [`StorageBatch::batch_transaction`]
+    fn batch_result(
+        result: <Self::Transaction as StorageTransaction>::Result,
+    ) -> Result<(), Self::Error>;
+}
+
 /// Main storage functionality trait
 #[async_trait]
 pub trait StorageBackend: Sized {
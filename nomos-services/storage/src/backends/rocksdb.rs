@@ -2,11 +2,11 @@ use std::{marker::PhantomData, path::PathBuf, sync::Arc};
 
 use async_trait::async_trait;
 use bytes::Bytes;
-pub use rocksdb::Error;
-use rocksdb::{Options, DB};
+pub use rocksdb::{Error, DB};
+use rocksdb::{Options, WriteBatch};
 use serde::{Deserialize, Serialize};
 
-use super::{StorageBackend, StorageSerde, StorageTransaction};
+use super::{BatchOperation, StorageBackend, StorageBatch, StorageSerde, StorageTransaction};
 
 /// Rocks backend setting
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -22,15 +22,22 @@ pub struct RocksBackendSettings {
 // support open by read-only mode. Thus, we cannot open the same db in two or
 // more processes.
 pub struct Transaction {
-    rocks: Arc<DB>,
     #[expect(clippy::type_complexity, reason = "TODO: Address this at some point.")]
     executor: Box<dyn FnOnce(&DB) -> Result<Option<Bytes>, Error> + Send + Sync>,
 }
 
 impl Transaction {
+    pub fn new(
+        executor: impl FnOnce(&DB) -> Result<Option<Bytes>, Error> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            executor: Box::new(executor),
+        }
+    }
+
     /// Execute a function over the transaction
-    pub fn execute(self) -> Result<Option<Bytes>, Error> {
-        (self.executor)(&self.rocks)
+    pub fn execute(self, rocks: &DB) -> Result<Option<Bytes>, Error> {
+        (self.executor)(rocks)
     }
 }
 
@@ -50,10 +57,7 @@ impl<SerdeOp> RocksBackend<SerdeOp> {
         &self,
         executor: impl FnOnce(&DB) -> Result<Option<Bytes>, Error> + Send + Sync + 'static,
     ) -> Transaction {
-        Transaction {
-            rocks: Arc::clone(&self.rocks),
-            executor: Box::new(executor),
-        }
+        Transaction::new(executor)
     }
 }
 
@@ -124,6 +128,8 @@ impl<SerdeOp: StorageSerde + Send + Sync + 'static> StorageBackend for RocksBack
 
         for item in iter {
             match item {
+                // The iterator starts at the prefix but is not bounded by it.
+                Ok((key, _value)) if !key.starts_with(prefix) => break,
                 Ok((_key, value)) => {
                     values.push(Bytes::from(value.to_vec()));
                 }
@@ -147,7 +153,28 @@ impl<SerdeOp: StorageSerde + Send + Sync + 'static> StorageBackend for RocksBack
         &mut self,
         transaction: Self::Transaction,
     ) -> Result<<Self::Transaction as StorageTransaction>::Result, Self::Error> {
-        Ok(transaction.execute())
+        Ok(transaction.execute(&self.rocks))
+    }
+}
+
+impl<SerdeOp: StorageSerde + Send + Sync + 'static> StorageBatch for RocksBackend<SerdeOp> {
+    fn batch_transaction(operations: Vec<BatchOperation>) -> Self::Transaction {
+        Transaction::new(move |db| {
+            let mut batch = WriteBatch::default();
+            for operation in operations {
+                match operation {
+                    BatchOperation::Store { key, value } => batch.put(key, value),
+                    BatchOperation::Remove { key } => batch.delete(key),
+                }
+            }
+            db.write(batch).map(|()| None)
+        })
+    }
+
+    fn batch_result(
+        result: <Self::Transaction as StorageTransaction>::Result,
+    ) -> Result<(), Self::Error> {
+        result.map(|_| ())
     }
 }
 
@@ -206,6 +233,55 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_batch_transaction(
+    ) -> Result<(), <RocksBackend<NoStorageSerde> as StorageBackend>::Error> {
+        let temp_path = TempDir::new().unwrap();
+        let settings = RocksBackendSettings {
+            db_path: temp_path.path().to_path_buf(),
+            read_only: false,
+            column_family: None,
+        };
+
+        let mut db: RocksBackend<NoStorageSerde> = RocksBackend::new(settings)?;
+        db.store("foo".into(), "bar".into()).await?;
+        let txn = RocksBackend::<NoStorageSerde>::batch_transaction(vec![
+            BatchOperation::Store {
+                key: "baz".into(),
+                value: "qux".into(),
+            },
+            BatchOperation::Remove { key: "foo".into() },
+        ]);
+        RocksBackend::<NoStorageSerde>::batch_result(db.execute(txn).await?)?;
+
+        assert_eq!(db.load(b"foo").await?, None);
+        assert_eq!(db.load(b"baz").await?, Some("qux".into()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_load_prefix(
+    ) -> Result<(), <RocksBackend<NoStorageSerde> as StorageBackend>::Error> {
+        let temp_path = TempDir::new().unwrap();
+        let settings = RocksBackendSettings {
+            db_path: temp_path.path().to_path_buf(),
+            read_only: false,
+            column_family: None,
+        };
+
+        let mut db: RocksBackend<NoStorageSerde> = RocksBackend::new(settings)?;
+        db.store("a/1".into(), "1".into()).await?;
+        db.store("b/1".into(), "2".into()).await?;
+        db.store("b/2".into(), "3".into()).await?;
+        db.store("c/1".into(), "4".into()).await?;
+
+        let values = db.load_prefix(b"b/").await?;
+        assert_eq!(values, vec![Bytes::from("2"), Bytes::from("3")]);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_multi_readers_single_writer(
     ) -> Result<(), <RocksBackend<NoStorageSerde> as StorageBackend>::Error> {
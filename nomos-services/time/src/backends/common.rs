@@ -23,9 +23,17 @@ pub fn slot_timer(
                 Some(current_slot),
                 |&slot| Some(slot + 1),
             )))
-            .map(move |(_, slot)| SlotTick {
-                epoch: epoch_config.epoch(slot, base_period_length),
-                slot,
-            }),
+            .map(move |(_, slot)| slot_tick(slot, epoch_config, base_period_length)),
     ))
 }
+
+pub fn slot_tick(
+    slot: Slot,
+    epoch_config: EpochConfig,
+    base_period_length: NonZero<u64>,
+) -> SlotTick {
+    SlotTick {
+        epoch: epoch_config.epoch(slot, base_period_length),
+        slot,
+    }
+}
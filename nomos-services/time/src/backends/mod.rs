@@ -7,11 +7,13 @@ pub mod system_time;
 pub use ntp::{NtpTimeBackend, NtpTimeBackendSettings};
 pub use system_time::{SystemTimeBackend, SystemTimeBackendSettings};
 
-use crate::EpochSlotTickStream;
+use crate::{EpochSlotTickStream, SlotTick};
 
 /// Abstraction over slot ticking systems
 pub trait TimeBackend {
     type Settings;
     fn init(settings: Self::Settings) -> Self;
+    /// Slot of the backend clock at the time of the call
+    fn current_slot(&self) -> SlotTick;
     fn tick_stream(self) -> EpochSlotTickStream;
 }
@@ -20,7 +20,7 @@ use tokio_stream::wrappers::IntervalStream;
 
 use crate::{
     backends::{
-        common::slot_timer,
+        common::{slot_tick, slot_timer},
         ntp::async_client::{AsyncNTPClient, NTPClientSettings},
         TimeBackend,
     },
@@ -60,6 +60,15 @@ impl TimeBackend for NtpTimeBackend {
         Self { settings, client }
     }
 
+    // the local clock is used until the tick stream synchronizes with the ntp server
+    fn current_slot(&self) -> SlotTick {
+        slot_tick(
+            Slot::from_offset_and_config(OffsetDateTime::now_utc(), self.settings.slot_config),
+            self.settings.epoch_config,
+            self.settings.base_period_length,
+        )
+    }
+
     fn tick_stream(self) -> EpochSlotTickStream {
         let Self { settings, client } = self;
         let mut update_interval = interval(settings.update_interval);
@@ -4,8 +4,11 @@ use cryptarchia_engine::{time::SlotConfig, EpochConfig, Slot};
 use time::OffsetDateTime;
 
 use crate::{
-    backends::{common::slot_timer, TimeBackend},
-    EpochSlotTickStream,
+    backends::{
+        common::{slot_tick, slot_timer},
+        TimeBackend,
+    },
+    EpochSlotTickStream, SlotTick,
 };
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -27,6 +30,14 @@ impl TimeBackend for SystemTimeBackend {
         Self { settings }
     }
 
+    fn current_slot(&self) -> SlotTick {
+        slot_tick(
+            Slot::from_offset_and_config(OffsetDateTime::now_utc(), self.settings.slot_config),
+            self.settings.epoch_config,
+            self.settings.base_period_length,
+        )
+    }
+
     fn tick_stream(self) -> EpochSlotTickStream {
         let Self { settings } = self;
         let local_date = OffsetDateTime::now_utc();
@@ -53,11 +64,8 @@ mod test {
         TimeBackend,
     };
 
-    #[tokio::test]
-    async fn test_stream() {
-        const SAMPLE_SIZE: u64 = 5;
-        let expected: Vec<_> = (0..SAMPLE_SIZE).map(Slot::from).collect();
-        let settings = SystemTimeBackendSettings {
+    fn settings() -> SystemTimeBackendSettings {
+        SystemTimeBackendSettings {
             slot_config: SlotConfig {
                 slot_duration: Duration::from_secs(1),
                 chain_start_time: OffsetDateTime::now_utc(),
@@ -68,8 +76,22 @@ mod test {
                 epoch_period_nonce_stabilization: NonZero::new(4).unwrap(),
             },
             base_period_length: NonZero::new(10).unwrap(),
-        };
+        }
+    }
+
+    #[test]
+    fn test_current_slot() {
+        let mut settings = settings();
+        settings.slot_config.chain_start_time -= Duration::from_secs(3);
         let backend = SystemTimeBackend::init(settings);
+        assert_eq!(backend.current_slot().slot, Slot::from(3));
+    }
+
+    #[tokio::test]
+    async fn test_stream() {
+        const SAMPLE_SIZE: u64 = 5;
+        let expected: Vec<_> = (0..SAMPLE_SIZE).map(Slot::from).collect();
+        let backend = SystemTimeBackend::init(settings());
         let stream = backend.tick_stream();
         let result: Vec<_> = stream
             .take(SAMPLE_SIZE as usize)
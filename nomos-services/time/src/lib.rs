@@ -39,12 +39,15 @@ pub enum TimeServiceMessage {
     Subscribe {
         sender: oneshot::Sender<EpochSlotTickStream>,
     },
+    /// Slot of the last tick, or of the clock if no tick happened yet
+    CurrentSlot { sender: oneshot::Sender<SlotTick> },
 }
 
 impl Debug for TimeServiceMessage {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Subscribe { .. } => f.write_str("New time service subscription"),
+            Self::CurrentSlot { .. } => f.write_str("Current slot request"),
         }
     }
 }
@@ -101,6 +104,7 @@ where
         let Self { state, backend } = self;
         let mut inbound_relay = state.inbound_relay;
         let mut lifecycle_relay = state.lifecycle_handle.message_stream();
+        let mut current_slot = backend.current_slot();
         let mut tick_stream = backend.tick_stream();
 
         // 3 slots buffer should be enough
@@ -125,9 +129,15 @@ where
                                 error!("Error subscribing to time event: Couldn't send back a response");
                             };
                         }
+                        TimeServiceMessage::CurrentSlot { sender } => {
+                            if let Err(_e) = sender.send(current_slot.clone()) {
+                                error!("Error sending current slot: Couldn't send back a response");
+                            };
+                        }
                     }
                 }
                 Some(slot_tick) = tick_stream.next() => {
+                    current_slot = slot_tick.clone();
                     if let Err(e) = broadcast_sender.send(slot_tick) {
                         error!("Error updating slot tick: {e}");
                     }
@@ -267,6 +267,7 @@ mod cfgsync_tests {
                 dispersal_factor: 1,
                 num_samples: 1,
                 num_subnets: 2,
+                max_blob_size: 1024 * 1024,
                 old_blobs_check_interval: Duration::from_secs(5),
                 blobs_validity_duration: Duration::from_secs(u64::MAX),
                 global_params_path: String::new(),
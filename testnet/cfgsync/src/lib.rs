@@ -15,10 +15,11 @@ mod tests {
     use futures::future::join_all;
     use nomos_da_dispersal::backend::kzgrs::MempoolPublishStrategy;
     use nomos_da_network_core::swarm::ReplicationConfig;
-    use nomos_libp2p::{ed25519, libp2p, Multiaddr, PeerId, Protocol};
+    use nomos_libp2p::{Multiaddr, Protocol};
     use nomos_node::Config as ValidatorConfig;
     use nomos_tracing_service::TracingSettings;
     use subnetworks_assignations::MembershipHandler;
+    use tests::kms_key_to_peer_id;
     use tokio::time::timeout;
 
     use crate::{
@@ -39,6 +40,7 @@ mod tests {
             dispersal_factor: 0,
             num_samples: 0,
             num_subnets: 0,
+            max_blob_size: 0,
             old_blobs_check_interval: Duration::ZERO,
             blobs_validity_duration: Duration::ZERO,
             global_params_path: String::new(),
@@ -90,10 +92,10 @@ mod tests {
     }
 
     pub fn assert_eq_da_membership(my_ip: Ipv4Addr, config: &ValidatorConfig) {
-        let key = libp2p::identity::Keypair::from(ed25519::Keypair::from(
-            config.da_network.backend.node_key.clone(),
-        ));
-        let my_peer_id = PeerId::from_public_key(&key.public());
+        let my_peer_id = kms_key_to_peer_id(
+            &config.kms.backend_settings,
+            &config.da_network.backend.node_key_id,
+        );
         let my_multiaddr = config
             .da_network
             .backend
@@ -36,6 +36,7 @@ pub struct CfgSyncConfig {
     pub dispersal_factor: usize,
     pub num_samples: u16,
     pub num_subnets: u16,
+    pub max_blob_size: usize,
     #[serde_as(as = "MinimalBoundedDuration<0, SECOND>")]
     pub old_blobs_check_interval: Duration,
     #[serde_as(as = "MinimalBoundedDuration<0, SECOND>")]
@@ -78,6 +79,7 @@ impl CfgSyncConfig {
             dispersal_factor: self.dispersal_factor,
             num_samples: self.num_samples,
             num_subnets: self.num_subnets,
+            max_blob_size: self.max_blob_size,
             old_blobs_check_interval: self.old_blobs_check_interval,
             blobs_validity_duration: self.blobs_validity_duration,
             global_params_path: self.global_params_path.clone(),
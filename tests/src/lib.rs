@@ -13,6 +13,7 @@ use std::{
     time::Duration,
 };
 
+use key_management_system::{secure_key::SecuredKey, NodeKMSBackendSettings};
 use nomos_libp2p::{Multiaddr, PeerId, Swarm};
 use rand::{thread_rng, Rng};
 
@@ -70,3 +71,15 @@ pub fn secret_key_to_peer_id(node_key: nomos_libp2p::ed25519::SecretKey) -> Peer
             .into(),
     )
 }
+
+/// Peer id of the ed25519 key preloaded under `key_id` in the key management
+/// service.
+#[must_use]
+pub fn kms_key_to_peer_id(kms: &NodeKMSBackendSettings, key_id: &str) -> PeerId {
+    let NodeKMSBackendSettings::Preload(kms) = kms else {
+        panic!("Keys should be preloaded");
+    };
+    let public_key = nomos_libp2p::ed25519::PublicKey::try_from_bytes(&kms.keys[key_id].as_pk())
+        .expect("Key should be an ed25519 key");
+    PeerId::from_public_key(&public_key.into())
+}
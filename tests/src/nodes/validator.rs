@@ -1,25 +1,24 @@
 use std::{
+    collections::HashMap,
     net::SocketAddr,
+    num::NonZeroU64,
     ops::Range,
     path::PathBuf,
     process::{Child, Command, Stdio},
     time::Duration,
 };
 
-use cryptarchia_consensus::{CryptarchiaInfo, CryptarchiaSettings};
+use cryptarchia_consensus::{
+    validation::BlockValidationSettings, CryptarchiaInfo, CryptarchiaSettings,
+};
 use cryptarchia_engine::time::SlotConfig;
 use kzgrs_backend::common::share::DaShare;
 use nomos_blend::{
-    message_blend::{
-        CryptographicProcessorSettings, MessageBlendSettings, TemporalSchedulerSettings,
-    },
+    message_blend::{CryptographicProcessorSettings, TemporalSchedulerSettings},
     persistent_transmission::PersistentTransmissionSettings,
 };
 use nomos_core::block::Block;
-use nomos_da_indexer::{
-    storage::adapters::rocksdb::RocksAdapterSettings as IndexerStorageAdapterSettings,
-    IndexerSettings,
-};
+use nomos_da_indexer::storage::adapters::rocksdb::RocksAdapterSettings as IndexerStorageAdapterSettings;
 use nomos_da_network_core::swarm::{BalancerStats, DAConnectionPolicySettings, MonitorStats};
 use nomos_da_network_service::{
     backends::libp2p::common::DaNetworkBackendSettings, NetworkConfig as DaNetworkConfig,
@@ -35,14 +34,19 @@ use nomos_da_verifier::{
 };
 use nomos_http_api_common::paths::{
     CL_METRICS, CRYPTARCHIA_HEADERS, CRYPTARCHIA_INFO, DA_BALANCER_STATS, DA_GET_RANGE,
-    DA_MONITOR_STATS, STORAGE_BLOCK,
+    DA_MONITOR_STATS, DA_RETRIEVE_RANGE, STORAGE_BLOCK,
 };
 use nomos_mempool::MempoolMetrics;
 use nomos_network::{backends::libp2p::Libp2pConfig, NetworkConfig};
 use nomos_node::{
-    api::backend::AxumBackendSettings, config::mempool::MempoolConfig, BlobInfo, Config, HeaderId,
-    RocksBackendSettings, Tx,
+    api::backend::AxumBackendSettings,
+    config::{
+        indexer::IndexerConfig,
+        mempool::{MempoolConfig, PoolConfig},
+    },
+    BlobInfo, Config, HeaderId, RocksBackendSettings, Tx,
 };
+use nomos_sdp::SdpSettings;
 use nomos_time::{
     backends::{ntp::async_client::NTPClientSettings, NtpTimeBackendSettings},
     TimeServiceSettings,
@@ -54,7 +58,13 @@ use tempfile::NamedTempFile;
 
 use super::{create_tempdir, persist_tempdir, GetRangeReq, CLIENT};
 use crate::{
-    adjust_timeout, nodes::LOGS_PREFIX, topology::configs::GeneralConfig, IS_DEBUG_TRACING,
+    adjust_timeout,
+    nodes::LOGS_PREFIX,
+    topology::configs::{
+        kms::{create_kms_config, BLEND_NODE_KEY_ID, DA_NETWORK_NODE_KEY_ID, NETWORK_NODE_KEY_ID},
+        GeneralConfig,
+    },
+    IS_DEBUG_TRACING,
 };
 
 const BIN_PATH: &str = "../target/debug/nomos-node";
@@ -202,6 +212,23 @@ impl Validator {
             .unwrap()
     }
 
+    pub async fn retrieve_range(
+        &self,
+        app_id: [u8; 32],
+        range: Range<[u8; 8]>,
+    ) -> Vec<([u8; 8], Vec<u8>)> {
+        CLIENT
+            .post(format!("http://{}{}", self.addr, DA_RETRIEVE_RANGE))
+            .header("Content-Type", "application/json")
+            .body(serde_json::to_string(&GetRangeReq { app_id, range }).unwrap())
+            .send()
+            .await
+            .unwrap()
+            .json::<Vec<([u8; 8], Vec<u8>)>>()
+            .await
+            .unwrap()
+    }
+
     // not async so that we can use this in `Drop`
     #[must_use]
     pub fn get_logs_from_file(&self) -> String {
@@ -272,20 +299,22 @@ impl Validator {
 #[must_use]
 #[expect(clippy::too_many_lines, reason = "TODO: Address this at some point.")]
 pub fn create_validator_config(config: GeneralConfig) -> Config {
+    let kms = create_kms_config(&config);
     let da_policy_settings = config.da_config.policy_settings;
     Config {
         network: NetworkConfig {
             backend: Libp2pConfig {
                 inner: config.network_config.swarm_config,
+                node_key_id: NETWORK_NODE_KEY_ID.to_owned(),
                 initial_peers: config.network_config.initial_peers,
             },
         },
         blend: nomos_blend_service::BlendConfig {
             backend: config.blend_config.backend,
             persistent_transmission: PersistentTransmissionSettings::default(),
-            message_blend: MessageBlendSettings {
+            message_blend: nomos_blend_service::MessageBlendConfig {
                 cryptographic_processor: CryptographicProcessorSettings {
-                    private_key: config.blend_config.private_key.to_bytes(),
+                    private_key: BLEND_NODE_KEY_ID.to_owned(),
                     num_blend_layers: 1,
                 },
                 temporal_processor: TemporalSchedulerSettings {
@@ -316,10 +345,19 @@ pub fn create_validator_config(config: GeneralConfig) -> Config {
                         },
                 },
             recovery_file: PathBuf::from("./recovery/cryptarchia.json"),
+            snapshot: None,
+            snapshot_export: None,
+            block_validation: BlockValidationSettings::default(),
+        },
+        sdp: SdpSettings {
+            service_params: HashMap::new(),
+            session_length: NonZeroU64::new(10).unwrap(),
+            min_stake: 1,
         },
+        kms,
         da_network: DaNetworkConfig {
             backend: DaNetworkBackendSettings {
-                node_key: config.da_config.node_key,
+                node_key_id: DA_NETWORK_NODE_KEY_ID.to_owned(),
                 membership: config.da_config.membership.clone(),
                 listening_address: config.da_config.listening_address,
                 policy_settings: DAConnectionPolicySettings {
@@ -336,15 +374,18 @@ pub fn create_validator_config(config: GeneralConfig) -> Config {
                 replication_settings: config.da_config.replication_settings,
             },
         },
-        da_indexer: IndexerSettings {
+        da_indexer: IndexerConfig {
             storage: IndexerStorageAdapterSettings {
                 blob_storage_directory: "./".into(),
             },
+            retention: None,
         },
         da_verifier: DaVerifierServiceSettings {
             verifier_settings: KzgrsDaVerifierSettings {
                 global_params_path: config.da_config.global_params_path,
-                domain_size: config.da_config.num_subnets as usize,
+                min_domain_size: config.da_config.num_subnets as usize,
+                max_domain_size: config.da_config.num_subnets as usize,
+                max_blob_size: config.da_config.max_blob_size,
             },
             network_adapter_settings: (),
             storage_adapter_settings: VerifierStorageAdapterSettings {
@@ -397,6 +438,8 @@ pub fn create_validator_config(config: GeneralConfig) -> Config {
         mempool: MempoolConfig {
             cl_pool_recovery_path: "./recovery/cl_mempool.json".into(),
             da_pool_recovery_path: "./recovery/da_mempool.json".into(),
+            cl_pool: PoolConfig::default(),
+            da_pool: PoolConfig::default(),
         },
     }
 }
@@ -4,8 +4,9 @@ use kzgrs_backend::{dispersal::Index, reconstruction::reconstruct_without_missin
 use nomos_core::da::blob::Share;
 use subnetworks_assignations::MembershipHandler;
 use tests::{
-    common::da::{disseminate_with_metadata, wait_for_indexed_blob, APP_ID},
-    secret_key_to_peer_id,
+    adjust_timeout,
+    common::da::{disseminate_with_metadata, wait_for_indexed_blob, APP_ID, DA_TESTS_TIMEOUT},
+    kms_key_to_peer_id,
     topology::{Topology, TopologyConfig},
 };
 
@@ -99,6 +100,39 @@ async fn disseminate_retrieve_reconstruct() {
     assert!(validator.monitor_stats().await.0.is_empty());
 }
 
+#[tokio::test]
+async fn disseminate_and_retrieve_from_network() {
+    let topology = Topology::spawn(TopologyConfig::validator_and_executor()).await;
+    let executor = &topology.executors()[0];
+    let validator = &topology.validators()[0];
+    let num_subnets = executor.config().da_network.backend.num_subnets as usize;
+
+    let app_id: [u8; 32] = hex::decode(APP_ID).unwrap().try_into().unwrap();
+    let data = [1u8; 31 * 4];
+    let metadata = kzgrs_backend::dispersal::Metadata::new(app_id, 0u64.into());
+    disseminate_with_metadata(executor, &data, metadata).await;
+
+    let from = 0u64.to_be_bytes();
+    let to = 1u64.to_be_bytes();
+    wait_for_indexed_blob(executor, app_id, from, to, num_subnets).await;
+
+    let retrieved_fut = async {
+        loop {
+            let retrieved = validator.retrieve_range(app_id, from..to).await;
+            if !retrieved.is_empty() {
+                return retrieved;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    };
+    let timeout = adjust_timeout(Duration::from_secs(DA_TESTS_TIMEOUT));
+    let retrieved = tokio::time::timeout(timeout, retrieved_fut)
+        .await
+        .expect("timed out waiting for retrieved blob");
+
+    assert_eq!(retrieved, vec![(from, data.to_vec())]);
+}
+
 #[ignore = "Reenable when tools to inspect mempool are added"]
 #[tokio::test]
 async fn four_subnets_disseminate_retrieve_reconstruct() {
@@ -115,8 +149,10 @@ async fn four_subnets_disseminate_retrieve_reconstruct() {
         .validators()
         .iter()
         .find(|v| {
-            let node_key = v.config().da_network.backend.node_key.clone();
-            let peer_id = secret_key_to_peer_id(node_key);
+            let peer_id = kms_key_to_peer_id(
+                &v.config().kms.backend_settings,
+                &v.config().da_network.backend.node_key_id,
+            );
             let subnets = membership.membership(&peer_id);
             subnets.contains(&0)
         })
@@ -126,8 +162,10 @@ async fn four_subnets_disseminate_retrieve_reconstruct() {
         .validators()
         .iter()
         .find(|v| {
-            let node_key = v.config().da_network.backend.node_key.clone();
-            let peer_id = secret_key_to_peer_id(node_key);
+            let peer_id = kms_key_to_peer_id(
+                &v.config().kms.backend_settings,
+                &v.config().da_network.backend.node_key_id,
+            );
             let subnets = membership.membership(&peer_id);
             subnets.contains(&1)
         })
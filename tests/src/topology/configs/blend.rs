@@ -2,18 +2,19 @@ use std::str::FromStr;
 
 use nomos_blend::membership::Node;
 use nomos_blend_message::{sphinx::SphinxMessage, BlendMessage};
-use nomos_blend_service::backends::libp2p::Libp2pBlendBackendSettings;
+use nomos_blend_service::{backends::libp2p::Libp2pBlendBackendSettings, encryption_private_key};
 use nomos_libp2p::{
     ed25519::{self, Keypair as Ed25519Keypair},
     identity::Keypair,
     Multiaddr, PeerId,
 };
 
-use crate::get_available_port;
+use crate::{get_available_port, topology::configs::kms::BLEND_NODE_KEY_ID};
 
 #[derive(Clone)]
 pub struct GeneralBlendConfig {
     pub backend: Libp2pBlendBackendSettings,
+    pub node_key: ed25519::SecretKey,
     pub private_key: x25519_dalek::StaticSecret,
     pub membership: Vec<Node<PeerId, <SphinxMessage as BlendMessage>::PublicKey>>,
 }
@@ -34,12 +35,15 @@ pub fn create_blend_configs(ids: &[[u8; 32]]) -> Vec<GeneralBlendConfig> {
                         get_available_port(),
                     ))
                     .unwrap(),
-                    node_key,
+                    node_key_id: BLEND_NODE_KEY_ID.to_owned(),
                     peering_degree: 1,
                     max_peering_degree: 3,
                     conn_monitor: None,
                 },
-                private_key: x25519_dalek::StaticSecret::random(),
+                node_key,
+                // peers derive the encryption key from the node key declared
+                // as provider id
+                private_key: x25519_dalek::StaticSecret::from(encryption_private_key(id)),
                 membership: Vec::new(),
             }
         })
@@ -60,7 +64,7 @@ fn blend_nodes(
         .iter()
         .map(|config| Node {
             id: PeerId::from_public_key(
-                &Keypair::from(Ed25519Keypair::from(config.backend.node_key.clone())).public(),
+                &Keypair::from(Ed25519Keypair::from(config.node_key.clone())).public(),
             ),
             address: config.backend.listening_address.clone(),
             public_key: x25519_dalek::PublicKey::from(&x25519_dalek::StaticSecret::from(
@@ -7,6 +7,8 @@ use nomos_core::staking::NMO_UNIT;
 use nomos_ledger::LedgerState;
 use rand::thread_rng;
 
+use crate::topology::configs::kms::LEADER_NF_SK_ID;
+
 #[derive(Clone)]
 pub struct ConsensusParams {
     pub n_participants: usize,
@@ -35,6 +37,7 @@ impl ConsensusParams {
 #[derive(Clone)]
 pub struct GeneralConsensusConfig {
     pub leader_config: LeaderConfig,
+    pub nf_sk: NullifierSecret,
     pub ledger_config: nomos_ledger::Config,
     pub genesis_state: LedgerState,
 }
@@ -80,8 +83,9 @@ pub fn create_consensus_configs(
         .map(|(note, nf_sk)| GeneralConsensusConfig {
             leader_config: LeaderConfig {
                 notes: vec![note],
-                nf_sk,
+                nf_sk_id: LEADER_NF_SK_ID.to_owned(),
             },
+            nf_sk,
             ledger_config,
             genesis_state: genesis_state.clone(),
         })
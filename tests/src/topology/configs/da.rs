@@ -34,6 +34,7 @@ pub struct DaParams {
     pub dispersal_factor: usize,
     pub num_samples: u16,
     pub num_subnets: u16,
+    pub max_blob_size: usize,
     pub old_blobs_check_interval: Duration,
     pub blobs_validity_duration: Duration,
     pub global_params_path: String,
@@ -52,6 +53,7 @@ impl Default for DaParams {
             dispersal_factor: 1,
             num_samples: 1,
             num_subnets: 2,
+            max_blob_size: 1024 * 1024,
             old_blobs_check_interval: Duration::from_secs(5),
             blobs_validity_duration: Duration::from_secs(60),
             global_params_path: GLOBAL_PARAMS_PATH.to_string(),
@@ -94,6 +96,7 @@ pub struct GeneralDaConfig {
     pub verifier_index: HashSet<u16>,
     pub num_samples: u16,
     pub num_subnets: u16,
+    pub max_blob_size: usize,
     pub mempool_strategy: MempoolPublishStrategy,
     pub old_blobs_check_interval: Duration,
     pub blobs_validity_duration: Duration,
@@ -158,6 +161,7 @@ pub fn create_da_configs(ids: &[[u8; 32]], da_params: &DaParams) -> Vec<GeneralD
                 verifier_index: subnetwork_ids,
                 num_samples: da_params.num_samples,
                 num_subnets: da_params.num_subnets,
+                max_blob_size: da_params.max_blob_size,
                 old_blobs_check_interval: da_params.old_blobs_check_interval,
                 blobs_validity_duration: da_params.blobs_validity_duration,
                 mempool_strategy: da_params.mempool_strategy.clone(),
@@ -0,0 +1,57 @@
+use std::{collections::HashMap, sync::Arc};
+
+use key_management_system::{
+    backend::preload::PreloadKMSBackendSettings,
+    keys::{ed25519::Ed25519Key, nullifier::NullifierSecretKey, Key},
+    KMSServiceSettings, NodeKMSBackendSettings,
+};
+use nomos_libp2p::ed25519;
+
+use crate::topology::configs::GeneralConfig;
+
+pub const NETWORK_NODE_KEY_ID: &str = "network";
+pub const BLEND_NODE_KEY_ID: &str = "blend";
+pub const DA_NETWORK_NODE_KEY_ID: &str = "da_network";
+pub const LEADER_NF_SK_ID: &str = "leader";
+
+/// Key management service settings preloaded with the keys of `config`, under
+/// the ids the services are configured with.
+#[must_use]
+pub fn create_kms_config(config: &GeneralConfig) -> KMSServiceSettings<NodeKMSBackendSettings> {
+    let keys = HashMap::from([
+        (
+            NETWORK_NODE_KEY_ID,
+            Key::Ed25519(ed25519_key(&config.network_config.node_key)),
+        ),
+        (
+            BLEND_NODE_KEY_ID,
+            Key::Ed25519(ed25519_key(&config.blend_config.node_key)),
+        ),
+        (
+            DA_NETWORK_NODE_KEY_ID,
+            Key::Ed25519(ed25519_key(&config.da_config.node_key)),
+        ),
+        (
+            LEADER_NF_SK_ID,
+            Key::NullifierSecret(NullifierSecretKey::new(config.consensus_config.nf_sk)),
+        ),
+    ]);
+
+    KMSServiceSettings {
+        backend_settings: NodeKMSBackendSettings::Preload(PreloadKMSBackendSettings {
+            keys: keys
+                .into_iter()
+                .map(|(id, key)| (id.to_owned(), Arc::new(key)))
+                .collect(),
+        }),
+    }
+}
+
+fn ed25519_key(node_key: &ed25519::SecretKey) -> Ed25519Key {
+    Ed25519Key::from_bytes(
+        node_key
+            .as_ref()
+            .try_into()
+            .expect("ed25519 secret keys are 32 bytes long"),
+    )
+}
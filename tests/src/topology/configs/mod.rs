@@ -2,6 +2,7 @@ pub mod api;
 pub mod blend;
 pub mod consensus;
 pub mod da;
+pub mod kms;
 pub mod network;
 pub mod tracing;
 
@@ -17,6 +17,7 @@ pub struct NetworkParams {
 #[derive(Clone)]
 pub struct GeneralNetworkConfig {
     pub swarm_config: SwarmConfig,
+    pub node_key: ed25519::SecretKey,
     pub initial_peers: Vec<Multiaddr>,
 }
 
@@ -25,18 +26,19 @@ pub fn create_network_configs(
     ids: &[[u8; 32]],
     network_params: &NetworkParams,
 ) -> Vec<GeneralNetworkConfig> {
-    let swarm_configs: Vec<SwarmConfig> = ids
+    let node_keys: Vec<ed25519::SecretKey> = ids
         .iter()
         .map(|id| {
             let mut node_key_bytes = *id;
-            let node_key = ed25519::SecretKey::try_from_bytes(&mut node_key_bytes)
-                .expect("Failed to generate secret key from bytes");
-
-            SwarmConfig {
-                node_key,
-                port: get_available_port(),
-                ..Default::default()
-            }
+            ed25519::SecretKey::try_from_bytes(&mut node_key_bytes)
+                .expect("Failed to generate secret key from bytes")
+        })
+        .collect();
+    let swarm_configs: Vec<SwarmConfig> = ids
+        .iter()
+        .map(|_| SwarmConfig {
+            port: get_available_port(),
+            ..Default::default()
         })
         .collect();
 
@@ -44,11 +46,15 @@ pub fn create_network_configs(
 
     swarm_configs
         .iter()
+        .zip(node_keys)
         .zip(all_initial_peers)
-        .map(|(swarm_config, initial_peers)| GeneralNetworkConfig {
-            swarm_config: swarm_config.to_owned(),
-            initial_peers,
-        })
+        .map(
+            |((swarm_config, node_key), initial_peers)| GeneralNetworkConfig {
+                swarm_config: swarm_config.to_owned(),
+                node_key,
+                initial_peers,
+            },
+        )
         .collect()
 }
 